@@ -0,0 +1,266 @@
+//! Registers every day's solver with [cargo-aoc](https://github.com/gobanos/cargo-aoc), so that
+//! its input downloading and runner work with this repository's solvers unchanged
+//!
+//! Run from this directory, e.g. `cargo aoc input -d 1` and then `cargo aoc -d 1`. Inputs are read
+//! from `input/2024/day<N>.txt` as usual for cargo-aoc
+
+use advent_of_code_2024::{Solution, days};
+use aoc_runner_derive::{aoc, aoc_lib};
+
+fn solve(day: u32, part: u32, input: &str) -> Solution {
+    days::get(days::DEFAULT_YEAR, day)
+        .and_then(|entry| entry.solver.solve_part(part, input))
+        .unwrap_or_else(|| panic!("No solver for day {day} part {part}"))
+}
+
+// The registrations are written out rather than generated with macro_rules!, because aoc_lib!
+// only sees `#[aoc]` attributes that have already been expanded by the point it is invoked.
+// Functions are prefixed by day so that cargo-aoc's generated per-function modules don't collide
+
+#[aoc(day1, part1)]
+fn day1_part1(input: &str) -> Solution {
+    solve(1, 1, input)
+}
+
+#[aoc(day1, part2)]
+fn day1_part2(input: &str) -> Solution {
+    solve(1, 2, input)
+}
+
+#[aoc(day2, part1)]
+fn day2_part1(input: &str) -> Solution {
+    solve(2, 1, input)
+}
+
+#[aoc(day2, part2)]
+fn day2_part2(input: &str) -> Solution {
+    solve(2, 2, input)
+}
+
+#[aoc(day3, part1)]
+fn day3_part1(input: &str) -> Solution {
+    solve(3, 1, input)
+}
+
+#[aoc(day3, part2)]
+fn day3_part2(input: &str) -> Solution {
+    solve(3, 2, input)
+}
+
+#[aoc(day4, part1)]
+fn day4_part1(input: &str) -> Solution {
+    solve(4, 1, input)
+}
+
+#[aoc(day4, part2)]
+fn day4_part2(input: &str) -> Solution {
+    solve(4, 2, input)
+}
+
+#[aoc(day5, part1)]
+fn day5_part1(input: &str) -> Solution {
+    solve(5, 1, input)
+}
+
+#[aoc(day5, part2)]
+fn day5_part2(input: &str) -> Solution {
+    solve(5, 2, input)
+}
+
+#[aoc(day6, part1)]
+fn day6_part1(input: &str) -> Solution {
+    solve(6, 1, input)
+}
+
+#[aoc(day6, part2)]
+fn day6_part2(input: &str) -> Solution {
+    solve(6, 2, input)
+}
+
+#[aoc(day7, part1)]
+fn day7_part1(input: &str) -> Solution {
+    solve(7, 1, input)
+}
+
+#[aoc(day7, part2)]
+fn day7_part2(input: &str) -> Solution {
+    solve(7, 2, input)
+}
+
+#[aoc(day8, part1)]
+fn day8_part1(input: &str) -> Solution {
+    solve(8, 1, input)
+}
+
+#[aoc(day8, part2)]
+fn day8_part2(input: &str) -> Solution {
+    solve(8, 2, input)
+}
+
+#[aoc(day9, part1)]
+fn day9_part1(input: &str) -> Solution {
+    solve(9, 1, input)
+}
+
+#[aoc(day9, part2)]
+fn day9_part2(input: &str) -> Solution {
+    solve(9, 2, input)
+}
+
+#[aoc(day10, part1)]
+fn day10_part1(input: &str) -> Solution {
+    solve(10, 1, input)
+}
+
+#[aoc(day10, part2)]
+fn day10_part2(input: &str) -> Solution {
+    solve(10, 2, input)
+}
+
+#[aoc(day11, part1)]
+fn day11_part1(input: &str) -> Solution {
+    solve(11, 1, input)
+}
+
+#[aoc(day11, part2)]
+fn day11_part2(input: &str) -> Solution {
+    solve(11, 2, input)
+}
+
+#[aoc(day12, part1)]
+fn day12_part1(input: &str) -> Solution {
+    solve(12, 1, input)
+}
+
+#[aoc(day12, part2)]
+fn day12_part2(input: &str) -> Solution {
+    solve(12, 2, input)
+}
+
+#[aoc(day13, part1)]
+fn day13_part1(input: &str) -> Solution {
+    solve(13, 1, input)
+}
+
+#[aoc(day13, part2)]
+fn day13_part2(input: &str) -> Solution {
+    solve(13, 2, input)
+}
+
+#[aoc(day14, part1)]
+fn day14_part1(input: &str) -> Solution {
+    solve(14, 1, input)
+}
+
+#[aoc(day14, part2)]
+fn day14_part2(input: &str) -> Solution {
+    solve(14, 2, input)
+}
+
+#[aoc(day15, part1)]
+fn day15_part1(input: &str) -> Solution {
+    solve(15, 1, input)
+}
+
+#[aoc(day15, part2)]
+fn day15_part2(input: &str) -> Solution {
+    solve(15, 2, input)
+}
+
+#[aoc(day16, part1)]
+fn day16_part1(input: &str) -> Solution {
+    solve(16, 1, input)
+}
+
+#[aoc(day16, part2)]
+fn day16_part2(input: &str) -> Solution {
+    solve(16, 2, input)
+}
+
+#[aoc(day17, part1)]
+fn day17_part1(input: &str) -> Solution {
+    solve(17, 1, input)
+}
+
+#[aoc(day17, part2)]
+fn day17_part2(input: &str) -> Solution {
+    solve(17, 2, input)
+}
+
+#[aoc(day18, part1)]
+fn day18_part1(input: &str) -> Solution {
+    solve(18, 1, input)
+}
+
+#[aoc(day18, part2)]
+fn day18_part2(input: &str) -> Solution {
+    solve(18, 2, input)
+}
+
+#[aoc(day19, part1)]
+fn day19_part1(input: &str) -> Solution {
+    solve(19, 1, input)
+}
+
+#[aoc(day19, part2)]
+fn day19_part2(input: &str) -> Solution {
+    solve(19, 2, input)
+}
+
+#[aoc(day20, part1)]
+fn day20_part1(input: &str) -> Solution {
+    solve(20, 1, input)
+}
+
+#[aoc(day20, part2)]
+fn day20_part2(input: &str) -> Solution {
+    solve(20, 2, input)
+}
+
+#[aoc(day21, part1)]
+fn day21_part1(input: &str) -> Solution {
+    solve(21, 1, input)
+}
+
+#[aoc(day21, part2)]
+fn day21_part2(input: &str) -> Solution {
+    solve(21, 2, input)
+}
+
+#[aoc(day22, part1)]
+fn day22_part1(input: &str) -> Solution {
+    solve(22, 1, input)
+}
+
+#[aoc(day22, part2)]
+fn day22_part2(input: &str) -> Solution {
+    solve(22, 2, input)
+}
+
+#[aoc(day23, part1)]
+fn day23_part1(input: &str) -> Solution {
+    solve(23, 1, input)
+}
+
+#[aoc(day23, part2)]
+fn day23_part2(input: &str) -> Solution {
+    solve(23, 2, input)
+}
+
+#[aoc(day24, part1)]
+fn day24_part1(input: &str) -> Solution {
+    solve(24, 1, input)
+}
+
+#[aoc(day24, part2)]
+fn day24_part2(input: &str) -> Solution {
+    solve(24, 2, input)
+}
+
+// Day 25 has no part 2
+#[aoc(day25, part1)]
+fn day25_part1(input: &str) -> Solution {
+    solve(25, 1, input)
+}
+
+aoc_lib! { year = 2024 }
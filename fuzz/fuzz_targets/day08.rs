@@ -0,0 +1,6 @@
+#![no_main]
+
+use advent_of_code_2024::fuzzing;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| fuzzing::run("day08", data));
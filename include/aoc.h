@@ -0,0 +1,22 @@
+/* C declarations for the solver library built with
+ * `cargo rustc --lib --release --features ffi --crate-type cdylib`. Link against
+ * target/release/libadvent_of_code_2024.so (or .dylib / .dll). See src/ffi.rs. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC_OK 0
+#define AOC_INVALID_DAY_OR_PART 1
+#define AOC_INVALID_ARGUMENT 2
+#define AOC_BUFFER_TOO_SMALL 3
+#define AOC_SOLVER_FAILED 4
+
+/* Solves one part (1 or 2) of a day and writes the answer to out_buf as UTF-8 without a trailing
+ * NUL. On entry *out_len is the capacity of out_buf; on AOC_OK or AOC_BUFFER_TOO_SMALL it is set
+ * to the length of the answer. */
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr, size_t input_len,
+                  uint8_t *out_buf, size_t *out_len);
+
+#endif
@@ -0,0 +1,92 @@
+// Global allocator wrapper that tracks current and peak heap usage. Only installed when the
+// `alloc-tracking` feature is enabled, since every allocation pays for two atomic operations
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+struct TrackingAllocator;
+
+#[global_allocator]
+static GLOBAL: TrackingAllocator = TrackingAllocator;
+
+fn record_alloc(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Bytes currently allocated on the heap
+pub fn current_bytes() -> usize {
+    CURRENT.load(Ordering::Relaxed)
+}
+
+// Highest value of `current_bytes` since the last `reset_peak`
+pub fn peak_bytes() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+pub fn reset_peak() {
+    PEAK.store(current_bytes(), Ordering::Relaxed);
+}
+
+// Runs `f` and returns its result along with the peak heap usage above what was allocated before
+// the call
+pub fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    reset_peak();
+    let baseline = current_bytes();
+    let result = f();
+    (result, peak_bytes().saturating_sub(baseline))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint;
+
+    #[test]
+    fn measures_peak() {
+        let ((), peak) = measure_peak(|| {
+            let v: Vec<u8> = hint::black_box(vec![0; 1 << 20]);
+            drop(v);
+        });
+        // Other tests may allocate concurrently, so only check the lower bound
+        assert!(peak >= 1 << 20, "{peak}");
+    }
+}
@@ -0,0 +1,171 @@
+// Colored terminal rendering of grid-shaped solver state using ANSI escape codes, for days that
+// animate in the terminal without needing the `viz` feature. Mirrors image.rs: callers pick a
+// character and color for every cell with a closure. Animation redraws frames in place and paces
+// them so that a fast simulation is still watchable
+use crate::Grid;
+use crate::image::Rgb;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Color {
+    // The terminal's own foreground color
+    #[default]
+    Default,
+    Rgb(Rgb),
+}
+
+pub type Glyph = (char, Color);
+
+const RESET: &str = "\x1b[0m";
+
+fn set_color(out: &mut String, color: Color) {
+    match color {
+        Color::Default => out.push_str(RESET),
+        Color::Rgb([r, g, b]) => out.push_str(&format!("\x1b[38;2;{r};{g};{b}m")),
+    }
+}
+
+// `glyph` is called with (x, y) = (column, row) for each cell. Escape codes are only written where
+// the color changes, and every line ends in the default color
+pub fn render_fn(cols: usize, rows: usize, glyph: impl Fn(usize, usize) -> Glyph) -> String {
+    let mut out = String::new();
+    for y in 0..rows {
+        let mut current = Color::Default;
+        for x in 0..cols {
+            let (c, color) = glyph(x, y);
+            if color != current {
+                set_color(&mut out, color);
+                current = color;
+            }
+            out.push(c);
+        }
+        if current != Color::Default {
+            out.push_str(RESET);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn render<T>(grid: &Grid<T>, glyph: impl Fn(&T) -> Glyph) -> String {
+    render_fn(grid.cols(), grid.rows(), |x, y| glyph(&grid.0[y][x]))
+}
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+// Clears from the cursor to the end of the screen, in case the previous frame was taller
+const CLEAR_BELOW: &str = "\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+pub const DEFAULT_FPS: u32 = 30;
+
+// Draws every `every`th frame in place, at most `fps` times per second. Skipped frames are never
+// rendered, and `finish` always draws the final state
+pub struct Animation<W: Write> {
+    out: W,
+    interval: Duration,
+    every: u64,
+    frames: u64,
+    last_draw: Option<Instant>,
+}
+
+impl<W: Write> Animation<W> {
+    pub fn new(out: W, fps: u32, every: u64) -> Self {
+        let interval = if fps == 0 { Duration::ZERO } else { Duration::from_secs(1) / fps };
+        Self { out, interval, every: every.max(1), frames: 0, last_draw: None }
+    }
+
+    pub fn frame(&mut self, render: impl FnOnce() -> String) -> io::Result<()> {
+        let frame = self.frames;
+        self.frames += 1;
+        if !frame.is_multiple_of(self.every) {
+            return Ok(());
+        }
+
+        if let Some(last_draw) = self.last_draw {
+            thread::sleep(self.interval.saturating_sub(last_draw.elapsed()));
+        }
+        self.draw(&render())
+    }
+
+    pub fn finish(mut self, render: impl FnOnce() -> String) -> io::Result<()> {
+        self.draw(&render())?;
+        write!(self.out, "{SHOW_CURSOR}")?;
+        self.out.flush()
+    }
+
+    fn draw(&mut self, frame: &str) -> io::Result<()> {
+        if self.last_draw.is_none() {
+            write!(self.out, "{HIDE_CURSOR}{CLEAR_SCREEN}")?;
+        }
+        write!(self.out, "{CURSOR_HOME}{frame}{CLEAR_BELOW}")?;
+        self.out.flush()?;
+        self.last_draw = Some(Instant::now());
+        Ok(())
+    }
+}
+
+// Steps `state` until `step` returns false, drawing it to stderr along the way. `every` trades
+// smoothness for speed on long simulations. Write errors are ignored, since a visualization that
+// can't be drawn shouldn't fail the solve
+pub fn animate<S>(
+    mut state: S,
+    mut step: impl FnMut(&mut S) -> bool,
+    render: impl Fn(&S) -> String,
+    every: u64,
+) {
+    let mut animation = Animation::new(io::stderr().lock(), DEFAULT_FPS, every);
+    let _ = animation.frame(|| render(&state));
+    while step(&mut state) {
+        let _ = animation.frame(|| render(&state));
+    }
+    let _ = animation.finish(|| render(&state));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors() {
+        let grid = Grid(vec![vec![0, 1, 1, 2], vec![2, 0, 0, 0]]);
+        let text = render(&grid, |&cell| match cell {
+            0 => ('.', Color::Default),
+            1 => ('#', Color::Rgb([255, 0, 0])),
+            _ => ('O', Color::Rgb([0, 0, 255])),
+        });
+        assert_eq!(
+            ".\x1b[38;2;255;0;0m##\x1b[38;2;0;0;255mO\x1b[0m\n\x1b[38;2;0;0;255mO\x1b[0m...\n",
+            text
+        );
+
+        let plain = render_fn(3, 2, |x, y| (char::from(b'a' + (x + 3 * y) as u8), Color::Default));
+        assert_eq!("abc\ndef\n", plain);
+    }
+
+    #[test]
+    fn animation_frames() {
+        let mut out = Vec::new();
+        let mut animation = Animation::new(&mut out, 0, 3);
+        let mut rendered = Vec::new();
+        for i in 0..7 {
+            animation
+                .frame(|| {
+                    rendered.push(i);
+                    format!("{i}\n")
+                })
+                .unwrap();
+        }
+        animation.finish(|| "done\n".into()).unwrap();
+
+        // Frames 0, 3, and 6 are drawn, then the final state
+        assert_eq!(vec![0, 3, 6], rendered);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[?25l\x1b[2J\x1b[H0\n\x1b[J\x1b[H3\n"), "{out:?}");
+        assert!(out.ends_with("\x1b[H6\n\x1b[J\x1b[Hdone\n\x1b[J\x1b[?25h"), "{out:?}");
+        assert_eq!(1, out.matches(CLEAR_SCREEN).count());
+    }
+}
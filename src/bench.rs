@@ -0,0 +1,410 @@
+// Per-day timing baselines for `aoc bench`: times every day against a directory of inputs, saves
+// the timings as JSON, compares a later run against a saved baseline, and renders either as an
+// HTML page of bar charts
+use crate::days::Day;
+use crate::input;
+use crate::report::{self, Align, Table};
+use crate::serialize::Json;
+use std::error::Error;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{fs, hint, io};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayTiming {
+    pub day: u32,
+    pub title: String,
+    pub micros: f64,
+}
+
+// Each day runs until it has used up MIN_BENCH_TIME or reached MAX_RUNS, and the median run time is
+// reported, so that fast days get enough samples without slow days taking minutes
+const MIN_BENCH_TIME: Duration = Duration::from_millis(500);
+const MAX_RUNS: usize = 100;
+
+fn median_micros(mut f: impl FnMut()) -> f64 {
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.is_empty() || (start.elapsed() < MIN_BENCH_TIME && samples.len() < MAX_RUNS) {
+        let run_start = Instant::now();
+        f();
+        samples.push(run_start.elapsed().as_secs_f64() * 1_000_000.0);
+    }
+
+    samples.sort_by(f64::total_cmp);
+    samples[samples.len() / 2]
+}
+
+// Days without an `input<N>.txt` file in `input_dir` are skipped
+pub fn measure(input_dir: &Path, days: &[Day]) -> Result<Vec<DayTiming>, Box<dyn Error>> {
+    let mut timings = Vec::new();
+    for day in days {
+        let path = input_dir.join(format!("input{}.txt", day.day));
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(format!("Error reading {}: {err}", path.display()).into()),
+        };
+
+        let input = input::normalize(&input);
+        let micros = median_micros(|| {
+            hint::black_box(day.solver.solve(&input));
+        });
+        eprintln!("Day {}: {}", day.day, report::format_duration(micros_to_duration(micros)));
+        timings.push(DayTiming { day: day.day, title: day.title.into(), micros });
+    }
+
+    if timings.is_empty() {
+        return Err(format!("No input files found in {}", input_dir.display()).into());
+    }
+
+    Ok(timings)
+}
+
+fn micros_to_duration(micros: f64) -> Duration {
+    Duration::from_secs_f64(micros / 1_000_000.0)
+}
+
+pub fn to_json(timings: &[DayTiming]) -> Json {
+    let days = timings.iter().map(|timing| {
+        Json::object([
+            ("day", timing.day.into()),
+            ("title", timing.title.as_str().into()),
+            ("micros", timing.micros.into()),
+        ])
+    });
+    Json::object([("days", Json::array(days))])
+}
+
+pub fn from_json(json: &Json) -> Result<Vec<DayTiming>, String> {
+    let days = json.get("days").and_then(Json::as_array).ok_or("Baseline has no days array")?;
+    days.iter()
+        .map(|day| {
+            let number =
+                day.get("day").and_then(Json::as_u64).ok_or("Baseline day has no number")?;
+            let micros = day
+                .get("micros")
+                .and_then(Json::as_f64)
+                .ok_or_else(|| format!("Baseline day {number} has no time"))?;
+            let title = day.get("title").and_then(Json::as_str).unwrap_or_default();
+            Ok(DayTiming { day: number as u32, title: title.into(), micros })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub title: String,
+    pub baseline_micros: f64,
+    pub current_micros: f64,
+}
+
+impl Comparison {
+    // Positive when the current run is slower than the baseline
+    pub fn percent_change(&self) -> f64 {
+        100.0 * (self.current_micros - self.baseline_micros) / self.baseline_micros
+    }
+
+    // Changes within `threshold_percent` in either direction are treated as noise
+    pub fn change(&self, threshold_percent: f64) -> Change {
+        let percent = self.percent_change();
+        if percent > threshold_percent {
+            Change::Regressed
+        } else if percent < -threshold_percent {
+            Change::Improved
+        } else {
+            Change::Unchanged
+        }
+    }
+}
+
+// Days that appear in only one of the two runs are left out
+pub fn compare(baseline: &[DayTiming], current: &[DayTiming]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|timing| {
+            let before = baseline.iter().find(|before| before.day == timing.day)?;
+            Some(Comparison {
+                day: timing.day,
+                title: timing.title.clone(),
+                baseline_micros: before.micros,
+                current_micros: timing.micros,
+            })
+        })
+        .collect()
+}
+
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 5.0;
+
+pub fn timing_table(timings: &[DayTiming]) -> Table {
+    let mut table =
+        Table::new(&[("Day", Align::Right), ("Title", Align::Left), ("Time", Align::Right)]);
+    for timing in timings {
+        table.row(vec![
+            timing.day.to_string(),
+            timing.title.clone(),
+            report::format_duration(micros_to_duration(timing.micros)),
+        ]);
+    }
+    table
+}
+
+pub fn comparison_table(comparisons: &[Comparison], threshold_percent: f64) -> Table {
+    let mut table = Table::new(&[
+        ("Day", Align::Right),
+        ("Title", Align::Left),
+        ("Baseline", Align::Right),
+        ("Current", Align::Right),
+        ("Change", Align::Right),
+        ("", Align::Left),
+    ]);
+
+    for comparison in comparisons {
+        let verdict = match comparison.change(threshold_percent) {
+            Change::Regressed => "regressed",
+            Change::Improved => "improved",
+            Change::Unchanged => "",
+        };
+        table.row(vec![
+            comparison.day.to_string(),
+            comparison.title.clone(),
+            report::format_duration(micros_to_duration(comparison.baseline_micros)),
+            report::format_duration(micros_to_duration(comparison.current_micros)),
+            format!("{:+.1}%", comparison.percent_change()),
+            verdict.into(),
+        ]);
+    }
+
+    let count = |change| {
+        comparisons
+            .iter()
+            .filter(|comparison| comparison.change(threshold_percent) == change)
+            .count()
+    };
+    let total = Comparison {
+        day: 0,
+        title: String::new(),
+        baseline_micros: comparisons.iter().map(|comparison| comparison.baseline_micros).sum(),
+        current_micros: comparisons.iter().map(|comparison| comparison.current_micros).sum(),
+    };
+    table.footer(vec![
+        "All".into(),
+        format!("{} days", comparisons.len()),
+        report::format_duration(micros_to_duration(total.baseline_micros)),
+        report::format_duration(micros_to_duration(total.current_micros)),
+        format!("{:+.1}%", total.percent_change()),
+        format!("{} regressed, {} improved", count(Change::Regressed), count(Change::Improved)),
+    ]);
+
+    table
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+td, th { padding: 2px 8px; text-align: left; white-space: nowrap; }
+td.time, td.change { text-align: right; font-variant-numeric: tabular-nums; }
+td.bars { width: 60vw; }
+.bar { height: 9px; margin: 2px 0; border-radius: 2px; }
+.baseline { background: #bbb; }
+.current { background: #4a7bd0; }
+.regressed { background: #d04a4a; }
+.improved { background: #4aa05a; }
+tfoot td { border-top: 1px solid #888; font-weight: bold; }";
+
+// Bar width as a percentage of the widest bar. Day times span several orders of magnitude, so bars
+// use a log scale to keep fast days visible
+fn bar_percent(micros: f64, max_micros: f64) -> f64 {
+    report::percent((1.0 + micros).log10(), (1.0 + max_micros).log10())
+}
+
+fn html_bar(out: &mut String, class: &str, micros: f64, max_micros: f64) {
+    let width = bar_percent(micros, max_micros);
+    let title = report::format_duration(micros_to_duration(micros));
+    write!(out, r#"<div class="bar {class}" style="width: {width:.1}%" title="{title}"></div>"#)
+        .unwrap();
+}
+
+// Self-contained page with a bar per day for `aoc bench --html`. With a baseline, each day that has
+// a baseline time also gets a gray baseline bar, and the current bar is colored by its change
+pub fn html_report(
+    timings: &[DayTiming],
+    baseline: Option<&[DayTiming]>,
+    threshold_percent: f64,
+) -> String {
+    let comparisons = baseline.map(|baseline| compare(baseline, timings)).unwrap_or_default();
+    let comparison = |day| comparisons.iter().find(|comparison| comparison.day == day);
+    let max_micros = timings
+        .iter()
+        .map(|timing| timing.micros)
+        .chain(comparisons.iter().map(|comparison| comparison.baseline_micros))
+        .fold(0.0, f64::max);
+    let format_micros = |micros| report::format_duration(micros_to_duration(micros));
+
+    let mut out = String::new();
+    writeln!(out, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">").unwrap();
+    writeln!(out, "<title>Benchmark report</title>\n<style>\n{HTML_STYLE}\n</style>").unwrap();
+    writeln!(out, "</head>\n<body>\n<h1>Benchmark report</h1>").unwrap();
+    if baseline.is_some() {
+        writeln!(
+            out,
+            "<p>Gray bars are the baseline. Changes beyond {threshold_percent}% are red \
+             (regressed) or green (improved). Bar lengths are on a log scale.</p>"
+        )
+        .unwrap();
+    } else {
+        writeln!(out, "<p>Bar lengths are on a log scale.</p>").unwrap();
+    }
+
+    writeln!(out, "<table>\n<thead><tr><th>Day</th><th>Title</th><th>Time</th>").unwrap();
+    if baseline.is_some() {
+        writeln!(out, "<th>Baseline</th><th>Change</th>").unwrap();
+    }
+    writeln!(out, "<th></th></tr></thead>\n<tbody>").unwrap();
+
+    for timing in timings {
+        let comparison = comparison(timing.day);
+        write!(
+            out,
+            r#"<tr><td>{}</td><td>{}</td><td class="time">{}</td>"#,
+            timing.day,
+            escape_html(&timing.title),
+            format_micros(timing.micros)
+        )
+        .unwrap();
+        if baseline.is_some() {
+            match comparison {
+                Some(comparison) => write!(
+                    out,
+                    r#"<td class="time">{}</td><td class="change">{:+.1}%</td>"#,
+                    format_micros(comparison.baseline_micros),
+                    comparison.percent_change()
+                ),
+                None => write!(out, "<td></td><td></td>"),
+            }
+            .unwrap();
+        }
+
+        write!(out, r#"<td class="bars">"#).unwrap();
+        let class = match comparison.map(|comparison| comparison.change(threshold_percent)) {
+            Some(Change::Regressed) => "regressed",
+            Some(Change::Improved) => "improved",
+            Some(Change::Unchanged) | None => "current",
+        };
+        if let Some(comparison) = comparison {
+            html_bar(&mut out, "baseline", comparison.baseline_micros, max_micros);
+        }
+        html_bar(&mut out, class, timing.micros, max_micros);
+        writeln!(out, "</td></tr>").unwrap();
+    }
+    writeln!(out, "</tbody>").unwrap();
+
+    let total: f64 = timings.iter().map(|timing| timing.micros).sum();
+    write!(
+        out,
+        r#"<tfoot><tr><td>All</td><td>{} days</td><td class="time">{}</td>"#,
+        timings.len(),
+        format_micros(total)
+    )
+    .unwrap();
+    if baseline.is_some() {
+        let baseline_total: f64 =
+            comparisons.iter().map(|comparison| comparison.baseline_micros).sum();
+        let current_total: f64 =
+            comparisons.iter().map(|comparison| comparison.current_micros).sum();
+        let change = report::percent(current_total - baseline_total, baseline_total);
+        write!(
+            out,
+            r#"<td class="time">{}</td><td class="change">{change:+.1}%</td>"#,
+            format_micros(baseline_total)
+        )
+        .unwrap();
+    }
+    writeln!(out, "<td></td></tr></tfoot>\n</table>\n</body>\n</html>").unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u32, micros: f64) -> DayTiming {
+        DayTiming { day, title: format!("Day {day}"), micros }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let timings = vec![timing(1, 105.5), timing(22, 20000.0)];
+        let json = Json::parse(&to_json(&timings).to_string()).unwrap();
+        assert_eq!(Ok(timings), from_json(&json));
+
+        assert!(from_json(&Json::parse("{}").unwrap()).is_err());
+        assert!(from_json(&Json::parse(r#"{"days":[{"day":1}]}"#).unwrap()).is_err());
+    }
+
+    #[test]
+    fn comparisons() {
+        let baseline = [timing(1, 100.0), timing(2, 100.0), timing(3, 100.0), timing(4, 100.0)];
+        let current = [timing(1, 150.0), timing(2, 50.0), timing(3, 103.0), timing(5, 10.0)];
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(vec![1, 2, 3], comparisons.iter().map(|c| c.day).collect::<Vec<_>>());
+        assert_eq!(50.0, comparisons[0].percent_change());
+        assert_eq!(Change::Regressed, comparisons[0].change(DEFAULT_THRESHOLD_PERCENT));
+        assert_eq!(Change::Improved, comparisons[1].change(DEFAULT_THRESHOLD_PERCENT));
+        assert_eq!(Change::Unchanged, comparisons[2].change(DEFAULT_THRESHOLD_PERCENT));
+        assert_eq!(Change::Regressed, comparisons[2].change(1.0));
+
+        let rendered = comparison_table(&comparisons, DEFAULT_THRESHOLD_PERCENT).render();
+        let lines: Vec<_> = rendered.lines().collect();
+        assert!(lines[2].contains("+50.0% | regressed"), "{rendered}");
+        assert!(lines[3].contains("-50.0% | improved"), "{rendered}");
+        assert!(lines[6].ends_with("+1.0% | 1 regressed, 1 improved"), "{rendered}");
+    }
+
+    #[test]
+    fn html() {
+        let current = [timing(1, 150.0), timing(2, 50.0), timing(3, 9999.0)];
+        let html = html_report(&current, None, DEFAULT_THRESHOLD_PERCENT);
+        assert!(html.starts_with("<!DOCTYPE html>"), "{html}");
+        assert!(!html.contains("Baseline"), "{html}");
+        assert_eq!(3, html.matches(r#"class="bar current""#).count(), "{html}");
+        assert!(html.contains(r#"<div class="bar current" style="width: 100.0%""#), "{html}");
+        assert!(html.contains(r#"<td>All</td><td>3 days</td><td class="time">10.20ms</td>"#));
+
+        let baseline = [timing(1, 100.0), timing(2, 100.0)];
+        let html = html_report(&current, Some(&baseline), DEFAULT_THRESHOLD_PERCENT);
+        assert_eq!(2, html.matches(r#"class="bar baseline""#).count(), "{html}");
+        assert!(html.contains(r#"<td class="change">+50.0%</td><td class="bars">"#), "{html}");
+        assert_eq!(1, html.matches(r#"class="bar regressed""#).count(), "{html}");
+        assert_eq!(1, html.matches(r#"class="bar improved""#).count(), "{html}");
+        // Day 3 isn't in the baseline, so it has no change and a plain bar
+        assert!(html.contains(r#"<td></td><td></td><td class="bars"><div class="bar current""#));
+        assert!(html.contains(r#"<td class="change">+0.0%</td><td></td></tr></tfoot>"#), "{html}");
+
+        assert_eq!("&lt;a href=&quot;x&quot;&gt;&amp;", escape_html(r#"<a href="x">&"#));
+    }
+}
@@ -0,0 +1,309 @@
+//! Runs every day against the `input<N>.txt` files in a directory, then prints a summary table of
+//! per-day cost sorted from most to least expensive
+//!
+//! Runs 2024's solvers unless another year is chosen with `--year YEAR`. Days without an input file
+//! are skipped. Peak memory is only reported when built with the `alloc-tracking` feature, and
+//! `--deterministic`, `--visualize`, and `--threads` work the same as for the individual day
+//! binaries.
+//!
+//! Days run one after another, never concurrently: each day's time and peak memory should be its
+//! own, and days running side by side would compete for cores and allocations and skew both. So
+//! `--threads` doesn't spread the suite across threads; it caps the threads that each day's
+//! parallel implementation uses while that day runs.
+//!
+//! With `--timeout 30s`, each day runs in its own child process, and a day that runs longer than
+//! that is killed, reported as timed out, and left out of the summary. The suite then exits with
+//! status 124
+//!
+//! A day that panics is reported with the part and panic message, also left out of the summary, and
+//! the suite moves on to the next day. Part 2 still runs when part 1 panics, and the suite exits
+//! with status 1 at the end unless a timeout already set status 124
+
+use advent_of_code_2024::days::Day;
+use advent_of_code_2024::report::{self, Align, Table};
+use advent_of_code_2024::serialize::Json;
+use advent_of_code_2024::timeout::{self, Watchdog};
+use advent_of_code_2024::{input, parallel};
+use std::error::Error;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, Instant};
+use std::{env, fs, io};
+
+// Set on the child process that runs a single day under `--timeout`
+const CHILD_DAY_VAR: &str = "AOC_ALL_DAY";
+
+struct DayResult {
+    day: u32,
+    title: &'static str,
+    time: Duration,
+    peak_bytes: Option<usize>,
+}
+
+#[cfg(feature = "alloc-tracking")]
+fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    let (result, peak) = advent_of_code_2024::alloc_tracking::measure_peak(f);
+    (result, Some(peak))
+}
+
+#[cfg(not(feature = "alloc-tracking"))]
+fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    (f(), None)
+}
+
+// What running one day printed and cost
+struct DayRun {
+    lines: Vec<String>,
+    panicked: bool,
+    time: Duration,
+    peak_bytes: Option<usize>,
+}
+
+fn run_day(day: &Day, input: &str) -> DayRun {
+    let start = Instant::now();
+    let (parts, peak_bytes) = measure_peak(|| day.solver.solve_catching(input));
+    let time = start.elapsed();
+
+    let mut lines = Vec::new();
+    let mut panics = Vec::new();
+    for (part, result) in (1..).zip(parts) {
+        match result {
+            Ok(solution) if solution.is_none() => {}
+            Ok(solution) => lines.push(format!("  Part {part}: {solution}")),
+            // Both parts of a combined solver report the same panic
+            Err(panic) if panics.contains(&panic) => {}
+            Err(panic) => {
+                lines.push(format!("  {panic}"));
+                panics.push(panic);
+            }
+        }
+    }
+
+    DayRun { lines, panicked: !panics.is_empty(), time, peak_bytes }
+}
+
+impl DayRun {
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("lines", Json::array(self.lines.iter().map(String::as_str))),
+            ("panicked", self.panicked.into()),
+            ("nanos", (self.time.as_nanos() as u64).into()),
+            ("peak_bytes", self.peak_bytes.map_or(Json::Null, Json::from)),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Option<Self> {
+        let lines = json.get("lines")?.as_array()?;
+        Some(Self {
+            lines: lines
+                .iter()
+                .map(|line| line.as_str().map(String::from))
+                .collect::<Option<_>>()?,
+            panicked: *json.get("panicked")? == Json::Bool(true),
+            time: Duration::from_nanos(json.get("nanos")?.as_u64()?),
+            peak_bytes: json.get("peak_bytes")?.as_u64().map(|bytes| bytes as usize),
+        })
+    }
+}
+
+// Runs one day in a child process, which exits with the timeout status if the day takes longer than
+// `--timeout`. Returns None in that case
+fn run_day_in_child(day: &Day) -> Result<Option<DayRun>, Box<dyn Error>> {
+    let output = Command::new(env::current_exe()?)
+        .args(env::args_os().skip(1))
+        .env(CHILD_DAY_VAR, day.day.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()?;
+    if output.status.code() == Some(timeout::EXIT_CODE) {
+        return Ok(None);
+    }
+    if !output.status.success() {
+        return Err(format!("Day {} exited with {}", day.day, output.status).into());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let run = Json::parse(stdout.trim()).ok().as_ref().and_then(DayRun::from_json);
+    run.map(Some).ok_or_else(|| format!("Day {} printed invalid results", day.day).into())
+}
+
+// Child side of `run_day_in_child`: prints the day's results as JSON on stdout
+fn run_child(day: &Day, input: &str, timeout: Duration) {
+    let _watchdog = Watchdog::start(&format!("Day {}", day.day), timeout);
+    println!("{}", run_day(day, input).to_json());
+}
+
+fn summary_table(results: &[DayResult]) -> Table {
+    let track_memory = results.iter().any(|result| result.peak_bytes.is_some());
+
+    let mut columns = vec![
+        ("Day", Align::Right),
+        ("Title", Align::Left),
+        ("Time", Align::Right),
+        ("% Time", Align::Right),
+    ];
+    if track_memory {
+        columns.push(("Peak memory", Align::Right));
+    }
+    let mut table = Table::new(&columns);
+
+    let total_time: Duration = results.iter().map(|result| result.time).sum();
+    let mut sorted: Vec<_> = results.iter().collect();
+    sorted.sort_by(|a, b| b.time.cmp(&a.time).then(a.day.cmp(&b.day)));
+
+    for result in sorted {
+        let percent = report::percent(result.time.as_secs_f64(), total_time.as_secs_f64());
+        let mut row = vec![
+            result.day.to_string(),
+            result.title.into(),
+            report::format_duration(result.time),
+            format!("{percent:.1}%"),
+        ];
+        if track_memory {
+            row.push(result.peak_bytes.map(report::format_bytes).unwrap_or_default());
+        }
+        table.row(row);
+    }
+
+    let mut footer = vec![
+        "All".into(),
+        format!("{} days", results.len()),
+        report::format_duration(total_time),
+        "100.0%".into(),
+    ];
+    if track_memory {
+        let max_peak = results.iter().filter_map(|result| result.peak_bytes).max();
+        footer.push(max_peak.map(report::format_bytes).unwrap_or_default());
+    }
+    table.footer(footer);
+
+    table
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input_dir = env::args().nth(1).expect("ARGS: <input dir> [--year YEAR]");
+    let input_dir = Path::new(&input_dir);
+    let (_, days) = advent_of_code_2024::year_flag()?;
+    advent_of_code_2024::set_deterministic(advent_of_code_2024::has_flag("--deterministic"));
+    advent_of_code_2024::set_visualize(advent_of_code_2024::has_flag("--visualize"));
+    parallel::set_thread_count(parallel::threads_from_env()?);
+    let timeout = advent_of_code_2024::flag_value("--timeout")
+        .map(|timeout| timeout::parse_duration(&timeout))
+        .transpose()?;
+    advent_of_code_2024::init_logging()?;
+
+    let child_day = env::var(CHILD_DAY_VAR).ok();
+
+    let mut results = Vec::new();
+    let mut timed_out = Vec::new();
+    let mut panicked = Vec::new();
+    for day in days {
+        if child_day.as_ref().is_some_and(|child_day| *child_day != day.day.to_string()) {
+            continue;
+        }
+
+        let path = input_dir.join(format!("input{}.txt", day.day));
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(format!("Error reading {}: {err}", path.display()).into()),
+        };
+        let input = input::normalize(&input);
+
+        let run = match timeout {
+            Some(timeout) if child_day.is_some() => {
+                run_child(day, &input, timeout);
+                return Ok(());
+            }
+            Some(_) => run_day_in_child(day)?,
+            None => Some(run_day(day, &input)),
+        };
+
+        println!("Day {}: {}", day.day, day.title);
+        let Some(run) = run else {
+            let timeout = timeout.unwrap_or_default();
+            println!("  Timed out after {}", report::format_duration(timeout));
+            timed_out.push(day.day);
+            continue;
+        };
+        for line in &run.lines {
+            println!("{line}");
+        }
+        if run.panicked {
+            panicked.push(day.day);
+            continue;
+        }
+
+        results.push(DayResult {
+            day: day.day,
+            title: day.title,
+            time: run.time,
+            peak_bytes: run.peak_bytes,
+        });
+    }
+
+    if results.is_empty() && timed_out.is_empty() && panicked.is_empty() {
+        return Err(format!("No input files found in {}", input_dir.display()).into());
+    }
+
+    println!();
+    if !results.is_empty() {
+        print!("{}", summary_table(&results).render());
+    }
+    if !timed_out.is_empty() {
+        let days: Vec<_> = timed_out.iter().map(u32::to_string).collect();
+        println!("Timed out: days {}", days.join(", "));
+    }
+    if !panicked.is_empty() {
+        let days: Vec<_> = panicked.iter().map(u32::to_string).collect();
+        println!("Panicked: days {}", days.join(", "));
+    }
+
+    if !timed_out.is_empty() {
+        process::exit(timeout::EXIT_CODE);
+    }
+    if !panicked.is_empty() {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_sorted_by_time() {
+        let results = [
+            DayResult { day: 1, title: "A", time: Duration::from_millis(1), peak_bytes: None },
+            DayResult { day: 2, title: "B", time: Duration::from_millis(3), peak_bytes: None },
+        ];
+        let rendered = summary_table(&results).render();
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(6, lines.len(), "{rendered}");
+        assert!(lines[2].starts_with("  2 | B"), "{rendered}");
+        assert!(lines[2].ends_with("75.0%"), "{rendered}");
+        assert!(lines[3].starts_with("  1 | A"), "{rendered}");
+        assert!(lines[5].starts_with("All | 2 days"), "{rendered}");
+    }
+
+    #[test]
+    fn child_results_round_trip() {
+        let run = DayRun {
+            lines: vec!["  Part 1: 3".into(), "  Part 2 panicked: \"bad\"".into()],
+            panicked: true,
+            time: Duration::from_micros(1500),
+            peak_bytes: Some(1 << 20),
+        };
+        let parsed = DayRun::from_json(&Json::parse(&run.to_json().to_string()).unwrap()).unwrap();
+        assert_eq!(run.lines, parsed.lines);
+        assert!(parsed.panicked);
+        assert_eq!(run.time, parsed.time);
+        assert_eq!(run.peak_bytes, parsed.peak_bytes);
+
+        let run = DayRun { peak_bytes: None, ..parsed };
+        let parsed = DayRun::from_json(&Json::parse(&run.to_json().to_string()).unwrap()).unwrap();
+        assert_eq!(None, parsed.peak_bytes);
+    }
+}
@@ -0,0 +1,416 @@
+//! Subcommands that operate on all days rather than on a single day's input
+//!
+//! Usage:
+//!   aoc list [input dir] [--year YEAR]
+//!   aoc serve [--addr ADDR]
+//!   aoc bench <input dir> [--year YEAR] [--save FILE] [--compare FILE] [--threshold PERCENT]
+//!             [--html FILE] [--threads N]
+//!   aoc run <day> --dir <input dir> [--year YEAR] [--threads N]
+//!   aoc today
+//!
+//! `list` shows every day of the year (default 2024) with its title, implemented parts, whether it
+//! supports `--visualize`, and puzzle URL. Given an input directory, it also shows which days have an `input<N>.txt` file and a line
+//! in `answers.txt` (the same layout that the real input tests use).
+//!
+//! `bench` times every day of the year that has an `input<N>.txt` file in the directory. `--save`
+//! writes the timings as a JSON baseline, and `--compare` reports each day's change against a saved
+//! baseline, flagging changes larger than the threshold (default 5%) as regressions or improvements.
+//! `--html` also writes the timings (and comparison, if any) as a self-contained page of bar charts.
+//! `--threads` (or `AOC_THREADS`) caps the threads used by days built with the `parallel` feature
+//!
+//! `run` solves one day for every file in the directory (e.g. inputs collected from other people, to
+//! cross-check answers) and prints a table of each file's answers and solve time, with the total
+//! time at the bottom. A part that panics is reported in its row without stopping the rest
+//!
+//! `today` solves the puzzle released today (by the date in EST, when puzzles unlock) from
+//! `inputs/<year>/day<N>.txt`, so that inputs from different years don't overwrite each other. A
+//! missing input is first downloaded with `curl` using the session cookie in `AOC_SESSION`, once the
+//! day is known to have a solver
+
+use advent_of_code_2024::days::{self, Day};
+use advent_of_code_2024::panics::Panicked;
+use advent_of_code_2024::report::{Align, Table};
+use advent_of_code_2024::serialize::Json;
+use advent_of_code_2024::{Solution, bench, input, parallel, report, timing};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, io};
+
+const USAGE: &str = "ARGS: list [input dir] [--year YEAR] | serve [--addr ADDR] \
+                     | bench <input dir> [--year YEAR] [--save FILE] [--compare FILE] \
+                     [--threshold PERCENT] [--html FILE] [--threads N] \
+                     | run <day> --dir <input dir> [--year YEAR] [--threads N] | today";
+
+// Which days have a cached input file and a line in the answers file
+#[derive(Debug, Default)]
+struct Cached {
+    inputs: BTreeSet<u32>,
+    answers: BTreeSet<u32>,
+}
+
+impl Cached {
+    fn read(input_dir: &Path, days: &[Day]) -> Result<Self, Box<dyn Error>> {
+        let inputs =
+            days.iter().map(|day| day.day).filter(|day| input_path(input_dir, *day).exists());
+        let answers = match fs::read_to_string(input_dir.join("answers.txt")) {
+            Ok(contents) => answered_days(&contents),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeSet::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self { inputs: inputs.collect(), answers })
+    }
+}
+
+fn input_path(input_dir: &Path, day: u32) -> PathBuf {
+    input_dir.join(format!("input{day}.txt"))
+}
+
+// Answers lines are `<day> <part 1 answer> [<part 2 answer>]`, with `#` starting a comment line
+fn answered_days(contents: &str) -> BTreeSet<u32> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_ascii_whitespace().next()?.parse().ok())
+        .collect()
+}
+
+fn list_table(days: &[Day], cached: Option<&Cached>) -> Table {
+    let mut columns = vec![
+        ("Day", Align::Right),
+        ("Title", Align::Left),
+        ("Parts", Align::Left),
+        ("Viz", Align::Left),
+    ];
+    if cached.is_some() {
+        columns.extend([("Input", Align::Left), ("Answers", Align::Left)]);
+    }
+    columns.push(("URL", Align::Left));
+    let mut table = Table::new(&columns);
+
+    let mark = |present: bool| if present { "yes" } else { "" }.to_string();
+    for day in days {
+        let parts = (1..=day.parts).map(|part| part.to_string()).collect::<Vec<_>>().join(", ");
+        let viz = mark(days::visualizes(day.year, day.day));
+        let mut row = vec![day.day.to_string(), day.title.into(), parts, viz];
+        if let Some(cached) = cached {
+            row.push(mark(cached.inputs.contains(&day.day)));
+            row.push(mark(cached.answers.contains(&day.day)));
+        }
+        row.push(day.url());
+        table.row(row);
+    }
+
+    table
+}
+
+fn list() -> Result<(), Box<dyn Error>> {
+    let input_dir = env::args().nth(2).filter(|arg| !arg.starts_with("--"));
+    let (_, days) = advent_of_code_2024::year_flag()?;
+    let cached = input_dir.map(|dir| Cached::read(Path::new(&dir), days)).transpose()?;
+    print!("{}", list_table(days, cached.as_ref()).render());
+    Ok(())
+}
+
+#[cfg(feature = "serve")]
+fn serve() -> Result<(), Box<dyn Error>> {
+    use advent_of_code_2024::serve;
+
+    let addr = advent_of_code_2024::flag_value("--addr");
+    serve::serve(addr.as_deref().unwrap_or(serve::DEFAULT_ADDR))
+}
+
+#[cfg(not(feature = "serve"))]
+fn serve() -> Result<(), Box<dyn Error>> {
+    Err("aoc serve requires building with --features serve".into())
+}
+
+fn bench() -> Result<(), Box<dyn Error>> {
+    let input_dir = env::args().nth(2).filter(|arg| !arg.starts_with("--")).ok_or(USAGE)?;
+    let (_, days) = advent_of_code_2024::year_flag()?;
+    let threshold = match advent_of_code_2024::flag_value("--threshold") {
+        Some(threshold) => threshold.parse()?,
+        None => bench::DEFAULT_THRESHOLD_PERCENT,
+    };
+    parallel::set_thread_count(parallel::threads_from_env()?);
+
+    // Read the baseline first so that a bad path fails before spending time on the benchmark
+    let baseline = advent_of_code_2024::flag_value("--compare")
+        .map(|path| -> Result<_, Box<dyn Error>> {
+            let json = Json::parse(&fs::read_to_string(&path)?)?;
+            Ok(bench::from_json(&json).map_err(|err| format!("{path}: {err}"))?)
+        })
+        .transpose()?;
+
+    let timings = bench::measure(Path::new(&input_dir), days)?;
+    match &baseline {
+        Some(baseline) => {
+            let comparisons = bench::compare(baseline, &timings);
+            print!("{}", bench::comparison_table(&comparisons, threshold).render());
+        }
+        None => print!("{}", bench::timing_table(&timings).render()),
+    }
+
+    if let Some(path) = advent_of_code_2024::flag_value("--html") {
+        fs::write(&path, bench::html_report(&timings, baseline.as_deref(), threshold))?;
+        eprintln!("Wrote HTML report to {path}");
+    }
+
+    if let Some(path) = advent_of_code_2024::flag_value("--save") {
+        fs::write(&path, format!("{}\n", bench::to_json(&timings)))?;
+        eprintln!("Saved baseline to {path}");
+    }
+
+    Ok(())
+}
+
+// One input file's answers for `aoc run`
+#[derive(Debug, Clone, PartialEq)]
+struct BatchRow {
+    file: String,
+    answers: [String; 2],
+    micros: f64,
+}
+
+fn answer_cell(answer: Result<Solution, Panicked>) -> String {
+    match answer {
+        Ok(solution) if solution.is_none() => String::new(),
+        Ok(solution) => solution.to_string(),
+        Err(panicked) => format!("panicked: {}", panicked.message),
+    }
+}
+
+fn solve_file(day: &Day, path: &Path) -> Result<BatchRow, Box<dyn Error>> {
+    let input = fs::read_to_string(path)
+        .map_err(|err| format!("Error reading {}: {err}", path.display()))?;
+    let input = input::normalize(&input);
+    let (answers, micros) = timing::time_once(|| day.solver.solve_catching(&input));
+    let file = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+    Ok(BatchRow { file, answers: answers.map(answer_cell), micros })
+}
+
+// Every file directly in `dir`, sorted by name. Hidden files (e.g. .DS_Store) are skipped
+fn batch_files(dir: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    for entry in
+        fs::read_dir(dir).map_err(|err| format!("Error reading {}: {err}", dir.display()))?
+    {
+        let entry = entry?;
+        if entry.file_type()?.is_file() && !entry.file_name().to_string_lossy().starts_with('.') {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn batch_table(rows: &[BatchRow]) -> Table {
+    let mut table = Table::new(&[
+        ("File", Align::Left),
+        ("Part 1", Align::Right),
+        ("Part 2", Align::Right),
+        ("Time", Align::Right),
+    ]);
+    let time = |micros: f64| report::format_duration(Duration::from_secs_f64(micros / 1_000_000.0));
+    for row in rows {
+        let [part1, part2] = row.answers.clone();
+        table.row(vec![row.file.clone(), part1, part2, time(row.micros)]);
+    }
+
+    let total = rows.iter().map(|row| row.micros).sum();
+    let inputs = if rows.len() == 1 { "1 input".into() } else { format!("{} inputs", rows.len()) };
+    table.footer(vec![inputs, String::new(), String::new(), time(total)]);
+    table
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let day_arg = env::args().nth(2).filter(|arg| !arg.starts_with("--")).ok_or(USAGE)?;
+    let dir = advent_of_code_2024::flag_value("--dir").ok_or(USAGE)?;
+    let (year, days) = advent_of_code_2024::year_flag()?;
+    let number: u32 = day_arg.parse().map_err(|_| format!("Invalid day '{day_arg}'"))?;
+    let day = days
+        .iter()
+        .find(|day| day.day == number)
+        .ok_or_else(|| format!("No solver for {year} day {number}"))?;
+    parallel::set_thread_count(parallel::threads_from_env()?);
+
+    let files = batch_files(Path::new(&dir))?;
+    if files.is_empty() {
+        return Err(format!("No input files found in {dir}").into());
+    }
+
+    eprintln!("Day {}: {}", day.day, day.title);
+    let rows = files.iter().map(|path| solve_file(day, path)).collect::<Result<Vec<_>, _>>()?;
+    print!("{}", batch_table(&rows).render());
+    Ok(())
+}
+
+// Puzzles unlock at midnight EST, which is UTC-5 all through December
+const EST_OFFSET_SECS: i64 = -5 * 60 * 60;
+
+// (year, month, day) of a day count since 1970-01-01, from Howard Hinnant's `civil_from_days`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// The (year, day) of the puzzle released on the EST date at `unix_secs`, or None outside of
+// December 1-25
+fn puzzle_day(unix_secs: i64) -> Option<(u32, u32)> {
+    let (year, month, day) = civil_from_days((unix_secs + EST_OFFSET_SECS).div_euclid(86_400));
+    (month == 12 && day <= 25).then_some((year as u32, day))
+}
+
+const USER_AGENT: &str = "github.com/jsgroth/advent-of-code-2024 (aoc today)";
+
+fn download_input(year: u32, day: u32, path: &Path) -> Result<(), Box<dyn Error>> {
+    let session = env::var("AOC_SESSION").map_err(
+        |_| "Set AOC_SESSION to your adventofcode.com session cookie to download inputs",
+    )?;
+    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
+    eprintln!("Downloading {url}");
+
+    // The cookie goes through a config file on stdin rather than the command line, where other
+    // users could read it
+    let mut curl = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--user-agent", USER_AGENT])
+        .args(["--config", "-"])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Error running curl: {err}"))?;
+    let session = session.replace('\\', "\\\\").replace('"', "\\\"");
+    // Dropping stdin closes it, so that curl stops reading the config
+    curl.stdin.take().unwrap().write_all(format!("cookie = \"session={session}\"\n").as_bytes())?;
+    let output = curl.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Error downloading {url}: {}", stderr.trim()).into());
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, output.stdout)?;
+    Ok(())
+}
+
+fn today_input_path(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(format!("inputs/{year}/day{day}.txt"))
+}
+
+fn today() -> Result<(), Box<dyn Error>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let (year, day) =
+        puzzle_day(now).ok_or("No puzzle today; puzzles are released December 1-25 (EST)")?;
+
+    let entry = days::get(year, day).ok_or_else(|| format!("No solver for {year} day {day}"))?;
+    let path = today_input_path(year, day);
+    if !path.exists() {
+        download_input(year, day, &path)?;
+    }
+
+    let input = fs::read_to_string(&path)?;
+    eprintln!("Day {day}: {}", entry.title);
+    let (part1, part2) = entry.solver.solve(&input::normalize(&input));
+    for solution in [part1, part2].into_iter().filter(|solution| !solution.is_none()) {
+        println!("{solution}");
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match env::args().nth(1).as_deref() {
+        Some("list") => list(),
+        Some("serve") => serve(),
+        Some("bench") => bench(),
+        Some("run") => run(),
+        Some("today") => today(),
+        Some(command) => Err(format!("Unknown command '{command}'; {USAGE}").into()),
+        None => Err(USAGE.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_days() {
+        assert_eq!((1970, 1, 1), civil_from_days(0));
+        assert_eq!((2024, 2, 29), civil_from_days(19_782));
+        assert_eq!((1969, 12, 31), civil_from_days(-1));
+
+        // 2024-12-01 05:00 UTC is midnight EST
+        let unlock = 1_733_029_200;
+        assert_eq!(Some((2024, 1)), puzzle_day(unlock));
+        assert_eq!(None, puzzle_day(unlock - 1));
+        assert_eq!(Some((2024, 25)), puzzle_day(unlock + 25 * 86_400 - 1));
+        assert_eq!(None, puzzle_day(unlock + 25 * 86_400));
+    }
+
+    #[test]
+    fn today_input_paths() {
+        assert_eq!(PathBuf::from("inputs/2024/day7.txt"), today_input_path(2024, 7));
+        assert_ne!(today_input_path(2023, 7), today_input_path(2024, 7));
+    }
+
+    #[test]
+    fn list_rows() {
+        assert_eq!(BTreeSet::from([1, 25]), answered_days("# day answers\n1 11 31\n\n25 3\n"));
+
+        let days = days::year(days::DEFAULT_YEAR).unwrap();
+        let cached = Cached { inputs: BTreeSet::from([1, 2]), answers: BTreeSet::from([1]) };
+        let rendered = list_table(days, Some(&cached)).render();
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(27, lines.len(), "{rendered}");
+        assert!(lines[2].starts_with("  1 | Historian Hysteria"), "{rendered}");
+        assert!(lines[2].contains("| 1, 2  |     | yes   | yes     |"), "{rendered}");
+        assert!(lines[3].contains("| 1, 2  |     | yes   |         |"), "{rendered}");
+        assert!(lines[15].contains("| 1, 2  | yes |       |         |"), "{rendered}");
+        assert!(lines[26].contains("| 1     |     |       |         |"), "{rendered}");
+        assert!(lines[26].ends_with("https://adventofcode.com/2024/day/25"), "{rendered}");
+
+        let rendered = list_table(days, None).render();
+        assert!(!rendered.contains("Input"), "{rendered}");
+    }
+
+    #[test]
+    fn batch_rows() {
+        let day = days::get(2024, 1).unwrap();
+        let row = solve_file(&day, Path::new("sample/2024/day1.txt")).unwrap();
+        assert_eq!("day1.txt", row.file);
+        assert_eq!(["11".to_string(), "31".to_string()], row.answers);
+
+        let panicked = Panicked { label: "Part 2".into(), message: "bad input".into() };
+        assert_eq!("panicked: bad input", answer_cell(Err(panicked)));
+        assert_eq!("", answer_cell(Ok(Solution::None)));
+
+        let rows = [
+            row,
+            BatchRow { file: "b.txt".into(), answers: ["7".into(), String::new()], micros: 2000.0 },
+        ];
+        let rendered = batch_table(&rows).render();
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(6, lines.len(), "{rendered}");
+        assert!(lines[2].starts_with("day1.txt |     11 |     31 |"), "{rendered}");
+        assert!(lines[3].starts_with("b.txt    |      7 |        |"), "{rendered}");
+        assert!(lines[5].starts_with("2 inputs |"), "{rendered}");
+    }
+}
@@ -0,0 +1,31 @@
+//! Writes a random valid input for a day to stdout, for stress testing beyond the official input
+//!
+//! Usage: gen <day> [--size N] [--seed S]
+
+use advent_of_code_2024::generate;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::env;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let day: u32 = env::args().nth(1).expect("ARGS: <day> [--size N] [--seed S]").parse()?;
+    let Some(generator) = generate::get(day) else {
+        let days: Vec<_> = generate::GENERATORS.iter().map(|g| g.day.to_string()).collect();
+        return Err(format!("No generator for day {day}; available: {}", days.join(", ")).into());
+    };
+
+    let size = match advent_of_code_2024::flag_value("--size") {
+        Some(size) => size.parse()?,
+        None => generator.default_size,
+    };
+    let mut rng = match advent_of_code_2024::flag_value("--seed") {
+        Some(seed) => StdRng::seed_from_u64(seed.parse()?),
+        None => StdRng::from_entropy(),
+    };
+
+    eprintln!("Generating day {day} input with {size} {}", generator.size_meaning);
+    print!("{}", (generator.generate)(&mut rng, size));
+
+    Ok(())
+}
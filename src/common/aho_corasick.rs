@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+
+// Finds occurrences of several byte patterns in one pass over a text (Aho-Corasick). The patterns
+// are stored as a trie in which every byte that doesn't continue a pattern instead leads to the
+// node for the longest suffix of the text so far that is still a prefix of some pattern, so
+// matching is one table lookup per byte no matter how many patterns there are
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    // One transition per byte for every node; the root is node 0
+    transitions: Vec<[u32; 256]>,
+    // Patterns that end at each node, including through suffixes, longest first
+    outputs: Vec<Vec<u32>>,
+    pattern_lens: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    // Index of the pattern in the order it was passed to `new`
+    pub pattern: usize,
+    // Byte range in the text
+    pub start: usize,
+    pub end: usize,
+}
+
+const MISSING: u32 = u32::MAX;
+
+impl AhoCorasick {
+    // Panics if any pattern is empty
+    pub fn new(patterns: impl IntoIterator<Item = impl AsRef<[u8]>>) -> Self {
+        let mut transitions = vec![[MISSING; 256]];
+        let mut outputs = vec![Vec::new()];
+        let mut pattern_lens = Vec::new();
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "Patterns must not be empty");
+
+            let mut node = 0;
+            for &byte in pattern {
+                if transitions[node][usize::from(byte)] == MISSING {
+                    transitions[node][usize::from(byte)] = transitions.len() as u32;
+                    transitions.push([MISSING; 256]);
+                    outputs.push(Vec::new());
+                }
+                node = transitions[node][usize::from(byte)] as usize;
+            }
+            outputs[node].push(pattern_lens.len() as u32);
+            pattern_lens.push(pattern.len());
+        }
+
+        // Breadth-first, so that every node's suffix link points at a node that is already complete
+        let mut suffix_links = vec![0; transitions.len()];
+        let mut queue = VecDeque::from([0]);
+        while let Some(node) = queue.pop_front() {
+            let link = suffix_links[node];
+            let children = transitions[node];
+            for (byte, child) in children.into_iter().enumerate() {
+                if child == MISSING {
+                    transitions[node][byte] = if node == 0 { 0 } else { transitions[link][byte] };
+                    continue;
+                }
+
+                let child = child as usize;
+                let child_link = if node == 0 { 0 } else { transitions[link][byte] as usize };
+                suffix_links[child] = child_link;
+                let inherited = outputs[child_link].clone();
+                outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        Self { transitions, outputs, pattern_lens }
+    }
+
+    fn step(&self, node: usize, byte: u8) -> usize {
+        self.transitions[node][usize::from(byte)] as usize
+    }
+
+    fn found(&self, pattern: u32, end: usize) -> Match {
+        let pattern = pattern as usize;
+        Match { pattern, start: end - self.pattern_lens[pattern], end }
+    }
+
+    // Every match in `text`, including overlapping ones, in order of where they end. Matches that
+    // end at the same byte come longest first
+    pub fn find_iter<'a, I>(&'a self, text: I) -> impl Iterator<Item = Match> + 'a
+    where
+        I: IntoIterator<Item = u8>,
+        I::IntoIter: 'a,
+    {
+        let mut node = 0;
+        text.into_iter().enumerate().flat_map(move |(i, byte)| {
+            node = self.step(node, byte);
+            self.outputs[node].iter().map(move |&pattern| self.found(pattern, i + 1))
+        })
+    }
+
+    // The match that ends first among those lying entirely within `text[start..]`, preferring the
+    // longest if several end at the same byte
+    pub fn find(&self, text: &[u8], start: usize) -> Option<Match> {
+        let mut node = 0;
+        for (i, &byte) in text.iter().enumerate().skip(start) {
+            node = self.step(node, byte);
+            if let Some(&pattern) = self.outputs[node].first() {
+                return Some(self.found(pattern, i + 1));
+            }
+        }
+        None
+    }
+
+    pub fn pattern_count(&self) -> usize {
+        self.pattern_lens.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(matcher: &AhoCorasick, text: &str) -> Vec<(usize, usize)> {
+        matcher.find_iter(text.bytes()).map(|m| (m.pattern, m.start)).collect()
+    }
+
+    #[test]
+    fn overlapping() {
+        let matcher = AhoCorasick::new(["he", "she", "his", "hers"]);
+        assert_eq!(4, matcher.pattern_count());
+        assert_eq!(vec![(1, 1), (0, 2), (3, 2)], matches(&matcher, "ushers"));
+        assert_eq!(vec![(2, 1)], matches(&matcher, "ahis"));
+        assert!(matches(&matcher, "xyz").is_empty());
+
+        let matcher = AhoCorasick::new(["XMAS", "SAMX"]);
+        assert_eq!(vec![(0, 0), (1, 3)], matches(&matcher, "XMASAMX"));
+    }
+
+    #[test]
+    fn first_match() {
+        let matcher = AhoCorasick::new(["mul(", "do()", "don't()"]);
+        let text = b"xdon't()mul(do()";
+        assert_eq!(Some(Match { pattern: 2, start: 1, end: 8 }), matcher.find(text, 0));
+        assert_eq!(Some(Match { pattern: 0, start: 8, end: 12 }), matcher.find(text, 2));
+        assert_eq!(Some(Match { pattern: 1, start: 12, end: 16 }), matcher.find(text, 12));
+        assert_eq!(None, matcher.find(text, 13));
+
+        // A shorter pattern that is a suffix of a longer one ends at the same byte
+        let matcher = AhoCorasick::new(["b", "ab"]);
+        assert_eq!(Some(Match { pattern: 1, start: 0, end: 2 }), matcher.find(b"ab", 0));
+    }
+}
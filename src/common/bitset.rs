@@ -0,0 +1,106 @@
+// Fixed-capacity set of small integers, stored as a bit vector
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    // Set that can hold values in 0..capacity
+    pub fn new(capacity: usize) -> Self {
+        Self { words: vec![0; capacity.div_ceil(64)] }
+    }
+
+    // Returns false if the value was already in the set
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / 64, 1 << (value % 64));
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / 64, 1 << (value % 64));
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words.get(value / 64).is_some_and(|&word| word & (1 << (value % 64)) != 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    // Number of values in the set
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn intersection_len(&self, other: &Self) -> usize {
+        self.words.iter().zip(&other.words).map(|(a, b)| (a & b).count_ones() as usize).sum()
+    }
+
+    pub fn union_len(&self, other: &Self) -> usize {
+        self.len() + other.len() - self.intersection_len(other)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut remaining = word;
+            std::iter::from_fn(move || {
+                if remaining == 0 {
+                    return None;
+                }
+                let bit = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                Some(64 * i + bit)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut set = BitSet::new(200);
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(set.insert(130));
+        assert!(!set.insert(3));
+        assert!(set.contains(130));
+        assert!(!set.contains(131));
+        assert!(!set.contains(10_000));
+        assert_eq!(vec![3, 130], set.iter().collect::<Vec<_>>());
+
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(1, set.len());
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_sizes() {
+        let mut a = BitSet::new(100);
+        let mut b = BitSet::new(100);
+        for i in [1, 2, 3, 64, 99] {
+            a.insert(i);
+        }
+        for i in [2, 3, 4, 99] {
+            b.insert(i);
+        }
+
+        assert_eq!(3, a.intersection_len(&b));
+        assert_eq!(6, a.union_len(&b));
+    }
+}
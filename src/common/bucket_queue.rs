@@ -0,0 +1,101 @@
+// Monotone priority queue for small integer priorities (Dial's algorithm). Pops never go back to a
+// lower priority, and every push must be within `max_increment` of the last priority popped, which
+// holds for Dijkstra-style searches whose edge costs are at most `max_increment`. Pushes and pops
+// are O(1) apart from skipping empty buckets, with no comparisons between items; items with equal
+// priority come out in an unspecified order
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+    // Circular buffer indexed by priority modulo the number of buckets
+    buckets: Vec<Vec<T>>,
+    current: usize,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new(max_increment: usize) -> Self {
+        Self { buckets: (0..=max_increment).map(|_| Vec::new()).collect(), current: 0, len: 0 }
+    }
+
+    // Panics if `priority` is lower than the last priority popped, or more than `max_increment`
+    // above it unless the queue is empty
+    pub fn push(&mut self, priority: usize, item: T) {
+        if self.len == 0 && priority >= self.current + self.buckets.len() {
+            self.current = priority;
+        }
+        let max = self.current + self.buckets.len() - 1;
+        assert!(
+            (self.current..=max).contains(&priority),
+            "Priority {priority} is outside of {}..={max}",
+            self.current
+        );
+
+        let bucket = priority % self.buckets.len();
+        self.buckets[bucket].push(item);
+        self.len += 1;
+    }
+
+    // Removes an item with the lowest priority
+    pub fn pop(&mut self) -> Option<(usize, T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        loop {
+            let bucket = self.current % self.buckets.len();
+            if let Some(item) = self.buckets[bucket].pop() {
+                self.len -= 1;
+                return Some((self.current, item));
+            }
+            self.current += 1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pops_in_priority_order() {
+        let mut queue = BucketQueue::new(1000);
+        for (priority, item) in [(5, 'a'), (1000, 'b'), (1, 'c'), (5, 'd')] {
+            queue.push(priority, item);
+        }
+        assert_eq!(4, queue.len());
+        assert_eq!(Some((1, 'c')), queue.pop());
+
+        let (priority, item) = queue.pop().unwrap();
+        assert_eq!(5, priority);
+        assert!(['a', 'd'].contains(&item));
+        assert_eq!(5, queue.pop().unwrap().0);
+
+        // Wraps around the buckets
+        queue.push(1005, 'e');
+        assert_eq!(Some((1000, 'b')), queue.pop());
+        assert_eq!(Some((1005, 'e')), queue.pop());
+        assert_eq!(None, queue.pop());
+        assert!(queue.is_empty());
+
+        // Empty queues can skip ahead
+        queue.push(5000, 'f');
+        assert_eq!(Some((5000, 'f')), queue.pop());
+    }
+
+    #[test]
+    #[should_panic(expected = "Priority 13 is outside of 2..=12")]
+    fn rejects_large_increments() {
+        let mut queue = BucketQueue::new(10);
+        queue.push(2, ());
+        queue.push(2, ());
+        queue.pop();
+        queue.push(13, ());
+    }
+}
@@ -0,0 +1,97 @@
+// Read-only view of a rectangular character map, borrowing the input text rather than copying it.
+// Row `y` is a slice of the input at a fixed stride, so building one allocates nothing; days that
+// only read their map should prefer this over collecting into a Grid
+use crate::{ParseError, Pos2};
+use std::ops::Index;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteGrid<'a> {
+    bytes: &'a [u8],
+    rows: usize,
+    cols: usize,
+    // Distance between row starts, including the line ending
+    stride: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    // Accepts `\n` or `\r\n` line endings and ignores trailing blank lines. Every line must have
+    // the same length
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let text = input.trim_end_matches(['\n', '\r']);
+        let first_line = text.lines().next().unwrap_or_default();
+        let cols = first_line.len();
+        let stride = text.find('\n').map_or(text.len(), |newline| newline + 1);
+
+        let mut rows = 0;
+        for (row, line) in text.lines().enumerate() {
+            if line.len() != cols {
+                let message = format!("Expected {cols} columns, found {}", line.len());
+                return Err(ParseError::at(row, line.len().min(cols), message));
+            }
+            rows += 1;
+        }
+
+        Ok(Self { bytes: text.as_bytes(), rows, cols, stride })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        assert!(y < self.rows, "Row {y} out of bounds for {} rows", self.rows);
+        let start = y * self.stride;
+        &self.bytes[start..start + self.cols]
+    }
+
+    // None outside the grid
+    pub fn get(&self, pos: Pos2<i32>) -> Option<u8> {
+        let (x, y) = (usize::try_from(pos.x).ok()?, usize::try_from(pos.y).ok()?);
+        (x < self.cols && y < self.rows).then(|| self.bytes[y * self.stride + x])
+    }
+}
+
+impl Index<usize> for ByteGrid<'_> {
+    type Output = [u8];
+
+    fn index(&self, y: usize) -> &Self::Output {
+        self.row(y)
+    }
+}
+
+impl Index<Pos2<usize>> for ByteGrid<'_> {
+    type Output = u8;
+
+    fn index(&self, pos: Pos2<usize>) -> &Self::Output {
+        &self.row(pos.y)[pos.x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn views() {
+        for input in ["ab\ncd\nef\n\n", "ab\r\ncd\r\nef\r\n"] {
+            let grid = ByteGrid::parse(input).unwrap();
+            assert_eq!((3, 2), (grid.rows(), grid.cols()));
+            assert_eq!(b"cd", grid.row(1));
+            assert_eq!(b'f', grid[Pos2 { x: 1_usize, y: 2 }]);
+            assert_eq!(b'e', grid[2][0]);
+            assert_eq!(Some(b'b'), grid.get(Pos2 { x: 1, y: 0 }));
+            assert_eq!(None, grid.get(Pos2 { x: 2, y: 0 }));
+            assert_eq!(None, grid.get(Pos2 { x: 0, y: -1 }));
+        }
+
+        let grid = ByteGrid::parse("abc").unwrap();
+        assert_eq!((1, 3), (grid.rows(), grid.cols()));
+
+        let err = ByteGrid::parse("abc\nab\n").unwrap_err();
+        assert_eq!("line 2, column 3: Expected 3 columns, found 2", err.to_string());
+    }
+}
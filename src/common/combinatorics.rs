@@ -0,0 +1,157 @@
+// Iterators over unordered selections and orderings of a slice. Items are never compared, so
+// duplicates in the slice are treated as distinct items
+
+// Every unordered pair (a, b) where a comes before b in `items`
+pub fn pairs<T>(items: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    items.iter().enumerate().flat_map(move |(i, a)| items[i + 1..].iter().map(move |b| (a, b)))
+}
+
+// Every selection of `k` items, in lexicographic order of positions in `items`. Each combination
+// lists its items in slice order
+pub fn combinations<T>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations { items, indices: (0..k).collect(), done: k > items.len() }
+}
+
+#[derive(Debug, Clone)]
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<'a, T> Iterator for Combinations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let combination = self.indices.iter().map(|&i| &self.items[i]).collect();
+
+        // Advance the rightmost index that still has room to move, and reset every index after it
+        // to follow on directly
+        let (n, k) = (self.items.len(), self.indices.len());
+        match (0..k).rev().find(|&i| self.indices[i] < n - k + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(combination)
+    }
+}
+
+// Every ordering of `items`, generated with Heap's algorithm so that each permutation differs from
+// the previous one by a single swap. Starts with the items in slice order
+pub fn permutations<T>(items: &[T]) -> Permutations<'_, T> {
+    let n = items.len();
+    Permutations { items, indices: (0..n).collect(), counters: vec![0; n], i: 1, started: false }
+}
+
+#[derive(Debug, Clone)]
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    // Heap's algorithm's loop counters, one per prefix length
+    counters: Vec<usize>,
+    i: usize,
+    started: bool,
+}
+
+impl<'a, T> Iterator for Permutations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.started {
+            if !heap_step(&mut self.indices, &mut self.counters, &mut self.i) {
+                return None;
+            }
+        } else {
+            self.started = true;
+        }
+
+        Some(self.indices.iter().map(|&i| &self.items[i]).collect())
+    }
+}
+
+// Calls `f` with every ordering of `items`, permuting the slice in place so that nothing is
+// allocated per permutation. Leaves `items` in the last ordering visited rather than restoring it
+pub fn for_each_permutation<T>(items: &mut [T], mut f: impl FnMut(&[T])) {
+    let mut counters = vec![0; items.len()];
+    let mut i = 1;
+    f(items);
+    while heap_step(items, &mut counters, &mut i) {
+        f(items);
+    }
+}
+
+// Swaps `items` into the next permutation, or returns false if every one has been visited
+fn heap_step<T>(items: &mut [T], counters: &mut [usize], i: &mut usize) -> bool {
+    while *i < items.len() {
+        if counters[*i] < *i {
+            let j = if (*i).is_multiple_of(2) { 0 } else { counters[*i] };
+            items.swap(j, *i);
+            counters[*i] += 1;
+            *i = 1;
+            return true;
+        }
+
+        counters[*i] = 0;
+        *i += 1;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn pairs_and_combinations() {
+        let items = [1, 2, 3, 4];
+        assert_eq!(
+            vec![(&1, &2), (&1, &3), (&1, &4), (&2, &3), (&2, &4), (&3, &4)],
+            pairs(&items).collect::<Vec<_>>()
+        );
+        assert_eq!(0, pairs(&[1]).count());
+
+        assert_eq!(
+            vec![vec![&1, &2, &3], vec![&1, &2, &4], vec![&1, &3, &4], vec![&2, &3, &4]],
+            combinations(&items, 3).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![Vec::<&i32>::new()], combinations(&items, 0).collect::<Vec<_>>());
+        assert_eq!(None, combinations(&items, 5).next());
+        assert_eq!(252, combinations(&[0; 10], 5).count());
+
+        let pairs_from_combinations: Vec<_> =
+            combinations(&items, 2).map(|pair| (pair[0], pair[1])).collect();
+        assert_eq!(pairs(&items).collect::<Vec<_>>(), pairs_from_combinations);
+    }
+
+    #[test]
+    fn permutation_orders() {
+        assert_eq!(
+            vec![[1, 2, 3], [2, 1, 3], [3, 1, 2], [1, 3, 2], [2, 3, 1], [3, 2, 1]],
+            permutations(&[1, 2, 3]).map(|p| [*p[0], *p[1], *p[2]]).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![Vec::<&i32>::new()], permutations::<i32>(&[]).collect::<Vec<_>>());
+
+        let items: Vec<_> = (0..6).collect();
+        let mut seen = HashSet::new();
+        let mut in_place = Vec::new();
+        for_each_permutation(&mut items.clone(), |p| {
+            assert!(seen.insert(p.to_vec()));
+            in_place.push(p.to_vec());
+        });
+        assert_eq!(720, seen.len());
+
+        let iterated: Vec<Vec<_>> =
+            permutations(&items).map(|p| p.into_iter().copied().collect()).collect();
+        assert_eq!(in_place, iterated);
+    }
+}
@@ -0,0 +1,125 @@
+use rustc_hash::FxHashMap;
+use std::collections::hash_map;
+use std::hash::Hash;
+
+// Multiset that counts occurrences of each distinct item
+#[derive(Debug, Clone)]
+pub struct Counter<T>(FxHashMap<T, u64>);
+
+impl<T> Default for Counter<T> {
+    fn default() -> Self {
+        Self(FxHashMap::default())
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, item: T, n: u64) {
+        *self.0.entry(item).or_default() += n;
+    }
+
+    pub fn incr(&mut self, item: T) {
+        self.add(item, 1);
+    }
+
+    // Returns 0 for items that have never been added
+    pub fn get(&self, item: &T) -> u64 {
+        self.0.get(item).copied().unwrap_or_default()
+    }
+
+    // Number of distinct items
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Adds every count from `other`, e.g. to combine counters built on separate threads
+    pub fn merge(&mut self, other: Self) {
+        for (item, count) in other {
+            self.add(item, count);
+        }
+    }
+
+    // Sum of all counts
+    pub fn total(&self) -> u64 {
+        self.0.values().sum()
+    }
+
+    // Returns the item with the highest count; ties are broken arbitrarily
+    pub fn most_common(&self) -> Option<(&T, u64)> {
+        self.iter().max_by_key(|&(_, count)| count)
+    }
+
+    // Returns the `n` items with the highest counts, in descending order of count
+    pub fn most_common_n(&self, n: usize) -> Vec<(&T, u64)> {
+        let mut items: Vec<_> = self.iter().collect();
+        items.sort_by(|(_, a), (_, b)| b.cmp(a));
+        items.truncate(n);
+        items
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> {
+        self.0.iter().map(|(item, &count)| (item, count))
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.incr(item);
+        }
+    }
+}
+
+impl<T> IntoIterator for Counter<T> {
+    type Item = (T, u64);
+    type IntoIter = hash_map::IntoIter<T, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(5, counter.get(&'a'));
+        assert_eq!(2, counter.get(&'b'));
+        assert_eq!(0, counter.get(&'z'));
+        assert_eq!(5, counter.len());
+        assert_eq!(11, counter.total());
+
+        counter.add('z', 10);
+        assert_eq!(Some((&'z', 10)), counter.most_common());
+        assert_eq!(vec![(&'z', 10), (&'a', 5)], counter.most_common_n(2));
+
+        counter.merge("aay".chars().collect());
+        assert_eq!((7, 1, 7), (counter.get(&'a'), counter.get(&'y'), counter.len()));
+    }
+
+    #[test]
+    fn empty() {
+        let counter: Counter<u32> = Counter::new();
+        assert!(counter.is_empty());
+        assert_eq!(None, counter.most_common());
+        assert_eq!(0, counter.total());
+    }
+}
@@ -0,0 +1,98 @@
+// Cycle detection for "simulate until the state repeats, then extrapolate" puzzles. With states
+// that are only comparable, Brent's algorithm keeps two states at a time instead of a set of every
+// state seen so far, at the cost of simulating the prefix and the cycle a few times over. Hashable
+// states can instead be simulated once with `first_repeat`
+use rustc_hash::FxHashSet;
+use std::hash::Hash;
+
+// Returns (mu, lambda) such that the state after mu steps is the first state that repeats, and it
+// repeats every lambda steps after that. Never returns if the states don't eventually cycle
+pub fn find_cycle<T: Clone + PartialEq>(
+    initial: T,
+    mut step: impl FnMut(&T) -> T,
+) -> (usize, usize) {
+    // Doubles the window until the hare comes back around to the tortoise, which is then inside
+    // the cycle
+    let mut power = 1;
+    let mut cycle_len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == cycle_len {
+            tortoise = hare.clone();
+            power *= 2;
+            cycle_len = 0;
+        }
+        hare = step(&hare);
+        cycle_len += 1;
+    }
+
+    // With the hare one cycle ahead, the two first meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..cycle_len {
+        hare = step(&hare);
+    }
+    let mut prefix_len = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_len += 1;
+    }
+
+    (prefix_len, cycle_len)
+}
+
+// The state after `n` steps, skipping whole cycles once `n` runs past the first one
+pub fn nth<T: Clone + PartialEq>(initial: T, n: usize, mut step: impl FnMut(&T) -> T) -> T {
+    let (prefix_len, cycle_len) = find_cycle(initial.clone(), &mut step);
+    let n = if n < prefix_len { n } else { prefix_len + (n - prefix_len) % cycle_len };
+    (0..n).fold(initial, |state, _| step(&state))
+}
+
+// Steps until a state comes up for the second time, returning the number of steps taken at that
+// point along with the repeated state. `step` returns None when the simulation ends, e.g. when a
+// guard walks off the map, in which case nothing repeated and the result is None
+pub fn first_repeat<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Option<(usize, T)> {
+    let mut seen = FxHashSet::default();
+    let mut state = initial;
+    for steps in 1.. {
+        let next = step(&state)?;
+        seen.insert(state);
+        if seen.contains(&next) {
+            return Some((steps, next));
+        }
+        state = next;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles() {
+        // 2 -> 4 -> 16 -> 256 -> 286 -> 16 -> ...
+        let square = |&n: &u64| n * n % 290;
+        assert_eq!((2, 3), find_cycle(2, square));
+        assert_eq!((0, 1), find_cycle(0, square));
+        assert_eq!((0, 10), find_cycle(0, |&n: &u32| (n + 1) % 10));
+
+        assert_eq!(256, nth(2, 3, square));
+        assert_eq!(286, nth(2, 1_000_000, square));
+        assert_eq!(2, nth(2, 0, square));
+        assert_eq!(7, nth(0, 1_000_007, |&n: &u32| (n + 1) % 10));
+    }
+
+    #[test]
+    fn repeats() {
+        let square = |&n: &u64| Some(n * n % 290);
+        assert_eq!(Some((5, 16)), first_repeat(2, square));
+        assert_eq!(Some((1, 0)), first_repeat(0, square));
+        assert_eq!(None, first_repeat(0, |&n: &u32| (n < 10).then_some(n + 1)));
+    }
+}
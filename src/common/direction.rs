@@ -0,0 +1,143 @@
+use crate::Pos2;
+use std::ops::{Index, IndexMut};
+
+// Grid directions, with up being towards row 0. Variants are in clockwise order so that rotations
+// are index arithmetic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub const fn rotate_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub const fn rotate_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub const fn delta(self) -> Pos2<i32> {
+        match self {
+            Self::Up => Pos2 { x: 0, y: -1 },
+            Self::Right => Pos2 { x: 1, y: 0 },
+            Self::Down => Pos2 { x: 0, y: 1 },
+            Self::Left => Pos2 { x: -1, y: 0 },
+        }
+    }
+
+    // One of `^>v<`, as used in puzzle inputs for moves and facings
+    pub const fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::Up),
+            '>' => Some(Self::Right),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub const fn arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+}
+
+// Fixed-size per-direction storage, for state that would otherwise be keyed by (position,
+// direction) in a hash map or packed into bit flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DirectionMap<T>(pub [T; 4]);
+
+impl<T: Copy> DirectionMap<T> {
+    pub const fn splat(value: T) -> Self {
+        Self([value; 4])
+    }
+}
+
+impl<T> DirectionMap<T> {
+    pub fn from_fn(mut f: impl FnMut(Direction) -> T) -> Self {
+        Self(Direction::ALL.map(&mut f))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &T)> {
+        Direction::ALL.into_iter().zip(&self.0)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+}
+
+impl DirectionMap<bool> {
+    pub fn any(&self) -> bool {
+        self.0.contains(&true)
+    }
+}
+
+impl<T> Index<Direction> for DirectionMap<T> {
+    type Output = T;
+
+    fn index(&self, index: Direction) -> &Self::Output {
+        &self.0[index as usize]
+    }
+}
+
+impl<T> IndexMut<Direction> for DirectionMap<T> {
+    fn index_mut(&mut self, index: Direction) -> &mut Self::Output {
+        &mut self.0[index as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations() {
+        for direction in Direction::ALL {
+            assert_eq!(direction, direction.rotate_left().rotate_right());
+            assert_eq!(direction.reverse(), direction.rotate_right().rotate_right());
+            assert_eq!(direction.reverse().delta(), Pos2 { x: 0, y: 0 } - direction.delta());
+        }
+        assert_eq!(Direction::Left, Direction::Up.rotate_left());
+        assert_eq!(Direction::Right, Direction::Up.rotate_right());
+    }
+
+    #[test]
+    fn arrows() {
+        for direction in Direction::ALL {
+            assert_eq!(Some(direction), Direction::from_arrow(direction.arrow()));
+        }
+        assert_eq!(Some(Direction::Down), Direction::from_arrow('v'));
+        assert_eq!(None, Direction::from_arrow('V'));
+    }
+
+    #[test]
+    fn direction_map() {
+        let mut map = DirectionMap::splat(false);
+        assert!(!map.any());
+        map[Direction::Down] = true;
+        assert!(map.any());
+        assert_eq!(
+            vec![(Direction::Down, &true)],
+            map.iter().filter(|&(_, &v)| v).collect::<Vec<_>>()
+        );
+
+        let deltas = DirectionMap::from_fn(Direction::delta);
+        assert_eq!(Pos2 { x: -1, y: 0 }, deltas[Direction::Left]);
+        assert_eq!(4, deltas.values().count());
+    }
+}
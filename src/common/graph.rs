@@ -0,0 +1,143 @@
+use crate::BitSet;
+
+// Undirected graph over the vertices 0..n, with adjacency stored as one bit set per vertex
+#[derive(Debug, Clone)]
+pub struct Graph {
+    adjacency: Vec<BitSet>,
+    // Neighbors of each vertex in ascending order
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    pub fn new(len: usize) -> Self {
+        Self { adjacency: vec![BitSet::new(len); len], neighbors: vec![Vec::new(); len] }
+    }
+
+    pub fn from_edges(len: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let mut graph = Self::new(len);
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    // Returns false if the edge already existed. Self-loops are ignored
+    pub fn add_edge(&mut self, a: usize, b: usize) -> bool {
+        if a == b || !self.adjacency[a].insert(b) {
+            return false;
+        }
+        self.adjacency[b].insert(a);
+
+        for (from, to) in [(a, b), (b, a)] {
+            let neighbors = &mut self.neighbors[from];
+            let i = neighbors.partition_point(|&n| n < to);
+            neighbors.insert(i, to);
+        }
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    pub fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.adjacency[a].contains(b)
+    }
+
+    pub fn neighbors(&self, vertex: usize) -> &[usize] {
+        &self.neighbors[vertex]
+    }
+
+    // Calls `f` once for every clique of exactly `k` vertices, with the vertices in ascending order.
+    // Each clique is built by extending a smaller clique with a common neighbor larger than all of
+    // its vertices, so no clique is visited twice
+    pub fn for_each_clique(&self, k: usize, mut f: impl FnMut(&[usize])) {
+        if k == 0 {
+            f(&[]);
+            return;
+        }
+
+        let mut clique = Vec::with_capacity(k);
+        for vertex in 0..self.len() {
+            let candidates: Vec<_> =
+                self.neighbors[vertex].iter().copied().filter(|&n| n > vertex).collect();
+            clique.push(vertex);
+            self.extend_clique(k, &mut clique, &candidates, &mut f);
+            clique.pop();
+        }
+    }
+
+    fn extend_clique(
+        &self,
+        k: usize,
+        clique: &mut Vec<usize>,
+        candidates: &[usize],
+        f: &mut impl FnMut(&[usize]),
+    ) {
+        if clique.len() == k {
+            f(clique);
+            return;
+        }
+
+        // Not enough candidates left to reach k vertices
+        if clique.len() + candidates.len() < k {
+            return;
+        }
+
+        for (i, &vertex) in candidates.iter().enumerate() {
+            let next_candidates: Vec<_> =
+                candidates[i + 1..].iter().copied().filter(|&n| self.has_edge(vertex, n)).collect();
+            clique.push(vertex);
+            self.extend_clique(k, clique, &next_candidates, f);
+            clique.pop();
+        }
+    }
+
+    pub fn cliques(&self, k: usize) -> Vec<Vec<usize>> {
+        let mut cliques = Vec::new();
+        self.for_each_clique(k, |clique| cliques.push(clique.to_vec()));
+        cliques
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges() {
+        let mut graph = Graph::from_edges(4, [(0, 1), (2, 1), (3, 1)]);
+        assert!(!graph.add_edge(1, 0));
+        assert!(!graph.add_edge(2, 2));
+        assert!(graph.has_edge(1, 2));
+        assert!(!graph.has_edge(0, 2));
+        assert_eq!(&[0, 2, 3], graph.neighbors(1));
+    }
+
+    #[test]
+    fn cliques() {
+        // K4 on 0..4 plus a triangle 3, 4, 5 sharing vertex 3
+        let mut graph = Graph::new(6);
+        for a in 0..4 {
+            for b in a + 1..4 {
+                graph.add_edge(a, b);
+            }
+        }
+        for (a, b) in [(3, 4), (4, 5), (5, 3)] {
+            graph.add_edge(a, b);
+        }
+
+        assert_eq!(6, graph.cliques(1).len());
+        assert_eq!(9, graph.cliques(2).len());
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![0, 1, 3], vec![0, 2, 3], vec![1, 2, 3], vec![3, 4, 5]],
+            graph.cliques(3)
+        );
+        assert_eq!(vec![vec![0, 1, 2, 3]], graph.cliques(4));
+        assert!(graph.cliques(5).is_empty());
+    }
+}
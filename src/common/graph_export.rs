@@ -0,0 +1,224 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Dot,
+    GraphMl,
+}
+
+impl ExportFormat {
+    // Picks the format based on the file extension; anything other than .graphml/.xml is DOT
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("graphml" | "xml") => Self::GraphMl,
+            _ => Self::Dot,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportNode {
+    pub id: String,
+    pub attrs: Vec<(&'static str, String)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportEdge {
+    pub from: String,
+    pub to: String,
+    pub attrs: Vec<(&'static str, String)>,
+}
+
+// Attributes use Graphviz names (label, color, style, ...); GraphML output emits each distinct
+// attribute name as a string-valued data key
+#[derive(Debug, Clone, Default)]
+pub struct ExportGraph {
+    pub directed: bool,
+    pub attrs: Vec<(&'static str, String)>,
+    pub node_attrs: Vec<(&'static str, String)>,
+    pub nodes: Vec<ExportNode>,
+    pub edges: Vec<ExportEdge>,
+}
+
+impl ExportNode {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into(), attrs: Vec::new() }
+    }
+
+    pub fn attr(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.attrs.push((name, value.into()));
+        self
+    }
+}
+
+impl ExportEdge {
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self { from: from.into(), to: to.into(), attrs: Vec::new() }
+    }
+
+    pub fn attr(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.attrs.push((name, value.into()));
+        self
+    }
+}
+
+impl ExportGraph {
+    pub fn new(directed: bool) -> Self {
+        Self { directed, ..Self::default() }
+    }
+
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Dot => self.to_dot(),
+            ExportFormat::GraphMl => self.to_graphml(),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let (keyword, edge_op) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+
+        let mut out = format!("{keyword} {{\n");
+        for (name, value) in &self.attrs {
+            writeln!(out, "    {name}=\"{}\";", dot_escape(value)).unwrap();
+        }
+        if !self.node_attrs.is_empty() {
+            writeln!(out, "    node [{}];", dot_attrs(&self.node_attrs)).unwrap();
+        }
+
+        for node in &self.nodes {
+            write!(out, "    \"{}\"", dot_escape(&node.id)).unwrap();
+            if !node.attrs.is_empty() {
+                write!(out, " [{}]", dot_attrs(&node.attrs)).unwrap();
+            }
+            out.push_str(";\n");
+        }
+
+        for edge in &self.edges {
+            write!(
+                out,
+                "    \"{}\" {edge_op} \"{}\"",
+                dot_escape(&edge.from),
+                dot_escape(&edge.to)
+            )
+            .unwrap();
+            if !edge.attrs.is_empty() {
+                write!(out, " [{}]", dot_attrs(&edge.attrs)).unwrap();
+            }
+            out.push_str(";\n");
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let node_keys: BTreeSet<_> =
+            self.nodes.iter().flat_map(|node| node.attrs.iter().map(|&(name, _)| name)).collect();
+        let edge_keys: BTreeSet<_> =
+            self.edges.iter().flat_map(|edge| edge.attrs.iter().map(|&(name, _)| name)).collect();
+
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        ));
+        for (domain, keys) in [("node", &node_keys), ("edge", &edge_keys)] {
+            for key in keys {
+                writeln!(
+                    out,
+                    "  <key id=\"{domain}_{key}\" for=\"{domain}\" attr.name=\"{key}\" attr.type=\"string\"/>"
+                )
+                .unwrap();
+            }
+        }
+
+        let edge_default = if self.directed { "directed" } else { "undirected" };
+        writeln!(out, "  <graph edgedefault=\"{edge_default}\">").unwrap();
+
+        for node in &self.nodes {
+            writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id)).unwrap();
+            write_graphml_data(&mut out, "node", &node.attrs);
+            out.push_str("    </node>\n");
+        }
+
+        for edge in &self.edges {
+            writeln!(
+                out,
+                "    <edge source=\"{}\" target=\"{}\">",
+                xml_escape(&edge.from),
+                xml_escape(&edge.to)
+            )
+            .unwrap();
+            write_graphml_data(&mut out, "edge", &edge.attrs);
+            out.push_str("    </edge>\n");
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+fn dot_attrs(attrs: &[(&'static str, String)]) -> String {
+    let attrs: Vec<_> =
+        attrs.iter().map(|(name, value)| format!("{name}=\"{}\"", dot_escape(value))).collect();
+    attrs.join(", ")
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn write_graphml_data(out: &mut String, domain: &str, attrs: &[(&'static str, String)]) {
+    for (name, value) in attrs {
+        writeln!(out, "      <data key=\"{domain}_{name}\">{}</data>", xml_escape(value)).unwrap();
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph(directed: bool) -> ExportGraph {
+        let mut graph = ExportGraph::new(directed);
+        graph.nodes.push(ExportNode::new("a").attr("color", "red"));
+        graph.nodes.push(ExportNode::new("b\"<"));
+        graph.edges.push(ExportEdge::new("a", "b\"<").attr("label", "x&y"));
+        graph
+    }
+
+    #[test]
+    fn dot() {
+        assert_eq!(
+            "graph {\n    \"a\" [color=\"red\"];\n    \"b\\\"<\";\n    \"a\" -- \"b\\\"<\" [label=\"x&y\"];\n}\n",
+            sample_graph(false).to_dot()
+        );
+
+        assert!(sample_graph(true).to_dot().contains("\"a\" -> \"b\\\"<\""));
+    }
+
+    #[test]
+    fn graphml() {
+        let graphml = sample_graph(true).to_graphml();
+        assert!(graphml.contains("<key id=\"node_color\" for=\"node\""));
+        assert!(graphml.contains("<key id=\"edge_label\" for=\"edge\""));
+        assert!(graphml.contains("<graph edgedefault=\"directed\">"));
+        assert!(graphml.contains("<node id=\"b&quot;&lt;\">"));
+        assert!(graphml.contains("<data key=\"edge_label\">x&amp;y</data>"));
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(ExportFormat::Dot, ExportFormat::from_path("out.dot"));
+        assert_eq!(ExportFormat::GraphMl, ExportFormat::from_path("out.graphml"));
+        assert_eq!(ExportFormat::Dot, ExportFormat::from_path("out"));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{Direction, Pos2, Rect};
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T>(pub Vec<Vec<T>>);
+
+impl<T: Default + Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self(vec![vec![T::default(); cols]; rows])
+    }
+
+    pub fn same_size_as<T2>(other: &Grid<T2>) -> Self {
+        Self(vec![vec![T::default(); other.cols()]; other.rows()])
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn rows(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.0[0].len()
+    }
+
+    // Every position that can index this grid; empty for a grid with no rows
+    pub fn bounds(&self) -> Rect<i32> {
+        Rect::from_size(self.0.first().map_or(0, Vec::len), self.rows())
+    }
+
+    pub fn in_bounds(&self, pos: Pos2<i32>) -> bool {
+        self.bounds().contains(pos)
+    }
+}
+
+impl<T> Index<Pos2<i32>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Pos2<i32>) -> &Self::Output {
+        &self.0[index.y as usize][index.x as usize]
+    }
+}
+
+impl<T> IndexMut<Pos2<i32>> for Grid<T> {
+    fn index_mut(&mut self, index: Pos2<i32>) -> &mut Self::Output {
+        &mut self.0[index.y as usize][index.x as usize]
+    }
+}
+
+impl<T> Index<Pos2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Pos2<usize>) -> &Self::Output {
+        &self.0[index.y][index.x]
+    }
+}
+
+impl<T> IndexMut<Pos2<usize>> for Grid<T> {
+    fn index_mut(&mut self, index: Pos2<usize>) -> &mut Self::Output {
+        &mut self.0[index.y][index.x]
+    }
+}
+
+// The 8 rotations and reflections of a rectangular grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GridTransform {
+    Identity,
+    // Clockwise
+    RotateRight,
+    RotateHalf,
+    // Counterclockwise
+    RotateLeft,
+    // Mirror left-to-right
+    FlipHorizontal,
+    // Mirror top-to-bottom
+    FlipVertical,
+    // Mirror along the main diagonal
+    Transpose,
+    // Mirror along the anti-diagonal
+    AntiTranspose,
+}
+
+impl GridTransform {
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::RotateRight,
+        Self::RotateHalf,
+        Self::RotateLeft,
+        Self::FlipHorizontal,
+        Self::FlipVertical,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::RotateRight => Self::RotateLeft,
+            Self::RotateLeft => Self::RotateRight,
+            _ => self,
+        }
+    }
+
+    pub const fn swaps_dimensions(self) -> bool {
+        matches!(self, Self::RotateRight | Self::RotateLeft | Self::Transpose | Self::AntiTranspose)
+    }
+
+    // Maps a position in a grid with the given dimensions to its position in the transformed grid
+    pub const fn map_pos(self, pos: Pos2<usize>, rows: usize, cols: usize) -> Pos2<usize> {
+        let Pos2 { x, y } = pos;
+        match self {
+            Self::Identity => Pos2 { x, y },
+            Self::RotateRight => Pos2 { x: rows - 1 - y, y: x },
+            Self::RotateHalf => Pos2 { x: cols - 1 - x, y: rows - 1 - y },
+            Self::RotateLeft => Pos2 { x: y, y: cols - 1 - x },
+            Self::FlipHorizontal => Pos2 { x: cols - 1 - x, y },
+            Self::FlipVertical => Pos2 { x, y: rows - 1 - y },
+            Self::Transpose => Pos2 { x: y, y: x },
+            Self::AntiTranspose => Pos2 { x: rows - 1 - y, y: cols - 1 - x },
+        }
+    }
+
+    pub const fn map_direction(self, direction: Direction) -> Direction {
+        match self {
+            Self::Identity => direction,
+            Self::RotateRight => direction.rotate_right(),
+            Self::RotateHalf => direction.reverse(),
+            Self::RotateLeft => direction.rotate_left(),
+            Self::FlipHorizontal => match direction {
+                Direction::Left | Direction::Right => direction.reverse(),
+                Direction::Up | Direction::Down => direction,
+            },
+            Self::FlipVertical => match direction {
+                Direction::Up | Direction::Down => direction.reverse(),
+                Direction::Left | Direction::Right => direction,
+            },
+            Self::Transpose => match direction {
+                Direction::Up => Direction::Left,
+                Direction::Left => Direction::Up,
+                Direction::Down => Direction::Right,
+                Direction::Right => Direction::Down,
+            },
+            Self::AntiTranspose => match direction {
+                Direction::Up => Direction::Right,
+                Direction::Right => Direction::Up,
+                Direction::Down => Direction::Left,
+                Direction::Left => Direction::Down,
+            },
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn transformed(&self, transform: GridTransform) -> Self {
+        let (rows, cols) = if transform.swaps_dimensions() {
+            (self.cols(), self.rows())
+        } else {
+            (self.rows(), self.cols())
+        };
+
+        // Pull each cell from its source position in the original grid
+        let inverse = transform.inverse();
+        Self(
+            (0..rows)
+                .map(|y| {
+                    (0..cols)
+                        .map(|x| self[inverse.map_pos(Pos2 { x, y }, rows, cols)].clone())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid<u8> {
+        Grid(s.lines().map(|line| line.bytes().collect()).collect())
+    }
+
+    #[test]
+    fn bounds() {
+        let grid = grid("abc\ndef");
+        assert_eq!(Rect::from_size(3, 2), grid.bounds());
+        assert!(grid.in_bounds(Pos2 { x: 2, y: 1 }));
+        assert!(!grid.in_bounds(Pos2 { x: 1, y: 2 }));
+        assert!(!grid.in_bounds(Pos2 { x: -1, y: 0 }));
+        assert!(!Grid::<u8>(vec![]).in_bounds(Pos2 { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn transforms() {
+        let original = grid("abc\ndef");
+        let expected = [
+            (GridTransform::Identity, "abc\ndef"),
+            (GridTransform::RotateRight, "da\neb\nfc"),
+            (GridTransform::RotateHalf, "fed\ncba"),
+            (GridTransform::RotateLeft, "cf\nbe\nad"),
+            (GridTransform::FlipHorizontal, "cba\nfed"),
+            (GridTransform::FlipVertical, "def\nabc"),
+            (GridTransform::Transpose, "ad\nbe\ncf"),
+            (GridTransform::AntiTranspose, "fc\neb\nda"),
+        ];
+        for (transform, expected) in expected {
+            let transformed = original.transformed(transform);
+            assert_eq!(grid(expected), transformed, "{transform:?}");
+            assert_eq!(original, transformed.transformed(transform.inverse()), "{transform:?}");
+        }
+    }
+
+    #[test]
+    fn positions_and_directions_follow_cells() {
+        let original = grid("abc\ndef");
+        for transform in GridTransform::ALL {
+            let transformed = original.transformed(transform);
+            for y in 0..original.rows() {
+                for x in 0..original.cols() {
+                    let pos = Pos2 { x, y };
+                    let mapped = transform.map_pos(pos, original.rows(), original.cols());
+                    assert_eq!(original[pos], transformed[mapped], "{transform:?} {pos:?}");
+
+                    // A step in any direction lands on the same neighbor after transforming
+                    for direction in Direction::ALL {
+                        let delta = direction.delta();
+                        let (Some(nx), Some(ny)) = (
+                            x.checked_add_signed(delta.x as isize),
+                            y.checked_add_signed(delta.y as isize),
+                        ) else {
+                            continue;
+                        };
+                        if nx >= original.cols() || ny >= original.rows() {
+                            continue;
+                        }
+
+                        let neighbor = Pos2 { x: nx, y: ny };
+                        let mapped_delta = transform.map_direction(direction).delta();
+                        let mapped_neighbor =
+                            transform.map_pos(neighbor, original.rows(), original.cols());
+                        assert_eq!(
+                            mapped_neighbor,
+                            Pos2 {
+                                x: mapped.x.wrapping_add_signed(mapped_delta.x as isize),
+                                y: mapped.y.wrapping_add_signed(mapped_delta.y as isize),
+                            },
+                            "{transform:?} {pos:?} {direction:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
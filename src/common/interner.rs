@@ -0,0 +1,66 @@
+use rustc_hash::FxHashMap;
+
+// Maps strings to dense u32 ids, assigned in order of first appearance, and ids back to strings.
+// Strings are borrowed rather than copied, typically from the puzzle input
+#[derive(Debug, Clone, Default)]
+pub struct Interner<'a> {
+    ids: FxHashMap<&'a str, u32>,
+    names: Vec<&'a str>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, name: &'a str) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len() as u32;
+        self.ids.insert(name, id);
+        self.names.push(name);
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u32) -> &'a str {
+        self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    // Iterates over (id, name) pairs in id order
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &'a str)> + '_ {
+        self.names.iter().enumerate().map(|(id, &name)| (id as u32, name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern() {
+        let mut interner = Interner::new();
+        assert_eq!(0, interner.intern("kh"));
+        assert_eq!(1, interner.intern("tc"));
+        assert_eq!(0, interner.intern("kh"));
+        assert_eq!(2, interner.len());
+
+        assert_eq!("tc", interner.name(1));
+        assert_eq!(Some(1), interner.get("tc"));
+        assert_eq!(None, interner.get("qp"));
+        assert_eq!(vec![(0, "kh"), (1, "tc")], interner.iter().collect::<Vec<_>>());
+    }
+}
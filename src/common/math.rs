@@ -0,0 +1,231 @@
+// Number of decimal digits, counting 0 as a single digit
+pub const fn num_digits(n: u64) -> u32 {
+    if n == 0 { 1 } else { n.ilog10() + 1 }
+}
+
+// Splits into the numbers formed by the left and right halves of the decimal digits, e.g. 1234
+// becomes (12, 34) and 1000 becomes (10, 0). With an odd number of digits, the left half gets the
+// extra digit
+pub const fn split_digits(n: u64) -> (u64, u64) {
+    let pow10 = 10_u64.pow(num_digits(n) / 2);
+    (n / pow10, n % pow10)
+}
+
+// Appends the decimal digits of `b` to `a`, e.g. concat(12, 345) = 12345 and concat(5, 0) = 50
+pub const fn concat(a: u64, b: u64) -> u64 {
+    a * 10_u64.pow(num_digits(b)) + b
+}
+
+// Always returns a non-negative value
+pub const fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub const fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+// Returns (g, x, y) such that a*x + b*y = g, where g = gcd(a, b)
+pub const fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 { (-old_r, -old_x, -old_y) } else { (old_r, old_x, old_y) }
+}
+
+// Returns x in 0..m such that a*x = 1 (mod m), or None if a and m are not coprime or m is not
+// positive
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+// Solves a*x = b (mod m), returning (r, n) such that the solutions are exactly x = r (mod n), where
+// n = m / gcd(a, m). Returns None if there are no solutions or m is not positive. If a = b = 0
+// (mod m) then every x is a solution and the result is (0, 1)
+pub fn linear_congruence(a: i64, b: i64, m: i64) -> Option<(i64, i64)> {
+    if m <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    let b = b.rem_euclid(m);
+    if b % g != 0 {
+        return None;
+    }
+
+    let n = m / g;
+    let r = (i128::from(b / g) * i128::from(x)).rem_euclid(i128::from(n));
+    Some((r as i64, n))
+}
+
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    if m == 1 {
+        return 0;
+    }
+
+    let m = u128::from(m);
+    let mut base = u128::from(base) % m;
+    let mut result = 1;
+    while exp != 0 {
+        if exp & 1 != 0 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+// Chinese Remainder Theorem: given congruences x = r (mod m), returns (x, M) where x is the
+// smallest non-negative solution and M is the lcm of all moduli. Moduli do not need to be
+// pairwise coprime; returns None if the congruences are inconsistent or any modulus is not positive
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut modulus: i128 = 1;
+    for &(r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+
+        let m = i128::from(m);
+        let r = i128::from(r).rem_euclid(m);
+
+        // Solve x + modulus*k = r (mod m) for k
+        let (g, inv, _) = extended_gcd_i128(modulus, m);
+        if (r - x) % g != 0 {
+            return None;
+        }
+
+        let step = m / g;
+        let k = ((r - x) / g % step * inv).rem_euclid(step);
+        x += modulus * k;
+        modulus *= step;
+        x = x.rem_euclid(modulus);
+    }
+
+    Some((i64::try_from(x).ok()?, i64::try_from(modulus).ok()?))
+}
+
+fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+
+    let (g, x, y) = extended_gcd_i128(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits() {
+        assert_eq!(1, num_digits(0));
+        assert_eq!(1, num_digits(9));
+        assert_eq!(2, num_digits(10));
+        assert_eq!(3, num_digits(999));
+        assert_eq!(20, num_digits(u64::MAX));
+
+        assert_eq!((12, 34), split_digits(1234));
+        assert_eq!((10, 0), split_digits(1000));
+        assert_eq!((1, 0), split_digits(10));
+        assert_eq!((12, 3), split_digits(123));
+        assert_eq!((7, 0), split_digits(7));
+
+        assert_eq!(12345, concat(12, 345));
+        assert_eq!(50, concat(5, 0));
+        assert_eq!(910, concat(9, 10));
+        assert_eq!(7, concat(0, 7));
+    }
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(6, gcd(54, 24));
+        assert_eq!(6, gcd(-54, 24));
+        assert_eq!(5, gcd(0, 5));
+        assert_eq!(0, gcd(0, 0));
+
+        assert_eq!(10403, lcm(101, 103));
+        assert_eq!(12, lcm(4, -6));
+        assert_eq!(0, lcm(0, 6));
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [(240, 46), (46, 240), (-35, 15), (17, 0), (101, 103)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(gcd(a, b), g, "{a} {b}");
+            assert_eq!(g, a * x + b * y, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(Some(7), mod_inverse(-3, 11));
+        assert_eq!(None, mod_inverse(6, 9));
+
+        assert_eq!(445, mod_pow(4, 13, 497));
+        assert_eq!(0, mod_pow(5, 0, 1));
+        assert_eq!(1, mod_pow(u64::MAX, u64::MAX, 2));
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((5, 10403)), crt(&[(5, 101), (5, 103)]));
+
+        // Non-coprime moduli
+        assert_eq!(Some((10, 12)), crt(&[(2, 4), (4, 6)]));
+        assert_eq!(None, crt(&[(1, 4), (2, 6)]));
+
+        assert_eq!(Some((0, 1)), crt(&[]));
+    }
+
+    #[test]
+    fn linear_congruences() {
+        assert_eq!(Some((4, 11)), linear_congruence(3, 1, 11));
+        assert_eq!(Some((3, 5)), linear_congruence(4, 2, 10));
+        assert_eq!(None, linear_congruence(4, 3, 10));
+        assert_eq!(Some((0, 1)), linear_congruence(0, 7, 7));
+        assert_eq!(None, linear_congruence(0, 3, 7));
+        assert_eq!(Some((48, 101)), linear_congruence(-2, 5, 101));
+
+        for (a, b, m) in [(6, 9, 15), (-7, 12, 103), (100, 1, 101)] {
+            let (r, n) = linear_congruence(a, b, m).unwrap();
+            for x in (r..3 * m).step_by(n as usize) {
+                assert_eq!(b.rem_euclid(m), (a * x).rem_euclid(m), "{a} {b} {m} {x}");
+            }
+        }
+    }
+
+    #[test]
+    fn non_positive_moduli() {
+        for m in [0, -7] {
+            assert_eq!(None, mod_inverse(3, m));
+            assert_eq!(None, linear_congruence(3, 1, m));
+            assert_eq!(None, crt(&[(2, 3), (1, m)]));
+        }
+    }
+}
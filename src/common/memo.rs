@@ -0,0 +1,112 @@
+use crate::stats;
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+// Cache for recursive memoization. `get_or_compute` passes the memo back into the compute closure
+// so that the closure can recurse without holding a borrow of the underlying map. Hits and misses
+// are counted for instrumentation
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    map: FxHashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self { map: FxHashMap::default(), hits: 0, misses: 0 }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.map.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self);
+        self.map.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // Clears cached values but not the hit/miss counts
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    // Fraction of lookups that were served from the cache, or 0 if there have been no lookups
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+
+    // Adds the hit and miss counts to the `memo_hits` and `memo_misses` stats counters, from which
+    // `--stats` also prints the hit rate
+    pub fn record_stats(&self) {
+        stats::add("memo_hits", self.hits);
+        stats::add("memo_misses", self.misses);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+
+        memo.get_or_compute(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn recursive() {
+        let mut memo = Memo::new();
+        assert_eq!(12586269025, fibonacci(50, &mut memo));
+        assert_eq!(49, memo.len());
+        assert_eq!(Some(&55), memo.get(&10));
+    }
+
+    #[test]
+    fn computes_once() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        for _ in 0..3 {
+            let value = memo.get_or_compute("key", |_| {
+                calls += 1;
+                5
+            });
+            assert_eq!(5, value);
+        }
+        assert_eq!(1, calls);
+        assert_eq!((2, 1), (memo.hits(), memo.misses()));
+        assert!((memo.hit_rate() - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,41 @@
+// Year-independent building blocks shared by every year's solvers: grids, positions, and
+// directions, graph search, string sets and multi-pattern search, input parsing, and number theory. Everything here is also re-exported
+// from the crate root, so solvers use e.g. `crate::Grid` and `crate::search`
+pub mod aho_corasick;
+mod bitset;
+mod bucket_queue;
+mod byte_grid;
+pub mod combinatorics;
+mod counter;
+pub mod cycle;
+mod direction;
+mod graph;
+pub mod graph_export;
+mod grid;
+mod interner;
+pub mod math;
+mod memo;
+pub mod parse;
+mod pos;
+mod range_set;
+pub mod search;
+mod trie;
+mod union_find;
+
+pub use aho_corasick::AhoCorasick;
+pub use bitset::BitSet;
+pub use bucket_queue::BucketQueue;
+pub use byte_grid::ByteGrid;
+pub use counter::Counter;
+pub use direction::{Direction, DirectionMap};
+pub use graph::Graph;
+pub use grid::{Grid, GridTransform};
+pub use interner::Interner;
+pub use memo::Memo;
+pub use parse::ParseError;
+pub use pos::Pos2;
+pub use pos::Pos3;
+pub use pos::Rect;
+pub use range_set::RangeSet;
+pub use trie::Trie;
+pub use union_find::UnionFind;
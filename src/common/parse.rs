@@ -0,0 +1,467 @@
+// Reusable winnow parsers for common puzzle input shapes, and the error type for malformed input
+use crate::{Direction, Grid, Pos2};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::iter;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use winnow::ascii::{digit1, newline};
+use winnow::combinator::{opt, preceded, separated, separated_pair, terminated};
+use winnow::error::ContextError;
+use winnow::prelude::*;
+use winnow::token::take_till;
+
+// Malformed puzzle input. Errors at a specific place in the input carry its 1-based line and
+// column, so that a mangled input file doesn't have to be bisected by hand
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    location: Option<(usize, usize)>,
+}
+
+impl ParseError {
+    // For problems with the input as a whole, e.g. a missing start position
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into(), location: None }
+    }
+
+    // `row` and `col` are 0-based, as produced by enumerating lines and the characters in a line
+    pub fn at(row: usize, col: usize, message: impl Into<String>) -> Self {
+        Self { message: message.into(), location: Some((row + 1, col + 1)) }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.location.map(|(line, _)| line)
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.location.map(|(_, column)| column)
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(f, "line {line}, column {column}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+// Errors returned from `main` are printed with Debug, so make it as readable as Display
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for ParseError {}
+
+// Runs `parser` on the whole of `input`, reporting a failure at the line and column where parsing
+// stopped. Line-based parsers stop at the end of the last line they could parse, in which case the
+// whole of the next line is reported instead
+pub fn complete<'a, O>(
+    mut parser: impl Parser<&'a str, O, ContextError>,
+    input: &'a str,
+) -> Result<O, ParseError> {
+    parser.parse(input).map_err(|err| {
+        let mut offset = err.offset();
+        if input[offset..].starts_with('\n') && offset + 1 < input.len() {
+            offset += 1;
+        }
+
+        let (before, after) = input.split_at(offset);
+        let row = before.matches('\n').count();
+        let col = before.len() - before.rfind('\n').map_or(0, |i| i + 1);
+        let line = after.lines().next().unwrap_or_default();
+        let message = match after.chars().next() {
+            _ if col == 0 && !line.is_empty() => format!("Unexpected line: '{line}'"),
+            Some('\n' | '\r') => "Unexpected end of line".into(),
+            Some(c) => format!("Unexpected character: '{c}'"),
+            None => "Unexpected end of input".into(),
+        };
+        ParseError::at(row, col, message)
+    })
+}
+
+pub fn unsigned<T: FromStr>(input: &mut &str) -> PResult<T> {
+    digit1.parse_to().parse_next(input)
+}
+
+// Integer with an optional leading '-'
+pub fn signed<T: FromStr>(input: &mut &str) -> PResult<T> {
+    (opt('-'), digit1).take().parse_to().parse_next(input)
+}
+
+// Parses `<label>: <value>`, e.g. `Register A: 729`
+pub fn labeled<'a, O>(
+    label: &'static str,
+    parser: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, O, ContextError> {
+    preceded((label, ": "), parser)
+}
+
+pub fn comma_separated<'a, O>(
+    parser: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, Vec<O>, ContextError> {
+    separated(1.., parser, ',')
+}
+
+pub fn space_separated<'a, O>(
+    parser: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, Vec<O>, ContextError> {
+    separated(1.., parser, ' ')
+}
+
+// One or more newline-separated items. Does not consume a trailing newline
+pub fn lines<'a, O>(
+    parser: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, Vec<O>, ContextError> {
+    separated(1.., parser, newline)
+}
+
+pub fn blank_line(input: &mut &str) -> PResult<()> {
+    (newline, newline).void().parse_next(input)
+}
+
+// Two sections separated by a blank line, e.g. rules followed by updates
+pub fn sections<'a, O1, O2>(
+    first: impl Parser<&'a str, O1, ContextError>,
+    second: impl Parser<&'a str, O2, ContextError>,
+) -> impl Parser<&'a str, (O1, O2), ContextError> {
+    separated_pair(first, blank_line, second)
+}
+
+// Section followed by the blank line that ends it, leaving the input at the start of the next
+// section. For parsing sections one at a time, e.g. when a later section is streamed with
+// line_items
+pub fn section<'a, O>(
+    parser: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, O, ContextError> {
+    terminated(parser, blank_line)
+}
+
+// Lazily parses one item per line, for sections too large to collect. Iteration stops at a blank
+// line or at the end of input, and each line must be consumed entirely by `parser`
+pub fn line_items<'a, O, P: Parser<&'a str, O, ContextError>>(
+    input: &'a str,
+    parser: P,
+) -> LineItems<'a, O, P> {
+    LineItems { input, parser, row: 0, output: PhantomData }
+}
+
+pub struct LineItems<'a, O, P> {
+    input: &'a str,
+    parser: P,
+    row: usize,
+    output: PhantomData<fn() -> O>,
+}
+
+impl<'a, O, P: Parser<&'a str, O, ContextError>> Iterator for LineItems<'a, O, P> {
+    // Error locations are relative to the start of the section
+    type Item = Result<O, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line, rest) = self.input.split_once('\n').unwrap_or((self.input, ""));
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            self.input = "";
+            return None;
+        }
+
+        self.input = rest;
+        self.row += 1;
+        Some(self.parser.parse(line).map_err(|err| {
+            let message = match line[err.offset()..].chars().next() {
+                Some(c) => format!("Unexpected character: '{c}'"),
+                None => "Unexpected end of line".into(),
+            };
+            ParseError::at(self.row - 1, err.offset(), message)
+        }))
+    }
+}
+
+// One or more items separated by blank lines
+pub fn blank_line_separated<'a, O>(
+    parser: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, Vec<O>, ContextError> {
+    separated(1.., parser, blank_line)
+}
+
+// Rectangular grid of characters, mapping each byte through `cell`. Stops at a blank line or at
+// the end of input, and does not consume a trailing newline
+pub fn grid<'a, T>(mut cell: impl FnMut(u8) -> T) -> impl Parser<&'a str, Grid<T>, ContextError> {
+    move |input: &mut &'a str| {
+        let rows: Vec<&str> = lines(take_till(1.., ['\n', '\r'])).parse_next(input)?;
+        Ok(Grid(rows.into_iter().map(|row| row.bytes().map(&mut cell).collect()).collect()))
+    }
+}
+
+// Lines of a byte slice without their `\n` or `\r\n` terminators, like str::lines. For inputs
+// large enough that working on bytes avoids a measurable amount of UTF-8 handling
+pub fn byte_lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = input;
+    iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let (line, next) = match rest.iter().position(|&b| b == b'\n') {
+            Some(i) => (&rest[..i], &rest[i + 1..]),
+            None => (rest, &rest[rest.len()..]),
+        };
+        rest = next;
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    })
+}
+
+// Fields separated by runs of ASCII whitespace, like str::split_ascii_whitespace
+pub fn byte_fields(line: &[u8]) -> impl Iterator<Item = &[u8]> {
+    line.split(u8::is_ascii_whitespace).filter(|field| !field.is_empty())
+}
+
+// Decimal number made up only of digits, or None if it is empty, has any other byte, or does not
+// fit in T
+pub fn unsigned_from_bytes<T: TryFrom<u64>>(bytes: &[u8]) -> Option<T> {
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let mut value: u64 = 0;
+    for &b in bytes {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_add(u64::from(b - b'0'))?;
+    }
+    T::try_from(value).ok()
+}
+
+// Same as `unsigned_from_bytes`, with an optional leading '-'
+pub fn signed_from_bytes<T: TryFrom<i64>>(bytes: &[u8]) -> Option<T> {
+    let (negative, digits) = match bytes.strip_prefix(b"-") {
+        Some(digits) => (true, digits),
+        None => (false, bytes),
+    };
+    let magnitude = i64::try_from(unsigned_from_bytes::<u64>(digits)?).ok()?;
+    T::try_from(if negative { -magnitude } else { magnitude }).ok()
+}
+
+// Map of single-character cells such as walls and open space, with marker characters for
+// positions like a start and an end. Reads `(row, line)` pairs up to the first blank line, where
+// `row` is the line's 0-based row in the whole input for error locations. Each marker is given as
+// (character, error message if it is missing); marker cells are stored as `open`, and the marker
+// positions are returned in the same order. Characters that `cell` maps to None are an error
+pub fn maze<'a, T: Clone, const N: usize>(
+    lines: impl IntoIterator<Item = (usize, &'a str)>,
+    markers: [(char, &str); N],
+    open: T,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<(Grid<T>, [Pos2<i32>; N]), ParseError> {
+    let mut map: Vec<Vec<T>> = Vec::new();
+    let mut positions = [None; N];
+    for (row, line) in lines.into_iter().take_while(|(_, line)| !line.is_empty()) {
+        let mut map_row = Vec::with_capacity(line.len());
+        for (col, c) in line.chars().enumerate() {
+            if let Some(i) = markers.iter().position(|&(marker, _)| marker == c) {
+                positions[i] = Some(Pos2 { x: map_row.len() as i32, y: map.len() as i32 });
+                map_row.push(open.clone());
+                continue;
+            }
+
+            let Some(value) = cell(c) else {
+                return Err(ParseError::at(row, col, format!("Unexpected map character: '{c}'")));
+            };
+            map_row.push(value);
+        }
+        map.push(map_row);
+    }
+
+    let mut found = [Pos2 { x: 0, y: 0 }; N];
+    for ((found, position), (_, missing)) in found.iter_mut().zip(positions).zip(markers) {
+        *found = position.ok_or_else(|| ParseError::new(missing))?;
+    }
+
+    Ok((Grid(map), found))
+}
+
+// `maze` for the usual '#' walls and '.' open space, where each cell is true for a wall
+pub fn wall_maze<const N: usize>(
+    input: &str,
+    markers: [(char, &str); N],
+) -> Result<(Grid<bool>, [Pos2<i32>; N]), ParseError> {
+    maze(input.lines().enumerate(), markers, false, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+// Moves written as `^>v<` arrows over any number of `(row, line)` pairs, where `row` is the
+// line's 0-based row in the whole input for error locations. Blank lines are skipped
+pub fn arrows<'a>(
+    lines: impl IntoIterator<Item = (usize, &'a str)>,
+) -> Result<Vec<Direction>, ParseError> {
+    let mut moves = Vec::new();
+    for (row, line) in lines {
+        for (col, c) in line.chars().enumerate() {
+            let Some(direction) = Direction::from_arrow(c) else {
+                let message = format!("Unexpected direction character: '{c}'");
+                return Err(ParseError::at(row, col, message));
+            };
+            moves.push(direction);
+        }
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Ok(123_u32), unsigned.parse("123"));
+        assert!(unsigned::<u32>.parse("-123").is_err());
+        assert_eq!(Ok(-45_i64), signed.parse("-45"));
+        assert_eq!(Ok(45_i64), signed.parse("45"));
+
+        assert_eq!(Ok(vec![1, -2, 3]), comma_separated(signed::<i32>).parse("1,-2,3"));
+        assert_eq!(Ok(vec![10, 19]), space_separated(unsigned::<u64>).parse("10 19"));
+        assert_eq!(Ok(729_u64), labeled("Register A", unsigned).parse("Register A: 729"));
+    }
+
+    #[test]
+    fn sections_and_lines() {
+        let input = "1|2\n3|4\n\n5,6\n7";
+        let rule = separated_pair(unsigned::<u32>, '|', unsigned);
+        let update = comma_separated(unsigned::<u32>);
+        assert_eq!(
+            Ok((vec![(1, 2), (3, 4)], vec![vec![5, 6], vec![7]])),
+            sections(lines(rule), lines(update)).parse(input)
+        );
+
+        assert_eq!(
+            Ok(vec![vec![1, 2], vec![3]]),
+            blank_line_separated(lines(unsigned::<u32>)).parse("1\n2\n\n3")
+        );
+    }
+
+    #[test]
+    fn streamed_sections() {
+        let mut input = "1|2\n3|4\n\n5,6\n7\n";
+        let rule = separated_pair(unsigned::<u32>, '|', unsigned);
+        assert_eq!(Ok(vec![(1, 2), (3, 4)]), section(lines(rule)).parse_next(&mut input));
+
+        let updates: Result<Vec<_>, _> =
+            line_items(input, comma_separated(unsigned::<u32>)).collect();
+        assert_eq!(Ok(vec![vec![5, 6], vec![7]]), updates);
+
+        // Items after a blank line belong to another section
+        let items: Vec<_> = line_items("1\r\n2\n\n3", unsigned::<u32>).collect();
+        assert_eq!(vec![Ok(1), Ok(2)], items);
+
+        let mut items = line_items("1\nx\n3", unsigned::<u32>);
+        assert_eq!(Some(Ok(1)), items.next());
+        let err = items.next().unwrap().unwrap_err();
+        assert_eq!("line 2, column 1: Unexpected character: 'x'", err.to_string());
+        assert_eq!(Some(Ok(3)), items.next());
+        assert_eq!(None, items.next());
+
+        let err = line_items("1,", comma_separated(unsigned::<u32>)).next().unwrap().unwrap_err();
+        assert_eq!((Some(1), Some(2)), (err.line(), err.column()));
+    }
+
+    #[test]
+    fn complete_input() {
+        let mut update = comma_separated(unsigned::<u32>);
+        assert_eq!(Ok(vec![5, 6]), complete(update.by_ref(), "5,6"));
+
+        let rules = lines(separated_pair(unsigned::<u32>, '|', unsigned::<u32>));
+        let err = complete(rules, "1|2\n3|x4").unwrap_err();
+        assert_eq!("line 2, column 1: Unexpected line: '3|x4'", err.to_string());
+        let err = complete(update, "5,6,x").unwrap_err();
+        assert_eq!("line 1, column 4: Unexpected character: ','", err.to_string());
+
+        let err = complete(unsigned::<u32>, "12\n").unwrap_err();
+        assert_eq!("line 1, column 3: Unexpected end of line", err.to_string());
+        let err = complete(labeled("Register A", unsigned::<u32>), "Register A: ").unwrap_err();
+        assert_eq!("line 1, column 13: Unexpected end of input", err.to_string());
+    }
+
+    #[test]
+    fn errors() {
+        let err = ParseError::at(2, 4, "Unexpected map character: 'x'");
+        assert_eq!((Some(3), Some(5)), (err.line(), err.column()));
+        assert_eq!("line 3, column 5: Unexpected map character: 'x'", err.to_string());
+
+        let err = ParseError::new("No start position in map");
+        assert_eq!(None, err.line());
+        assert_eq!("No start position in map", err.to_string());
+    }
+
+    #[test]
+    fn byte_slices() {
+        let lines: Vec<_> = byte_lines(b"3   4\r\n\n-5 ok\n").collect();
+        assert_eq!(vec![&b"3   4"[..], b"", b"-5 ok"], lines);
+        assert_eq!(0, byte_lines(b"").count());
+        assert_eq!(vec![&b"x"[..]], byte_lines(b"x").collect::<Vec<_>>());
+
+        let fields: Vec<_> = byte_fields(lines[0]).collect();
+        assert_eq!(vec![&b"3"[..], b"4"], fields);
+        assert_eq!(Some(3_u32), unsigned_from_bytes(fields[0]));
+        assert_eq!(Some(18446744073709551615_u64), unsigned_from_bytes(b"18446744073709551615"));
+        assert_eq!(None, unsigned_from_bytes::<u64>(b"18446744073709551616"));
+        assert_eq!(None, unsigned_from_bytes::<u8>(b"256"));
+        assert_eq!(None, unsigned_from_bytes::<u32>(b""));
+        assert_eq!(None, unsigned_from_bytes::<u32>(b"-5"));
+
+        assert_eq!(Some(-5_i32), signed_from_bytes(b"-5"));
+        assert_eq!(Some(5_i64), signed_from_bytes(b"5"));
+        assert_eq!(None, signed_from_bytes::<i32>(b"-"));
+        assert_eq!(None, signed_from_bytes::<i32>(b"5x"));
+    }
+
+    #[test]
+    fn mazes() {
+        let markers = [('S', "No start"), ('E', "No end")];
+        let (walls, [start, end]) = wall_maze("####\n#S.#\n#.E#\n####\n", markers).unwrap();
+        assert_eq!((4, 4), (walls.rows(), walls.cols()));
+        assert_eq!((Pos2 { x: 1, y: 1 }, Pos2 { x: 2, y: 2 }), (start, end));
+        assert!(walls[Pos2 { x: 0, y: 0 }] && !walls[start] && !walls[end]);
+
+        assert_eq!(Err(ParseError::new("No end")), wall_maze("#S.#\n", markers));
+        let err = wall_maze("#S.#\n#E~#\n", markers).unwrap_err();
+        assert_eq!("line 2, column 3: Unexpected map character: '~'", err.to_string());
+
+        // Stops at a blank line, leaving the rest for the caller
+        let mut lines = "O@O\n\n<>".lines().enumerate();
+        let cell = |c| (c == 'O').then_some(2);
+        let (map, [robot]) = maze(lines.by_ref(), [('@', "No robot")], 0, cell).unwrap();
+        assert_eq!((Grid(vec![vec![2, 0, 2]]), Pos2 { x: 1, y: 0 }), (map, robot));
+        assert_eq!(Some((2, "<>")), lines.next());
+    }
+
+    #[test]
+    fn arrow_moves() {
+        use Direction::*;
+
+        let lines = "<^\n\nv>\n".lines().enumerate();
+        assert_eq!(Ok(vec![Left, Up, Down, Right]), arrows(lines));
+        let err = arrows("^^\n>x".lines().enumerate()).unwrap_err();
+        assert_eq!("line 2, column 2: Unexpected direction character: 'x'", err.to_string());
+    }
+
+    #[test]
+    fn grid_then_section() {
+        let input = "#.#\n.#.\n\n<>";
+        let (parsed, moves) =
+            sections(grid(|b| b == b'#'), take_till(1.., '\n')).parse(input).unwrap();
+        assert_eq!(Grid(vec![vec![true, false, true], vec![false, true, false]]), parsed);
+        assert_eq!("<>", moves);
+    }
+}
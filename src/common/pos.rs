@@ -0,0 +1,184 @@
+use crate::math;
+use std::iter;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pos2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Pos2<T> {
+    pub const fn xy(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Pos2<T> {
+    // This position followed by every step of `delta` from it; never ends, so bound it with e.g.
+    // `take` or `zip`
+    pub fn ray(self, delta: Self) -> impl Iterator<Item = Self> {
+        iter::successors(Some(self), move |&pos| Some(pos + delta))
+    }
+}
+
+impl<T: Copy + Default + PartialOrd + Add<Output = T>> Pos2<T> {
+    // Same as `ray`, but stops at the first position outside of 0..size.x by 0..size.y. Empty if
+    // this position is already outside
+    pub fn ray_within(self, delta: Self, size: Self) -> impl Iterator<Item = Self> {
+        let zero = T::default();
+        self.ray(delta).take_while(move |pos| {
+            zero <= pos.x && pos.x < size.x && zero <= pos.y && pos.y < size.y
+        })
+    }
+}
+
+impl<T> Pos2<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Into<i64> + TryFrom<i64>,
+{
+    // Every integer position on the segment from `a` to `b`, including both ends, in order from `a`
+    pub fn line_between(a: Self, b: Self) -> impl Iterator<Item = Self> {
+        let (dx, dy) = ((b.x - a.x).into(), (b.y - a.y).into());
+        let steps = math::gcd(dx, dy);
+
+        // Each step is at most as large as the whole segment, so it always fits in T
+        let step_of = |d: i64| T::try_from(if steps == 0 { 0 } else { d / steps }).ok().unwrap();
+        a.ray(Self { x: step_of(dx), y: step_of(dy) }).take(steps as usize + 1)
+    }
+}
+
+// Positions from `min` up to but not including `max` in each dimension, like a range, e.g. the
+// cells of a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Pos2<T>,
+    pub max: Pos2<T>,
+}
+
+impl<T: Copy + PartialOrd> Rect<T> {
+    pub fn contains(&self, pos: Pos2<T>) -> bool {
+        self.min.x <= pos.x && pos.x < self.max.x && self.min.y <= pos.y && pos.y < self.max.y
+    }
+}
+
+impl Rect<i32> {
+    // 0..cols by 0..rows
+    pub fn from_size(cols: usize, rows: usize) -> Self {
+        Self { min: Pos2 { x: 0, y: 0 }, max: Pos2 { x: cols as i32, y: rows as i32 } }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pos3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+macro_rules! impl_arithmetic_traits {
+    ($t:ident, [$($var:ident),* $(,)?]) => {
+        impl<T: Copy + Add<Output = T>> Add for $t<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self {
+                    $(
+                        $var: self.$var + rhs.$var,
+                    )*
+                }
+            }
+        }
+
+        impl<T: Copy + AddAssign> AddAssign for $t<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(
+                    self.$var += rhs.$var;
+                )*
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> Sub for $t<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self {
+                    $(
+                        $var: self.$var - rhs.$var,
+                    )*
+                }
+            }
+        }
+
+        impl<T: Copy + SubAssign> SubAssign for $t<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(
+                    self.$var -= rhs.$var;
+                )*
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $t<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self {
+                    $(
+                        $var: self.$var * rhs,
+                    )*
+                }
+            }
+        }
+
+        impl<T: Copy + MulAssign> MulAssign<T> for $t<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                $(
+                    self.$var *= rhs;
+                )*
+            }
+        }
+    }
+}
+
+impl_arithmetic_traits!(Pos2, [x, y]);
+impl_arithmetic_traits!(Pos3, [x, y, z]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rect_contains() {
+        let rect = Rect::from_size(3, 2);
+        assert!(rect.contains(Pos2::xy(0, 0)));
+        assert!(rect.contains(Pos2::xy(2, 1)));
+        assert!(!rect.contains(Pos2::xy(3, 1)));
+        assert!(!rect.contains(Pos2::xy(2, 2)));
+        assert!(!rect.contains(Pos2::xy(-1, 0)));
+
+        let rect = Rect { min: Pos2::xy(-5_i64, 10), max: Pos2::xy(5, 12) };
+        assert!(rect.contains(Pos2::xy(-5, 11)));
+        assert!(!rect.contains(Pos2::xy(0, 9)));
+        assert!(!Rect::from_size(0, 0).contains(Pos2::xy(0, 0)));
+    }
+
+    #[test]
+    fn rays_and_lines() {
+        let start = Pos2::xy(1, 2);
+        let ray: Vec<_> = start.ray(Pos2::xy(2, -1)).take(3).collect();
+        assert_eq!(vec![Pos2::xy(1, 2), Pos2::xy(3, 1), Pos2::xy(5, 0)], ray);
+
+        let size = Pos2::xy(6, 3);
+        assert_eq!(ray, start.ray_within(Pos2::xy(2, -1), size).collect::<Vec<_>>());
+        assert_eq!(2, start.ray_within(Pos2::xy(-1, 0), size).count());
+        assert_eq!(0, Pos2::xy(6, 0).ray_within(Pos2::xy(-1, 0), size).count());
+
+        let line: Vec<_> = Pos2::line_between(Pos2::xy(0, 0), Pos2::xy(6, -4)).collect();
+        assert_eq!(vec![Pos2::xy(0, 0), Pos2::xy(3, -2), Pos2::xy(6, -4)], line);
+        assert_eq!(
+            vec![Pos2::xy(5_i64, 5)],
+            Pos2::line_between(Pos2::xy(5, 5), Pos2::xy(5, 5)).collect::<Vec<_>>()
+        );
+        assert_eq!(5, Pos2::line_between(Pos2::xy(0, 4), Pos2::xy(0, 0)).count());
+    }
+}
@@ -0,0 +1,183 @@
+use std::ops::{Add, Range, Sub};
+
+// Set of values stored as sorted, non-overlapping, non-adjacent half-open ranges. Inserting a range
+// that overlaps or touches existing ranges merges them together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T> RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // First range that ends at or after the new range's start; everything from here that starts
+        // at or before the new range's end gets merged
+        let i = self.ranges.partition_point(|existing| existing.end < range.start);
+        let mut j = i;
+        let mut merged = range;
+        while j < self.ranges.len() && self.ranges[j].start <= merged.end {
+            merged.start = merged.start.min(self.ranges[j].start);
+            merged.end = merged.end.max(self.ranges[j].end);
+            j += 1;
+        }
+
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let i = self.ranges.partition_point(|existing| existing.end <= range.start);
+        let mut j = i;
+        let mut remaining = Vec::with_capacity(2);
+        while j < self.ranges.len() && self.ranges[j].start < range.end {
+            let existing = self.ranges[j].clone();
+            if existing.start < range.start {
+                remaining.push(existing.start..range.start);
+            }
+            if existing.end > range.end {
+                remaining.push(range.end..existing.end);
+            }
+            j += 1;
+        }
+
+        self.ranges.splice(i..j, remaining);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges.get(i).is_some_and(|range| range.start <= value)
+    }
+
+    // Total number of values covered by the set
+    pub fn covered_len(&self) -> T {
+        self.ranges.iter().fold(T::default(), |sum, range| sum + (range.end - range.start))
+    }
+
+    // Number of disjoint ranges in the set
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    // Returns the uncovered ranges within `bounds`, in order
+    pub fn gaps(&self, bounds: Range<T>) -> Vec<Range<T>> {
+        let mut gaps = Vec::new();
+        let mut current = bounds.start;
+        for range in &self.ranges {
+            if range.end <= current {
+                continue;
+            }
+            if range.start >= bounds.end {
+                break;
+            }
+
+            if range.start > current {
+                gaps.push(current..range.start);
+            }
+            current = range.end;
+        }
+
+        if current < bounds.end {
+            gaps.push(current..bounds.end);
+        }
+
+        gaps
+    }
+
+    // Returns the leftmost uncovered range within `bounds` that has at least `len` values
+    pub fn first_gap_of_len(&self, bounds: Range<T>, len: T) -> Option<Range<T>> {
+        self.gaps(bounds).into_iter().find(|gap| gap.end - gap.start >= len)
+    }
+}
+
+impl<T> FromIterator<Range<T>> for RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_merges() {
+        let mut set = RangeSet::new();
+        set.insert(10..20);
+        set.insert(30..40);
+        set.insert(0..5);
+        assert_eq!(vec![0..5, 10..20, 30..40], set.iter().collect::<Vec<_>>());
+
+        // Adjacent ranges merge
+        set.insert(5..7);
+        // Overlaps two ranges
+        set.insert(15..35);
+        // Empty ranges are ignored
+        set.insert(50..50);
+        assert_eq!(vec![0..7, 10..40], set.iter().collect::<Vec<_>>());
+
+        assert_eq!(37, set.covered_len());
+        assert!(set.contains(0));
+        assert!(set.contains(39));
+        assert!(!set.contains(7));
+        assert!(!set.contains(40));
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set: RangeSet<i32> = [0..10, 20..30].into_iter().collect();
+        set.remove(5..25);
+        assert_eq!(vec![0..5, 25..30], set.iter().collect::<Vec<_>>());
+
+        set.remove(2..3);
+        assert_eq!(vec![0..2, 3..5, 25..30], set.iter().collect::<Vec<_>>());
+
+        set.remove(-10..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn gaps() {
+        let set: RangeSet<u32> = [2..4, 6..7, 10..15].into_iter().collect();
+        assert_eq!(vec![0..2, 4..6, 7..10, 15..20], set.gaps(0..20));
+        assert_eq!(vec![4..6, 7..8], set.gaps(3..8));
+        assert_eq!(Vec::<Range<u32>>::new(), set.gaps(11..14));
+
+        assert_eq!(Some(7..10), set.first_gap_of_len(0..20, 3));
+        assert_eq!(Some(15..20), set.first_gap_of_len(0..20, 4));
+        assert_eq!(None, set.first_gap_of_len(0..20, 6));
+    }
+}
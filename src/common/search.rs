@@ -0,0 +1,542 @@
+use crate::{BucketQueue, Direction, DirectionMap, Grid, Pos2, Rect, stats};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::ops::{Add, Range};
+
+// How to order heap entries that have the same estimated total cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    // Prefer the node with the higher cost so far, i.e. the one closest to the goal according to
+    // the heuristic. Usually expands the fewest nodes on grids with many equal-cost paths
+    #[default]
+    PreferDeeper,
+    // Prefer the node with the lower cost so far
+    PreferShallower,
+    // Prefer whichever node was pushed first
+    Fifo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<N, C> {
+    pub cost: C,
+    pub path: Vec<N>,
+    pub expanded: usize,
+}
+
+#[derive(Debug)]
+struct HeapEntry<N, C> {
+    estimate: C,
+    cost: C,
+    seq: u64,
+    tie_break: TieBreak,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for HeapEntry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for HeapEntry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for HeapEntry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for HeapEntry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse cmp for min heap
+        let tie = match self.tie_break {
+            TieBreak::PreferDeeper => self.cost.cmp(&other.cost),
+            TieBreak::PreferShallower => other.cost.cmp(&self.cost),
+            TieBreak::Fifo => Ordering::Equal,
+        };
+        other.estimate.cmp(&self.estimate).then(tie).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+// Open set for best-first search: nodes ordered by estimated total cost, along with the cost so far
+trait Frontier<N, C> {
+    fn push(&mut self, estimate: C, cost: C, node: N);
+
+    fn pop(&mut self) -> Option<(C, N)>;
+
+    fn len(&self) -> usize;
+}
+
+struct HeapFrontier<N, C> {
+    heap: BinaryHeap<HeapEntry<N, C>>,
+    seq: u64,
+    tie_break: TieBreak,
+}
+
+impl<N, C: Ord> Frontier<N, C> for HeapFrontier<N, C> {
+    fn push(&mut self, estimate: C, cost: C, node: N) {
+        self.heap.push(HeapEntry {
+            estimate,
+            cost,
+            seq: self.seq,
+            tie_break: self.tie_break,
+            node,
+        });
+        self.seq += 1;
+    }
+
+    fn pop(&mut self) -> Option<(C, N)> {
+        self.heap.pop().map(|HeapEntry { cost, node, .. }| (cost, node))
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+impl<N, C: Copy + Into<u64>> Frontier<N, C> for BucketQueue<(C, N)> {
+    fn push(&mut self, estimate: C, cost: C, node: N) {
+        BucketQueue::push(self, estimate.into() as usize, (cost, node));
+    }
+
+    fn pop(&mut self) -> Option<(C, N)> {
+        BucketQueue::pop(self).map(|(_, entry)| entry)
+    }
+
+    fn len(&self) -> usize {
+        BucketQueue::len(self)
+    }
+}
+
+// A* search from `start` to the first node satisfying `is_goal`.
+//
+// `heuristic` must never overestimate the remaining cost to a goal, or the returned cost may not
+// be the minimum. Returns None if no goal is reachable
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+    tie_break: TieBreak,
+) -> Option<SearchResult<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let frontier = HeapFrontier { heap: BinaryHeap::new(), seq: 0, tie_break };
+    best_first(start, successors, heuristic, is_goal, frontier)
+}
+
+fn best_first<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut frontier: impl Frontier<N, C>,
+) -> Option<SearchResult<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut min_costs: FxHashMap<N, C> = FxHashMap::default();
+    let mut parents: FxHashMap<N, N> = FxHashMap::default();
+
+    min_costs.insert(start, C::default());
+    frontier.push(heuristic(&start), C::default(), start);
+
+    let mut expanded = 0;
+    while let Some((cost, node)) = frontier.pop() {
+        if min_costs.get(&node).is_some_and(|&min_cost| min_cost < cost) {
+            // Stale entry; a cheaper path to this node has already been expanded
+            continue;
+        }
+
+        if is_goal(&node) {
+            debug!(expanded, heap_len = frontier.len(), "Search reached goal");
+            record_search(expanded);
+            return Some(SearchResult { cost, path: build_path(&parents, node), expanded });
+        }
+        expanded += 1;
+
+        for (next, edge_cost) in successors(&node) {
+            let next_cost = cost + edge_cost;
+            if min_costs.get(&next).is_some_and(|&min_cost| min_cost <= next_cost) {
+                continue;
+            }
+
+            min_costs.insert(next, next_cost);
+            parents.insert(next, node);
+            frontier.push(next_cost + heuristic(&next), next_cost, next);
+        }
+    }
+
+    debug!(expanded, "Search exhausted without reaching a goal");
+    record_search(expanded);
+    None
+}
+
+// Records one finished search in the `searches` and `nodes_expanded` stats counters. Solvers with
+// their own search loops call this too, so that `--stats` compares them on the same terms
+pub fn record_search(expanded: usize) {
+    stats::incr("searches");
+    stats::add("nodes_expanded", expanded as u64);
+}
+
+// Plain Dijkstra; equivalent to A* with a heuristic that always returns 0
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<SearchResult<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal, TieBreak::Fifo)
+}
+
+// Dijkstra with a bucket queue instead of a binary heap, for small integer edge costs. Every edge
+// cost must be at most `max_edge_cost`. Equal-cost nodes are expanded in an unspecified order, so
+// the path may differ from `dijkstra`'s but the cost is the same
+pub fn dijkstra_buckets<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+    max_edge_cost: C,
+) -> Option<SearchResult<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C> + Into<u64>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let frontier = BucketQueue::new(max_edge_cost.into() as usize);
+    best_first(start, successors, |_| C::default(), is_goal, frontier)
+}
+
+// A position on a grid along with the direction being faced, for searches where turning costs
+// something, e.g. a reindeer that can only move forward
+pub type Facing = (Pos2<i32>, Direction);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveCosts {
+    // Stepping one cell in the direction being faced
+    pub forward: u32,
+    // Turning 90 degrees in place
+    pub turn: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacingSearchResult {
+    pub cost: u32,
+    // Every goal state reached at the min cost
+    pub ends: Vec<Facing>,
+    // Min cost to every state that was settled, or u32::MAX
+    pub costs: Grid<DirectionMap<u32>>,
+    // The states each state was reached from at its min cost, if requested
+    pub predecessors: FxHashMap<Facing, Vec<Facing>>,
+}
+
+impl FacingSearchResult {
+    // States on at least one min-cost path from the start to a goal. Requires predecessors
+    pub fn optimal_states(&self) -> FxHashSet<Facing> {
+        let mut states: FxHashSet<Facing> = self.ends.iter().copied().collect();
+        let mut stack = self.ends.clone();
+        while let Some(state) = stack.pop() {
+            for &predecessor in self.predecessors.get(&state).into_iter().flatten() {
+                if states.insert(predecessor) {
+                    stack.push(predecessor);
+                }
+            }
+        }
+        states
+    }
+
+    pub fn optimal_cells(&self) -> FxHashSet<Pos2<i32>> {
+        self.optimal_states().into_iter().map(|(pos, _)| pos).collect()
+    }
+}
+
+// Dijkstra over (position, facing) states on a `cols` x `rows` grid, from `start` to any cell
+// satisfying `is_goal` (facing any direction). Each move either steps forward into an open cell or
+// turns in place; turning to face a blocked cell is skipped, since from there the only useful move
+// is another turn, which costs the same as turning the other way twice.
+//
+// With `record_predecessors`, every state that reaches another at its min cost is kept, so that all
+// optimal paths can be recovered with `optimal_states` rather than just one. Returns None if no goal
+// is reachable
+pub fn facing_dijkstra(
+    (cols, rows): (usize, usize),
+    start: Facing,
+    is_open: impl Fn(Pos2<i32>) -> bool,
+    costs: MoveCosts,
+    is_goal: impl Fn(Pos2<i32>) -> bool,
+    record_predecessors: bool,
+) -> Option<FacingSearchResult> {
+    let bounds = Rect::from_size(cols, rows);
+    let is_open = |pos: Pos2<i32>| bounds.contains(pos) && is_open(pos);
+
+    let mut min_costs = Grid(vec![vec![DirectionMap::splat(u32::MAX); cols]; rows]);
+    let mut predecessors: FxHashMap<Facing, Vec<Facing>> = FxHashMap::default();
+    let mut queue = BucketQueue::new(costs.forward.max(costs.turn) as usize);
+    min_costs[start.0][start.1] = 0;
+    queue.push(0, start);
+
+    let mut end_cost = None;
+    let mut ends = Vec::new();
+    let mut expanded = 0;
+    while let Some((cost, (pos, direction))) = queue.pop() {
+        let cost = cost as u32;
+        if end_cost.is_some_and(|end_cost| end_cost < cost) {
+            break;
+        }
+        if min_costs[pos][direction] < cost {
+            continue;
+        }
+
+        if is_goal(pos) {
+            end_cost = Some(cost);
+            ends.push((pos, direction));
+            continue;
+        }
+        expanded += 1;
+
+        let forward = (pos + direction.delta(), direction, costs.forward);
+        let turns = [direction.rotate_left(), direction.rotate_right()]
+            .map(|turned| (pos, turned, costs.turn))
+            .into_iter()
+            .filter(|&(pos, turned, _)| is_open(pos + turned.delta()));
+        for (next_pos, next_direction, move_cost) in [forward].into_iter().chain(turns) {
+            if !is_open(next_pos) {
+                continue;
+            }
+
+            let next_cost = cost + move_cost;
+            let min_cost = &mut min_costs[next_pos][next_direction];
+            if next_cost > *min_cost {
+                continue;
+            }
+            if next_cost < *min_cost {
+                *min_cost = next_cost;
+                queue.push(next_cost as usize, (next_pos, next_direction));
+                if record_predecessors {
+                    predecessors.insert((next_pos, next_direction), Vec::new());
+                }
+            }
+            if record_predecessors {
+                predecessors.entry((next_pos, next_direction)).or_default().push((pos, direction));
+            }
+        }
+    }
+
+    record_search(expanded);
+    end_cost.map(|cost| FacingSearchResult { cost, ends, costs: min_costs, predecessors })
+}
+
+// Binary search for the first index in `range` at which `predicate` is false, given that it is true
+// for every index before that point and false for every index after it, as with
+// `slice::partition_point`. Returns `range.end` if it is true everywhere
+pub fn partition_point_over(
+    range: Range<usize>,
+    mut predicate: impl FnMut(usize) -> bool,
+) -> usize {
+    let Range { start: mut lo, end: mut hi } = range;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if predicate(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+fn build_path<N: Copy + Eq + Hash>(parents: &FxHashMap<N, N>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(&parent) = parents.get(path.last().unwrap()) {
+        path.push(parent);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pos2;
+
+    type Position = Pos2<i32>;
+
+    const MAZE: &str = "\
+........#.......
+.######.#.#####.
+.#......#.....#.
+.#.######.###.#.
+.#........#...#.
+.##########.###.
+...........#....
+.#########.#.##.
+.#.......#...#..
+...#####...#...#";
+
+    fn parse_maze(maze: &str) -> Vec<Vec<bool>> {
+        maze.lines().map(|line| line.chars().map(|c| c == '#').collect()).collect()
+    }
+
+    fn neighbors(walls: &[Vec<bool>], pos: Position) -> Vec<(Position, u32)> {
+        [(-1, 0), (0, -1), (1, 0), (0, 1)]
+            .into_iter()
+            .map(|(dx, dy)| pos + Position { x: dx, y: dy })
+            .filter(|new_pos| {
+                Rect::from_size(walls[0].len(), walls.len()).contains(*new_pos)
+                    && !walls[new_pos.y as usize][new_pos.x as usize]
+            })
+            .map(|new_pos| (new_pos, 1))
+            .collect()
+    }
+
+    fn manhattan(a: Position, b: Position) -> u32 {
+        (a.x - b.x).unsigned_abs() + (a.y - b.y).unsigned_abs()
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let walls = parse_maze(MAZE);
+        let start = Position { x: 0, y: 0 };
+        let end = Position { x: walls[0].len() as i32 - 1, y: walls.len() as i32 - 2 };
+
+        let expected = dijkstra(start, |&pos| neighbors(&walls, pos), |&pos| pos == end).unwrap();
+
+        for tie_break in [TieBreak::PreferDeeper, TieBreak::PreferShallower, TieBreak::Fifo] {
+            let result = astar(
+                start,
+                |&pos| neighbors(&walls, pos),
+                |&pos| manhattan(pos, end),
+                |&pos| pos == end,
+                tie_break,
+            )
+            .unwrap();
+
+            assert_eq!(expected.cost, result.cost, "{tie_break:?}");
+            assert_eq!(result.cost as usize + 1, result.path.len(), "{tie_break:?}");
+            assert_eq!(Some(&start), result.path.first());
+            assert_eq!(Some(&end), result.path.last());
+            assert!(result.expanded <= expected.expanded, "{tie_break:?}");
+        }
+    }
+
+    #[test]
+    fn weighted_edges() {
+        // 0 -> 1 -> 3 costs 2 + 2, the direct edge 0 -> 3 costs 5, 0 -> 2 -> 3 costs 1 + 4
+        let edges: [&[(u32, u32)]; 4] = [&[(1, 2), (2, 1), (3, 5)], &[(3, 2)], &[(3, 4)], &[]];
+
+        let result =
+            dijkstra(0, |&node| edges[node as usize].iter().copied(), |&node| node == 3).unwrap();
+        assert_eq!(4, result.cost);
+        assert_eq!(vec![0, 1, 3], result.path);
+
+        let successors = |&node: &u32| edges[node as usize].iter().copied();
+        assert_eq!(Some(result), dijkstra_buckets(0, successors, |&node| node == 3, 5));
+    }
+
+    #[test]
+    fn buckets_match_heap() {
+        let walls = parse_maze(MAZE);
+        let start = Position { x: 0, y: 0 };
+        for end in [Position { x: 15, y: 8 }, Position { x: 9, y: 4 }, Position { x: 2, y: 0 }] {
+            let heap = dijkstra(start, |&pos| neighbors(&walls, pos), |&pos| pos == end).unwrap();
+            let buckets =
+                dijkstra_buckets(start, |&pos| neighbors(&walls, pos), |&pos| pos == end, 1)
+                    .unwrap();
+            assert_eq!(heap.cost, buckets.cost);
+            assert_eq!(Some(&end), buckets.path.last());
+        }
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let walls = parse_maze("..#.\n..#.");
+        let end = Position { x: 3, y: 0 };
+
+        let result = astar(
+            Position { x: 0, y: 0 },
+            |&pos| neighbors(&walls, pos),
+            |&pos| manhattan(pos, end),
+            |&pos| pos == end,
+            TieBreak::default(),
+        );
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn records_stats() {
+        let walls = parse_maze(MAZE);
+        let end = Position { x: 15, y: 8 };
+
+        stats::reset();
+        let result =
+            dijkstra(Position { x: 0, y: 0 }, |&pos| neighbors(&walls, pos), |&pos| pos == end)
+                .unwrap();
+        assert_eq!(
+            vec![("nodes_expanded", result.expanded as u64), ("searches", 1)],
+            stats::take()
+        );
+    }
+
+    #[test]
+    fn facing_search() {
+        let walls = parse_maze("......\n.####.\n......");
+        let is_open = |pos: Position| !walls[pos.y as usize][pos.x as usize];
+        let costs = MoveCosts { forward: 1, turn: 10 };
+        let start = (Position { x: 0, y: 0 }, Direction::Right);
+        let end = Position { x: 0, y: 2 };
+
+        // A turn to face down, then 2 steps
+        let result = facing_dijkstra((6, 3), start, is_open, costs, |pos| pos == end, false);
+        let result = result.unwrap();
+        assert_eq!(12, result.cost);
+        assert_eq!(vec![(end, Direction::Down)], result.ends);
+        assert_eq!(1, result.costs[Position { x: 1, y: 0 }][Direction::Right]);
+        assert!(result.predecessors.is_empty());
+
+        let costs = MoveCosts { forward: 1, turn: 2 };
+        let result = facing_dijkstra((6, 3), start, is_open, costs, |pos| pos == end, true);
+        let result = result.unwrap();
+        assert_eq!(4, result.cost);
+        assert_eq!(3, result.optimal_cells().len());
+
+        // Straight across the top, then down the right side
+        let end = Position { x: 5, y: 2 };
+        let result = facing_dijkstra((6, 3), start, is_open, costs, |pos| pos == end, true);
+        let result = result.unwrap();
+        assert_eq!(9, result.cost);
+        assert_eq!(8, result.optimal_cells().len());
+
+        // Both ways around the wall cost the same once turning is free
+        let costs = MoveCosts { forward: 1, turn: 0 };
+        let result = facing_dijkstra((6, 3), start, is_open, costs, |pos| pos == end, true);
+        let result = result.unwrap();
+        assert_eq!(7, result.cost);
+        assert_eq!(14, result.optimal_cells().len());
+
+        assert_eq!(None, facing_dijkstra((6, 3), start, is_open, costs, |pos| pos.y == 3, true));
+    }
+
+    #[test]
+    fn partition_points() {
+        let values = [1, 3, 3, 5, 8, 13];
+        for target in 0..15 {
+            let expected = values.partition_point(|&value| value < target);
+            assert_eq!(expected, partition_point_over(0..values.len(), |i| values[i] < target));
+        }
+
+        assert_eq!(5, partition_point_over(5..5, |_| panic!("empty range")));
+        assert_eq!(7, partition_point_over(3..10, |i| i < 7));
+        assert_eq!(usize::MAX, partition_point_over(usize::MAX - 3..usize::MAX, |_| true));
+    }
+}
@@ -0,0 +1,128 @@
+// Set of byte strings stored as a tree of shared prefixes, so that every key starting some text can
+// be found in a single walk along the text instead of testing each key in turn. Children are kept
+// in a short unsorted list per node, which is fast for the small alphabets that puzzles use
+#[derive(Debug, Clone)]
+pub struct Trie {
+    // The root is node 0
+    nodes: Vec<Node>,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(u8, u32)>,
+    // Whether the path from the root to this node spells a key
+    terminal: bool,
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self { nodes: vec![Node::default()], len: 0 }
+    }
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|&&(b, _)| b == byte)
+            .map(|&(_, child)| child as usize)
+    }
+
+    // The node reached by walking `path` from the root, if every byte has a child
+    fn walk(&self, path: &[u8]) -> Option<usize> {
+        path.iter().try_fold(0, |node, &byte| self.child(node, byte))
+    }
+
+    // Returns whether the key was newly added
+    pub fn insert(&mut self, key: &[u8]) -> bool {
+        let mut node = 0;
+        for &byte in key {
+            node = match self.child(node, byte) {
+                Some(child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.push((byte, child as u32));
+                    child
+                }
+            };
+        }
+
+        let added = !self.nodes[node].terminal;
+        self.nodes[node].terminal = true;
+        self.len += usize::from(added);
+        added
+    }
+
+    pub fn contains(&self, key: &[u8]) -> bool {
+        self.walk(key).is_some_and(|node| self.nodes[node].terminal)
+    }
+
+    // Whether any key starts with `prefix`
+    pub fn contains_prefix(&self, prefix: &[u8]) -> bool {
+        self.walk(prefix).is_some()
+    }
+
+    // Lengths of every key that is a prefix of `text`, shortest first. The walk stops as soon as no
+    // longer key can match
+    pub fn prefix_lens<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        let nodes = text.iter().scan(0, move |node, &byte| {
+            *node = self.child(*node, byte)?;
+            Some(*node)
+        });
+        [0].into_iter()
+            .chain(nodes)
+            .enumerate()
+            .filter(|&(_, node)| self.nodes[node].terminal)
+            .map(|(len, _)| len)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<'a> FromIterator<&'a [u8]> for Trie {
+    fn from_iter<I: IntoIterator<Item = &'a [u8]>>(iter: I) -> Self {
+        let mut trie = Self::new();
+        for key in iter {
+            trie.insert(key);
+        }
+        trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes() {
+        let mut trie: Trie = [b"b".as_slice(), b"br", b"bwu", b"r"].into_iter().collect();
+        assert_eq!(4, trie.len());
+        assert!(!trie.insert(b"br"));
+        assert_eq!(4, trie.len());
+
+        assert!(trie.contains(b"bwu"));
+        assert!(!trie.contains(b"bw"));
+        assert!(trie.contains_prefix(b"bw"));
+        assert!(!trie.contains_prefix(b"g"));
+
+        assert_eq!(vec![1, 2], trie.prefix_lens(b"brwr").collect::<Vec<_>>());
+        assert_eq!(vec![1, 3], trie.prefix_lens(b"bwu").collect::<Vec<_>>());
+        assert_eq!(Vec::<usize>::new(), trie.prefix_lens(b"gb").collect::<Vec<_>>());
+
+        assert!(trie.insert(b""));
+        assert_eq!(vec![0], trie.prefix_lens(b"g").collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,128 @@
+// Disjoint set forest over the elements 0..n, with path compression and union by size
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    num_components: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        Self { parents: (0..len).collect(), sizes: vec![1; len], num_components: len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Point every element on the path directly at the root
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    // Returns false if the two elements were already in the same component
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let mut a_root = self.find(a);
+        let mut b_root = self.find(b);
+        if a_root == b_root {
+            return false;
+        }
+
+        if self.sizes[a_root] < self.sizes[b_root] {
+            (a_root, b_root) = (b_root, a_root);
+        }
+        self.parents[b_root] = a_root;
+        self.sizes[a_root] += self.sizes[b_root];
+        self.num_components -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    // Returns every component as a list of elements, in order of each component's smallest element
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut root_to_component = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.num_components);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            if root_to_component[root] == usize::MAX {
+                root_to_component[root] = components.len();
+                components.push(Vec::with_capacity(self.sizes[root]));
+            }
+            components[root_to_component[root]].push(element);
+        }
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_find() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(6, uf.num_components());
+
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+
+        assert!(uf.connected(0, 3));
+        assert!(!uf.connected(0, 4));
+        assert_eq!(4, uf.component_size(2));
+        assert_eq!(1, uf.component_size(5));
+        assert_eq!(3, uf.num_components());
+    }
+
+    #[test]
+    fn components() {
+        let mut uf = UnionFind::new(7);
+        uf.union(5, 1);
+        uf.union(6, 3);
+        uf.union(3, 0);
+
+        assert_eq!(vec![vec![0, 3, 6], vec![1, 5], vec![2], vec![4]], uf.components());
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut uf = UnionFind::new(10_000);
+        for i in 1..uf.len() {
+            uf.union(i - 1, i);
+        }
+
+        assert_eq!(1, uf.num_components());
+        assert_eq!(10_000, uf.component_size(9_999));
+    }
+}
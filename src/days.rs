@@ -0,0 +1,140 @@
+// Solver registry across years. Each year module (e.g. `y2024`) lists its days in a `DAYS` array,
+// and everything that looks up a solver by number goes through `get` here
+use crate::Solution;
+use crate::panics::{self, Panicked};
+use crate::y2024;
+
+// A day's solver, either as separate functions per part or as a single function for days that
+// solve both parts together
+#[derive(Debug, Clone, Copy)]
+pub enum Solver {
+    Parts(fn(&str) -> Solution, fn(&str) -> Solution),
+    Combined(fn(&str) -> (Solution, Solution)),
+    // Days with no part 2
+    Part1(fn(&str) -> Solution),
+}
+
+impl Solver {
+    pub fn solve(self, input: &str) -> (Solution, Solution) {
+        match self {
+            Self::Parts(part1, part2) => (part1(input), part2(input)),
+            Self::Combined(solve) => solve(input),
+            Self::Part1(part1) => (part1(input), Solution::None),
+        }
+    }
+
+    // Same as `solve`, but a panic is caught and returned in place of the part that panicked, and
+    // part 2 still runs after part 1 panics. Both parts of a combined solver fail together
+    pub fn solve_catching(self, input: &str) -> [Result<Solution, Panicked>; 2] {
+        match self {
+            Self::Parts(part1, part2) => {
+                [panics::catch("Part 1", || part1(input)), panics::catch("Part 2", || part2(input))]
+            }
+            Self::Combined(solve) => match panics::catch("Solution", || solve(input)) {
+                Ok((solution1, solution2)) => [Ok(solution1), Ok(solution2)],
+                Err(panicked) => [Err(panicked.clone()), Err(panicked)],
+            },
+            Self::Part1(part1) => [panics::catch("Part 1", || part1(input)), Ok(Solution::None)],
+        }
+    }
+
+    // Solves only the requested part (1 or 2) where possible; days with a combined solver always
+    // solve both. Returns None for any other part number, including part 2 of a day without one
+    pub fn solve_part(self, part: u32, input: &str) -> Option<Solution> {
+        match (self, part) {
+            (Self::Parts(part1, _), 1) => Some(part1(input)),
+            (Self::Parts(_, part2), 2) => Some(part2(input)),
+            (Self::Combined(solve), 1) => Some(solve(input).0),
+            (Self::Combined(solve), 2) => Some(solve(input).1),
+            (Self::Part1(part1), 1) => Some(part1(input)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    // Number of implemented parts; 1 for days like the last day of each year, which have no part 2
+    pub parts: u32,
+    pub solver: Solver,
+}
+
+impl Day {
+    pub fn url(&self) -> String {
+        format!("https://adventofcode.com/{}/day/{}", self.year, self.day)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Year {
+    pub year: u32,
+    pub days: &'static [Day],
+    // Days whose solvers draw something with `--visualize` (see crate::visualize); every other day
+    // ignores the flag
+    pub visualized: &'static [u32],
+}
+
+pub const YEARS: [Year; 1] =
+    [Year { year: 2024, days: &y2024::DAYS, visualized: &y2024::VISUALIZED_DAYS }];
+
+// Used wherever a year isn't given, so that existing `aoc run 5`-style commands keep working
+pub const DEFAULT_YEAR: u32 = 2024;
+
+pub fn year(year: u32) -> Option<&'static [Day]> {
+    YEARS.iter().find(|entry| entry.year == year).map(|entry| entry.days)
+}
+
+pub fn visualizes(year: u32, day: u32) -> bool {
+    YEARS.iter().any(|entry| entry.year == year && entry.visualized.contains(&day))
+}
+
+pub fn get(year: u32, day: u32) -> Option<Day> {
+    self::year(year)?.iter().find(|entry| entry.day == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        for year in YEARS {
+            for (i, day) in year.days.iter().enumerate() {
+                assert_eq!(i as u32 + 1, day.day);
+                assert_eq!(year.year, day.year);
+                assert!(matches!(day.parts, 1 | 2));
+            }
+        }
+        assert!(year(DEFAULT_YEAR).is_some());
+        assert!(year(2015).is_none());
+
+        assert_eq!(Some("Historian Hysteria"), get(2024, 1).map(|day| day.title));
+        assert!(get(2024, 26).is_none());
+        assert!(get(2015, 1).is_none());
+        assert_eq!("https://adventofcode.com/2024/day/16", get(2024, 16).unwrap().url());
+        assert_eq!(1, get(2024, 25).unwrap().parts);
+        assert!(visualizes(2024, 14));
+        assert!(!visualizes(2024, 1));
+        assert!(!visualizes(2015, 14));
+
+        let input = include_str!("../sample/2024/day1.txt");
+        let solver = get(2024, 1).unwrap().solver;
+        assert_eq!(Some(Solution::U64(11)), solver.solve_part(1, input));
+        assert_eq!(Some(Solution::U64(31)), solver.solve_part(2, input));
+        assert_eq!(None, solver.solve_part(3, input));
+        assert_eq!([Ok(Solution::U64(11)), Ok(Solution::U64(31))], solver.solve_catching(input));
+
+        let [part1, part2] = solver.solve_catching("not a list of numbers");
+        assert_eq!("Part 1", part1.unwrap_err().label);
+        assert_eq!("Part 2", part2.unwrap_err().label);
+
+        let input = include_str!("../sample/2024/day25.txt");
+        let solver = get(2024, 25).unwrap().solver;
+        assert_eq!(Some(Solution::U64(3)), solver.solve_part(1, input));
+        assert_eq!(None, solver.solve_part(2, input));
+        assert_eq!((Solution::U64(3), Solution::None), solver.solve(input));
+    }
+}
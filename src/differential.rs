@@ -0,0 +1,107 @@
+// Differential testing: runs a day's optimized solver and a naive reference implementation on
+// generated inputs and reports the first input where they disagree. The references are slow by
+// design, so generated sizes should stay small. A mismatch is shrunk to the smallest size that still
+// disagrees for the same seed, which is usually much easier to debug than the full-size input
+use crate::Solution;
+use crate::days::Solver;
+use crate::y2024;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Differential {
+    // Both solvers may use different parameters than the day's registered solver (e.g. fewer
+    // blinks), but they must use the same parameters as each other
+    pub optimized: Solver,
+    pub reference: Solver,
+    pub generate: fn(&mut StdRng, usize) -> String,
+    // Generated input size that the reference can handle quickly
+    pub size: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub day: u32,
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub optimized: (Solution, Solution),
+    pub reference: (Solution, Solution),
+}
+
+pub const CASES: [(u32, Differential); 7] = [
+    (2, y2024::day02::DIFFERENTIAL),
+    (7, y2024::day07::DIFFERENTIAL),
+    (9, y2024::day09::DIFFERENTIAL),
+    (11, y2024::day11::DIFFERENTIAL),
+    (19, y2024::day19::DIFFERENTIAL),
+    (20, y2024::day20::DIFFERENTIAL),
+    (22, y2024::day22::DIFFERENTIAL),
+];
+
+pub fn get(day: u32) -> Option<Differential> {
+    CASES.iter().find(|&&(case_day, _)| case_day == day).map(|&(_, differential)| differential)
+}
+
+// Runs both implementations on inputs generated from each seed in `seeds`
+pub fn check(
+    day: u32,
+    differential: &Differential,
+    seeds: impl IntoIterator<Item = u64>,
+) -> Result<(), Box<Mismatch>> {
+    for seed in seeds {
+        if compare(day, differential, seed, differential.size).is_some() {
+            let mismatch = (0..=differential.size)
+                .find_map(|size| compare(day, differential, seed, size))
+                .unwrap();
+            return Err(Box::new(mismatch));
+        }
+    }
+
+    Ok(())
+}
+
+fn compare(day: u32, differential: &Differential, seed: u64, size: usize) -> Option<Mismatch> {
+    let input = (differential.generate)(&mut StdRng::seed_from_u64(seed), size);
+    let optimized = differential.optimized.solve(&input);
+    let reference = differential.reference.solve(&input);
+    (optimized != reference).then_some(Mismatch { day, seed, size, input, optimized, reference })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_mismatch() {
+        let broken = Differential {
+            reference: Solver::Parts(|input| input.len().into(), |_| Solution::None),
+            optimized: Solver::Parts(|input| input.trim().len().into(), |_| Solution::None),
+            generate: |_, _| "abc\n".into(),
+            size: 0,
+        };
+        let mismatch = check(0, &broken, [5]).unwrap_err();
+        assert_eq!(5, mismatch.seed);
+        assert_eq!((Solution::U64(3), Solution::None), mismatch.optimized);
+        assert_eq!((Solution::U64(4), Solution::None), mismatch.reference);
+    }
+
+    #[test]
+    fn shrinks_mismatch() {
+        // Disagrees on every input with at least 3 lines
+        let broken = Differential {
+            reference: Solver::Parts(|input| input.lines().count().into(), |_| Solution::None),
+            optimized: Solver::Parts(
+                |input| input.lines().count().min(2).into(),
+                |_| Solution::None,
+            ),
+            generate: |_, size| "x\n".repeat(size),
+            size: 10,
+        };
+        assert!(check(0, &Differential { size: 2, ..broken }, 0..5).is_ok());
+
+        let mismatch = check(0, &broken, [1, 2]).unwrap_err();
+        assert_eq!((1, 3), (mismatch.seed, mismatch.size));
+        assert_eq!("x\nx\nx\n", mismatch.input);
+    }
+}
@@ -0,0 +1,102 @@
+// C ABI entry points, for linking the solvers into non-Rust harnesses. Build the shared library
+// with `cargo rustc --lib --release --features ffi --crate-type cdylib` (the default crate type is
+// rlib only); declarations are in include/aoc.h
+use crate::days;
+use std::panic;
+use std::{ptr, slice, str};
+
+pub const AOC_OK: i32 = 0;
+// No solver for the requested day, or part is not 1 or 2
+pub const AOC_INVALID_DAY_OR_PART: i32 = 1;
+// A pointer argument was null or the input was not valid UTF-8
+pub const AOC_INVALID_ARGUMENT: i32 = 2;
+// The answer does not fit in the output buffer; `*out_len` is set to the required length
+pub const AOC_BUFFER_TOO_SMALL: i32 = 3;
+// The solver panicked, usually because of malformed input
+pub const AOC_SOLVER_FAILED: i32 = 4;
+
+/// Solves one part (1 or 2) of a day and writes the answer to `out_buf` as UTF-8 bytes, without
+/// a trailing NUL. On entry `*out_len` is the capacity of `out_buf`; on `AOC_OK` or
+/// `AOC_BUFFER_TOO_SMALL` it is set to the length of the answer. Returns one of the `AOC_`
+/// status codes.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `input_len` readable bytes, `out_len` must point to a writable
+/// `usize`, and `out_buf` must point to `*out_len` writable bytes. `input_ptr` and `out_buf` may
+/// be null only if the corresponding length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_len.is_null() || (input_ptr.is_null() && input_len != 0) {
+        return AOC_INVALID_ARGUMENT;
+    }
+
+    let input = if input_len == 0 { &[] } else { slice::from_raw_parts(input_ptr, input_len) };
+    let Ok(input) = str::from_utf8(input) else {
+        return AOC_INVALID_ARGUMENT;
+    };
+
+    let Some(entry) = days::get(days::DEFAULT_YEAR, day) else {
+        return AOC_INVALID_DAY_OR_PART;
+    };
+
+    // Unwinding across an extern "C" boundary aborts the process, so catch solver panics here
+    let answer = match panic::catch_unwind(|| entry.solver.solve_part(part, input)) {
+        Ok(Some(answer)) => answer.to_string(),
+        Ok(None) => return AOC_INVALID_DAY_OR_PART,
+        Err(_) => return AOC_SOLVER_FAILED,
+    };
+
+    let capacity = *out_len;
+    *out_len = answer.len();
+    if answer.len() > capacity {
+        return AOC_BUFFER_TOO_SMALL;
+    }
+    if !answer.is_empty() {
+        if out_buf.is_null() {
+            return AOC_INVALID_ARGUMENT;
+        }
+        ptr::copy_nonoverlapping(answer.as_ptr(), out_buf, answer.len());
+    }
+
+    AOC_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve_into(day: u32, part: u32, input: &str, buf: &mut [u8]) -> (i32, usize) {
+        let mut len = buf.len();
+        let status = unsafe {
+            aoc_solve(day, part, input.as_ptr(), input.len(), buf.as_mut_ptr(), &mut len)
+        };
+        (status, len)
+    }
+
+    #[test]
+    fn solve() {
+        let input = include_str!("../sample/2024/day1.txt");
+        let mut buf = [0; 16];
+        assert_eq!((AOC_OK, 2), solve_into(1, 2, input, &mut buf));
+        assert_eq!(b"31", &buf[..2]);
+
+        assert_eq!((AOC_BUFFER_TOO_SMALL, 2), solve_into(1, 2, input, &mut buf[..1]));
+        assert_eq!(AOC_INVALID_DAY_OR_PART, solve_into(26, 1, input, &mut buf).0);
+        assert_eq!(AOC_INVALID_DAY_OR_PART, solve_into(1, 3, input, &mut buf).0);
+        assert_eq!(AOC_SOLVER_FAILED, solve_into(1, 1, "not a list", &mut buf).0);
+
+        let invalid_utf8 = [0xff, 0xfe];
+        let mut len = buf.len();
+        let status =
+            unsafe { aoc_solve(1, 1, invalid_utf8.as_ptr(), 2, buf.as_mut_ptr(), &mut len) };
+        assert_eq!(AOC_INVALID_ARGUMENT, status);
+    }
+}
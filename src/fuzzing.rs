@@ -0,0 +1,35 @@
+// Parser entry points for fuzzing (see fuzz/). Each target parses and validates input without
+// solving it, so malformed input should always come back as a ParseError; a panic is a parser bug
+use crate::{ByteGrid, ParseError, y2024};
+
+pub type Target = fn(&str) -> Result<(), ParseError>;
+
+pub const TARGETS: [(&str, Target); 14] = [
+    ("byte_grid", |input| ByteGrid::parse(input).map(drop)),
+    ("day05", y2024::day05::FUZZ_TARGET),
+    ("day06", y2024::day06::FUZZ_TARGET),
+    ("day07", y2024::day07::FUZZ_TARGET),
+    ("day08", y2024::day08::FUZZ_TARGET),
+    ("day13", y2024::day13::FUZZ_TARGET),
+    ("day14", y2024::day14::FUZZ_TARGET),
+    ("day15", y2024::day15::FUZZ_TARGET),
+    ("day16", y2024::day16::FUZZ_TARGET),
+    ("day17", y2024::day17::FUZZ_TARGET),
+    ("day18", y2024::day18::FUZZ_TARGET),
+    ("day20", y2024::day20::FUZZ_TARGET),
+    ("day24", y2024::day24::FUZZ_TARGET),
+    ("day25", y2024::day25::FUZZ_TARGET),
+];
+
+pub fn get(name: &str) -> Option<Target> {
+    TARGETS.iter().find(|&&(target, _)| target == name).map(|&(_, target)| target)
+}
+
+// Runs the named target on arbitrary bytes. Bytes that aren't UTF-8 are skipped, since input is
+// always read as a str before parsing
+pub fn run(name: &str, data: &[u8]) {
+    let target = get(name).unwrap_or_else(|| panic!("No fuzz target named {name}"));
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = target(input);
+    }
+}
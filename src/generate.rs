@@ -0,0 +1,503 @@
+// Random valid puzzle inputs for stress testing beyond the official inputs. Each generator takes a
+// single size parameter whose meaning depends on the day
+use crate::math;
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rustc_hash::FxHashSet;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub day: u32,
+    // What `size` controls, for usage messages
+    pub size_meaning: &'static str,
+    // Size that roughly matches the official input
+    pub default_size: usize,
+    pub generate: fn(&mut StdRng, usize) -> String,
+}
+
+pub const GENERATORS: [Generator; 14] = [
+    Generator { day: 1, size_meaning: "lines", default_size: 1000, generate: location_lists },
+    Generator { day: 2, size_meaning: "reports", default_size: 1000, generate: reports },
+    Generator {
+        day: 7,
+        size_meaning: "equations",
+        default_size: 850,
+        generate: calibration_equations,
+    },
+    Generator { day: 9, size_meaning: "digits", default_size: 19999, generate: disk_map },
+    Generator { day: 10, size_meaning: "map width", default_size: 50, generate: height_map },
+    Generator { day: 11, size_meaning: "stones", default_size: 8, generate: stones },
+    Generator { day: 15, size_meaning: "map width", default_size: 50, generate: warehouse },
+    Generator { day: 16, size_meaning: "maze width", default_size: 141, generate: maze },
+    Generator { day: 19, size_meaning: "designs", default_size: 400, generate: towel_designs },
+    Generator { day: 20, size_meaning: "track width", default_size: 141, generate: racetrack },
+    Generator { day: 21, size_meaning: "codes", default_size: 5, generate: door_codes },
+    Generator { day: 22, size_meaning: "buyers", default_size: 2000, generate: secret_numbers },
+    Generator { day: 24, size_meaning: "adder bits", default_size: 45, generate: adder_circuit },
+    Generator { day: 25, size_meaning: "schematics", default_size: 500, generate: schematics },
+];
+
+pub fn get(day: u32) -> Option<Generator> {
+    GENERATORS.iter().copied().find(|generator| generator.day == day)
+}
+
+// Day 1: two columns of 5-digit location IDs
+pub fn location_lists(rng: &mut StdRng, lines: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..lines {
+        let left: u32 = rng.gen_range(10000..100000);
+        let right: u32 = rng.gen_range(10000..100000);
+        writeln!(input, "{left}   {right}").unwrap();
+    }
+    input
+}
+
+// Day 2: reports of 5 to 8 levels that mostly step by 1-3 in one direction, with about half of them
+// having a single bad step (no change, a change in direction, or a jump of 4-5) so that part 2's
+// dampener matters
+pub fn reports(rng: &mut StdRng, lines: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..lines {
+        let len = rng.gen_range(5..=8);
+        let sign = if rng.gen() { 1 } else { -1 };
+        let bad_step = rng.gen::<bool>().then(|| rng.gen_range(0..len - 1));
+
+        let mut levels: Vec<i32> = vec![rng.gen_range(30..70)];
+        for i in 0..len - 1 {
+            let step = if bad_step == Some(i) {
+                *[0, -sign * rng.gen_range(1..=3), sign * rng.gen_range(4..=5)].choose(rng).unwrap()
+            } else {
+                sign * rng.gen_range(1..=3)
+            };
+            levels.push(levels[i] + step);
+        }
+
+        let levels: Vec<_> = levels.iter().map(i32::to_string).collect();
+        writeln!(input, "{}", levels.join(" ")).unwrap();
+    }
+    input
+}
+
+// Day 7: calibration equations with 2 to 8 operands. About half have a test value produced by a
+// random choice of operators (including concatenation) and the rest have a random test value
+pub fn calibration_equations(rng: &mut StdRng, lines: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..lines {
+        let len = rng.gen_range(2..=8);
+        let operands: Vec<u64> = (0..len).map(|_| rng.gen_range(1..100)).collect();
+
+        let test = if rng.gen() {
+            operands[1..].iter().fold(operands[0], |acc, &operand| match rng.gen_range(0..3) {
+                0 => acc + operand,
+                1 => acc * operand,
+                _ => math::concat(acc, operand),
+            })
+        } else {
+            rng.gen_range(1..1_000_000)
+        };
+
+        let operands: Vec<_> = operands.iter().map(u64::to_string).collect();
+        writeln!(input, "{test}: {}", operands.join(" ")).unwrap();
+    }
+    input
+}
+
+// Day 9: disk map with `len` digits. File sizes are 1-9 and free space sizes are 0-9
+pub fn disk_map(rng: &mut StdRng, len: usize) -> String {
+    let mut input: String = (0..len.max(1))
+        .map(|i| {
+            let digit = if i % 2 == 0 { rng.gen_range(1..=9) } else { rng.gen_range(0..=9) };
+            char::from(b'0' + digit)
+        })
+        .collect();
+    input.push('\n');
+    input
+}
+
+// Day 10: square topographic map. Heights mostly rise by one per step along diagonal bands, which
+// forms many long overlapping trails, with some cells replaced by random heights to break them up
+pub fn height_map(rng: &mut StdRng, width: usize) -> String {
+    let width = width.max(1);
+    let mut input = String::with_capacity(width * (width + 1));
+    for y in 0..width {
+        for x in 0..width {
+            let height = if rng.gen_ratio(1, 4) { rng.gen_range(0..10) } else { (x + y) % 10 };
+            input.push(char::from(b'0' + height as u8));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+// Day 11: space-separated stones with up to 6 digits
+pub fn stones(rng: &mut StdRng, count: usize) -> String {
+    let stones: Vec<_> = (0..count.max(1))
+        .map(|_| {
+            let digits = rng.gen_range(1..=6);
+            rng.gen_range(0..10_u64.pow(digits)).to_string()
+        })
+        .collect();
+    format!("{}\n", stones.join(" "))
+}
+
+const MOVE_LINE_LEN: usize = 1000;
+
+// Day 15: square walled warehouse with scattered interior walls and boxes, the robot in the center,
+// and 8 moves per map cell (the official input's ratio)
+pub fn warehouse(rng: &mut StdRng, width: usize) -> String {
+    let width = width.max(5);
+    let center = width / 2;
+
+    let mut input = String::new();
+    for y in 0..width {
+        for x in 0..width {
+            let c = if x == 0 || y == 0 || x == width - 1 || y == width - 1 {
+                '#'
+            } else if (x, y) == (center, center) {
+                '@'
+            } else {
+                match rng.gen_range(0..100) {
+                    0..8 => '#',
+                    8..35 => 'O',
+                    _ => '.',
+                }
+            };
+            input.push(c);
+        }
+        input.push('\n');
+    }
+    input.push('\n');
+
+    let moves = 8 * width * width;
+    for i in 0..moves {
+        input.push(*['^', 'v', '<', '>'].choose(rng).unwrap());
+        if i % MOVE_LINE_LEN == MOVE_LINE_LEN - 1 || i == moves - 1 {
+            input.push('\n');
+        }
+    }
+
+    input
+}
+
+// Day 16: square maze carved by randomized depth-first search, with some extra walls knocked out
+// so that there are multiple paths between start (bottom left) and end (top right). Even widths
+// are rounded up
+pub fn maze(rng: &mut StdRng, width: usize) -> String {
+    let mut walls = carve_maze(rng, width);
+    let width = walls.len();
+
+    for (y, row) in walls.iter_mut().enumerate().take(width - 1).skip(1) {
+        for (x, wall) in row.iter_mut().enumerate().take(width - 1).skip(1) {
+            // Walls between two horizontally or vertically adjacent cells
+            let between_cells = (x % 2 == 0) != (y % 2 == 0);
+            if between_cells && *wall && rng.gen_ratio(1, 10) {
+                *wall = false;
+            }
+        }
+    }
+
+    render_maze(&walls)
+}
+
+const TOWEL_COLORS: [u8; 5] = *b"wubrg";
+
+// Far fewer than the ~490k distinct towels of 1-8 stripes, so that redrawing a repeat stays quick
+const MAX_TOWELS: usize = 100_000;
+
+// Day 19: about as many towels (1-8 stripes) as designs (20-60 stripes). One color never appears as
+// a single-stripe towel, as in the official input, so that not every design is possible. Half of
+// the designs are concatenations of towels and the rest are random stripes
+pub fn towel_designs(rng: &mut StdRng, designs: usize) -> String {
+    let missing = *TOWEL_COLORS.choose(rng).unwrap();
+    let random_stripes = |rng: &mut StdRng, len: usize| -> String {
+        (0..len).map(|_| char::from(*TOWEL_COLORS.choose(rng).unwrap())).collect()
+    };
+
+    // Real inputs never list a towel twice, so redraw repeats
+    let mut towels: Vec<String> = Vec::new();
+    let mut seen = FxHashSet::default();
+    while towels.len() < designs.clamp(1, MAX_TOWELS) {
+        let len = rng.gen_range(1..=8);
+        let towel = random_stripes(rng, len);
+        if towel.as_bytes() != [missing] && seen.insert(towel.clone()) {
+            towels.push(towel);
+        }
+    }
+
+    let mut input = format!("{}\n\n", towels.join(", "));
+    for _ in 0..designs {
+        let len = rng.gen_range(20..=60);
+        let design = if rng.gen() {
+            let mut design = String::new();
+            while design.len() < len {
+                design.push_str(towels.choose(rng).unwrap());
+            }
+            design
+        } else {
+            random_stripes(rng, len)
+        };
+        writeln!(input, "{design}").unwrap();
+    }
+    input
+}
+
+// Day 20: single track from start (bottom left) to end (top right), taken as the path between
+// them through a randomly carved maze with every dead end walled off. Even widths are rounded up
+pub fn racetrack(rng: &mut StdRng, width: usize) -> String {
+    let maze = carve_maze(rng, width);
+    let width = maze.len();
+    let (start, end) = ((1, width - 2), (width - 2, 1));
+
+    // Perfect mazes have exactly one path between any two cells, so DFS parents give the track
+    let mut parents = vec![vec![None; width]; width];
+    let mut stack = vec![start];
+    while let Some((x, y)) = stack.pop() {
+        for (nx, ny) in [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
+            if !maze[ny][nx] && parents[ny][nx].is_none() && (nx, ny) != start {
+                parents[ny][nx] = Some((x, y));
+                stack.push((nx, ny));
+            }
+        }
+    }
+
+    let mut walls = vec![vec![true; width]; width];
+    let mut pos = end;
+    walls[pos.1][pos.0] = false;
+    while let Some(parent) = parents[pos.1][pos.0] {
+        walls[parent.1][parent.0] = false;
+        pos = parent;
+    }
+
+    render_maze(&walls)
+}
+
+// Returns a grid of walls with a perfect maze carved by randomized depth-first search. Cells are
+// at odd coordinates and walls between adjacent cells are at the midpoints
+fn carve_maze(rng: &mut StdRng, width: usize) -> Vec<Vec<bool>> {
+    let width = (width.max(5)) | 1;
+    let mut walls = vec![vec![true; width]; width];
+
+    let mut stack: Vec<(usize, usize)> = vec![(1, width - 2)];
+    walls[width - 2][1] = false;
+    while let Some(&(x, y)) = stack.last() {
+        let mut neighbors: Vec<(usize, usize)> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                (nx < width - 1 && ny < width - 1 && walls[ny][nx]).then_some((nx, ny))
+            })
+            .collect();
+        if neighbors.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        neighbors.shuffle(rng);
+        let (nx, ny) = neighbors[0];
+        walls[(y + ny) / 2][(x + nx) / 2] = false;
+        walls[ny][nx] = false;
+        stack.push((nx, ny));
+    }
+
+    walls
+}
+
+// Start is in the bottom left corner and end is in the top right corner
+fn render_maze(walls: &[Vec<bool>]) -> String {
+    let width = walls.len();
+    let mut input = String::new();
+    for (y, row) in walls.iter().enumerate() {
+        for (x, &wall) in row.iter().enumerate() {
+            let c = if (x, y) == (1, width - 2) {
+                'S'
+            } else if (x, y) == (width - 2, 1) {
+                'E'
+            } else if wall {
+                '#'
+            } else {
+                '.'
+            };
+            input.push(c);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+// Day 21: door codes of 3 digits followed by A, e.g. 029A
+pub fn door_codes(rng: &mut StdRng, count: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..count {
+        writeln!(input, "{:03}A", rng.gen_range(0..1000)).unwrap();
+    }
+    input
+}
+
+// Day 22: initial secret numbers
+pub fn secret_numbers(rng: &mut StdRng, buyers: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..buyers {
+        writeln!(input, "{}", rng.gen_range(1..1 << 24)).unwrap();
+    }
+    input
+}
+
+// Day 24: ripple-carry adder with random inputs, random internal wire names, and 4 pairs of
+// swapped gate outputs
+pub fn adder_circuit(rng: &mut StdRng, bits: usize) -> String {
+    adder_with_swaps(rng, bits, 4).0
+}
+
+// Returns the circuit along with the sorted names of the swapped wires. Swaps are all within a
+// single bit's full adder (never bit 0 or the last bit) so that the circuit stays acyclic, and no
+// bit has more than one swap
+pub fn adder_with_swaps(rng: &mut StdRng, bits: usize, swaps: usize) -> (String, Vec<String>) {
+    assert!(bits >= 3 && swaps <= bits - 2, "Too many swaps for a {bits}-bit adder");
+
+    let mut used_names = FxHashSet::default();
+    let mut random_name = |rng: &mut StdRng| loop {
+        let name: String = (0..3).map(|_| rng.gen_range(b'a'..b'x') as char).collect();
+        if used_names.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    // Per-bit wires: XOR of inputs, AND of inputs, AND of XOR and carry-in, and carry-out
+    let mut sum_xor = Vec::with_capacity(bits);
+    let mut input_and = Vec::with_capacity(bits);
+    let mut carry_and = Vec::with_capacity(bits);
+    let mut carry = Vec::with_capacity(bits);
+    for i in 0..bits {
+        sum_xor.push(random_name(rng));
+        input_and.push(random_name(rng));
+        carry_and.push(random_name(rng));
+        carry.push(if i == bits - 1 { format!("z{bits:02}") } else { random_name(rng) });
+    }
+
+    // Gates as (left, op, right, output)
+    let z = |i: usize| format!("z{i:02}");
+    let mut gates = vec![
+        ("x00".to_string(), "XOR", "y00".to_string(), z(0)),
+        ("x00".to_string(), "AND", "y00".to_string(), carry[0].clone()),
+    ];
+    for i in 1..bits {
+        let (x, y) = (format!("x{i:02}"), format!("y{i:02}"));
+        gates.push((x.clone(), "XOR", y.clone(), sum_xor[i].clone()));
+        gates.push((x, "AND", y, input_and[i].clone()));
+        gates.push((sum_xor[i].clone(), "XOR", carry[i - 1].clone(), z(i)));
+        gates.push((sum_xor[i].clone(), "AND", carry[i - 1].clone(), carry_and[i].clone()));
+        gates.push((input_and[i].clone(), "OR", carry_and[i].clone(), carry[i].clone()));
+    }
+
+    let mut swap_bits: Vec<_> = (1..bits - 1).collect();
+    swap_bits.shuffle(rng);
+    let mut swapped = Vec::new();
+    for &i in &swap_bits[..swaps] {
+        let (left, right) = match rng.gen_range(0..4) {
+            0 => (z(i), carry[i].clone()),
+            1 => (z(i), input_and[i].clone()),
+            2 => (z(i), carry_and[i].clone()),
+            _ => (sum_xor[i].clone(), input_and[i].clone()),
+        };
+        for gate in &mut gates {
+            if gate.3 == left {
+                gate.3.clone_from(&right);
+            } else if gate.3 == right {
+                gate.3.clone_from(&left);
+            }
+        }
+        swapped.extend([left, right]);
+    }
+    swapped.sort();
+
+    gates.shuffle(rng);
+
+    let mut input = String::new();
+    for prefix in ['x', 'y'] {
+        for i in 0..bits {
+            writeln!(input, "{prefix}{i:02}: {}", u8::from(rng.gen::<bool>())).unwrap();
+        }
+    }
+    input.push('\n');
+    for (left, op, right, output) in gates {
+        let (left, right) = if rng.gen() { (left, right) } else { (right, left) };
+        writeln!(input, "{left} {op} {right} -> {output}").unwrap();
+    }
+
+    (input, swapped)
+}
+
+const SCHEMATIC_WIDTH: usize = 5;
+const SCHEMATIC_HEIGHT: usize = 7;
+
+// Day 25: 5x7 lock and key schematics, roughly half of each
+pub fn schematics(rng: &mut StdRng, count: usize) -> String {
+    let schematics: Vec<_> = (0..count)
+        .map(|_| {
+            let lock = rng.gen();
+            schematic(rng, lock)
+        })
+        .collect();
+    schematics.join("\n")
+}
+
+// Locks have their top row filled and keys have their bottom row filled
+pub fn schematic(rng: &mut StdRng, lock: bool) -> String {
+    let heights: Vec<usize> =
+        (0..SCHEMATIC_WIDTH).map(|_| rng.gen_range(1..SCHEMATIC_HEIGHT)).collect();
+
+    let mut schematic = String::new();
+    for row in 0..SCHEMATIC_HEIGHT {
+        for &height in &heights {
+            let filled = if lock { row < height } else { row >= SCHEMATIC_HEIGHT - height };
+            schematic.push(if filled { '#' } else { '.' });
+        }
+        schematic.push('\n');
+    }
+    schematic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use rand::SeedableRng;
+
+    #[test]
+    fn generated_inputs_solve() {
+        let mut rng = StdRng::seed_from_u64(2024);
+        for generator in GENERATORS {
+            let size = generator.default_size / 4;
+            let input = (generator.generate)(&mut rng, size);
+            // Only checks that the solvers accept the input; correctness is checked per day
+            days::get(days::DEFAULT_YEAR, generator.day).unwrap().solver.solve(&input);
+        }
+    }
+
+    #[test]
+    fn towels_are_unique() {
+        for seed in 1..=3 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let input = towel_designs(&mut rng, 400);
+            let (towels, _) = input.split_once("\n\n").unwrap();
+            let towels: Vec<_> = towels.split(", ").collect();
+            let unique: FxHashSet<_> = towels.iter().collect();
+            assert_eq!(towels.len(), unique.len(), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn maze_is_bordered() {
+        let mut rng = StdRng::seed_from_u64(16);
+        let maze = maze(&mut rng, 10);
+        let rows: Vec<_> = maze.lines().collect();
+        assert_eq!(11, rows.len());
+        assert!(rows.iter().all(|row| row.len() == 11));
+        assert!(rows[0].bytes().chain(rows[10].bytes()).all(|b| b == b'#'));
+        assert_eq!(Some(1), rows[9].find('S'));
+        assert_eq!(Some(9), rows[1].find('E'));
+    }
+}
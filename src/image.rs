@@ -0,0 +1,190 @@
+// Image export for grid-shaped solver state, as PNG stills or animated GIFs. Callers build each
+// frame from a closure that picks the color of every cell, and each cell is drawn as a square of
+// `scale` pixels. Building frames always works; writing files needs the `image` feature
+#![cfg_attr(not(feature = "image"), allow(dead_code))]
+
+use crate::Grid;
+use std::error::Error;
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+// 8-bit RGB image, stored row-major with 3 bytes per pixel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    // `color` is called with (x, y) = (column, row) for each cell
+    pub fn from_fn(
+        cols: usize,
+        rows: usize,
+        scale: usize,
+        color: impl Fn(usize, usize) -> Rgb,
+    ) -> Self {
+        let width = cols * scale;
+        let height = rows * scale;
+        let mut pixels = Vec::with_capacity(3 * width * height);
+        for y in 0..rows {
+            let row: Vec<u8> = (0..cols).flat_map(|x| color(x, y).repeat(scale)).collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        Self { width, height, pixels }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, scale: usize, color: impl Fn(&T) -> Rgb) -> Self {
+        Self::from_fn(grid.cols(), grid.rows(), scale, |x, y| color(&grid.0[y][x]))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        let i = 3 * (y * self.width + x);
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]]
+    }
+
+    // Palette of distinct colors in first-seen order and the palette index of each pixel, or None if
+    // there are more than 256 colors
+    fn indexed(&self) -> Option<(Vec<u8>, Vec<u8>)> {
+        let mut palette: Vec<Rgb> = Vec::new();
+        let mut indices = Vec::with_capacity(self.width * self.height);
+        for pixel in self.pixels.chunks_exact(3) {
+            let index = match palette.iter().position(|color| color == pixel) {
+                Some(index) => index,
+                None if palette.len() < 256 => {
+                    palette.push([pixel[0], pixel[1], pixel[2]]);
+                    palette.len() - 1
+                }
+                None => return None,
+            };
+            indices.push(index as u8);
+        }
+
+        Some((palette.concat(), indices))
+    }
+}
+
+#[cfg(feature = "image")]
+pub fn save_png(image: &Image, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+    use std::fs::File;
+    use std::io::BufWriter;
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(file, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&image.pixels)?;
+
+    Ok(())
+}
+
+// Writes an animated GIF that loops forever, showing each frame for `delay_centis` hundredths of a
+// second. All frames must be the same size
+#[cfg(feature = "image")]
+pub fn save_gif(
+    path: impl AsRef<Path>,
+    delay_centis: u16,
+    frames: impl IntoIterator<Item = Image>,
+) -> Result<(), Box<dyn Error>> {
+    use std::fs::File;
+    use std::io::BufWriter;
+
+    let mut frames = frames.into_iter().peekable();
+    let Some(first) = frames.peek() else {
+        return Err("GIF needs at least one frame".into());
+    };
+    let size = (first.width, first.height);
+    let (width, height) = (u16::try_from(size.0)?, u16::try_from(size.1)?);
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for image in frames {
+        if (image.width, image.height) != size {
+            return Err("GIF frames must all be the same size".into());
+        }
+
+        // Grid renders rarely need more than a handful of colors, so skip quantization when possible
+        let mut frame = match image.indexed() {
+            Some((palette, indices)) => {
+                gif::Frame::from_palette_pixels(width, height, indices, palette, None)
+            }
+            None => gif::Frame::from_rgb_speed(width, height, &image.pixels, 10),
+        };
+        frame.delay = delay_centis;
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "image"))]
+pub fn save_png(_image: &Image, _path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+    Err("Image export requires building with `--features image`".into())
+}
+
+#[cfg(not(feature = "image"))]
+pub fn save_gif(
+    _path: impl AsRef<Path>,
+    _delay_centis: u16,
+    _frames: impl IntoIterator<Item = Image>,
+) -> Result<(), Box<dyn Error>> {
+    Err("Image export requires building with `--features image`".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let grid = Grid(vec![vec![true, false], vec![false, false]]);
+        let image = Image::from_grid(&grid, 2, |&dark| if dark { BLACK } else { WHITE });
+        assert_eq!((4, 4), (image.width(), image.height()));
+        assert_eq!(BLACK, image.pixel(1, 1));
+        assert_eq!(WHITE, image.pixel(2, 1));
+        assert_eq!(WHITE, image.pixel(0, 2));
+
+        let (palette, indices) = image.indexed().unwrap();
+        assert_eq!([BLACK, WHITE].concat(), palette);
+        assert_eq!(&[0, 0, 1, 1], &indices[..4]);
+
+        let noise = Image::from_fn(300, 1, 1, |x, _| [x as u8, (x / 256) as u8, 0]);
+        assert_eq!(None, noise.indexed());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn save() {
+        let dir = std::env::temp_dir();
+        let image = Image::from_fn(3, 2, 1, |x, y| [x as u8 * 100, y as u8 * 100, 0]);
+        let png_path = dir.join("aoc-image-test.png");
+        save_png(&image, &png_path).unwrap();
+        let png = std::fs::read(&png_path).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let gif_path = dir.join("aoc-image-test.gif");
+        save_gif(&gif_path, 5, [image.clone(), image]).unwrap();
+        let gif = std::fs::read(&gif_path).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+
+        assert!(save_gif(&gif_path, 5, []).is_err());
+        let _ = std::fs::remove_file(png_path);
+        let _ = std::fs::remove_file(gif_path);
+    }
+}
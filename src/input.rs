@@ -0,0 +1,244 @@
+// Input files for the runner. With the `mmap` feature the file is memory-mapped, so that huge
+// stress-test inputs are paged in on demand rather than copied into a String up front; otherwise it
+// is read into a buffer. Either way the contents are only checked to be UTF-8, and only copied if
+// they need normalizing. Gzip-compressed input and standard input are always read into a buffer
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::Path;
+
+pub struct InputFile {
+    contents: Contents,
+}
+
+enum Contents {
+    Buffered(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl InputFile {
+    // Opens `path`, or reads standard input if it is `-`. Files ending in `.gz` are decompressed,
+    // as is any input when `gzip` is set
+    pub fn read(path: &str, gzip: bool) -> io::Result<Self> {
+        let gzip = gzip || path.ends_with(".gz");
+        let bytes = if path == "-" {
+            let mut bytes = Vec::new();
+            io::stdin().lock().read_to_end(&mut bytes)?;
+            bytes
+        } else if gzip {
+            fs::read(path)?
+        } else {
+            return Self::open(path);
+        };
+
+        let bytes = if gzip { decompress(&bytes)? } else { bytes };
+        Ok(Self { contents: Contents::Buffered(bytes) })
+    }
+
+    // Input that didn't come from a file, e.g. from an environment variable
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { contents: Contents::Buffered(bytes) }
+    }
+
+    #[cfg(feature = "mmap")]
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        // Mapping an empty file fails on some platforms
+        if file.metadata()?.len() == 0 {
+            return Ok(Self { contents: Contents::Buffered(Vec::new()) });
+        }
+
+        // SAFETY: The mapping is read-only. Another process truncating or writing the input file
+        // while a solver runs is undefined behavior, which is accepted for puzzle inputs
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self { contents: Contents::Mapped(map) })
+    }
+
+    #[cfg(not(feature = "mmap"))]
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self { contents: Contents::Buffered(fs::read(path)?) })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.contents {
+            Contents::Buffered(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            Contents::Mapped(map) => map,
+        }
+    }
+
+    pub fn as_str(&self) -> io::Result<&str> {
+        std::str::from_utf8(self.as_bytes())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl Deref for InputFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_bytes()
+    }
+}
+
+// Failure to read the runner's input, naming the file since it may be the default path
+pub struct InputError {
+    path: String,
+    source: io::Error,
+}
+
+impl InputError {
+    pub fn new(path: impl Into<String>, source: io::Error) -> Self {
+        Self { path: path.into(), source }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error reading {}: {}", self.path, self.source)?;
+        if self.source.kind() == io::ErrorKind::NotFound {
+            f.write_str("; pass the input path as the first argument or set AOC_INPUT")?;
+        }
+        Ok(())
+    }
+}
+
+// Errors returned from `main` are printed with Debug, so make it as readable as Display
+impl fmt::Debug for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+// Multi-member decoding, so that concatenated .gz files read as one input
+#[cfg(feature = "gzip")]
+fn decompress(compressed: &[u8]) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    flate2::read::MultiGzDecoder::new(compressed).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn decompress(_compressed: &[u8]) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Reading gzip-compressed input needs the `gzip` feature",
+    ))
+}
+
+// Canonical form of puzzle input, so that parsers only have to handle one: no byte order mark,
+// `\n` line endings, no trailing whitespace on any line, and exactly one `\n` after the last
+// non-blank line. Blank lines within the input are kept. Input that is already canonical is
+// returned as-is
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    if is_normalized(input) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.trim_end().lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+fn is_normalized(input: &str) -> bool {
+    input.is_empty()
+        || (input.ends_with('\n')
+            && !input.ends_with("\n\n")
+            && !input.contains('\r')
+            && !input.contains(" \n")
+            && !input.contains("\t\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_file() {
+        let path = std::env::temp_dir().join("aoc-input-test.txt");
+        fs::write(&path, "3   4\n4   3\n").unwrap();
+        let input = InputFile::open(&path).unwrap();
+        assert_eq!("3   4\n4   3\n", input.as_str().unwrap());
+
+        fs::write(&path, "").unwrap();
+        assert_eq!(b"", &*InputFile::open(&path).unwrap());
+
+        fs::write(&path, b"\xff\n").unwrap();
+        let err = InputFile::open(&path).unwrap().as_str().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn input_errors() {
+        let missing = io::Error::from(io::ErrorKind::NotFound);
+        let err = InputError::new("inputs/day1.txt", missing);
+        assert_eq!(format!("{err:?}"), err.to_string());
+        assert!(err.to_string().starts_with("Error reading inputs/day1.txt: "), "{err}");
+        assert!(err.to_string().ends_with("or set AOC_INPUT"), "{err}");
+
+        let invalid = io::Error::new(io::ErrorKind::InvalidData, "invalid UTF-8");
+        let err = InputError::new("-", invalid);
+        assert_eq!("Error reading -: invalid UTF-8", err.to_string());
+    }
+
+    #[test]
+    fn read_gzip_file() {
+        let path = std::env::temp_dir().join("aoc-input-test.txt.gz");
+        let path_str = path.to_str().unwrap();
+
+        #[cfg(feature = "gzip")]
+        {
+            use flate2::Compression;
+            use flate2::write::GzEncoder;
+            use std::io::Write;
+
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(b"3   4\n4   3\n").unwrap();
+            fs::write(&path, encoder.finish().unwrap()).unwrap();
+            assert_eq!(
+                "3   4\n4   3\n",
+                InputFile::read(path_str, false).unwrap().as_str().unwrap()
+            );
+
+            fs::write(&path, "not gzip").unwrap();
+            assert!(InputFile::read(path_str, false).is_err());
+        }
+
+        #[cfg(not(feature = "gzip"))]
+        {
+            fs::write(&path, "").unwrap();
+            let err = InputFile::read(path_str, false).err().unwrap();
+            assert_eq!(io::ErrorKind::Unsupported, err.kind());
+        }
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn normalization() {
+        assert!(matches!(normalize("1\n\n2\n"), Cow::Borrowed("1\n\n2\n")));
+        assert!(matches!(normalize("\u{feff}1\n"), Cow::Borrowed("1\n")));
+        assert!(matches!(normalize(""), Cow::Borrowed("")));
+
+        assert_eq!("1\n\n2\n", normalize("\u{feff}1\r\n\r\n2\r\n"));
+        assert_eq!("a b\nc\n", normalize("a b  \nc\t\n\n \n"));
+        assert_eq!("xmas\n", normalize("xmas"));
+        assert_eq!("", normalize("\r\n"));
+    }
+}
@@ -1,37 +1,489 @@
-mod grid;
-mod pos;
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
-pub use grid::Grid;
-pub use pos::Pos2;
-pub use pos::Pos3;
+// Solver instrumentation for `--verbose`, taking the same `field = value, "message"` arguments as
+// `tracing::debug!`. Without the `logging` feature the fields are still type-checked but never
+// evaluated. Defined before the modules so that all of them can use it
+#[cfg(feature = "logging")]
+macro_rules! debug {
+    ($($args:tt)*) => {
+        tracing::debug!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! debug {
+    ($($field:ident $(= $value:expr)?,)* $message:literal) => {
+        let _ = || {
+            $(let _ = debug!(@field $field $(= $value)?);)*
+        };
+    };
+    (@field $field:ident) => {
+        &$field
+    };
+    (@field $field:ident = $value:expr) => {
+        &$value
+    };
+}
+
+#[cfg(feature = "alloc-tracking")]
+pub mod alloc_tracking;
+pub mod ansi;
+pub mod bench;
+pub mod common;
+pub mod days;
+#[cfg(feature = "reference")]
+pub mod differential;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+pub mod generate;
+pub mod image;
+pub mod input;
+pub mod panics;
+pub mod parallel;
+pub mod pin;
+pub mod profile;
+pub mod report;
+pub mod serialize;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(test)]
+mod snapshot;
+mod solution;
+pub mod stats;
+pub mod timeout;
+pub mod timing;
+pub mod visualizer;
+mod viz;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod y2024;
 
+pub use common::{
+    AhoCorasick, BitSet, BucketQueue, ByteGrid, Counter, Direction, DirectionMap, Graph, Grid,
+    GridTransform, Interner, Memo, ParseError, Pos2, Pos3, RangeSet, Rect, Trie, UnionFind,
+};
+pub use common::{aho_corasick, combinatorics, cycle, graph_export, math, parse, search};
+pub use solution::{CombinedOutput, Solution, SolverOutput};
+pub use timing::time_micros;
+
+use input::{InputError, InputFile};
+use panics::Panicked;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serialize::Json;
+use std::borrow::Cow;
 use std::error::Error;
-use std::fmt::Display;
-use std::time::Instant;
-use std::{env, fs, hint, io};
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use std::{env, io};
+use timing::{TimeFormat, Timing};
+#[cfg(feature = "logging")]
+use tracing::Level;
+
+// The first argument if it isn't a flag, otherwise the `AOC_INPUT` environment variable, otherwise
+// `inputs/<binary name>.txt` (e.g. `inputs/day16.txt`)
+pub fn input_filename() -> String {
+    input_path(env::args().nth(1), env_value("AOC_INPUT"), &binary_name())
+}
+
+fn env_value(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|var| !var.is_empty())
+}
+
+fn input_path(first_arg: Option<String>, path_var: Option<String>, binary_name: &str) -> String {
+    first_arg
+        .filter(|arg| !arg.starts_with("--"))
+        .or(path_var)
+        .unwrap_or_else(|| format!("inputs/{binary_name}.txt"))
+}
+
+// The input itself from `AOC_INPUT_TEXT`, for harnesses that can't pass arguments or write files.
+// An input path from the arguments or `AOC_INPUT` takes precedence
+fn input_text(
+    first_arg: Option<String>,
+    path_var: Option<String>,
+    text_var: Option<String>,
+) -> Option<String> {
+    let has_path = first_arg.is_some_and(|arg| !arg.starts_with("--")) || path_var.is_some();
+    text_var.filter(|_| !has_path)
+}
+
+// Arguments after the input filename, or every argument if the filename was left out
+fn flag_args() -> impl Iterator<Item = String> {
+    let mut args = env::args().skip(1).peekable();
+    if args.peek().is_some_and(|arg| !arg.starts_with("--")) {
+        args.next();
+    }
+    args
+}
+
+// `-` is standard input, and `--gzip` decompresses input that doesn't end in `.gz`
+fn open_input() -> Result<InputFile, InputError> {
+    let text = env_value("AOC_INPUT_TEXT");
+    if let Some(text) = input_text(env::args().nth(1), env_value("AOC_INPUT"), text) {
+        return Ok(InputFile::from_bytes(text.into_bytes()));
+    }
+
+    let path = input_filename();
+    InputFile::read(&path, has_flag("--gzip")).map_err(|err| InputError::new(path, err))
+}
+
+fn input_str(input: &InputFile) -> Result<&str, InputError> {
+    input.as_str().map_err(|err| InputError::new(input_filename(), err))
+}
+
+// Input file contents, normalized (see input::normalize) unless `--raw-input` was passed
+pub fn read_input() -> Result<String, InputError> {
+    let input = open_input()?;
+    Ok(prepare_input(input_str(&input)?).into_owned())
+}
+
+fn prepare_input(input: &str) -> Cow<'_, str> {
+    if has_flag("--raw-input") { Cow::Borrowed(input) } else { input::normalize(input) }
+}
+
+// Returns true if `name` was passed anywhere after the input filename, e.g. `--print-program`
+pub fn has_flag(name: &str) -> bool {
+    flag_args().any(|arg| arg == name)
+}
+
+// Returns the value passed for `name` after the input filename, accepting both `--name value` and
+// `--name=value`
+pub fn flag_value(name: &str) -> Option<String> {
+    let mut args = flag_args();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+
+        if let Some(value) = arg.strip_prefix(name).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value.into());
+        }
+    }
+
+    None
+}
+
+// Picks one of a day's alternate implementations by the name passed with `--algo`, defaulting to
+// the first, so that variants can stay in-tree and be compared rather than replacing each other
+pub fn select_algo<T: Copy>(variants: &[(&str, T)]) -> Result<T, String> {
+    pick_algo(flag_value("--algo").as_deref(), variants)
+}
+
+fn pick_algo<T: Copy>(name: Option<&str>, variants: &[(&str, T)]) -> Result<T, String> {
+    let Some(name) = name else {
+        return Ok(variants[0].1);
+    };
+
+    variants.iter().find(|&&(variant, _)| variant == name).map(|&(_, algo)| algo).ok_or_else(|| {
+        let names: Vec<_> = variants.iter().map(|&(variant, _)| variant).collect();
+        format!("Unknown algorithm '{name}'; expected one of: {}", names.join(", "))
+    })
+}
+
+// File stem of the running binary, e.g. `day16`
+pub fn binary_name() -> String {
+    env::args()
+        .next()
+        .and_then(|path| PathBuf::from(path).file_stem().map(|stem| stem.to_string_lossy().into()))
+        .unwrap_or_else(|| String::from("aoc"))
+}
+
+// Year selected with `--year`, defaulting to days::DEFAULT_YEAR, along with that year's days
+pub fn year_flag() -> Result<(u32, &'static [days::Day]), String> {
+    let year = match flag_value("--year") {
+        Some(year) => year.parse().map_err(|_| format!("Invalid year '{year}'"))?,
+        None => days::DEFAULT_YEAR,
+    };
+    let days = days::year(year).ok_or_else(|| format!("No solvers for year {year}"))?;
+    Ok((year, days))
+}
+
+// Parses the log level for `init_logging`. `AOCLOG` takes precedence and accepts any level name
+// (error, warn, info, debug, trace); `--verbose` on its own means debug
+#[cfg(feature = "logging")]
+fn log_level(verbose: bool, aoclog: Option<&str>) -> Result<Option<Level>, String> {
+    match aoclog.filter(|value| !value.is_empty()) {
+        Some(value) => {
+            value.parse().map(Some).map_err(|_| format!("Invalid AOCLOG level: '{value}'"))
+        }
+        None => Ok(verbose.then_some(Level::DEBUG)),
+    }
+}
+
+// Installs a logger for solver instrumentation if `--verbose` was passed or `AOCLOG` is set.
+// Logs go to stderr so that stdout only ever contains solutions
+#[cfg(feature = "logging")]
+pub fn init_logging() -> Result<(), Box<dyn Error>> {
+    let aoclog = env::var("AOCLOG").ok();
+    let Some(level) = log_level(has_flag("--verbose"), aoclog.as_deref())? else {
+        return Ok(());
+    };
+
+    // Ignore the error if a logger is already installed, e.g. by an earlier call
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(io::stderr)
+        .without_time()
+        .try_init();
+
+    Ok(())
+}
 
-pub fn read_input() -> io::Result<String> {
-    let mut args = env::args();
-    args.next();
+#[cfg(not(feature = "logging"))]
+pub fn init_logging() -> Result<(), Box<dyn Error>> {
+    if has_flag("--verbose") || env_value("AOCLOG").is_some() {
+        return Err("--verbose and AOCLOG require building with `--features logging`".into());
+    }
+    Ok(())
+}
 
-    let input_filename = args.next().expect("ARGS: <filename>");
-    fs::read_to_string(&input_filename)
+// How solver state is drawn, for days that support it; other days ignore both modes.
+//
+// Stderr (`--visualize` or `AOCVISUALIZE`) solves as usual while the solver draws snapshots to
+// stderr through `visualize` or visualizer::Visualizer. Those days are listed in their year's
+// `VISUALIZED_DAYS`.
+//
+// Terminal (`--viz`, with the `viz` feature) replaces solving with an animated terminal view of the
+// solver state (see viz.rs), for days whose main checks `terminal_viz`. It wins if both are passed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Visualize {
+    #[default]
+    Off,
+    Stderr,
+    Terminal,
 }
 
-const TIME_ITERATIONS: u128 = 100;
+// Controls what the runner writes in addition to the solutions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    // Print counters recorded through `stats`
+    pub print_stats: bool,
+    // Time each solver over repeated runs
+    pub time: bool,
+    // Text writes the median time after the answers; CSV writes only timing rows
+    pub time_format: TimeFormat,
+    // Write everything as a single JSON object instead of lines of text
+    pub json: bool,
+    // Write a flamegraph of each solver to the current directory
+    pub profile: bool,
+    // Seed randomized solvers with a fixed value, so that repeated runs produce identical output
+    pub deterministic: bool,
+    // Let days that support it draw their solver state (see Visualize)
+    pub visualize: Visualize,
+    // Abort if a part's solver runs longer than this
+    pub timeout: Option<Duration>,
+    // Run each solver this many extra times after solving, with no timing, to give an external
+    // profiler a steady workload
+    pub repeat: u32,
+    // Only repeat this part, for days that solve the parts separately
+    pub repeat_part: Option<u32>,
+    // Cap the threads used by days with parallel implementations (see parallel::set_thread_count)
+    pub threads: Option<NonZeroUsize>,
+    // Pin the solving thread to one core and raise its priority, for steadier timings (see pin.rs)
+    pub pin: bool,
+}
 
-fn should_time() -> bool {
-    env::var("AOCTIME").is_ok_and(|var| !var.is_empty())
+impl RunOptions {
+    // Reads options from the `--stats`, `--json`, `--profile`, `--deterministic`, `--visualize`,
+    // `--viz`, `--timeout`, `--repeat`, `--part`, `--time-format`, `--threads`, and `--pin` flags and
+    // the `AOCTIME`, `AOCTIME_FORMAT`, `AOCVISUALIZE`, and `AOC_THREADS` environment variables.
+    // Choosing a time format also turns on timing
+    pub fn from_env() -> Result<Self, String> {
+        let time_format = flag_value("--time-format")
+            .or_else(|| env::var("AOCTIME_FORMAT").ok().filter(|var| !var.is_empty()));
+        Ok(Self {
+            print_stats: has_flag("--stats"),
+            time: time_format.is_some() || env::var("AOCTIME").is_ok_and(|var| !var.is_empty()),
+            time_format: time_format.as_deref().map(str::parse).transpose()?.unwrap_or_default(),
+            json: has_flag("--json"),
+            profile: has_flag("--profile"),
+            deterministic: has_flag("--deterministic"),
+            visualize: if has_flag("--viz") {
+                Visualize::Terminal
+            } else if has_flag("--visualize")
+                || env::var("AOCVISUALIZE").is_ok_and(|var| !var.is_empty())
+            {
+                Visualize::Stderr
+            } else {
+                Visualize::Off
+            },
+            timeout: flag_value("--timeout").as_deref().map(timeout::parse_duration).transpose()?,
+            repeat: match flag_value("--repeat") {
+                Some(n) => n.parse().map_err(|_| format!("Invalid repeat count '{n}'"))?,
+                None => 0,
+            },
+            repeat_part: match flag_value("--part") {
+                Some(part) if matches!(part.as_str(), "1" | "2") => part.parse().ok(),
+                Some(part) => return Err(format!("Invalid part '{part}'; expected 1 or 2")),
+                None => None,
+            },
+            threads: parallel::threads_from_env()?,
+            pin: has_flag("--pin"),
+        })
+    }
 }
 
-fn time_micros<T>(f: impl Fn() -> T) -> u128 {
-    let mut elapsed_sum = 0;
-    for _ in 0..TIME_ITERATIONS {
-        let start = Instant::now();
-        hint::black_box(f());
-        elapsed_sum += (Instant::now() - start).as_micros();
+static DETERMINISTIC: AtomicBool = AtomicBool::new(false);
+
+// Set by the runner from RunOptions; solvers check `deterministic()` rather than the flag so that the
+// registry and tests can control it too
+pub fn set_deterministic(deterministic: bool) {
+    DETERMINISTIC.store(deterministic, Ordering::Relaxed);
+}
+
+pub fn deterministic() -> bool {
+    DETERMINISTIC.load(Ordering::Relaxed)
+}
+
+static VISUALIZE: AtomicBool = AtomicBool::new(false);
+
+// Set by the runner from RunOptions for the first solve of each part only, so that timing and
+// profiling runs don't draw anything
+pub fn set_visualize(visualize: bool) {
+    VISUALIZE.store(visualize, Ordering::Relaxed);
+}
+
+pub fn visualizing() -> bool {
+    VISUALIZE.load(Ordering::Relaxed)
+}
+
+// Whether a day's main should run its terminal view instead of solving (see Visualize::Terminal)
+pub fn terminal_viz() -> Result<bool, String> {
+    Ok(RunOptions::from_env()?.visualize == Visualize::Terminal)
+}
+
+// Visualization hook for solvers that draw their own text, e.g. in color; plain grid snapshots can
+// go through visualizer::Visualizer instead. Writes the rendered text to stderr if visualization is
+// on, and otherwise doesn't render at all. Days that call either should be listed in their year's
+// `VISUALIZED_DAYS`
+pub fn visualize(render: impl FnOnce() -> String) {
+    if visualizing() {
+        eprint!("{}", render());
+    }
+}
+
+const DETERMINISTIC_SEED: u64 = 2024;
+
+// RNG for randomized solvers: fixed-seed in deterministic mode, seeded from the OS otherwise
+pub fn rng() -> StdRng {
+    if deterministic() { StdRng::seed_from_u64(DETERMINISTIC_SEED) } else { StdRng::from_entropy() }
+}
+
+// Solves one part, exiting the process if it runs past `timeout`
+fn solve_within<T>(label: &str, timeout: Option<Duration>, solve: impl FnOnce() -> T) -> T {
+    let _watchdog = timeout.map(|timeout| timeout::Watchdog::start(label, timeout));
+    solve()
+}
+
+fn write_profile<T>(label: &str, f: impl Fn() -> T) -> Result<(), Box<dyn Error>> {
+    let path = profile::profile(label, f)?;
+    eprintln!("Wrote {label} flamegraph to {}", path.display());
+    Ok(())
+}
+
+fn write_stats(
+    out: &mut impl Write,
+    label: &str,
+    counters: &[(&'static str, u64)],
+) -> io::Result<()> {
+    if counters.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "{label} stats:")?;
+    for (name, value) in counters {
+        writeln!(out, "  {name}: {value}")?;
+    }
+    for (name, rate) in hit_rates(counters) {
+        writeln!(out, "  {name}_hit_rate: {:.1}%", 100.0 * rate)?;
+    }
+
+    Ok(())
+}
+
+// Hit rate for every pair of `<name>_hits` and `<name>_misses` counters with at least one lookup,
+// e.g. `memo` from Memo::record_stats
+fn hit_rates<'a>(counters: &'a [(&'static str, u64)]) -> impl Iterator<Item = (&'a str, f64)> {
+    counters.iter().filter_map(move |&(name, hits)| {
+        let prefix = name.strip_suffix("_hits")?;
+        let misses =
+            counters.iter().find(|&&(other, _)| other.strip_suffix("_misses") == Some(prefix))?.1;
+        (hits + misses > 0).then(|| (prefix, hits as f64 / (hits + misses) as f64))
+    })
+}
+
+fn stats_json(counters: Vec<(&'static str, u64)>) -> Json {
+    Json::object(counters.into_iter().map(|(name, value)| (name, Json::from(value))))
+}
+
+// `timings` are (part, timing). Rows are labeled with the day number taken from the binary name
+fn write_csv_timings(out: &mut impl Write, timings: &[(&str, Timing)]) -> io::Result<()> {
+    let binary = binary_name();
+    let day = binary.strip_prefix("day").unwrap_or(&binary);
+    for (part, timing) in timings {
+        writeln!(out, "{}", timing.csv_row(day, part))?;
+    }
+    Ok(())
+}
+
+// Writes a solution on its own line, or nothing for a part with no solution
+fn write_solution(out: &mut impl Write, solution: &Solution) -> io::Result<()> {
+    if solution.is_none() {
+        return Ok(());
+    }
+    writeln!(out, "{solution}")
+}
+
+// For a run where a part panicked: the parts that finished are still written, with the panic in
+// place of the other part's answer, and the runner fails after writing them
+fn write_panicked(
+    out: &mut impl Write,
+    json: bool,
+    parts: [Result<Result<Solution, ParseError>, Panicked>; 2],
+) -> Result<(), Box<dyn Error>> {
+    let mut solutions = Vec::new();
+    for part in parts {
+        solutions.push(match part {
+            Ok(solution) => Ok(solution?),
+            Err(panicked) => Err(panicked),
+        });
+    }
+
+    if json {
+        let mut fields = Vec::new();
+        for (solution, (name, panic_name)) in
+            solutions.iter().zip([("part1", "part1_panic"), ("part2", "part2_panic")])
+        {
+            match solution {
+                Ok(solution) => fields.push((name, solution.clone().into())),
+                Err(panicked) => {
+                    fields.push((name, Json::Null));
+                    fields.push((panic_name, Json::String(panicked.message.clone())));
+                }
+            }
+        }
+        writeln!(out, "{}", Json::object(fields))?;
+    } else {
+        for solution in &solutions {
+            match solution {
+                Ok(solution) => write_solution(out, solution)?,
+                Err(panicked) => writeln!(out, "{panicked}")?,
+            }
+        }
     }
-    elapsed_sum / TIME_ITERATIONS
+
+    let labels: Vec<_> = solutions
+        .iter()
+        .filter_map(|solution| solution.as_ref().err())
+        .map(|panicked| panicked.label.as_str())
+        .collect();
+    Err(format!("{} panicked", labels.join(" and ")).into())
 }
 
 pub fn run<T1, T2>(
@@ -39,42 +491,429 @@ pub fn run<T1, T2>(
     solve2: impl Fn(&str) -> T2,
 ) -> Result<(), Box<dyn Error>>
 where
-    T1: Display,
-    T2: Display,
+    T1: SolverOutput,
+    T2: SolverOutput,
 {
-    let input = read_input()?;
+    let input = open_input()?;
+    run_with_input(&prepare_input(input_str(&input)?), solve1, solve2)
+}
 
-    let solution1 = solve1(&input);
-    println!("{solution1}");
+// Same as `run`, but for input that did not come directly from the input file
+pub fn run_with_input<T1, T2>(
+    input: &str,
+    solve1: impl Fn(&str) -> T1,
+    solve2: impl Fn(&str) -> T2,
+) -> Result<(), Box<dyn Error>>
+where
+    T1: SolverOutput,
+    T2: SolverOutput,
+{
+    init_logging()?;
+    run_to(&mut io::stdout(), input, RunOptions::from_env()?, solve1, solve2)
+}
 
-    let solution2 = solve2(&input);
-    println!("{solution2}");
+// Runs both parts on `input`, writing solutions (and stats/timing if enabled) to `out`. Solvers may
+// return a ParseError, which is returned before anything is written. A part that panics doesn't
+// stop the other from running (see write_panicked)
+pub fn run_to<T1, T2>(
+    out: &mut impl Write,
+    input: &str,
+    options: RunOptions,
+    solve1: impl Fn(&str) -> T1,
+    solve2: impl Fn(&str) -> T2,
+) -> Result<(), Box<dyn Error>>
+where
+    T1: SolverOutput,
+    T2: SolverOutput,
+{
+    set_deterministic(options.deterministic);
+    set_visualize(options.visualize == Visualize::Stderr);
+    if options.threads.is_some() {
+        parallel::set_thread_count(options.threads);
+    }
+    if options.pin {
+        eprintln!("{}", pin::pin_current_thread()?);
+    }
+    stats::reset();
+    let (part1, cold1) = timing::time_once(|| {
+        panics::catch("Part 1", || solve_within("Part 1", options.timeout, || solve1(input)))
+    });
+    let stats1 = stats::take();
+    let (part2, cold2) = timing::time_once(|| {
+        panics::catch("Part 2", || solve_within("Part 2", options.timeout, || solve2(input)))
+    });
+    let stats2 = stats::take();
+    set_visualize(false);
+
+    // Timing or repeating a part that panicked would only panic again
+    let (solution1, solution2) = match (part1, part2) {
+        (Ok(part1), Ok(part2)) => (part1.into_solution()?, part2.into_solution()?),
+        (part1, part2) => {
+            let parts = [part1.map(T1::into_solution), part2.map(T2::into_solution)];
+            return write_panicked(out, options.json, parts);
+        }
+    };
+
+    // A part 2 solver that returns Solution::None is a day with no part 2, e.g. the last day of a
+    // year, so there is nothing to time, repeat, profile, or report for it
+    let has_part2 = !solution2.is_none();
+
+    let timings = options.time.then(|| {
+        let timing1 = timing::measure_with_cold(cold1, || solve1(input));
+        (timing1, has_part2.then(|| timing::measure_with_cold(cold2, || solve2(input))))
+    });
 
-    if should_time() {
-        let duration1 = time_micros(|| solve1(&input));
-        println!("Part 1 time: {duration1}μs");
+    if options.repeat_part != Some(2) {
+        timing::repeat(options.repeat, || solve1(input));
+    }
+    if options.repeat_part != Some(1) && has_part2 {
+        timing::repeat(options.repeat, || solve2(input));
+    }
+
+    if options.profile {
+        write_profile("part1", || solve1(input))?;
+        if has_part2 {
+            write_profile("part2", || solve2(input))?;
+        }
+    }
+
+    if options.json {
+        let mut fields = vec![("part1", solution1.into()), ("part2", solution2.into())];
+        if options.print_stats {
+            fields.push(("part1_stats", stats_json(stats1)));
+            if has_part2 {
+                fields.push(("part2_stats", stats_json(stats2)));
+            }
+        }
+        if let Some((timing1, timing2)) = timings {
+            fields.push(("part1_time_micros", Json::UInt(timing1.median_micros as u64)));
+            fields.push(("part1_cold_micros", Json::UInt(timing1.cold_micros as u64)));
+            if let Some(timing2) = timing2 {
+                fields.push(("part2_time_micros", Json::UInt(timing2.median_micros as u64)));
+                fields.push(("part2_cold_micros", Json::UInt(timing2.cold_micros as u64)));
+            }
+        }
+        writeln!(out, "{}", Json::object(fields))?;
+        return Ok(());
+    }
 
-        let duration2 = time_micros(|| solve2(&input));
-        println!("Part 2 time: {duration2}μs");
+    if let Some((timing1, timing2)) = timings.filter(|_| options.time_format == TimeFormat::Csv) {
+        let mut rows = vec![("1", timing1)];
+        rows.extend(timing2.map(|timing2| ("2", timing2)));
+        write_csv_timings(out, &rows)?;
+        return Ok(());
+    }
+
+    write_solution(out, &solution1)?;
+    write_solution(out, &solution2)?;
+
+    if options.print_stats {
+        write_stats(out, "Part 1", &stats1)?;
+        if has_part2 {
+            write_stats(out, "Part 2", &stats2)?;
+        }
+    }
+
+    if let Some((timing1, timing2)) = timings {
+        writeln!(out, "Part 1 time: {}", timing1.summary())?;
+        if let Some(timing2) = timing2 {
+            writeln!(out, "Part 2 time: {}", timing2.summary())?;
+        }
     }
 
     Ok(())
 }
 
-pub fn run_single_fn<T1, T2>(solve: impl Fn(&str) -> (T1, T2)) -> Result<(), Box<dyn Error>>
-where
-    T1: Display,
-    T2: Display,
-{
-    let input = read_input()?;
-    let (solution1, solution2) = solve(&input);
-    println!("{solution1}");
-    println!("{solution2}");
+// For days with no part 2
+pub fn run_part1_only<T: SolverOutput>(solve1: impl Fn(&str) -> T) -> Result<(), Box<dyn Error>> {
+    run(solve1, |_| Solution::None)
+}
+
+pub fn run_single_fn<T: CombinedOutput>(solve: impl Fn(&str) -> T) -> Result<(), Box<dyn Error>> {
+    init_logging()?;
+    let input = open_input()?;
+    let input = prepare_input(input_str(&input)?);
+    run_single_fn_to(&mut io::stdout(), &input, RunOptions::from_env()?, solve)
+}
+
+// Same as `run_to`, for days that solve both parts in one function
+pub fn run_single_fn_to<T: CombinedOutput>(
+    out: &mut impl Write,
+    input: &str,
+    options: RunOptions,
+    solve: impl Fn(&str) -> T,
+) -> Result<(), Box<dyn Error>> {
+    set_deterministic(options.deterministic);
+    set_visualize(options.visualize == Visualize::Stderr);
+    if options.threads.is_some() {
+        parallel::set_thread_count(options.threads);
+    }
+    if options.pin {
+        eprintln!("{}", pin::pin_current_thread()?);
+    }
+    stats::reset();
+    let (solution, cold) = timing::time_once(|| {
+        panics::catch("Solution", || solve_within("Solution", options.timeout, || solve(input)))
+    });
+    let (solution1, solution2) = solution?.into_solutions()?;
+    let stats = stats::take();
+    set_visualize(false);
+
+    let timing = options.time.then(|| timing::measure_with_cold(cold, || solve(input)));
+
+    timing::repeat(options.repeat, || solve(input));
+
+    if options.profile {
+        write_profile("solution", || solve(input))?;
+    }
+
+    if options.json {
+        let mut fields = vec![("part1", solution1.into()), ("part2", solution2.into())];
+        if options.print_stats {
+            fields.push(("stats", stats_json(stats)));
+        }
+        if let Some(timing) = timing {
+            fields.push(("time_micros", Json::UInt(timing.median_micros as u64)));
+            fields.push(("cold_micros", Json::UInt(timing.cold_micros as u64)));
+        }
+        writeln!(out, "{}", Json::object(fields))?;
+        return Ok(());
+    }
+
+    if let Some(timing) = timing.filter(|_| options.time_format == TimeFormat::Csv) {
+        write_csv_timings(out, &[("both", timing)])?;
+        return Ok(());
+    }
+
+    write_solution(out, &solution1)?;
+    write_solution(out, &solution2)?;
+
+    if options.print_stats {
+        write_stats(out, "Solution", &stats)?;
+    }
 
-    if should_time() {
-        let duration = time_micros(|| solve(&input));
-        println!("Solution time: {duration}μs");
+    if let Some(timing) = timing {
+        writeln!(out, "Solution time: {}", timing.summary())?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output_of(f: impl FnOnce(&mut Vec<u8>) -> Result<(), Box<dyn Error>>) -> String {
+        let mut out = Vec::new();
+        f(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "logging")]
+    fn log_levels() {
+        assert_eq!(Ok(None), log_level(false, None));
+        assert_eq!(Ok(Some(Level::DEBUG)), log_level(true, None));
+        assert_eq!(Ok(None), log_level(false, Some("")));
+        assert_eq!(Ok(Some(Level::TRACE)), log_level(true, Some("trace")));
+        assert_eq!(Ok(Some(Level::INFO)), log_level(false, Some("INFO")));
+        assert!(log_level(false, Some("loud")).is_err());
+    }
+
+    #[test]
+    fn input_paths() {
+        assert_eq!("in.txt", input_path(Some("in.txt".into()), None, "day16"));
+        assert_eq!("inputs/day16.txt", input_path(None, None, "day16"));
+        assert_eq!("inputs/day16.txt", input_path(Some("--json".into()), None, "day16"));
+
+        let var = Some("env.txt".to_string());
+        assert_eq!("in.txt", input_path(Some("in.txt".into()), var.clone(), "day16"));
+        assert_eq!("env.txt", input_path(Some("--json".into()), var, "day16"));
+    }
+
+    #[test]
+    fn input_texts() {
+        let text = Some("1 2\n".to_string());
+        assert_eq!(text, input_text(None, None, text.clone()));
+        assert_eq!(text, input_text(Some("--json".into()), None, text.clone()));
+        assert_eq!(None, input_text(Some("in.txt".into()), None, text.clone()));
+        assert_eq!(None, input_text(None, Some("env.txt".into()), text));
+        assert_eq!(None, input_text(None, None, None));
+    }
+
+    #[test]
+    fn run_writes_solutions() {
+        let output = output_of(|out| {
+            run_to(out, "abc", RunOptions::default(), str::len, |input| input.to_uppercase())
+        });
+        assert_eq!("3\nABC\n", output);
+
+        let output = output_of(|out| {
+            run_single_fn_to(out, "abc", RunOptions::default(), |input| (input.len(), 5))
+        });
+        assert_eq!("3\n5\n", output);
+    }
+
+    #[test]
+    fn run_writes_stats() {
+        let options = RunOptions { print_stats: true, ..RunOptions::default() };
+        let solve1 = |input: &str| {
+            stats::add("chars", input.len() as u64);
+            1
+        };
+        let output = output_of(|out| run_to(out, "abcd", options, solve1, |_| 2));
+        assert_eq!("1\n2\nPart 1 stats:\n  chars: 4\n", output);
+
+        let solve2 = |_: &str| {
+            stats::add("memo_hits", 3);
+            stats::add("memo_misses", 1);
+            stats::add("other_hits", 2);
+            2
+        };
+        let output = output_of(|out| run_to(out, "abcd", options, |_| 1, solve2));
+        assert_eq!(
+            "1\n2\nPart 2 stats:\n  memo_hits: 3\n  memo_misses: 1\n  other_hits: 2\n  \
+             memo_hit_rate: 75.0%\n",
+            output
+        );
+    }
+
+    #[test]
+    fn run_writes_timing() {
+        let options = RunOptions { time: true, ..RunOptions::default() };
+        let output = output_of(|out| run_single_fn_to(out, "", options, |_| (1, 2)));
+
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(3, lines.len());
+        assert!(lines[2].starts_with("Solution time: "), "{output}");
+        assert!(lines[2].ends_with("μs cold"), "{output}");
+    }
+
+    #[test]
+    fn run_writes_csv_timing() {
+        let options =
+            RunOptions { time: true, time_format: TimeFormat::Csv, ..RunOptions::default() };
+        let output = output_of(|out| run_to(out, "", options, |_| 1, |_| 2));
+
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(2, lines.len(), "{output}");
+        for (line, part) in lines.iter().zip(["1", "2"]) {
+            let fields: Vec<_> = line.split(',').collect();
+            assert_eq!(7, fields.len(), "{output}");
+            assert_eq!(part, fields[1], "{output}");
+            assert!(fields[2..].iter().all(|field| field.parse::<f64>().is_ok()), "{output}");
+        }
+    }
+
+    #[test]
+    fn run_writes_json() {
+        let options = RunOptions { json: true, ..RunOptions::default() };
+        let output =
+            output_of(|out| run_to(out, "abc", options, str::len, |input| input.to_uppercase()));
+        assert_eq!("{\"part1\":3,\"part2\":\"ABC\"}\n", output);
+
+        let options = RunOptions { print_stats: true, ..options };
+        let output =
+            output_of(|out| run_single_fn_to(out, "", options, |_| (-1_i64, Solution::None)));
+        assert_eq!("{\"part1\":-1,\"part2\":null,\"stats\":{}}\n", output);
+    }
+
+    #[test]
+    fn algo_selection() {
+        let variants = [("fast", 1), ("slow", 2)];
+        assert_eq!(Ok(1), pick_algo(None, &variants));
+        assert_eq!(Ok(2), pick_algo(Some("slow"), &variants));
+        assert_eq!(
+            Err("Unknown algorithm 'medium'; expected one of: fast, slow".into()),
+            pick_algo(Some("medium"), &variants)
+        );
+    }
+
+    #[test]
+    fn deterministic_rng() {
+        use rand::Rng;
+
+        let options = RunOptions { deterministic: true, ..RunOptions::default() };
+        output_of(|out| run_to(out, "", options, |_| 0, |_| 0));
+        assert!(deterministic());
+        assert_eq!(rng().gen::<u64>(), rng().gen::<u64>());
+
+        set_deterministic(false);
+        assert!(!deterministic());
+    }
+
+    #[test]
+    fn run_reports_parse_errors() {
+        let solve1 = |_: &str| Err::<u32, _>(ParseError::at(1, 0, "Unexpected map character: 'x'"));
+        let mut out = Vec::new();
+        let err = run_to(&mut out, "", RunOptions::default(), solve1, |_| 2).unwrap_err();
+        assert_eq!("line 2, column 1: Unexpected map character: 'x'", err.to_string());
+        assert!(out.is_empty());
+
+        let solve = |_: &str| Err::<(u32, u32), _>(ParseError::new("No start position in map"));
+        let err = run_single_fn_to(&mut out, "", RunOptions::default(), solve).unwrap_err();
+        assert_eq!("No start position in map", err.to_string());
+    }
+
+    #[test]
+    fn run_continues_after_panic() {
+        let solve1 = |_: &str| -> u32 { panic!("index out of bounds") };
+        let options = RunOptions { time: true, ..RunOptions::default() };
+        let mut out = Vec::new();
+        let err = run_to(&mut out, "", options, solve1, |_| 2).unwrap_err();
+        assert_eq!("Part 1 panicked", err.to_string());
+        assert_eq!("Part 1 panicked: index out of bounds\n2\n", String::from_utf8(out).unwrap());
+
+        let options = RunOptions { json: true, ..RunOptions::default() };
+        let mut out = Vec::new();
+        let err = run_to(&mut out, "", options, solve1, solve1).unwrap_err();
+        assert_eq!("Part 1 and Part 2 panicked", err.to_string());
+        let expected = r#"{"part1":null,"part1_panic":"index out of bounds","part2":null,"part2_panic":"index out of bounds"}"#;
+        assert_eq!(format!("{expected}\n"), String::from_utf8(out).unwrap());
+
+        let solve = |_: &str| -> (u32, u32) { panic!("no path") };
+        let err = run_single_fn_to(&mut Vec::new(), "", RunOptions::default(), solve).unwrap_err();
+        assert_eq!("Solution panicked: no path", err.to_string());
+    }
+
+    #[test]
+    fn run_within_timeout() {
+        let options =
+            RunOptions { timeout: Some(Duration::from_secs(60)), ..RunOptions::default() };
+        let output = output_of(|out| run_to(out, "abc", options, str::len, |_| 2));
+        assert_eq!("3\n2\n", output);
+    }
+
+    #[test]
+    fn run_repeats() {
+        use std::cell::Cell;
+
+        let (runs1, runs2) = (Cell::new(0), Cell::new(0));
+        let solve1 = |_: &str| runs1.replace(runs1.get() + 1);
+        let solve2 = |_: &str| runs2.replace(runs2.get() + 1);
+        let options = RunOptions { repeat: 3, ..RunOptions::default() };
+        assert_eq!("0\n0\n", output_of(|out| run_to(out, "", options, solve1, solve2)));
+        assert_eq!((4, 4), (runs1.get(), runs2.get()));
+
+        let options = RunOptions { repeat_part: Some(2), ..options };
+        output_of(|out| run_to(out, "", options, solve1, solve2));
+        assert_eq!((5, 8), (runs1.get(), runs2.get()));
+    }
+
+    #[test]
+    fn run_skips_missing_solution() {
+        let output =
+            output_of(|out| run_to(out, "", RunOptions::default(), |_| 25, |_| Solution::None));
+        assert_eq!("25\n", output);
+
+        let options = RunOptions { time: true, ..RunOptions::default() };
+        let output = output_of(|out| run_to(out, "", options, |_| 25, |_| Solution::None));
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(2, lines.len(), "{output}");
+        assert!(lines[1].starts_with("Part 1 time: "), "{output}");
+
+        let options = RunOptions { json: true, time: true, ..RunOptions::default() };
+        let output = output_of(|out| run_to(out, "", options, |_| 25, |_| Solution::None));
+        assert!(!output.contains("part2_time_micros"), "{output}");
+    }
+}
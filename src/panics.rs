@@ -0,0 +1,66 @@
+// Catching solver panics, so that a part that panics (usually on input it doesn't expect) is
+// reported by label and message instead of taking down the rest of the run. The default panic hook
+// still prints the location to stderr as usual
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Panicked {
+    // What was running, e.g. "Part 1"
+    pub label: String,
+    pub message: String,
+}
+
+impl Display for Panicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} panicked: {}", self.label, self.message)
+    }
+}
+
+impl fmt::Debug for Panicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for Panicked {}
+
+// Panics with a formatted message carry a String and `panic!("literal")` a &str; anything else
+// came from `panic_any`
+pub fn message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => (*message).into(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| "non-string panic payload".into()),
+    }
+}
+
+// Solvers only share the input and global flags, neither of which a panic can leave half-updated,
+// so treating them as unwind safe is fine
+pub fn catch<T>(label: &str, f: impl FnOnce() -> T) -> Result<T, Panicked> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|payload| Panicked { label: label.into(), message: message(payload.as_ref()) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages() {
+        assert_eq!(Ok(5), catch("Part 1", || 5));
+
+        let panicked = catch("Part 2", || panic!("bad input")).unwrap_err();
+        assert_eq!("Part 2 panicked: bad input", panicked.to_string());
+
+        let panicked = catch("Part 1", || -> u32 { panic!("line {}", 3) }).unwrap_err();
+        assert_eq!("line 3", panicked.message);
+
+        let panicked = catch("Part 1", || panic::panic_any(7)).unwrap_err();
+        assert_eq!("non-string panic payload", panicked.message);
+    }
+}
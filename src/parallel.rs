@@ -0,0 +1,141 @@
+// Glue for the `parallel` feature. Days hand independent work to these helpers, which split it into
+// one contiguous chunk per available thread on scoped threads and return the per-chunk results in
+// order. Without the feature, everything except `spawn_chunks` runs as a single chunk on the
+// calling thread, so the sequential path stays the default.
+//
+// Solver stats are thread-local, so counters gathered by a worker have to be returned and recorded
+// by the caller
+use std::iter::Sum;
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, panic, thread};
+
+pub const ENABLED: bool = cfg!(feature = "parallel");
+
+// 0 means every available thread
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// Caps how many chunks the helpers split work into, e.g. to benchmark on a fixed number of cores of
+// a shared machine. None restores the default of one per available thread
+pub fn set_thread_count(threads: Option<NonZeroUsize>) {
+    THREADS.store(threads.map_or(0, NonZeroUsize::get), Ordering::Relaxed);
+}
+
+pub fn thread_count() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    }
+}
+
+fn parse_threads(threads: &str) -> Result<NonZeroUsize, String> {
+    threads.parse().map_err(|_| format!("Invalid thread count '{threads}'"))
+}
+
+// Reads the `--threads` flag, falling back to the `AOC_THREADS` environment variable
+pub fn threads_from_env() -> Result<Option<NonZeroUsize>, String> {
+    crate::flag_value("--threads")
+        .or_else(|| env::var("AOC_THREADS").ok().filter(|var| !var.is_empty()))
+        .as_deref()
+        .map(parse_threads)
+        .transpose()
+}
+
+// Splits 0..len into at most `threads` contiguous ranges, with none if `len` is 0
+fn split(len: usize, threads: usize) -> impl Iterator<Item = Range<usize>> {
+    let chunk_size = len.div_ceil(threads).max(1);
+    (0..len).step_by(chunk_size).map(move |start| start..(start + chunk_size).min(len))
+}
+
+fn spawn_ranges<R: Send>(len: usize, f: impl Fn(Range<usize>) -> R + Sync) -> Vec<R> {
+    thread::scope(|scope| {
+        let f = &f;
+        let handles: Vec<_> =
+            split(len, thread_count()).map(|range| scope.spawn(move || f(range))).collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|payload| panic::resume_unwind(payload)))
+            .collect()
+    })
+}
+
+// Always uses threads, for explicitly parallel variants that are compared against the sequential
+// path regardless of the feature, e.g. by `--bench-scaling`
+pub fn spawn_chunks<T: Sync, R: Send>(items: &[T], f: impl Fn(&[T]) -> R + Sync) -> Vec<R> {
+    spawn_ranges(items.len(), |range| f(&items[range]))
+}
+
+// For work over several parallel slices, where `f` indexes them with each range itself
+pub fn map_ranges<R: Send>(len: usize, f: impl Fn(Range<usize>) -> R + Sync) -> Vec<R> {
+    if !ENABLED {
+        return if len == 0 { Vec::new() } else { vec![f(0..len)] };
+    }
+    spawn_ranges(len, f)
+}
+
+pub fn map_chunks<T: Sync, R: Send>(items: &[T], f: impl Fn(&[T]) -> R + Sync) -> Vec<R> {
+    map_ranges(items.len(), |range| f(&items[range]))
+}
+
+pub fn sum_by<T: Sync, R: Send + Sum>(items: &[T], f: impl Fn(&T) -> R + Sync) -> R {
+    map_chunks(items, |chunk| chunk.iter().map(&f).sum::<R>()).into_iter().sum()
+}
+
+pub fn count_by<T: Sync>(items: &[T], f: impl Fn(&T) -> bool + Sync) -> usize {
+    sum_by(items, |item| usize::from(f(item)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_cover_input() {
+        for len in [0, 1, 7, 1000] {
+            let ranges: Vec<_> = split(len, 4).collect();
+            assert!(ranges.len() <= 4);
+            assert_eq!(
+                (0..len).collect::<Vec<_>>(),
+                ranges.into_iter().flatten().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn uneven_ranges() {
+        assert_eq!(vec![0..4, 4..8, 8..10], split(10, 3).collect::<Vec<_>>());
+        assert_eq!(vec![0..1, 1..2], split(2, 8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn helpers() {
+        let items: Vec<u64> = (1..=1000).collect();
+        assert_eq!(500500, sum_by(&items, |&n| n));
+        assert_eq!(500, count_by(&items, |&n| n % 2 == 0));
+        assert_eq!(0, sum_by(&[] as &[u64], |&n| n));
+
+        let chunks = spawn_chunks(&items, |chunk| chunk.to_vec());
+        assert_eq!(items, chunks.concat());
+        assert_eq!(items, map_chunks(&items, <[u64]>::to_vec).concat());
+    }
+
+    #[test]
+    fn thread_count_override() {
+        assert_eq!(Ok(NonZeroUsize::new(3).unwrap()), parse_threads("3"));
+        assert!(parse_threads("0").is_err());
+        assert!(parse_threads("many").is_err());
+
+        set_thread_count(NonZeroUsize::new(3));
+        assert_eq!(3, thread_count());
+        assert_eq!(3, spawn_chunks(&[1, 2, 3, 4, 5, 6, 7], <[i32]>::len).len());
+        set_thread_count(None);
+        assert!(thread_count() >= 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "bad item")]
+    fn propagates_panics() {
+        spawn_chunks(&[1, 2, 3], |_| panic!("bad item"));
+    }
+}
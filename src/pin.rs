@@ -0,0 +1,44 @@
+// Core pinning for `--pin`, which keeps the solving thread on one core and raises its priority
+// where the OS allows it, so that AOCTIME numbers don't swing as the scheduler moves the process
+// between cores. Threads spawned after pinning (e.g. by the `parallel` helpers) inherit the same
+// core on Linux, so combine it with `--threads 1` there. Pinning needs the `pin` feature
+use std::error::Error;
+
+// Nice value to request. Going below 0 needs e.g. root or CAP_SYS_NICE; without it the call fails,
+// which only means the timing isn't as stable as it could be
+#[cfg(all(feature = "pin", unix))]
+const HIGH_PRIORITY: libc::c_int = -10;
+
+// Pins to the last core, since the OS tends to handle interrupts on the first ones. Returns a
+// description of what was done, for stderr
+#[cfg(feature = "pin")]
+pub fn pin_current_thread() -> Result<String, Box<dyn Error>> {
+    let cores = core_affinity::get_core_ids().unwrap_or_default();
+    let &core = cores.last().ok_or("Couldn't list CPU cores to pin to")?;
+    if !core_affinity::set_for_current(core) {
+        return Err(format!("Couldn't pin to core {}", core.id).into());
+    }
+
+    let priority = if raise_priority() {
+        "raised priority"
+    } else {
+        "priority unchanged (raising it needs elevated permissions)"
+    };
+    Ok(format!("Pinned to core {}, {priority}", core.id))
+}
+
+#[cfg(not(feature = "pin"))]
+pub fn pin_current_thread() -> Result<String, Box<dyn Error>> {
+    Err("--pin requires building with `--features pin`".into())
+}
+
+#[cfg(all(feature = "pin", unix))]
+fn raise_priority() -> bool {
+    // SAFETY: setpriority takes no pointers and only affects the calling process
+    unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, HIGH_PRIORITY) == 0 }
+}
+
+#[cfg(all(feature = "pin", not(unix)))]
+fn raise_priority() -> bool {
+    false
+}
@@ -0,0 +1,59 @@
+// Sampling profiler support for `--profile`, which writes a flamegraph SVG for each part. A part
+// is run repeatedly until it has run for at least PROFILE_DURATION so that fast parts still collect
+// a useful number of samples. Profiling needs the `profile` feature
+use std::error::Error;
+use std::hint;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[cfg_attr(not(feature = "profile"), allow(dead_code))]
+const PROFILE_DURATION: Duration = Duration::from_secs(1);
+#[cfg_attr(not(feature = "profile"), allow(dead_code))]
+const SAMPLE_FREQUENCY: i32 = 1000;
+
+// Flamegraphs are named after the binary and the part, e.g. `day16-part1.svg`
+pub fn flamegraph_path(label: &str) -> PathBuf {
+    PathBuf::from(format!("{}-{label}.svg", crate::binary_name()))
+}
+
+#[cfg(feature = "profile")]
+pub fn profile<T>(label: &str, f: impl Fn() -> T) -> Result<PathBuf, Box<dyn Error>> {
+    use std::fs::File;
+    use std::time::Instant;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(SAMPLE_FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+
+    let start = Instant::now();
+    loop {
+        hint::black_box(f());
+        if start.elapsed() >= PROFILE_DURATION {
+            break;
+        }
+    }
+
+    let path = flamegraph_path(label);
+    guard.report().build()?.flamegraph(File::create(&path)?)?;
+    Ok(path)
+}
+
+#[cfg(not(feature = "profile"))]
+pub fn profile<T>(_label: &str, f: impl Fn() -> T) -> Result<PathBuf, Box<dyn Error>> {
+    hint::black_box(f);
+    Err("--profile requires building with `--features profile`".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flamegraph_names() {
+        let path = flamegraph_path("part1");
+        let name = path.to_str().unwrap();
+        assert!(name.ends_with("-part1.svg"), "{name}");
+        assert!(!name.contains('/'), "{name}");
+    }
+}
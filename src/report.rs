@@ -0,0 +1,148 @@
+// Plain-text table rendering and human-readable formatting for summary reports
+use std::fmt::Write;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+// Table with a header row, an optional footer row (e.g. totals), and columns padded to the width
+// of their widest cell
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    aligns: Vec<Align>,
+    rows: Vec<Vec<String>>,
+    footer: Option<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&str, Align)]) -> Self {
+        Self {
+            headers: columns.iter().map(|&(header, _)| header.into()).collect(),
+            aligns: columns.iter().map(|&(_, align)| align).collect(),
+            rows: Vec::new(),
+            footer: None,
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        assert_eq!(self.headers.len(), cells.len(), "wrong number of cells in row");
+        self.rows.push(cells);
+    }
+
+    pub fn footer(&mut self, cells: Vec<String>) {
+        assert_eq!(self.headers.len(), cells.len(), "wrong number of cells in footer");
+        self.footer = Some(cells);
+    }
+
+    pub fn render(&self) -> String {
+        let all_rows =
+            || std::iter::once(&self.headers).chain(&self.rows).chain(self.footer.as_ref());
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|i| all_rows().map(|row| row[i].chars().count()).max().unwrap_or(0))
+            .collect();
+        let separator: String =
+            widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<_>>().join("-+-");
+
+        let mut out = String::new();
+        self.render_row(&mut out, &self.headers, &widths);
+        writeln!(out, "{separator}").unwrap();
+        for row in &self.rows {
+            self.render_row(&mut out, row, &widths);
+        }
+        if let Some(footer) = &self.footer {
+            writeln!(out, "{separator}").unwrap();
+            self.render_row(&mut out, footer, &widths);
+        }
+        out
+    }
+
+    fn render_row(&self, out: &mut String, row: &[String], widths: &[usize]) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .zip(&self.aligns)
+            .map(|((cell, &width), align)| match align {
+                Align::Left => format!("{cell:<width$}"),
+                Align::Right => format!("{cell:>width$}"),
+            })
+            .collect();
+        writeln!(out, "{}", cells.join(" | ").trim_end()).unwrap();
+    }
+}
+
+// Formats with 3 significant-ish digits in the largest sensible unit, e.g. `1.23ms`
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{nanos}ns")
+    } else if nanos < 1_000_000 {
+        format!("{:.1}μs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.3}s", nanos as f64 / 1e9)
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+// Returns `part` as a percentage of `total`, or 0 if `total` is 0
+pub fn percent(part: f64, total: f64) -> f64 {
+    if total == 0.0 { 0.0 } else { 100.0 * part / total }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_table() {
+        let mut table = Table::new(&[("Day", Align::Right), ("Title", Align::Left)]);
+        table.row(vec!["1".into(), "Historian Hysteria".into()]);
+        table.row(vec!["25".into(), "Code Chronicle".into()]);
+        table.footer(vec!["2".into(), String::new()]);
+
+        let expected = "\
+Day | Title
+----+-------------------
+  1 | Historian Hysteria
+ 25 | Code Chronicle
+----+-------------------
+  2 |
+";
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn formats_units() {
+        assert_eq!("999ns", format_duration(Duration::from_nanos(999)));
+        assert_eq!("12.3μs", format_duration(Duration::from_nanos(12_345)));
+        assert_eq!("4.50ms", format_duration(Duration::from_micros(4_500)));
+        assert_eq!("2.000s", format_duration(Duration::from_secs(2)));
+
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("3.0 MiB", format_bytes(3 << 20));
+
+        assert_eq!(25.0, percent(1.0, 4.0));
+        assert_eq!(0.0, percent(1.0, 0.0));
+    }
+}
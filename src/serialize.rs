@@ -0,0 +1,370 @@
+// Minimal JSON and CSV writers for exporting solver data to external tools, and a JSON reader for
+// loading previously exported data back in
+use std::fmt::{self, Display, Write};
+use std::path::Path;
+use winnow::ascii::{digit1, multispace0};
+use winnow::combinator::{alt, delimited, fail, opt, separated, separated_pair};
+use winnow::prelude::*;
+use winnow::token::{any, one_of, take, take_till};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    Json,
+    Csv,
+}
+
+impl DataFormat {
+    // Picks the format based on the file extension; anything other than .csv is JSON
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("csv") => Self::Csv,
+            _ => Self::Json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    // Separate from Int so that u64 values above i64::MAX are written exactly
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    // Fields are written in insertion order
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Self::Object(fields.into_iter().map(|(key, value)| (key.into(), value)).collect())
+    }
+
+    pub fn array<T: Into<Json>>(items: impl IntoIterator<Item = T>) -> Self {
+        Self::Array(items.into_iter().map(Into::into).collect())
+    }
+
+    // Integers parse as Int when they fit and UInt otherwise; anything with a fraction or exponent
+    // parses as Float
+    pub fn parse(input: &str) -> Result<Self, String> {
+        json_value.parse(input).map_err(|err| format!("Invalid JSON: {err}"))
+    }
+
+    // Value of the first field named `key`, if this is an object
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Self::Object(fields) => {
+                fields.iter().find(|(field, _)| field == key).map(|(_, value)| value)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Self::Int(value) => u64::try_from(value).ok(),
+            Self::UInt(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Self::Int(value) => Some(value as f64),
+            Self::UInt(value) => Some(value as f64),
+            Self::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+fn json_value(input: &mut &str) -> PResult<Json> {
+    delimited(
+        multispace0,
+        alt((
+            "null".value(Json::Null),
+            "true".value(Json::Bool(true)),
+            "false".value(Json::Bool(false)),
+            json_number,
+            json_string.map(Json::String),
+            json_array,
+            json_object,
+        )),
+        multispace0,
+    )
+    .parse_next(input)
+}
+
+fn json_number(input: &mut &str) -> PResult<Json> {
+    let fraction = opt(('.', digit1));
+    let exponent = opt((one_of(['e', 'E']), opt(one_of(['+', '-'])), digit1));
+    let number = (opt('-'), digit1, fraction, exponent).take().parse_next(input)?;
+
+    let parsed = if number.contains(['.', 'e', 'E']) {
+        number.parse().ok().map(Json::Float)
+    } else {
+        number.parse().map(Json::Int).or_else(|_| number.parse().map(Json::UInt)).ok()
+    };
+    match parsed {
+        Some(value) => Ok(value),
+        None => fail(input),
+    }
+}
+
+fn json_string(input: &mut &str) -> PResult<String> {
+    '"'.parse_next(input)?;
+    let mut out = String::new();
+    loop {
+        out.push_str(take_till(0.., ['"', '\\']).parse_next(input)?);
+        if any.parse_next(input)? == '"' {
+            return Ok(out);
+        }
+
+        let c = match any.parse_next(input)? {
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let hex = take(4_usize).parse_next(input)?;
+                match u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
+                    Some(c) => c,
+                    None => return fail(input),
+                }
+            }
+            c @ ('"' | '\\' | '/') => c,
+            _ => return fail(input),
+        };
+        out.push(c);
+    }
+}
+
+fn json_array(input: &mut &str) -> PResult<Json> {
+    delimited('[', separated(0.., json_value, ','), (multispace0, ']'))
+        .map(Json::Array)
+        .parse_next(input)
+}
+
+fn json_object(input: &mut &str) -> PResult<Json> {
+    let key = delimited(multispace0, json_string, multispace0);
+    delimited('{', separated(0.., separated_pair(key, ':', json_value), ','), (multispace0, '}'))
+        .map(Json::Object)
+        .parse_next(input)
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for Json {
+    fn from(value: u64) -> Self {
+        Self::UInt(value)
+    }
+}
+
+impl From<u32> for Json {
+    fn from(value: u32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Self::Int(value as i64)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+fn write_json_string(f: &mut impl Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+// Compact rendering with no whitespace between tokens
+impl Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::UInt(value) => write!(f, "{value}"),
+            // JSON has no representation for NaN or infinities
+            Self::Float(value) if !value.is_finite() => f.write_str("null"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::String(value) => write_json_string(f, value),
+            Self::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            Self::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(',')?;
+                    }
+                    write_json_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+// CSV with a fixed header row. Fields containing commas, quotes, or newlines are quoted
+#[derive(Debug, Clone)]
+pub struct Csv {
+    columns: usize,
+    out: String,
+}
+
+impl Csv {
+    pub fn new(headers: &[&str]) -> Self {
+        let mut csv = Self { columns: headers.len(), out: String::new() };
+        csv.row(headers);
+        csv
+    }
+
+    pub fn row<S: AsRef<str>>(&mut self, fields: &[S]) {
+        assert_eq!(self.columns, fields.len(), "wrong number of fields in CSV row");
+
+        for (i, field) in fields.iter().enumerate() {
+            if i != 0 {
+                self.out.push(',');
+            }
+
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                self.out.push('"');
+                self.out.push_str(&field.replace('"', "\"\""));
+                self.out.push('"');
+            } else {
+                self.out.push_str(field);
+            }
+        }
+        self.out.push('\n');
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json() {
+        let value = Json::object([
+            ("name", Json::from("a \"b\"\n")),
+            ("values", Json::array([1_i64, -2])),
+            ("missing", Json::from(None::<i64>)),
+            ("nested", Json::object([("ok", Json::Bool(true)), ("x", Json::Float(1.5))])),
+        ]);
+        assert_eq!(
+            r#"{"name":"a \"b\"\n","values":[1,-2],"missing":null,"nested":{"ok":true,"x":1.5}}"#,
+            value.to_string()
+        );
+        assert_eq!("null", Json::Float(f64::NAN).to_string());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let value = Json::object([
+            ("name", Json::from("a \"b\"\n\u{1}")),
+            ("values", Json::array([Json::Int(1), Json::Int(-2), Json::UInt(u64::MAX)])),
+            ("empty", Json::Array(vec![])),
+            ("nested", Json::object([("ok", Json::Bool(false)), ("x", Json::Float(1.5))])),
+            ("missing", Json::Null),
+        ]);
+        assert_eq!(Ok(value.clone()), Json::parse(&value.to_string()));
+
+        let parsed = Json::parse(" { \"a\" : [ 1 , 2.5e1 ] , \"b\": \"\\u00e9\\/\" } ").unwrap();
+        assert_eq!(Some(25.0), parsed.get("a").and_then(|a| a.as_array()?[1].as_f64()));
+        assert_eq!(Some(1), parsed.get("a").and_then(|a| a.as_array()?[0].as_u64()));
+        assert_eq!(Some("é/"), parsed.get("b").and_then(Json::as_str));
+        assert_eq!(None, parsed.get("c"));
+
+        assert!(Json::parse("[1,]").is_err());
+        assert!(Json::parse("{\"a\" 1}").is_err());
+        assert!(Json::parse("\"unterminated").is_err());
+        assert!(Json::parse("1 2").is_err());
+    }
+
+    #[test]
+    fn csv() {
+        let mut csv = Csv::new(&["a", "b"]);
+        csv.row(&["1", "x,y"]);
+        csv.row(&[String::from("say \"hi\""), String::new()]);
+        assert_eq!("a,b\n1,\"x,y\"\n\"say \"\"hi\"\"\",\n", csv.finish());
+
+        assert_eq!(DataFormat::Csv, DataFormat::from_path("out.csv"));
+        assert_eq!(DataFormat::Json, DataFormat::from_path("out.json"));
+    }
+}
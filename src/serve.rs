@@ -0,0 +1,109 @@
+// HTTP endpoints for running solvers from other programs, e.g. a leaderboard bot:
+//
+//   POST /solve/{day}/{part}         (body: raw puzzle input)
+//   POST /solve/{year}/{day}/{part}  (same, for a year other than 2024)
+//
+// Responses are JSON objects with `year`, `day`, `part`, `answer`, and `time_micros` on success, or with
+// `error` on failure. Answers are JSON numbers for numeric solutions and strings otherwise
+use crate::days;
+use crate::serialize::Json;
+use axum::Router;
+use axum::extract::Path;
+use axum::http::{StatusCode, header};
+use axum::response::IntoResponse;
+use axum::routing::post;
+use std::error::Error;
+use std::panic;
+use std::time::Instant;
+use tokio::net::TcpListener;
+use tokio::runtime;
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8024";
+
+fn error(status: StatusCode, message: String) -> (StatusCode, Json) {
+    (status, Json::object([("error", message.into())]))
+}
+
+// Runs one part of a day and builds the response. Solvers panic on malformed input, which is
+// reported as an unprocessable request rather than taking down the server
+fn solve(year: u32, day: u32, part: u32, input: &str) -> (StatusCode, Json) {
+    let Some(entry) = days::get(year, day) else {
+        return error(StatusCode::NOT_FOUND, format!("No solver for {year} day {day}"));
+    };
+    if !matches!(part, 1 | 2) {
+        return error(StatusCode::NOT_FOUND, format!("Invalid part {part}"));
+    }
+
+    let start = Instant::now();
+    let answer = panic::catch_unwind(|| entry.solver.solve_part(part, input));
+    let time_micros = start.elapsed().as_micros();
+
+    match answer {
+        Ok(Some(answer)) => (
+            StatusCode::OK,
+            Json::object([
+                ("year", year.into()),
+                ("day", day.into()),
+                ("part", part.into()),
+                ("answer", answer.into()),
+                ("time_micros", (time_micros as i64).into()),
+            ]),
+        ),
+        Ok(None) => error(StatusCode::NOT_FOUND, format!("Invalid part {part}")),
+        Err(_) => error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Day {day} solver failed; the input is probably malformed"),
+        ),
+    }
+}
+
+async fn solve_handler(Path((day, part)): Path<(u32, u32)>, input: String) -> impl IntoResponse {
+    solve_year_handler(Path((days::DEFAULT_YEAR, day, part)), input).await
+}
+
+async fn solve_year_handler(
+    Path((year, day, part)): Path<(u32, u32, u32)>,
+    input: String,
+) -> impl IntoResponse {
+    // Solvers are CPU-bound, so keep them off the async worker threads
+    let (status, body) = tokio::task::spawn_blocking(move || solve(year, day, part, &input))
+        .await
+        .unwrap_or_else(|err| error(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()));
+    (status, [(header::CONTENT_TYPE, "application/json")], body.to_string())
+}
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/solve/{day}/{part}", post(solve_handler))
+        .route("/solve/{year}/{day}/{part}", post(solve_year_handler))
+}
+
+// Serves until the process is killed
+pub fn serve(addr: &str) -> Result<(), Box<dyn Error>> {
+    let runtime = runtime::Builder::new_multi_thread().enable_io().build()?;
+    runtime.block_on(async {
+        let listener = TcpListener::bind(addr).await?;
+        eprintln!("Listening on http://{}", listener.local_addr()?);
+        axum::serve(listener, router()).await?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_responses() {
+        let input = include_str!("../sample/2024/day1.txt");
+        let (status, body) = solve(2024, 1, 2, input);
+        assert_eq!(StatusCode::OK, status);
+        let body = body.to_string();
+        assert!(body.starts_with(r#"{"year":2024,"day":1,"part":2,"answer":31,"time_micros":"#));
+
+        assert_eq!(StatusCode::NOT_FOUND, solve(2024, 26, 1, input).0);
+        assert_eq!(StatusCode::NOT_FOUND, solve(2015, 1, 1, input).0);
+        assert_eq!(StatusCode::NOT_FOUND, solve(2024, 1, 3, input).0);
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, solve(2024, 1, 1, "not a list").0);
+    }
+}
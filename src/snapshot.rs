@@ -0,0 +1,98 @@
+// Golden-file tests for rendered output such as maps and visualization frames, which are too big to
+// write out inline. Snapshots live in tests/snapshots/<name>.snap. A missing snapshot is written on
+// the first run (except under CI, where it fails instead), and `UPDATE_SNAPSHOTS=1 cargo test`
+// rewrites the ones that no longer match after an intentional change; check the diff before
+// committing them. ANSI escape characters are stored as ␛ so that colored frames stay readable
+use std::path::PathBuf;
+use std::{env, fs};
+
+// How many differing lines a failure shows
+const MAX_DIFF_LINES: usize = 10;
+
+fn path(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots", &format!("{name}.snap")].iter().collect()
+}
+
+fn env_flag(name: &str) -> bool {
+    env::var(name).is_ok_and(|var| !var.is_empty())
+}
+
+// Line-by-line differences as `line N:` followed by the expected and actual lines, or None if the
+// texts are equal
+fn diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let (expected_lines, actual_lines): (Vec<_>, Vec<_>) =
+        (expected.split('\n').collect(), actual.split('\n').collect());
+    let mut out = String::new();
+    let mut differing = 0;
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        let (expected, actual) = (expected_lines.get(i), actual_lines.get(i));
+        if expected == actual {
+            continue;
+        }
+
+        differing += 1;
+        if differing <= MAX_DIFF_LINES {
+            let show =
+                |line: Option<&&str>| line.map_or("<missing>".into(), |line| format!("{line:?}"));
+            out.push_str(&format!(
+                "line {}:\n  - {}\n  + {}\n",
+                i + 1,
+                show(expected),
+                show(actual)
+            ));
+        }
+    }
+    if differing > MAX_DIFF_LINES {
+        out.push_str(&format!("...and {} more differing lines\n", differing - MAX_DIFF_LINES));
+    }
+    Some(out)
+}
+
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = path(name);
+    let actual = actual.replace('\x1b', "␛");
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(_) if env_flag("CI") => panic!("Missing snapshot {}", path.display()),
+        Err(_) => {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            return;
+        }
+    };
+
+    if let Some(diff) = diff(&expected, &actual) {
+        if env_flag("UPDATE_SNAPSHOTS") {
+            fs::write(&path, &actual).unwrap();
+            return;
+        }
+        panic!(
+            "Snapshot {name} doesn't match (- snapshot, + actual); rerun with UPDATE_SNAPSHOTS=1 \
+             to accept the change:\n{diff}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs() {
+        assert_eq!(None, diff("a\nb\n", "a\nb\n"));
+        assert_eq!(Some("line 2:\n  - \"b\"\n  + \"c\"\n".into()), diff("a\nb\n", "a\nc\n"));
+        assert_eq!(
+            Some("line 2:\n  - \"\"\n  + \"b\"\nline 3:\n  - <missing>\n  + \"\"\n".into()),
+            diff("a\n", "a\nb\n")
+        );
+
+        let expected = "x\n".repeat(15);
+        let diff = diff(&expected, &"y\n".repeat(15)).unwrap();
+        assert!(diff.ends_with("...and 5 more differing lines\n"), "{diff}");
+    }
+}
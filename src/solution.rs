@@ -0,0 +1,179 @@
+// Answer for one part of a day. Most answers are integers; a few are strings such as
+// comma-separated names or coordinates, and day 25 has no part 2
+use crate::parse::ParseError;
+use crate::serialize::Json;
+use std::fmt::{self, Display};
+
+// Conversions from integers always produce U64 for non-negative values, so that answers compare
+// equal regardless of which integer type a day's solver happens to return
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum Solution {
+    U64(u64),
+    I64(i64),
+    String(String),
+    None,
+}
+
+impl Solution {
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
+// None renders as an empty string
+impl Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::U64(value) => write!(f, "{value}"),
+            Self::I64(value) => write!(f, "{value}"),
+            Self::String(value) => f.write_str(value),
+            Self::None => Ok(()),
+        }
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Solution {
+                fn from(value: $t) -> Self {
+                    Self::U64(value as u64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Solution {
+                fn from(value: $t) -> Self {
+                    if value >= 0 { Self::U64(value as u64) } else { Self::I64(value as i64) }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+impl_from_signed!(i8, i16, i32, i64, isize);
+
+impl From<String> for Solution {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for Solution {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl<T: Into<Solution>> From<Option<T>> for Solution {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::None, Into::into)
+    }
+}
+
+impl From<Solution> for Json {
+    fn from(value: Solution) -> Self {
+        match value {
+            Solution::U64(value) => Self::UInt(value),
+            Solution::I64(value) => Self::Int(value),
+            Solution::String(value) => Self::String(value),
+            Solution::None => Self::Null,
+        }
+    }
+}
+
+// Anything that a part's solver can return: either an answer, or an answer that is only available
+// if the input parses. The runner reports parse errors instead of panicking
+pub trait SolverOutput: Sized {
+    fn into_solution(self) -> Result<Solution, ParseError>;
+
+    // For the day registry, which has no way to report errors; the panic message is the error
+    fn unwrap_solution(self) -> Solution {
+        self.into_solution().unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<T: Into<Solution>> SolverOutput for T {
+    fn into_solution(self) -> Result<Solution, ParseError> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Solution>> SolverOutput for Result<T, ParseError> {
+    fn into_solution(self) -> Result<Solution, ParseError> {
+        self.map(Into::into)
+    }
+}
+
+// Same as SolverOutput, for days that solve both parts in one function
+pub trait CombinedOutput: Sized {
+    fn into_solutions(self) -> Result<(Solution, Solution), ParseError>;
+
+    fn unwrap_solutions(self) -> (Solution, Solution) {
+        self.into_solutions().unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<T1: Into<Solution>, T2: Into<Solution>> CombinedOutput for (T1, T2) {
+    fn into_solutions(self) -> Result<(Solution, Solution), ParseError> {
+        Ok((self.0.into(), self.1.into()))
+    }
+}
+
+impl<T1: Into<Solution>, T2: Into<Solution>> CombinedOutput for Result<(T1, T2), ParseError> {
+    fn into_solutions(self) -> Result<(Solution, Solution), ParseError> {
+        self.and_then(CombinedOutput::into_solutions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Solution::U64(5), 5_u32.into());
+        assert_eq!(Solution::I64(-5), (-5_i32).into());
+        assert_eq!(Solution::from(5_u8), 5_i64.into());
+        assert_eq!(Solution::String("a,b".into()), "a,b".into());
+        assert_eq!(Solution::None, None::<u64>.into());
+        assert_eq!(Solution::U64(7), Some(7_usize).into());
+
+        assert_eq!("18446744073709551615", Solution::from(u64::MAX).to_string());
+        assert_eq!("", Solution::None.to_string());
+        assert_eq!(
+            r#"[18446744073709551615,-1,"x",null]"#,
+            Json::array([
+                Solution::U64(u64::MAX),
+                Solution::I64(-1),
+                Solution::from("x"),
+                Solution::None
+            ])
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn solver_outputs() {
+        assert_eq!(Ok(Solution::U64(5)), 5_u32.into_solution());
+        assert_eq!(Ok(Solution::I64(-5)), Ok::<_, ParseError>(-5_i32).into_solution());
+        let err = ParseError::at(0, 2, "Unexpected map character: 'x'");
+        assert_eq!(Err(err.clone()), Err::<u32, _>(err.clone()).into_solution());
+
+        assert_eq!(Ok((Solution::U64(1), Solution::None)), (1_u8, Solution::None).into_solutions());
+        assert_eq!(Err(err.clone()), Err::<(u32, u32), _>(err).into_solutions());
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1, column 3: Unexpected map character: 'x'")]
+    fn unwrap_panics_with_error() {
+        Err::<u32, _>(ParseError::at(0, 2, "Unexpected map character: 'x'")).unwrap_solution();
+    }
+}
@@ -0,0 +1,61 @@
+// Lightweight named counters that solvers can record into and the runner prints with `--stats`.
+//
+// Counters are thread-local so that tests running in parallel don't see each other's counts. Hot
+// loops should accumulate into locals and flush once with `add`/`max` rather than calling `incr`
+// per iteration
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+thread_local! {
+    static COUNTERS: RefCell<BTreeMap<&'static str, u64>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+pub fn incr(name: &'static str) {
+    add(name, 1);
+}
+
+pub fn add(name: &'static str, n: u64) {
+    COUNTERS.with_borrow_mut(|counters| *counters.entry(name).or_default() += n);
+}
+
+// Records `value` if it is larger than the counter's current value
+pub fn max(name: &'static str, value: u64) {
+    COUNTERS.with_borrow_mut(|counters| {
+        let counter = counters.entry(name).or_default();
+        *counter = (*counter).max(value);
+    });
+}
+
+pub fn get(name: &'static str) -> u64 {
+    COUNTERS.with_borrow(|counters| counters.get(name).copied().unwrap_or_default())
+}
+
+pub fn reset() {
+    COUNTERS.with_borrow_mut(BTreeMap::clear);
+}
+
+// Returns all counters sorted by name and clears them
+pub fn take() -> Vec<(&'static str, u64)> {
+    COUNTERS.take().into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters() {
+        reset();
+
+        incr("b");
+        add("b", 4);
+        add("a", 2);
+        max("c", 3);
+        max("c", 1);
+
+        assert_eq!(5, get("b"));
+        assert_eq!(0, get("missing"));
+        assert_eq!(vec![("a", 2), ("b", 5), ("c", 3)], take());
+        assert_eq!(Vec::<(&str, u64)>::new(), take());
+    }
+}
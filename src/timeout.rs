@@ -0,0 +1,86 @@
+// Per-part timeouts for `--timeout`. There is no way to stop a running thread, so the process
+// reports the part that ran too long and exits with status 124 (as coreutils `timeout` does). The
+// whole-suite runner runs each day in a child process for this reason, so that a stuck solver is
+// killed before the next day starts
+use std::process;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+pub const EXIT_CODE: i32 = 124;
+
+// Accepts `500ms`, `30s`, `2m`, or a bare number of seconds
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid timeout '{s}'; expected e.g. 500ms, 30s, or 2m");
+    let (number, unit_secs) = if let Some(ms) = s.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        (secs, 1.0)
+    } else if let Some(mins) = s.strip_suffix('m') {
+        (mins, 60.0)
+    } else {
+        (s, 1.0)
+    };
+
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    Duration::try_from_secs_f64(number * unit_secs).map_err(|_| invalid())
+}
+
+// Runs `on_timeout` on a background thread unless dropped within `timeout`
+pub struct Watchdog {
+    _cancel: Sender<()>,
+}
+
+impl Watchdog {
+    // Exits the process if `label` is still running after `timeout`
+    pub fn start(label: &str, timeout: Duration) -> Self {
+        let label = label.to_owned();
+        Self::with_handler(timeout, move || {
+            eprintln!("{label} timed out after {}", crate::report::format_duration(timeout));
+            process::exit(EXIT_CODE)
+        })
+    }
+
+    fn with_handler(timeout: Duration, on_timeout: impl FnOnce() + Send + 'static) -> Self {
+        let (cancel, cancelled) = mpsc::channel();
+        thread::spawn(move || {
+            // Dropping the watchdog disconnects the channel
+            if cancelled.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                on_timeout();
+            }
+        });
+        Self { _cancel: cancel }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(Ok(Duration::from_millis(500)), parse_duration("500ms"));
+        assert_eq!(Ok(Duration::from_secs(30)), parse_duration("30s"));
+        assert_eq!(Ok(Duration::from_secs(90)), parse_duration("1.5m"));
+        assert_eq!(Ok(Duration::from_secs(5)), parse_duration("5"));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("10h").is_err());
+    }
+
+    #[test]
+    fn watchdog() {
+        let (sender, receiver) = mpsc::channel();
+        let fired = sender.clone();
+        let _watchdog = Watchdog::with_handler(Duration::from_millis(10), move || {
+            fired.send("late").unwrap();
+        });
+        assert_eq!(Ok("late"), receiver.recv_timeout(Duration::from_secs(5)));
+
+        let watchdog = Watchdog::with_handler(Duration::from_millis(200), move || {
+            sender.send("cancelled").unwrap();
+        });
+        drop(watchdog);
+        assert!(receiver.recv_timeout(Duration::from_millis(400)).is_err());
+    }
+}
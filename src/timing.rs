@@ -0,0 +1,218 @@
+// Solver timing for `AOCTIME`: runs a solver repeatedly and summarizes the run times. The runner
+// writes the median and median absolute deviation after the answers by default, along with the
+// first (cold) run, or with `AOCTIME_FORMAT=csv` (or `--time-format csv`) writes
+// `day,part,mean_us,median_us,p95_us,mad_us,cold_us` rows in place of the answers, for tracking
+// timings across commits
+use std::hint;
+use std::str::FromStr;
+use std::time::Instant;
+
+// Runs before measuring starts, so that caches and the branch predictor are warm. The runner has
+// already solved once by then, so a single extra run is enough
+const WARMUP_RUNS: usize = 1;
+// Measuring stops after MIN_RUNS once the median is known to within TARGET_RELATIVE_ERROR, or
+// after MAX_RUNS if it never settles, so that slow parts aren't run 100 times just to report a time
+const MIN_RUNS: usize = 5;
+const MAX_RUNS: usize = 100;
+const TARGET_RELATIVE_ERROR: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timing {
+    pub mean_micros: f64,
+    pub median_micros: f64,
+    pub p95_micros: f64,
+    // Median absolute deviation from the median, which unlike the standard deviation isn't thrown
+    // off by the occasional run that gets descheduled
+    pub mad_micros: f64,
+    pub runs: usize,
+    // The very first run, before caches and the branch predictor have warmed up. Kept out of the
+    // steady-state statistics above, since optimizations can help one and not the other
+    pub cold_micros: f64,
+}
+
+impl Timing {
+    // `samples` are steady-state run times in microseconds and must not be empty
+    pub fn from_samples(cold_micros: f64, mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let median_micros = percentile(&samples, 50.0);
+        Self {
+            mean_micros: samples.iter().sum::<f64>() / samples.len() as f64,
+            median_micros,
+            p95_micros: percentile(&samples, 95.0),
+            mad_micros: median_absolute_deviation(&samples, median_micros),
+            runs: samples.len(),
+            cold_micros,
+        }
+    }
+
+    // Estimated standard error of the median, as a fraction of it. 1.4826 * MAD estimates the
+    // standard deviation of normally distributed samples, and the median's standard error is about
+    // sqrt(pi / 2) times the mean's
+    fn relative_error(&self) -> f64 {
+        let std_dev = 1.4826 * self.mad_micros;
+        let std_error = (std::f64::consts::PI / 2.0).sqrt() * std_dev / (self.runs as f64).sqrt();
+        if self.median_micros == 0.0 { 0.0 } else { std_error / self.median_micros }
+    }
+
+    // E.g. `1234μs ± 12μs (8 runs), 2345μs cold`, for the text output
+    pub fn summary(&self) -> String {
+        format!(
+            "{}μs ± {}μs ({} runs), {}μs cold",
+            self.median_micros as u128,
+            self.mad_micros as u128,
+            self.runs,
+            self.cold_micros as u128
+        )
+    }
+
+    pub fn csv_row(&self, day: &str, part: &str) -> String {
+        format!(
+            "{day},{part},{:.1},{:.1},{:.1},{:.1},{:.1}",
+            self.mean_micros,
+            self.median_micros,
+            self.p95_micros,
+            self.mad_micros,
+            self.cold_micros
+        )
+    }
+}
+
+// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn median_absolute_deviation(samples: &[f64], median: f64) -> f64 {
+    let mut deviations: Vec<_> = samples.iter().map(|sample| (sample - median).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    percentile(&deviations, 50.0)
+}
+
+fn converged(samples: &[f64]) -> bool {
+    samples.len() >= MAX_RUNS
+        || (samples.len() >= MIN_RUNS
+            && Timing::from_samples(0.0, samples.to_vec()).relative_error()
+                <= TARGET_RELATIVE_ERROR)
+}
+
+// Runs `f` once, returning its result and how long it took in microseconds
+pub fn time_once<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = Instant::now();
+    let value = hint::black_box(f());
+    (value, start.elapsed().as_secs_f64() * 1_000_000.0)
+}
+
+// The first run is the cold one
+pub fn measure<T>(f: impl Fn() -> T) -> Timing {
+    let (_, cold_micros) = time_once(&f);
+    measure_with_cold(cold_micros, f)
+}
+
+// For when `f` has already run once, taking `cold_micros`, e.g. the runner's solve for the answers
+pub fn measure_with_cold<T>(cold_micros: f64, f: impl Fn() -> T) -> Timing {
+    repeat(WARMUP_RUNS as u32, &f);
+
+    let mut samples = Vec::new();
+    while !converged(&samples) {
+        samples.push(time_once(&f).1);
+    }
+    Timing::from_samples(cold_micros, samples)
+}
+
+// Runs `f` `n` times without measuring anything, for `--repeat`
+pub fn repeat<T>(n: u32, f: impl Fn() -> T) {
+    for _ in 0..n {
+        hint::black_box(f());
+    }
+}
+
+// Median run time in whole microseconds
+pub fn time_micros<T>(f: impl Fn() -> T) -> u128 {
+    measure(f).median_micros as u128
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    #[default]
+    Text,
+    Csv,
+}
+
+impl FromStr for TimeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Invalid time format '{s}'; expected text or csv")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summaries() {
+        let timing = Timing::from_samples(300.0, (1..=100).rev().map(f64::from).collect());
+        let expected = Timing {
+            mean_micros: 50.5,
+            median_micros: 50.0,
+            p95_micros: 95.0,
+            mad_micros: 25.0,
+            runs: 100,
+            cold_micros: 300.0,
+        };
+        assert_eq!(expected, timing);
+        assert_eq!("16,2,50.5,50.0,95.0,25.0,300.0", timing.csv_row("16", "2"));
+        assert_eq!("50μs ± 25μs (100 runs), 300μs cold", timing.summary());
+
+        let timing = Timing::from_samples(7.0, vec![7.0]);
+        assert_eq!((7.0, 7.0, 0.0), (timing.median_micros, timing.p95_micros, timing.mad_micros));
+
+        // A single descheduled run barely moves the median or MAD, unlike the mean
+        let timing = Timing::from_samples(10.0, vec![10.0, 11.0, 9.0, 10.0, 1000.0]);
+        assert_eq!(
+            (208.0, 10.0, 1.0),
+            (timing.mean_micros, timing.median_micros, timing.mad_micros)
+        );
+    }
+
+    #[test]
+    fn stops_when_converged() {
+        assert!(!converged(&[]));
+        assert!(!converged(&[100.0; MIN_RUNS - 1]));
+        assert!(converged(&[100.0; MIN_RUNS]));
+        assert!(converged(&[100.0, 101.0, 99.0, 100.0, 100.0, 170.0]));
+
+        // Noisy samples keep going until MAX_RUNS
+        let noisy: Vec<_> = (0..MAX_RUNS - 1).map(|i| [50.0, 100.0, 150.0][i % 3]).collect();
+        assert!(!converged(&noisy));
+        assert!(converged(&[noisy, vec![100.0]].concat()));
+    }
+
+    #[test]
+    fn measure_runs() {
+        use std::cell::Cell;
+
+        let runs = Cell::new(0);
+        let timing = measure(|| runs.set(runs.get() + 1));
+        assert!((MIN_RUNS..=MAX_RUNS).contains(&timing.runs), "{timing:?}");
+        assert_eq!(1 + WARMUP_RUNS + timing.runs, runs.get());
+
+        runs.set(0);
+        let timing = measure_with_cold(123.0, || runs.set(runs.get() + 1));
+        assert_eq!(123.0, timing.cold_micros);
+        assert_eq!(WARMUP_RUNS + timing.runs, runs.get());
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(Ok(TimeFormat::Csv), "CSV".parse());
+        assert_eq!(Ok(TimeFormat::Text), "text".parse());
+        assert!("tsv".parse::<TimeFormat>().is_err());
+    }
+}
@@ -0,0 +1,77 @@
+// Where solvers send state they want to show, so that they never write to stdout themselves and the
+// answers (or JSON/CSV output) stay clean. A solver that supports it takes a `&mut dyn Visualizer`
+// and its part function passes `current()`, which draws to stderr with `--visualize` and ignores
+// everything otherwise. Tests pass a Recorder to check what would have been shown
+use crate::Grid;
+
+pub trait Visualizer {
+    // A snapshot of the solver's state as a grid of characters
+    fn frame(&mut self, _grid: &Grid<char>) {}
+
+    // A line of text about what the solver found, e.g. which frame is the answer
+    fn message(&mut self, _message: &str) {}
+}
+
+pub struct NoOp;
+
+impl Visualizer for NoOp {}
+
+// Frames are each followed by a blank line, to separate one from the next
+pub struct Stderr;
+
+impl Visualizer for Stderr {
+    fn frame(&mut self, grid: &Grid<char>) {
+        eprint!("{}", render(grid));
+    }
+
+    fn message(&mut self, message: &str) {
+        eprintln!("{message}");
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Recorder {
+    // Rendered with `render`
+    pub frames: Vec<String>,
+    pub messages: Vec<String>,
+}
+
+impl Visualizer for Recorder {
+    fn frame(&mut self, grid: &Grid<char>) {
+        self.frames.push(render(grid));
+    }
+
+    fn message(&mut self, message: &str) {
+        self.messages.push(message.into());
+    }
+}
+
+// One line per row, then a blank line
+pub fn render(grid: &Grid<char>) -> String {
+    let mut text = String::with_capacity((grid.rows() + 1) * (grid.cols() + 1));
+    for row in &grid.0 {
+        text.extend(row);
+        text.push('\n');
+    }
+    text.push('\n');
+    text
+}
+
+// Stderr if visualization is on for this solve (see crate::visualizing), otherwise NoOp
+pub fn current() -> Box<dyn Visualizer> {
+    if crate::visualizing() { Box::new(Stderr) } else { Box::new(NoOp) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorder() {
+        let mut recorder = Recorder::default();
+        recorder.frame(&Grid(vec![vec!['#', '.'], vec!['.', '#']]));
+        recorder.message("done");
+        assert_eq!(vec!["#.\n.#\n\n"], recorder.frames);
+        assert_eq!(vec!["done"], recorder.messages);
+    }
+}
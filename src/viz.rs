@@ -0,0 +1,264 @@
+// Terminal visualization loop that days can plug their state into. A day provides a step function
+// that advances its state, returning false once there is nothing left to show, and a render
+// function that draws the current state as lines of text.
+//
+// Controls: space pauses/resumes, n or right arrow steps once while paused, + and - change speed,
+// and q or Esc quits. The real loop needs the `viz` feature; without it `run` returns an error
+#![cfg_attr(not(feature = "viz"), allow(dead_code))]
+
+use std::error::Error;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub lines: Vec<String>,
+    // Shown in the status bar next to the playback state
+    pub status: String,
+    // (x, y) position to keep in view when the frame is larger than the terminal
+    pub focus: Option<(usize, usize)>,
+}
+
+// Playback speeds in steps per second
+const SPEEDS: [u32; 10] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000];
+const DEFAULT_SPEED: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Controls {
+    paused: bool,
+    finished: bool,
+    speed: usize,
+    // Fractional steps carried over between ticks, so that slow speeds still advance
+    step_credit: f64,
+    manual_steps: u32,
+}
+
+impl Controls {
+    fn new() -> Self {
+        Self {
+            paused: false,
+            finished: false,
+            speed: DEFAULT_SPEED,
+            step_credit: 0.0,
+            manual_steps: 0,
+        }
+    }
+
+    fn steps_per_second(&self) -> u32 {
+        SPEEDS[self.speed]
+    }
+
+    // Returns false if the visualization should exit
+    fn handle(&mut self, action: Action) -> bool {
+        match action {
+            Action::TogglePause => {
+                self.paused = !self.paused;
+                self.step_credit = 0.0;
+            }
+            Action::Step => {
+                self.paused = true;
+                self.manual_steps += 1;
+            }
+            Action::Faster => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+            Action::Slower => self.speed = self.speed.saturating_sub(1),
+            Action::Quit => return false,
+        }
+        true
+    }
+
+    // Number of steps to take now that `elapsed` has passed since the previous call
+    fn steps_due(&mut self, elapsed: Duration) -> u32 {
+        if self.finished {
+            return 0;
+        }
+
+        if self.paused {
+            return std::mem::take(&mut self.manual_steps);
+        }
+
+        self.step_credit += elapsed.as_secs_f64() * f64::from(self.steps_per_second());
+        let steps = self.step_credit.floor();
+        self.step_credit -= steps;
+        steps as u32
+    }
+
+    fn state_name(&self) -> &'static str {
+        if self.finished {
+            "finished"
+        } else if self.paused {
+            "paused"
+        } else {
+            "playing"
+        }
+    }
+}
+
+// First row or column to draw so that `focus` stays roughly centered, without scrolling past the
+// end of the content
+fn scroll_offset(focus: usize, content_len: usize, view_len: usize) -> usize {
+    if content_len <= view_len {
+        return 0;
+    }
+    focus.saturating_sub(view_len / 2).min(content_len - view_len)
+}
+
+#[cfg(feature = "viz")]
+pub fn run<S>(
+    title: &str,
+    mut state: S,
+    mut step: impl FnMut(&mut S) -> bool,
+    render: impl Fn(&S) -> Frame,
+) -> Result<(), Box<dyn Error>> {
+    let mut terminal = ratatui::init();
+    let result = terminal::event_loop(&mut terminal, title, &mut state, &mut step, &render);
+    ratatui::restore();
+    Ok(result?)
+}
+
+#[cfg(not(feature = "viz"))]
+pub fn run<S>(
+    _title: &str,
+    _state: S,
+    _step: impl FnMut(&mut S) -> bool,
+    _render: impl Fn(&S) -> Frame,
+) -> Result<(), Box<dyn Error>> {
+    Err("Visualization requires building with `--features viz`".into())
+}
+
+#[cfg(feature = "viz")]
+mod terminal {
+    use super::{Action, Controls, Frame, scroll_offset};
+    use ratatui::DefaultTerminal;
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+    use ratatui::layout::{Constraint, Layout};
+    use ratatui::widgets::{Block, Paragraph};
+    use std::io;
+    use std::time::{Duration, Instant};
+
+    const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+
+    fn action(code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Char(' ') => Some(Action::TogglePause),
+            KeyCode::Char('n') | KeyCode::Right => Some(Action::Step),
+            KeyCode::Char('+' | '=') => Some(Action::Faster),
+            KeyCode::Char('-') => Some(Action::Slower),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+            _ => None,
+        }
+    }
+
+    pub(super) fn event_loop<S>(
+        terminal: &mut DefaultTerminal,
+        title: &str,
+        state: &mut S,
+        step: &mut impl FnMut(&mut S) -> bool,
+        render: &impl Fn(&S) -> Frame,
+    ) -> io::Result<()> {
+        let mut controls = Controls::new();
+        let mut steps: u64 = 0;
+        let mut last_tick = Instant::now();
+        loop {
+            let frame = render(state);
+            terminal.draw(|f| draw(f, title, &frame, &controls, steps))?;
+
+            if event::poll(FRAME_INTERVAL)? {
+                if let Event::Key(key) = event::read()? {
+                    let action = action(key.code).filter(|_| key.kind == KeyEventKind::Press);
+                    if action.is_some_and(|action| !controls.handle(action)) {
+                        return Ok(());
+                    }
+                }
+            }
+
+            let now = Instant::now();
+            for _ in 0..controls.steps_due(now - last_tick) {
+                if !step(state) {
+                    controls.finished = true;
+                    break;
+                }
+                steps += 1;
+            }
+            last_tick = now;
+        }
+    }
+
+    fn draw(
+        f: &mut ratatui::Frame<'_>,
+        title: &str,
+        frame: &Frame,
+        controls: &Controls,
+        steps: u64,
+    ) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(f.area());
+
+        let block = Block::bordered().title(format!(" {title} "));
+        let inner = block.inner(main);
+        let (x, y) = frame.focus.unwrap_or_default();
+        let width = frame.lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let scroll = (
+            scroll_offset(y, frame.lines.len(), inner.height.into()) as u16,
+            scroll_offset(x, width, inner.width.into()) as u16,
+        );
+        let text = frame.lines.join("\n");
+        f.render_widget(Paragraph::new(text).block(block).scroll(scroll), main);
+
+        let status_line = format!(
+            "{} | step {steps} | {}/s | {} | space: pause  n: step  +/-: speed  q: quit",
+            frame.status,
+            controls.steps_per_second(),
+            controls.state_name()
+        );
+        f.render_widget(Paragraph::new(status_line), status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn controls() {
+        let mut controls = Controls::new();
+        assert_eq!(10, controls.steps_per_second());
+        assert_eq!(0, controls.steps_due(Duration::from_millis(50)));
+        assert_eq!(1, controls.steps_due(Duration::from_millis(60)));
+        assert_eq!(20, controls.steps_due(Duration::from_secs(2)));
+
+        controls.handle(Action::Step);
+        controls.handle(Action::Step);
+        assert!(controls.paused);
+        assert_eq!(2, controls.steps_due(Duration::from_secs(10)));
+        assert_eq!(0, controls.steps_due(Duration::from_secs(10)));
+
+        controls.handle(Action::TogglePause);
+        controls.handle(Action::Faster);
+        assert_eq!(20, controls.steps_per_second());
+        for _ in 0..20 {
+            controls.handle(Action::Slower);
+        }
+        assert_eq!(1, controls.steps_per_second());
+
+        controls.finished = true;
+        assert_eq!(0, controls.steps_due(Duration::from_secs(10)));
+        assert_eq!("finished", controls.state_name());
+        assert!(!controls.handle(Action::Quit));
+    }
+
+    #[test]
+    fn scrolling() {
+        assert_eq!(0, scroll_offset(50, 20, 30));
+        assert_eq!(0, scroll_offset(5, 100, 30));
+        assert_eq!(35, scroll_offset(50, 100, 30));
+        assert_eq!(70, scroll_offset(99, 100, 30));
+    }
+}
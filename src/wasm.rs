@@ -0,0 +1,31 @@
+// WebAssembly entry points, for running solvers in a browser or in Node-based tooling. The library
+// is a plain rlib by default, so build the cdylib explicitly and run wasm-bindgen on it, e.g.
+// `cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+// followed by `wasm-bindgen --target web target/wasm32-unknown-unknown/release/advent_of_code_2024.wasm --out-dir pkg`
+use crate::days;
+use wasm_bindgen::prelude::*;
+
+// Returns the solution for one part (1 or 2) of a day, or an error for an unknown day or part.
+// Solvers panic on malformed input
+#[wasm_bindgen]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, JsError> {
+    let entry = days::get(days::DEFAULT_YEAR, day)
+        .ok_or_else(|| JsError::new(&format!("No solver for day {day}")))?;
+    entry
+        .solver
+        .solve_part(part, input)
+        .map(|answer| answer.to_string())
+        .ok_or_else(|| JsError::new(&format!("Invalid part {part}")))
+}
+
+// Puzzle title for a day, or undefined if there is no solver for it
+#[wasm_bindgen]
+pub fn title(day: u32) -> Option<String> {
+    days::get(days::DEFAULT_YEAR, day).map(|entry| entry.title.into())
+}
+
+// Days that have solvers, in order
+#[wasm_bindgen]
+pub fn days() -> Vec<u32> {
+    days::year(days::DEFAULT_YEAR).unwrap_or_default().iter().map(|entry| entry.day).collect()
+}
@@ -0,0 +1,105 @@
+//! Day 1: Historian Hysteria
+//!
+//! <https://adventofcode.com/2024/day/1>
+//!
+//! With the `simd` feature, part 1 sums the absolute differences with std::simd.
+
+use crate::days::Solver;
+use crate::{Counter, parallel, parse};
+use std::error::Error;
+
+// Works on bytes since this is most of the time spent on large generated inputs
+fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
+    parse::byte_lines(input.as_bytes())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut fields = parse::byte_fields(line);
+            let l: i32 = parse::signed_from_bytes(fields.next().unwrap()).unwrap();
+            let r: i32 = parse::signed_from_bytes(fields.next().unwrap()).unwrap();
+            (l, r)
+        })
+        .unzip()
+}
+
+fn solve_part_1(input: &str) -> i32 {
+    let (mut left, mut right) = parse_input(input);
+
+    left.sort();
+    right.sort();
+
+    parallel::map_ranges(left.len(), |range| abs_diff_sum(&left[range.clone()], &right[range]))
+        .into_iter()
+        .sum()
+}
+
+#[cfg(not(feature = "simd"))]
+use abs_diff_sum_scalar as abs_diff_sum;
+#[cfg(feature = "simd")]
+use simd::abs_diff_sum;
+
+fn abs_diff_sum_scalar(left: &[i32], right: &[i32]) -> i32 {
+    left.iter().zip(right).map(|(a, b)| (a - b).abs()).sum()
+}
+
+#[cfg(feature = "simd")]
+mod simd {
+    use std::simd::prelude::*;
+
+    const LANES: usize = 16;
+
+    // `left` and `right` must be the same length
+    pub(super) fn abs_diff_sum(left: &[i32], right: &[i32]) -> i32 {
+        debug_assert_eq!(left.len(), right.len());
+        let (left_chunks, left_rest) = left.as_chunks::<LANES>();
+        let (right_chunks, right_rest) = right.as_chunks::<LANES>();
+
+        let mut sums = Simd::<i32, LANES>::splat(0);
+        for (l, r) in left_chunks.iter().zip(right_chunks) {
+            sums += (Simd::from_array(*l) - Simd::from_array(*r)).abs();
+        }
+        sums.reduce_sum() + super::abs_diff_sum_scalar(left_rest, right_rest)
+    }
+}
+
+fn solve_part_2(input: &str) -> i32 {
+    let (left, right) = parse_input(input);
+
+    let right_counts: Counter<i32> = right.into_iter().collect();
+
+    parallel::sum_by(&left, |&n| n * right_counts.get(&n) as i32)
+}
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve_part_1(input).into(), |input| solve_part_2(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day1.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(11, solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(31, solve_part_2(SAMPLE_INPUT));
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_matches_scalar() {
+        let left: Vec<i32> = (0..1000).map(|i| (i * 7919) % 100_000).collect();
+        let right: Vec<i32> = (0..1000).map(|i| (i * 104_729) % 100_000).collect();
+        for len in [0, 5, 16, 999, 1000] {
+            let (left, right) = (&left[..len], &right[..len]);
+            assert_eq!(abs_diff_sum_scalar(left, right), simd::abs_diff_sum(left, right));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Day 2: Red-Nosed Reports
+//!
+//! <https://adventofcode.com/2024/day/2>
+
+use crate::days::Solver;
+use crate::parallel;
+use std::error::Error;
+
+fn parse_input(input: &str) -> Vec<Vec<i32>> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split(' ').map(|level| level.parse::<i32>().unwrap()).collect())
+        .collect()
+}
+
+fn solve_part_1(input: &str) -> usize {
+    parallel::count_by(&parse_input(input), |levels| levels_valid(levels))
+}
+
+fn levels_valid(levels: &[i32]) -> bool {
+    if levels.len() <= 1 {
+        return true;
+    }
+
+    let sign = (levels[1] - levels[0]).signum();
+    levels.windows(2).all(|window| {
+        let diff = window[1] - window[0];
+        diff.signum() == sign && (1..=3).contains(&diff.abs())
+    })
+}
+
+fn solve_part_2(input: &str) -> usize {
+    parallel::count_by(&parse_input(input), |levels| {
+        levels_valid(levels)
+            || (0..levels.len()).any(|i| {
+                let mut levels_with_skip = levels.clone();
+                levels_with_skip.remove(i);
+                levels_valid(&levels_with_skip)
+            })
+    })
+}
+
+// Checks the increasing and decreasing cases separately instead of by sign, and tries removing
+// every level from every report
+#[cfg(feature = "reference")]
+fn solve_reference(input: &str, dampener: bool) -> usize {
+    let safe = |levels: &[i32]| {
+        let steps: Vec<_> = levels.windows(2).map(|window| window[1] - window[0]).collect();
+        steps.iter().all(|step| (1..=3).contains(step))
+            || steps.iter().all(|step| (-3..=-1).contains(step))
+    };
+
+    parse_input(input)
+        .iter()
+        .filter(|levels| {
+            safe(levels)
+                || (dampener
+                    && (0..levels.len()).any(|i| {
+                        let (before, after) = levels.split_at(i);
+                        safe(&[before, &after[1..]].concat())
+                    }))
+        })
+        .count()
+}
+
+#[cfg(feature = "reference")]
+pub const DIFFERENTIAL: crate::differential::Differential = crate::differential::Differential {
+    optimized: SOLVER,
+    reference: Solver::Parts(
+        |input| solve_reference(input, false).into(),
+        |input| solve_reference(input, true).into(),
+    ),
+    generate: crate::generate::reports,
+    size: 100,
+};
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve_part_1(input).into(), |input| solve_part_2(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day2.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(2, solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(4, solve_part_2(SAMPLE_INPUT));
+    }
+}
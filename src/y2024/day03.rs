@@ -0,0 +1,192 @@
+//! Day 3: Mull It Over
+//!
+//! <https://adventofcode.com/2024/day/3>
+//!
+//! `--stats` reports how many of each instruction the input contains, how many `mul(` near-misses
+//! didn't parse, and (for part 2) the longest disabled stretch in bytes. `--verbose` logs the
+//! location of each near-miss, which helps when the answer looks wrong
+
+use crate::aho_corasick::Match;
+use crate::days::Solver;
+use crate::parse::unsigned;
+use crate::{AhoCorasick, stats};
+use std::error::Error;
+use std::ops::Range;
+use winnow::combinator::{separated_pair, terminated};
+use winnow::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Mul(i32, i32),
+    Do,
+    Dont,
+    // `mul(` that isn't followed by two numbers and a closing parenthesis
+    MalformedMul,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Spanned {
+    instruction: Instruction,
+    // Byte range in the input
+    span: Range<usize>,
+}
+
+fn parse_mul_suffix(input: &mut &str) -> PResult<(i32, i32)> {
+    terminated(separated_pair(unsigned, ',', unsigned), ')').parse_next(input)
+}
+
+// Scans for recognized instructions, skipping everything else. A malformed `mul(` only spans the
+// `mul(` itself, so that an instruction right after it is still found
+fn instructions(input: &str) -> impl Iterator<Item = Spanned> + '_ {
+    let matcher = AhoCorasick::new(["mul(", "do()", "don't()"]);
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let Match { pattern, start, end } = matcher.find(input.as_bytes(), offset)?;
+        offset = end;
+        let instruction = match pattern {
+            0 => {
+                let mut args = &input[end..];
+                match parse_mul_suffix(&mut args) {
+                    Ok((l, r)) => {
+                        offset = input.len() - args.len();
+                        Instruction::Mul(l, r)
+                    }
+                    Err(_) => Instruction::MalformedMul,
+                }
+            }
+            1 => Instruction::Do,
+            _ => Instruction::Dont,
+        };
+
+        Some(Spanned { instruction, span: start..offset })
+    })
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct InstructionStats {
+    muls: u64,
+    dos: u64,
+    donts: u64,
+    malformed_muls: u64,
+    // Longest stretch of input in bytes from a `don't()` to the next `do()` (or the end of the
+    // input) that wasn't already inside a disabled stretch
+    longest_disabled_span: u64,
+}
+
+impl InstructionStats {
+    fn record(self) {
+        stats::add("mul", self.muls);
+        stats::add("do", self.dos);
+        stats::add("dont", self.donts);
+        stats::add("malformed_mul", self.malformed_muls);
+        stats::max("longest_disabled_span", self.longest_disabled_span);
+    }
+}
+
+fn solve<const PART2: bool>(input: &str) -> i32 {
+    let mut enabled = true;
+    let mut disabled_start = 0;
+    let mut counts = InstructionStats::default();
+    let mut sum = 0;
+
+    for Spanned { instruction, span } in instructions(input) {
+        match instruction {
+            Instruction::Mul(l, r) => {
+                counts.muls += 1;
+                if enabled {
+                    sum += l * r;
+                }
+            }
+            Instruction::Do => {
+                counts.dos += 1;
+                if PART2 && !enabled {
+                    enabled = true;
+                    let len = (span.start - disabled_start) as u64;
+                    counts.longest_disabled_span = counts.longest_disabled_span.max(len);
+                }
+            }
+            Instruction::Dont => {
+                counts.donts += 1;
+                if PART2 && enabled {
+                    enabled = false;
+                    disabled_start = span.start;
+                }
+            }
+            Instruction::MalformedMul => {
+                counts.malformed_muls += 1;
+                let context = &input[span.start..input.len().min(span.start + 16)];
+                debug!(offset = span.start, context, "Malformed mul");
+            }
+        }
+    }
+
+    if !enabled {
+        let len = (input.len() - disabled_start) as u64;
+        counts.longest_disabled_span = counts.longest_disabled_span.max(len);
+    }
+    counts.record();
+
+    sum
+}
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve::<false>(input).into(), |input| solve::<true>(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(solve::<false>, solve::<true>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day3.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample/2024/day3-2.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(161, solve::<false>(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(48, solve::<true>(SAMPLE_INPUT_2));
+    }
+
+    #[test]
+    fn instruction_stream() {
+        let input = "mul(2,3)xmul(4,5]don't()mul(1,1)do()";
+        let spanned: Vec<_> = instructions(input).collect();
+        assert_eq!(
+            vec![
+                Spanned { instruction: Instruction::Mul(2, 3), span: 0..8 },
+                Spanned { instruction: Instruction::MalformedMul, span: 9..13 },
+                Spanned { instruction: Instruction::Dont, span: 17..24 },
+                Spanned { instruction: Instruction::Mul(1, 1), span: 24..32 },
+                Spanned { instruction: Instruction::Do, span: 32..36 },
+            ],
+            spanned
+        );
+
+        assert_eq!(
+            vec![Instruction::MalformedMul, Instruction::Mul(3, 4)],
+            instructions("mul(mul(3,4)").map(|spanned| spanned.instruction).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn instruction_stats() {
+        stats::reset();
+        assert_eq!(48, solve::<true>(SAMPLE_INPUT_2));
+        assert_eq!(
+            vec![
+                ("do", 1),
+                ("dont", 1),
+                ("longest_disabled_span", 39),
+                ("malformed_mul", 1),
+                ("mul", 4),
+            ],
+            stats::take()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Day 4: Ceres Search
+//!
+//! <https://adventofcode.com/2024/day/4>
+//!
+//! With the `simd` feature, part 1 compares whole rows at a time with std::simd.
+
+use crate::days::Solver;
+use crate::{AhoCorasick, ByteGrid, ParseError, Pos2, SolverOutput};
+use std::error::Error;
+
+fn solve_part_1(input: &str) -> Result<u32, ParseError> {
+    let grid = ByteGrid::parse(input)?;
+    Ok(count_xmas(&grid))
+}
+
+#[cfg(not(feature = "simd"))]
+use count_xmas_scalar as count_xmas;
+#[cfg(feature = "simd")]
+use simd::count_xmas;
+
+// Every row, column, and diagonal as (start, delta), from the left column, top row, or right
+// column. Searching each line for the word and its reverse covers all eight directions
+fn lines(size: Pos2<i32>) -> impl Iterator<Item = (Pos2<i32>, Pos2<i32>)> {
+    let Pos2 { x: cols, y: rows } = size;
+    let left = (0..rows).map(|y| Pos2 { x: 0, y });
+    let top = (0..cols).map(|x| Pos2 { x, y: 0 });
+    let right = (0..rows).map(move |y| Pos2 { x: cols - 1, y });
+
+    let across = left.clone().map(|start| (start, Pos2 { x: 1, y: 0 }));
+    let down = top.clone().map(|start| (start, Pos2 { x: 0, y: 1 }));
+    let down_right = left.chain(top.clone().skip(1)).map(|start| (start, Pos2 { x: 1, y: 1 }));
+    let down_left =
+        right.chain(top.take(cols.max(1) as usize - 1)).map(|start| (start, Pos2 { x: -1, y: 1 }));
+    across.chain(down).chain(down_right).chain(down_left)
+}
+
+#[cfg_attr(feature = "simd", allow(dead_code))]
+fn count_xmas_scalar(grid: &ByteGrid<'_>) -> u32 {
+    let matcher = AhoCorasick::new(["XMAS", "SAMX"]);
+    let size = Pos2 { x: grid.cols() as i32, y: grid.rows() as i32 };
+    lines(size)
+        .map(|(start, delta)| {
+            let line =
+                start.ray_within(delta, size).map(|pos| grid[pos.y as usize][pos.x as usize]);
+            matcher.find_iter(line).count() as u32
+        })
+        .sum()
+}
+
+// For each direction and starting row, lines up the four rows that the word passes through, each
+// shifted by its column offset, so that lane i of every slice belongs to the word starting at the
+// same point
+#[cfg(feature = "simd")]
+mod simd {
+    use crate::ByteGrid;
+    use std::ops::Range;
+    use std::simd::prelude::*;
+
+    const LANES: usize = 32;
+
+    // Starting coordinates for which the whole word stays within 0..len
+    fn starts(len: usize, delta: isize) -> Range<usize> {
+        match delta {
+            -1 => 3.min(len)..len,
+            0 => 0..len,
+            _ => 0..len.saturating_sub(3),
+        }
+    }
+
+    fn offset(start: usize, delta: isize, i: usize) -> usize {
+        start.wrapping_add_signed(delta * i as isize)
+    }
+
+    // Counts the indices at which slice i holds letter i of XMAS; the slices must be the same length
+    fn count_matches(slices: [&[u8]; 4]) -> u32 {
+        let chunks = slices.map(|slice| slice.as_chunks::<LANES>());
+        let mut count = 0;
+        for chunk in 0..chunks[0].0.len() {
+            let matched = (0..4)
+                .map(|i| Simd::from_array(chunks[i].0[chunk]).simd_eq(Simd::splat(b"XMAS"[i])))
+                .fold(Mask::splat(true), |matched, letter| matched & letter);
+            count += matched.to_bitmask().count_ones();
+        }
+
+        let rest = chunks.map(|(_, rest)| rest);
+        count
+            + (0..rest[0].len()).filter(|&j| (0..4).all(|i| rest[i][j] == b"XMAS"[i])).count()
+                as u32
+    }
+
+    pub(super) fn count_xmas(grid: &ByteGrid<'_>) -> u32 {
+        let mut count = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dy == 0 && dx == 0 {
+                    continue;
+                }
+
+                let xs = starts(grid.cols(), dx);
+                if xs.is_empty() {
+                    continue;
+                }
+
+                for y in starts(grid.rows(), dy) {
+                    let slices = [0, 1, 2, 3].map(|i| {
+                        let row = grid.row(offset(y, dy, i));
+                        let x = offset(xs.start, dx, i);
+                        &row[x..x + xs.len()]
+                    });
+                    count += count_matches(slices);
+                }
+            }
+        }
+        count
+    }
+}
+
+fn solve_part_2(input: &str) -> Result<usize, ParseError> {
+    let grid = ByteGrid::parse(input)?;
+
+    Ok((0..grid.rows())
+        .map(|y| (0..grid.cols()).filter(|&x| check_mas_x_centered_at_point(&grid, y, x)).count())
+        .sum())
+}
+
+fn check_mas_x_centered_at_point(grid: &ByteGrid<'_>, y: usize, x: usize) -> bool {
+    if grid[y][x] != b'A' || y == 0 || x == 0 || y == grid.rows() - 1 || x == grid.cols() - 1 {
+        // Can't be centered at a boundary row or column
+        return false;
+    }
+
+    let top_left = grid[y - 1][x - 1];
+    if !b"MS".contains(&top_left) {
+        return false;
+    }
+
+    let other = if top_left == b'M' { b'S' } else { b'M' };
+
+    if grid[y - 1][x + 1] == top_left {
+        // Top right matches top left; bottom left and bottom right must both be other
+        grid[y + 1][x - 1] == other && grid[y + 1][x + 1] == other
+    } else if grid[y + 1][x - 1] == top_left {
+        // Bottom left matches top left; top right and bottom right must both be other
+        grid[y - 1][x + 1] == other && grid[y + 1][x + 1] == other
+    } else {
+        // Not a match
+        false
+    }
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input).unwrap_solution(),
+    |input| solve_part_2(input).unwrap_solution(),
+);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day4.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(18), solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(9), solve_part_2(SAMPLE_INPUT));
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_matches_scalar() {
+        // Pseudo-random letters, with rows long enough to need a scalar tail after the SIMD chunks
+        let mut state = 4_u32;
+        let input: String = (0..50)
+            .map(|_| {
+                (0..77)
+                    .map(|_| {
+                        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                        b"XMAS"[(state >> 16) as usize % 4] as char
+                    })
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect();
+
+        for input in [SAMPLE_INPUT, &input, "XMAS\n", "X\nM\nA\nS\n", "SAMX\n"] {
+            let grid = ByteGrid::parse(input).unwrap();
+            assert_eq!(count_xmas_scalar(&grid), simd::count_xmas(&grid), "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve_part_1("XMAS\nXMA\n").unwrap_err();
+        assert_eq!("line 2, column 4: Expected 4 columns, found 3", err.to_string());
+    }
+}
@@ -0,0 +1,204 @@
+//! Day 5: Print Queue
+//!
+//! <https://adventofcode.com/2024/day/5>
+//!
+//! -------
+//! Part 1
+//! -------
+//! 1. Build up a hash map from page number P to pages that must come after P
+//! 2. For each update, iterate through the pages in order, and record which pages have been seen;
+//!    for each page P, if any pages that must come after P have already been seen, the update is
+//!    not valid
+//!
+//! -------
+//! Part 2
+//! -------
+//! Setup is identical to part 1, but the hash map is interpreted as a directed graph where each
+//! node represents a page and an edge from A to B means that A must come before B.
+//!
+//! Each update is sorted individually using DFS-based topological sort. If the sort changed the
+//! update's ordering, the middle page in the sorted update is added to the running sum.
+//!
+//! Attempting to topological sort the entire rules graph does not work because the graph contains
+//! cycles. However, when filtering to the list of pages within a single update, there are
+//! guaranteed not to be any cycles (otherwise the problem would not be solvable).
+//!
+//! Updates are parsed and checked one at a time rather than collected, so memory use beyond the
+//! input itself doesn't grow with the number of updates.
+
+use crate::SolverOutput;
+use crate::days::Solver;
+use crate::parse::{self, ParseError, comma_separated, line_items, lines, section, unsigned};
+use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
+use std::collections::HashSet;
+use std::error::Error;
+use winnow::combinator::{rest, separated_pair};
+use winnow::prelude::*;
+
+// (before, after): page `before` must come before page `after` in an update
+type Rule = (u32, u32);
+
+fn parse_rule(input: &mut &str) -> PResult<Rule> {
+    separated_pair(unsigned, '|', unsigned).parse_next(input)
+}
+
+// Returns the rules and the rest of the input, which is the updates section
+fn parse_input(input: &str) -> Result<(Vec<Rule>, &str), ParseError> {
+    parse::complete((section(lines(parse_rule)), rest), input)
+}
+
+// Lazy iterator over the updates
+fn parse_updates(updates: &str) -> impl Iterator<Item = Result<Vec<u32>, ParseError>> + '_ {
+    line_items(updates, comma_separated(unsigned))
+}
+
+fn solve_part_1(input: &str) -> Result<u32, ParseError> {
+    let (rules, updates) = parse_input(input)?;
+    let updates = parse_updates(updates);
+
+    let rules_graph = make_rules_graph(&rules);
+
+    let mut seen: FxHashSet<u32> = FxHashSet::default();
+    let mut sum = 0;
+    for update in updates {
+        let update = update?;
+        if is_ordered(&update, &rules_graph, &mut seen) {
+            sum += update[update.len() / 2];
+        }
+    }
+
+    Ok(sum)
+}
+
+fn is_ordered(
+    update: &[u32],
+    rules_graph: &FxHashMap<u32, Vec<u32>>,
+    seen: &mut FxHashSet<u32>,
+) -> bool {
+    seen.clear();
+    for &page in update {
+        if let Some(edges) = rules_graph.get(&page) {
+            for &edge in edges {
+                if seen.contains(&edge) {
+                    return false;
+                }
+            }
+        }
+
+        seen.insert(page);
+    }
+
+    true
+}
+
+fn make_rules_graph(rules: &[Rule]) -> FxHashMap<u32, Vec<u32>> {
+    let mut graph: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+    for &(before, after) in rules {
+        graph.entry(before).or_default().push(after);
+    }
+
+    graph
+}
+
+fn solve_part_2(input: &str) -> Result<u32, ParseError> {
+    let (rules, updates) = parse_input(input)?;
+    let updates = parse_updates(updates);
+
+    let rules_graph = make_rules_graph(&rules);
+
+    let mut sum = 0;
+    for update in updates {
+        let update = update?;
+        let sorted = topological_sort(&rules_graph, &update);
+        if sorted != update {
+            sum += sorted[sorted.len() / 2];
+        }
+    }
+
+    Ok(sum)
+}
+
+fn topological_sort(graph: &FxHashMap<u32, Vec<u32>>, update: &[u32]) -> Vec<u32> {
+    let update_set: FxHashSet<_> = update.iter().copied().collect();
+
+    let mut visited = HashSet::with_capacity_and_hasher(update.len(), FxBuildHasher);
+    let mut sorted = Vec::with_capacity(update.len());
+    for &page in update {
+        if !visited.contains(&page) {
+            topological_sort_visit(graph, page, &update_set, &mut visited, &mut sorted);
+        }
+    }
+    sorted.reverse();
+
+    sorted
+}
+
+fn topological_sort_visit(
+    graph: &FxHashMap<u32, Vec<u32>>,
+    page: u32,
+    update: &FxHashSet<u32>,
+    visited: &mut FxHashSet<u32>,
+    sorted: &mut Vec<u32>,
+) {
+    visited.insert(page);
+
+    if let Some(edges) = graph.get(&page) {
+        for &edge in edges {
+            if !visited.contains(&edge) && update.contains(&edge) {
+                topological_sort_visit(graph, edge, update, visited, sorted);
+            }
+        }
+    }
+
+    sorted.push(page);
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input).unwrap_solution(),
+    |input| solve_part_2(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> =
+    |input| parse_updates(parse_input(input)?.1).try_for_each(|update| update.map(drop));
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day5.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(143), solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(123), solve_part_2(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn streamed_updates() {
+        let (rules, updates) = parse_input(SAMPLE_INPUT).unwrap();
+        assert_eq!(21, rules.len());
+        let updates: Vec<_> = parse_updates(updates).collect::<Result<_, _>>().unwrap();
+        assert_eq!(6, updates.len());
+        assert_eq!(vec![97, 13, 75, 29, 47], updates[5]);
+
+        // A malformed update only fails once the iterator reaches it
+        let input = SAMPLE_INPUT.replace("61,13,29", "61,,29");
+        let mut updates = parse_updates(parse_input(&input).unwrap().1);
+        assert_eq!(Some(Ok(vec![75, 47, 61, 53, 29])), updates.next());
+        let err = updates.nth(3).unwrap().unwrap_err();
+        assert_eq!("line 5, column 3: Unexpected character: ','", err.to_string());
+        assert_eq!(err, solve_part_2(&input).unwrap_err());
+
+        let err = solve_part_1("47|53\n97-13\n\n75,47\n").unwrap_err();
+        assert_eq!("line 2, column 1: Unexpected line: '97-13'", err.to_string());
+    }
+}
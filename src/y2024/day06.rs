@@ -0,0 +1,837 @@
+//! Day 6: Guard Gallivant
+//!
+//! <https://adventofcode.com/2024/day/6>
+//!
+//! -------
+//! Part 1
+//! -------
+//! Straightforward 2D grid walking
+//!
+//! -------
+//! Part 2
+//! -------
+//! Walk the grid as in part 1, but at each step, check if it's possible to place an obstacle at
+//! the next position that the guard would step onto. This is possible if the following are true:
+//!   - There is not already an obstacle in that position
+//!   - No obstacle has been placed yet
+//!   - The guard has not already stepped on the potential obstacle position
+//!
+//! If an obstacle can be placed then snapshot the current visited state, place the obstacle, and
+//! recursively check if the guard will enter a loop when starting from the current state. When the
+//! recursive call returns then restore visited state, remove the obstacle, and continue on normally.
+//!
+//! Loops are detected based on (row, column, direction) triples. If the guard ever steps on a
+//! position twice while facing the same direction, there is a loop.
+//!
+//! -------
+//! Maximum coverage
+//! -------
+//! Passing `--max-coverage` instead finds the single obstacle placement that maximizes the number
+//! of distinct positions the guard visits before either leaving the map or entering a loop. Only
+//! positions on the original patrol route need to be simulated, since an obstacle anywhere else is
+//! never reached and leaves the coverage unchanged from part 1.
+//!
+//! -------
+//! Visualization
+//! -------
+//! Passing `--viz` (with the `viz` feature) animates the part 1 patrol in the terminal, with
+//! controls. `--visualize` plays the same animation in color on stderr while solving.
+//!
+//! -------
+//! Bitmap import
+//! -------
+//! Passing `--bitmap` treats the input file as a black-and-white PBM image (or PNG when built with
+//! the `png` feature) and converts it to a map with dark pixels as obstacles. The guard starts at
+//! `--guard x,y` if specified, otherwise at the leftmost empty pixel in the bottom-most row that has
+//! one. `--write-map <path>` additionally writes the converted map in puzzle input format.
+
+use crate::ansi::{self, Color, Glyph};
+use crate::days::Solver;
+use crate::image::Rgb;
+use crate::viz::{self, Frame};
+use crate::{Direction, DirectionMap, ParseError, Pos2, Rect, SolverOutput, parallel, parse};
+use std::error::Error;
+use std::fs;
+
+type Position = Pos2<i32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Empty,
+    Obstacle,
+}
+
+#[derive(Debug)]
+struct Input {
+    map: Vec<Vec<Space>>,
+    guard_start: Position,
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let lines = input.lines().enumerate().filter(|(_, line)| !line.is_empty());
+    let (map, [guard_start]) =
+        parse::maze(lines, [('^', "No guard position in input")], Space::Empty, |c| match c {
+            '.' => Some(Space::Empty),
+            '#' => Some(Space::Obstacle),
+            _ => None,
+        })?;
+    Ok(Input { map: map.0, guard_start })
+}
+
+fn solve_part_1(input: &str) -> Result<usize, ParseError> {
+    let Input { map, guard_start } = parse_input(input)?;
+    if crate::visualizing() {
+        ansi::animate(Patrol::new(map.clone(), guard_start), Patrol::step, Patrol::render_ansi, 5);
+    }
+
+    Ok(traverse_map(&map, guard_start))
+}
+
+fn traverse_map(map: &[Vec<Space>], start: Position) -> usize {
+    let mut visited = vec![vec![false; map[0].len()]; map.len()];
+
+    let mut current_pos = start;
+    let mut direction = Direction::Up;
+    loop {
+        visited[current_pos.y as usize][current_pos.x as usize] = true;
+
+        let next_pos = current_pos + direction.delta();
+        if !Rect::from_size(map[0].len(), map.len()).contains(next_pos) {
+            break;
+        }
+
+        if map[next_pos.y as usize][next_pos.x as usize] == Space::Obstacle {
+            direction = direction.rotate_right();
+        } else {
+            current_pos = next_pos;
+        }
+    }
+
+    visited.into_iter().map(|row| row.into_iter().filter(|&b| b).count()).sum()
+}
+
+fn solve_part_2(input: &str) -> Result<u32, ParseError> {
+    let Input { mut map, guard_start } = parse_input(input)?;
+    if parallel::ENABLED {
+        return Ok(count_loop_obstacles(&map, guard_start));
+    }
+
+    let mut visited = vec![vec![DirectionMap::splat(false); map[0].len()]; map.len()];
+    Ok(traverse_part_2(
+        &mut map,
+        &mut visited,
+        guard_start,
+        Direction::Up,
+        false,
+        &mut VisitsBuffer::new(),
+    ))
+}
+
+struct VisitsBuffer {
+    visits: Vec<(Position, Direction)>,
+    indices: Vec<usize>,
+}
+
+impl VisitsBuffer {
+    fn new() -> Self {
+        Self { visits: Vec::new(), indices: Vec::new() }
+    }
+
+    fn checkpoint(&mut self) {
+        self.indices.push(self.visits.len());
+    }
+
+    fn unwind(&mut self, visited: &mut [Vec<DirectionMap<bool>>]) {
+        let i = self.indices.pop().unwrap();
+        for &(pos, direction) in &self.visits[i..] {
+            visited[pos.y as usize][pos.x as usize][direction] = false;
+        }
+        self.visits.truncate(i);
+    }
+
+    fn push(&mut self, pos: Position, direction: Direction) {
+        self.visits.push((pos, direction));
+    }
+}
+
+fn traverse_part_2(
+    map: &mut [Vec<Space>],
+    visited: &mut Vec<Vec<DirectionMap<bool>>>,
+    mut current_pos: Position,
+    mut direction: Direction,
+    obstacle_placed: bool,
+    visits: &mut VisitsBuffer,
+) -> u32 {
+    visits.checkpoint();
+
+    let mut loops = 0;
+    loop {
+        if visited[current_pos.y as usize][current_pos.x as usize][direction] {
+            loops += 1;
+            break;
+        }
+        visited[current_pos.y as usize][current_pos.x as usize][direction] = true;
+        visits.push(current_pos, direction);
+
+        let next_pos = current_pos + direction.delta();
+        if !Rect::from_size(map[0].len(), map.len()).contains(next_pos) {
+            // Went out of bounds
+            break;
+        }
+
+        if map[next_pos.y as usize][next_pos.x as usize] == Space::Obstacle {
+            // Ran into an obstacle; rotate
+            direction = direction.rotate_right();
+        } else {
+            if !obstacle_placed && !visited[next_pos.y as usize][next_pos.x as usize].any() {
+                // No obstacle has been inserted yet, and the space ahead is:
+                //   * Empty
+                //   * Has not been visited yet
+                // Insert the obstacle, recurse, then remove the obstacle
+                map[next_pos.y as usize][next_pos.x as usize] = Space::Obstacle;
+                loops += traverse_part_2(
+                    map,
+                    visited,
+                    current_pos,
+                    direction.rotate_right(),
+                    true,
+                    visits,
+                );
+                map[next_pos.y as usize][next_pos.x as usize] = Space::Empty;
+            }
+            current_pos = next_pos;
+        }
+    }
+
+    visits.unwind(visited);
+
+    loops
+}
+
+// Obstacle position on the original route, with the guard's state just before it would first step
+// there
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    obstacle: Position,
+    pos: Position,
+    direction: Direction,
+}
+
+fn obstacle_candidates(map: &[Vec<Space>], start: Position) -> Vec<Candidate> {
+    let mut seen = vec![vec![false; map[0].len()]; map.len()];
+    seen[start.y as usize][start.x as usize] = true;
+
+    let mut candidates = Vec::new();
+    let (mut pos, mut direction) = (start, Direction::Up);
+    loop {
+        let next_pos = pos + direction.delta();
+        if !Rect::from_size(map[0].len(), map.len()).contains(next_pos) {
+            return candidates;
+        }
+
+        if map[next_pos.y as usize][next_pos.x as usize] == Space::Obstacle {
+            direction = direction.rotate_right();
+        } else {
+            if !seen[next_pos.y as usize][next_pos.x as usize] {
+                seen[next_pos.y as usize][next_pos.x as usize] = true;
+                candidates.push(Candidate { obstacle: next_pos, pos, direction });
+            }
+            pos = next_pos;
+        }
+    }
+}
+
+// Part 2 for the `parallel` feature. An obstacle only changes the patrol from the first time the
+// guard would step onto it, so each candidate is checked independently by resuming the patrol from
+// just before that step. Any loop repeats a state visited after resuming, so earlier visits don't
+// need to be carried over
+fn count_loop_obstacles(map: &[Vec<Space>], start: Position) -> u32 {
+    let candidates = obstacle_candidates(map, start);
+    parallel::map_chunks(&candidates, |candidates| {
+        let mut map = map.to_vec();
+        let mut state = PatrolState::new(&map);
+        let mut loops = 0;
+        for &Candidate { obstacle, pos, direction } in candidates {
+            let (x, y) = (obstacle.x as usize, obstacle.y as usize);
+            map[y][x] = Space::Obstacle;
+            loops += u32::from(state.loops(&map, pos, direction));
+            map[y][x] = Space::Empty;
+        }
+        loops
+    })
+    .into_iter()
+    .sum()
+}
+
+// Visited state for repeated patrols over the same map. Each patrol gets a new generation number
+// so that the grids never need to be cleared
+struct PatrolState {
+    visited: Vec<Vec<u32>>,
+    visited_directions: Vec<Vec<DirectionMap<u32>>>,
+    generation: u32,
+}
+
+impl PatrolState {
+    fn new(map: &[Vec<Space>]) -> Self {
+        Self {
+            visited: vec![vec![0; map[0].len()]; map.len()],
+            visited_directions: vec![vec![DirectionMap::splat(0); map[0].len()]; map.len()],
+            generation: 0,
+        }
+    }
+
+    // Returns the number of distinct positions visited before the guard leaves the map or loops
+    fn coverage(&mut self, map: &[Vec<Space>], start: Position) -> usize {
+        self.generation += 1;
+        let generation = self.generation;
+
+        let mut covered = 0;
+        let mut current_pos = start;
+        let mut direction = Direction::Up;
+        loop {
+            let (x, y) = (current_pos.x as usize, current_pos.y as usize);
+            if self.visited_directions[y][x][direction] == generation {
+                // Loop
+                break;
+            }
+            self.visited_directions[y][x][direction] = generation;
+            if self.visited[y][x] != generation {
+                self.visited[y][x] = generation;
+                covered += 1;
+            }
+
+            let next_pos = current_pos + direction.delta();
+            if !Rect::from_size(map[0].len(), map.len()).contains(next_pos) {
+                break;
+            }
+
+            if map[next_pos.y as usize][next_pos.x as usize] == Space::Obstacle {
+                direction = direction.rotate_right();
+            } else {
+                current_pos = next_pos;
+            }
+        }
+
+        covered
+    }
+
+    // Returns whether the guard loops when starting from `start` facing `direction`
+    fn loops(&mut self, map: &[Vec<Space>], start: Position, mut direction: Direction) -> bool {
+        self.generation += 1;
+        let generation = self.generation;
+
+        let mut current_pos = start;
+        loop {
+            let (x, y) = (current_pos.x as usize, current_pos.y as usize);
+            if self.visited_directions[y][x][direction] == generation {
+                return true;
+            }
+            self.visited_directions[y][x][direction] = generation;
+
+            let next_pos = current_pos + direction.delta();
+            if !Rect::from_size(map[0].len(), map.len()).contains(next_pos) {
+                return false;
+            }
+
+            if map[next_pos.y as usize][next_pos.x as usize] == Space::Obstacle {
+                direction = direction.rotate_right();
+            } else {
+                current_pos = next_pos;
+            }
+        }
+    }
+}
+
+// Returns the obstacle position that maximizes the guard's coverage, along with that coverage.
+// Ties go to the first position in reading order. None if there is nowhere to place an obstacle
+fn max_coverage_obstacle(input: &str) -> Result<Option<(Position, usize)>, ParseError> {
+    let Input { mut map, guard_start } = parse_input(input)?;
+
+    let mut state = PatrolState::new(&map);
+    let original_coverage = state.coverage(&map, guard_start);
+    let original_route = state.visited.clone();
+    let original_generation = state.generation;
+
+    let mut best: Option<(Position, usize)> = None;
+    let (rows, cols) = (map.len(), map[0].len());
+    for (y, x) in (0..rows).flat_map(|y| (0..cols).map(move |x| (y, x))) {
+        let pos = Position { x: x as i32, y: y as i32 };
+        if pos == guard_start || map[y][x] == Space::Obstacle {
+            continue;
+        }
+
+        let coverage = if original_route[y][x] == original_generation {
+            map[y][x] = Space::Obstacle;
+            let coverage = state.coverage(&map, guard_start);
+            map[y][x] = Space::Empty;
+            coverage
+        } else {
+            original_coverage
+        };
+
+        if best.is_none_or(|(_, best_coverage)| coverage > best_coverage) {
+            best = Some((pos, coverage));
+        }
+    }
+
+    Ok(best)
+}
+
+// Part 1 patrol advanced one move (step or turn) at a time, for visualization
+struct Patrol {
+    map: Vec<Vec<Space>>,
+    pos: Position,
+    direction: Direction,
+    visited: Vec<Vec<DirectionMap<bool>>>,
+    visited_count: usize,
+    exited: bool,
+}
+
+impl Patrol {
+    fn new(map: Vec<Vec<Space>>, start: Position) -> Self {
+        let mut visited = vec![vec![DirectionMap::splat(false); map[0].len()]; map.len()];
+        visited[start.y as usize][start.x as usize][Direction::Up] = true;
+        Self { map, pos: start, direction: Direction::Up, visited, visited_count: 1, exited: false }
+    }
+
+    // Returns false once the guard has left the map or entered a loop
+    fn step(&mut self) -> bool {
+        let next_pos = self.pos + self.direction.delta();
+        if !Rect::from_size(self.map[0].len(), self.map.len()).contains(next_pos) {
+            self.exited = true;
+            return false;
+        }
+
+        if self.map[next_pos.y as usize][next_pos.x as usize] == Space::Obstacle {
+            self.direction = self.direction.rotate_right();
+        } else {
+            self.pos = next_pos;
+        }
+
+        let visited = &mut self.visited[self.pos.y as usize][self.pos.x as usize];
+        if visited[self.direction] {
+            return false;
+        }
+        if !visited.any() {
+            self.visited_count += 1;
+        }
+        visited[self.direction] = true;
+        true
+    }
+
+    fn glyph(&self, x: usize, y: usize) -> Glyph {
+        let pos = Position { x: x as i32, y: y as i32 };
+        if pos == self.pos && !self.exited {
+            (self.direction.arrow(), Color::Rgb(GUARD_COLOR))
+        } else if self.map[y][x] == Space::Obstacle {
+            ('#', Color::Rgb(OBSTACLE_COLOR))
+        } else if self.visited[y][x].any() {
+            ('X', Color::Rgb(VISITED_COLOR))
+        } else {
+            ('.', Color::Default)
+        }
+    }
+
+    fn status(&self) -> String {
+        format!("{} positions visited", self.visited_count)
+    }
+
+    fn render(&self) -> Frame {
+        let lines = (0..self.map.len())
+            .map(|y| (0..self.map[0].len()).map(|x| self.glyph(x, y).0).collect())
+            .collect();
+
+        Frame {
+            lines,
+            status: self.status(),
+            focus: Some((self.pos.x as usize, self.pos.y as usize)),
+        }
+    }
+
+    fn render_ansi(&self) -> String {
+        let map = ansi::render_fn(self.map[0].len(), self.map.len(), |x, y| self.glyph(x, y));
+        format!("{map}{}\n", self.status())
+    }
+}
+
+const GUARD_COLOR: Rgb = [220, 40, 40];
+const OBSTACLE_COLOR: Rgb = [110, 110, 110];
+const VISITED_COLOR: Rgb = [200, 150, 60];
+
+// Black-and-white image where true pixels are dark
+type Bitmap = Vec<Vec<bool>>;
+
+fn import_bitmap(bytes: &[u8]) -> Result<Bitmap, String> {
+    if bytes.starts_with(b"P1") || bytes.starts_with(b"P4") {
+        parse_pbm(bytes)
+    } else if bytes.starts_with(b"\x89PNG") {
+        decode_png(bytes)
+    } else {
+        Err("Unrecognized image format; expected PBM (P1/P4) or PNG".into())
+    }
+}
+
+fn parse_pbm(bytes: &[u8]) -> Result<Bitmap, String> {
+    let mut i = 2;
+    let width = parse_pbm_header_number(bytes, &mut i)?;
+    let height = parse_pbm_header_number(bytes, &mut i)?;
+    if width == 0 || height == 0 {
+        return Err(format!("Invalid PBM dimensions {width}x{height}"));
+    }
+
+    if bytes.starts_with(b"P1") {
+        // Plain format: ASCII 0s and 1s, whitespace ignored
+        let pixels = bytes[i..]
+            .iter()
+            .filter(|b| !b.is_ascii_whitespace())
+            .take(width * height)
+            .map(|&b| match b {
+                b'0' => Ok(false),
+                b'1' => Ok(true),
+                _ => Err(format!("Invalid plain PBM pixel '{}'", b as char)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if pixels.len() != width * height {
+            return Err("Truncated PBM pixel data".into());
+        }
+
+        Ok(pixels.chunks(width).map(<[bool]>::to_vec).collect())
+    } else {
+        // Raw format: a single whitespace byte, then rows packed MSB first, padded to whole bytes
+        let row_len = width.div_ceil(8);
+        let data = bytes.get(i + 1..).unwrap_or_default();
+        if data.len() < row_len * height {
+            return Err("Truncated PBM pixel data".into());
+        }
+
+        Ok(data
+            .chunks(row_len)
+            .take(height)
+            .map(|row| (0..width).map(|x| row[x / 8] & (0x80 >> (x % 8)) != 0).collect())
+            .collect())
+    }
+}
+
+fn parse_pbm_header_number(bytes: &[u8], i: &mut usize) -> Result<usize, String> {
+    // Skip whitespace and comments
+    loop {
+        match bytes.get(*i) {
+            Some(b) if b.is_ascii_whitespace() => *i += 1,
+            Some(b'#') => {
+                while bytes.get(*i).is_some_and(|&b| b != b'\n') {
+                    *i += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    let start = *i;
+    while bytes.get(*i).is_some_and(u8::is_ascii_digit) {
+        *i += 1;
+    }
+
+    std::str::from_utf8(&bytes[start..*i])
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| "Invalid PBM header".into())
+}
+
+#[cfg(feature = "png")]
+fn decode_png(bytes: &[u8]) -> Result<Bitmap, String> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|err| err.to_string())?;
+
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|err| err.to_string())?;
+
+    let samples = info.color_type.samples();
+    let row_len = info.line_size;
+    Ok(buf[..row_len * info.height as usize]
+        .chunks(row_len)
+        .map(|row| {
+            row[..info.width as usize * samples]
+                .chunks(samples)
+                .map(|pixel| {
+                    // Fully transparent pixels are treated as light
+                    let luma = match (info.color_type, pixel) {
+                        (png::ColorType::Grayscale, &[l]) => u32::from(l),
+                        (png::ColorType::GrayscaleAlpha, &[l, a]) => {
+                            if a == 0 {
+                                255
+                            } else {
+                                u32::from(l)
+                            }
+                        }
+                        (png::ColorType::Rgb, &[r, g, b]) => rgb_luma(r, g, b),
+                        (png::ColorType::Rgba, &[r, g, b, a]) => {
+                            if a == 0 {
+                                255
+                            } else {
+                                rgb_luma(r, g, b)
+                            }
+                        }
+                        _ => 255,
+                    };
+                    luma < 128
+                })
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(feature = "png")]
+fn rgb_luma(r: u8, g: u8, b: u8) -> u32 {
+    (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000
+}
+
+#[cfg(not(feature = "png"))]
+fn decode_png(_bytes: &[u8]) -> Result<Bitmap, String> {
+    Err("PNG import requires building with `--features png`".into())
+}
+
+// Converts a bitmap to puzzle input format
+fn bitmap_to_map(bitmap: &Bitmap, guard_start: Option<Position>) -> Result<String, String> {
+    let guard_start = match guard_start {
+        Some(pos) => pos,
+        None => (0..bitmap.len())
+            .rev()
+            .find_map(|y| {
+                let x = bitmap[y].iter().position(|&dark| !dark)?;
+                Some(Position { x: x as i32, y: y as i32 })
+            })
+            .ok_or("Bitmap has no empty pixels for the guard to start on")?,
+    };
+
+    let in_bounds = (0..bitmap.len() as i32).contains(&guard_start.y)
+        && (0..bitmap[guard_start.y as usize].len() as i32).contains(&guard_start.x);
+    if !in_bounds || bitmap[guard_start.y as usize][guard_start.x as usize] {
+        return Err(format!(
+            "Guard start {},{} is not an empty pixel in the bitmap",
+            guard_start.x, guard_start.y
+        ));
+    }
+
+    let mut map = String::with_capacity(bitmap.len() * (bitmap[0].len() + 1));
+    for (y, row) in bitmap.iter().enumerate() {
+        for (x, &dark) in row.iter().enumerate() {
+            map.push(if dark {
+                '#'
+            } else if guard_start == (Position { x: x as i32, y: y as i32 }) {
+                '^'
+            } else {
+                '.'
+            });
+        }
+        map.push('\n');
+    }
+
+    Ok(map)
+}
+
+fn parse_guard_start(s: &str) -> Result<Position, String> {
+    s.split_once(',')
+        .and_then(|(x, y)| Some(Position { x: x.trim().parse().ok()?, y: y.trim().parse().ok()? }))
+        .ok_or_else(|| format!("Invalid guard start '{s}', expected x,y"))
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input).unwrap_solution(),
+    |input| solve_part_2(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::has_flag("--max-coverage") {
+        match max_coverage_obstacle(&crate::read_input()?)? {
+            Some((pos, coverage)) => {
+                println!("Obstacle at {},{}: guard visits {coverage} positions", pos.x, pos.y);
+            }
+            None => println!("No position to place an obstacle"),
+        }
+        return Ok(());
+    }
+
+    if crate::terminal_viz()? {
+        let Input { map, guard_start } = parse_input(&crate::read_input()?)?;
+        return viz::run(
+            "Day 6: Guard Gallivant",
+            Patrol::new(map, guard_start),
+            Patrol::step,
+            Patrol::render,
+        );
+    }
+
+    if crate::has_flag("--bitmap") {
+        let bitmap = import_bitmap(&fs::read(crate::input_filename())?)?;
+        let guard_start =
+            crate::flag_value("--guard").map(|s| parse_guard_start(&s)).transpose()?;
+        let map = bitmap_to_map(&bitmap, guard_start)?;
+        if let Some(path) = crate::flag_value("--write-map") {
+            fs::write(path, &map)?;
+        }
+
+        return crate::run_with_input(&map, solve_part_1, solve_part_2);
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycle;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day6.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(41), solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(6), solve_part_2(SAMPLE_INPUT));
+
+        let Input { map, guard_start } = parse_input(SAMPLE_INPUT).unwrap();
+        assert_eq!(6, count_loop_obstacles(&map, guard_start));
+    }
+
+    // One move of the guard, as a state that can be hashed
+    fn guard_step(
+        map: &[Vec<Space>],
+        (pos, direction): (Position, Direction),
+    ) -> Option<(Position, Direction)> {
+        let next_pos = pos + direction.delta();
+        let space = map.get(next_pos.y as usize)?.get(next_pos.x as usize)?;
+        match space {
+            Space::Obstacle => Some((pos, direction.rotate_right())),
+            Space::Empty => Some((next_pos, direction)),
+        }
+    }
+
+    #[test]
+    fn loops_match_state_hashing() {
+        let Input { mut map, guard_start } = parse_input(SAMPLE_INPUT).unwrap();
+        let mut state = PatrolState::new(&map);
+        let mut loops = 0;
+        for y in 0..map.len() {
+            for x in 0..map[0].len() {
+                if map[y][x] == Space::Obstacle
+                    || (Position { x: x as i32, y: y as i32 }) == guard_start
+                {
+                    continue;
+                }
+                map[y][x] = Space::Obstacle;
+                let repeat =
+                    cycle::first_repeat((guard_start, Direction::Up), |&s| guard_step(&map, s));
+                assert_eq!(repeat.is_some(), state.loops(&map, guard_start, Direction::Up));
+                loops += u32::from(repeat.is_some());
+                map[y][x] = Space::Empty;
+            }
+        }
+        assert_eq!(6, loops);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse_input("..#\n.^x\n").unwrap_err();
+        assert_eq!("line 2, column 3: Unexpected map character: 'x'", err.to_string());
+        assert_eq!(Err(ParseError::new("No guard position in input")), solve_part_1("..#\n"));
+    }
+
+    #[test]
+    fn max_coverage() {
+        let (pos, coverage) = max_coverage_obstacle(SAMPLE_INPUT).unwrap().unwrap();
+
+        // Brute force over every empty position, including ones off the original route
+        let Input { mut map, guard_start } = parse_input(SAMPLE_INPUT).unwrap();
+        let mut state = PatrolState::new(&map);
+        assert_eq!(41, state.coverage(&map, guard_start));
+        let mut best = 0;
+        for y in 0..map.len() {
+            for x in 0..map[0].len() {
+                if map[y][x] == Space::Obstacle
+                    || (Position { x: x as i32, y: y as i32 }) == guard_start
+                {
+                    continue;
+                }
+                map[y][x] = Space::Obstacle;
+                best = best.max(state.coverage(&map, guard_start));
+                map[y][x] = Space::Empty;
+            }
+        }
+        assert_eq!(best, coverage);
+        assert_eq!(41, coverage);
+
+        // Turning the guard before it leaves the map covers one extra position
+        assert_eq!(
+            Ok(Some((Position { x: 2, y: 0 }, 4))),
+            max_coverage_obstacle(".....\n.....\n..^..\n")
+        );
+
+        map[pos.y as usize][pos.x as usize] = Space::Obstacle;
+        assert_eq!(coverage, state.coverage(&map, guard_start));
+    }
+
+    #[test]
+    fn patrol_steps() {
+        let Input { map, guard_start } = parse_input(SAMPLE_INPUT).unwrap();
+        let mut patrol = Patrol::new(map, guard_start);
+        assert_eq!("....#.....", patrol.render().lines[0]);
+        assert_eq!(".#..^.....", patrol.render().lines[6]);
+
+        patrol.step();
+        assert_eq!("....^.....", patrol.render().lines[5]);
+        assert_eq!(".#..X.....", patrol.render().lines[6]);
+
+        while patrol.step() {}
+        assert!(patrol.exited);
+        assert_eq!(41, patrol.visited_count);
+        assert_eq!("41 positions visited", patrol.render().status);
+        crate::snapshot::assert_snapshot("day06_patrol", &patrol.render_ansi());
+    }
+
+    fn sample_as_plain_pbm() -> String {
+        let lines: Vec<_> = SAMPLE_INPUT.lines().filter(|line| !line.is_empty()).collect();
+        let mut pbm = format!("P1\n# day 6 sample\n{} {}\n", lines[0].len(), lines.len());
+        for line in lines {
+            let row: Vec<_> = line.chars().map(|c| if c == '#' { "1" } else { "0" }).collect();
+            pbm.push_str(&row.join(" "));
+            pbm.push('\n');
+        }
+        pbm
+    }
+
+    #[test]
+    fn bitmap_import() {
+        let bitmap = import_bitmap(sample_as_plain_pbm().as_bytes()).unwrap();
+        let map = bitmap_to_map(&bitmap, Some(Position { x: 4, y: 6 })).unwrap();
+        assert_eq!(SAMPLE_INPUT.trim_end(), map.trim_end());
+        assert_eq!(Ok(41), solve_part_1(&map));
+        assert_eq!(Ok(6), solve_part_2(&map));
+
+        assert!(bitmap_to_map(&bitmap, Some(Position { x: 4, y: 0 })).is_err());
+    }
+
+    #[test]
+    fn raw_pbm() {
+        // 10x2: row 0 has the first and last pixels dark, row 1 is empty
+        let bytes = b"P4 10 2\n\x80\x40\x00\x00";
+        let bitmap = import_bitmap(bytes).unwrap();
+        assert_eq!(
+            vec![
+                vec![true, false, false, false, false, false, false, false, false, true],
+                vec![false; 10],
+            ],
+            bitmap
+        );
+
+        // Default guard start is the leftmost empty pixel in the bottom row
+        assert_eq!("#........#\n^.........\n", bitmap_to_map(&bitmap, None).unwrap());
+        assert!(import_bitmap(b"P4 10 2\n\x80").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! Day 7: Bridge Repair
+//!
+//! <https://adventofcode.com/2024/day/7>
+
+use crate::days::Solver;
+use crate::parse::{self, ParseError, lines, space_separated, unsigned};
+use crate::{SolverOutput, math, parallel};
+use std::error::Error;
+use winnow::ascii::newline;
+use winnow::combinator::{opt, separated_pair};
+use winnow::prelude::*;
+
+#[derive(Debug)]
+struct Equation {
+    test: u64,
+    operands: Vec<u64>,
+}
+
+fn parse_equation(input: &mut &str) -> PResult<Equation> {
+    let (test, operands) =
+        separated_pair(unsigned, ": ", space_separated(unsigned)).parse_next(input)?;
+    Ok(Equation { test, operands })
+}
+
+fn parse_equations(input: &mut &str) -> PResult<Vec<Equation>> {
+    let equations = lines(parse_equation).parse_next(input)?;
+    opt(newline).parse_next(input)?;
+    Ok(equations)
+}
+
+// Operands of 0 never show up in the input, and would break the pruning in test_equation, which
+// assumes that no operator can decrease the running value
+fn parse_input(input: &str) -> Result<Vec<Equation>, ParseError> {
+    let equations = parse::complete(parse_equations, input)?;
+    match equations.iter().position(|equation| equation.operands.contains(&0)) {
+        Some(row) => Err(ParseError::at(row, 0, "Operands of 0 are not supported")),
+        None => Ok(equations),
+    }
+}
+
+fn solve<const PART2: bool>(input: &str) -> Result<u64, ParseError> {
+    let equations = parse_input(input)?;
+
+    Ok(parallel::sum_by(&equations, |equation| {
+        let valid =
+            test_equation::<PART2>(equation.test, equation.operands[0], &equation.operands[1..]);
+        if valid { equation.test } else { 0 }
+    }))
+}
+
+fn test_equation<const PART2: bool>(test: u64, acc: u64, remaining: &[u64]) -> bool {
+    if remaining.is_empty() {
+        return acc == test;
+    }
+
+    // All values are unsigned - operators can only increase the value, not decrease
+    if acc > test {
+        return false;
+    }
+
+    test_add::<PART2>(test, acc, remaining)
+        || test_mul::<PART2>(test, acc, remaining)
+        || (PART2 && test_concat(test, acc, remaining))
+}
+
+fn test_add<const PART2: bool>(test: u64, acc: u64, remaining: &[u64]) -> bool {
+    test_equation::<PART2>(test, acc + remaining[0], &remaining[1..])
+}
+
+fn test_mul<const PART2: bool>(test: u64, acc: u64, remaining: &[u64]) -> bool {
+    test_equation::<PART2>(test, acc * remaining[0], &remaining[1..])
+}
+
+fn test_concat(test: u64, acc: u64, remaining: &[u64]) -> bool {
+    test_equation::<true>(test, math::concat(acc, remaining[0]), &remaining[1..])
+}
+
+// Tries every combination of operators without pruning
+#[cfg(feature = "reference")]
+fn solve_reference(input: &str, operators: u32) -> u64 {
+    let equations = parse_input(input).unwrap();
+
+    equations
+        .into_iter()
+        .filter(|equation| {
+            let Equation { test, operands } = equation;
+            let combinations = operators.pow(operands.len() as u32 - 1);
+            (0..combinations).any(|mut combination| {
+                let mut acc = operands[0];
+                for &operand in &operands[1..] {
+                    acc = match combination % operators {
+                        0 => acc + operand,
+                        1 => acc * operand,
+                        _ => format!("{acc}{operand}").parse().unwrap(),
+                    };
+                    combination /= operators;
+                }
+                acc == *test
+            })
+        })
+        .map(|equation| equation.test)
+        .sum()
+}
+
+#[cfg(feature = "reference")]
+pub const DIFFERENTIAL: crate::differential::Differential = crate::differential::Differential {
+    optimized: SOLVER,
+    reference: Solver::Parts(
+        |input| solve_reference(input, 2).into(),
+        |input| solve_reference(input, 3).into(),
+    ),
+    generate: crate::generate::calibration_equations,
+    size: 50,
+};
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve::<false>(input).unwrap_solution(),
+    |input| solve::<true>(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(solve::<false>, solve::<true>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day7.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(3749), solve::<false>(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(11387), solve::<true>(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve::<false>("190: 10 19\n3267 81 40 27\n").unwrap_err();
+        assert_eq!("line 2, column 1: Unexpected line: '3267 81 40 27'", err.to_string());
+
+        let err = solve::<true>("190: 10 19\n83: 17 0\n").unwrap_err();
+        assert_eq!("line 2, column 1: Operands of 0 are not supported", err.to_string());
+    }
+}
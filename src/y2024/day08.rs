@@ -0,0 +1,118 @@
+//! Day 8: Resonant Collinearity
+//!
+//! <https://adventofcode.com/2024/day/8>
+
+use crate::days::Solver;
+use crate::{ParseError, Pos2, SolverOutput, combinatorics};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Empty,
+    Antenna(u8),
+}
+
+type Position = Pos2<i32>;
+
+// Antenna frequencies are single letters or digits
+fn parse_input(input: &str) -> Result<Vec<Vec<Space>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(col, c)| match c {
+                    '.' => Ok(Space::Empty),
+                    _ if c.is_ascii_alphanumeric() => Ok(Space::Antenna(c as u8)),
+                    _ => Err(ParseError::at(row, col, format!("Unexpected map character: '{c}'"))),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn solve<const PART2: bool>(input: &str) -> Result<usize, ParseError> {
+    let map = parse_input(input)?;
+    let rows = map.len() as i32;
+    let cols = map[0].len() as i32;
+
+    let antenna_positions = build_positions_map(&map);
+
+    let mut result: FxHashSet<Position> = FxHashSet::default();
+    for positions in antenna_positions.values() {
+        if positions.len() < 2 {
+            // Doesn't seem to happen in the input, but there can't be an antinode for a character
+            // with only one antenna
+            continue;
+        }
+
+        if PART2 {
+            // For part 2, every antenna position is a valid antinode location
+            result.extend(positions);
+        }
+
+        for (&a, &b) in combinatorics::pairs(positions) {
+            for (p1, p2) in [(a, b), (b, a)] {
+                let delta = p2 - p1;
+                let mut antinodes = (p2 + delta).ray_within(delta, Position { x: cols, y: rows });
+                if PART2 {
+                    result.extend(antinodes);
+                } else {
+                    // For part 1, only the first position on the line is a valid antinode location
+                    result.extend(antinodes.next());
+                }
+            }
+        }
+    }
+
+    Ok(result.len())
+}
+
+fn build_positions_map(map: &[Vec<Space>]) -> FxHashMap<u8, Vec<Position>> {
+    let mut antenna_positions: FxHashMap<u8, Vec<Position>> = FxHashMap::default();
+    for (i, row) in map.iter().enumerate() {
+        for (j, &space) in row.iter().enumerate() {
+            let Space::Antenna(c) = space else { continue };
+            antenna_positions.entry(c).or_default().push(Position { x: j as i32, y: i as i32 });
+        }
+    }
+    antenna_positions
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve::<false>(input).unwrap_solution(),
+    |input| solve::<true>(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(solve::<false>, solve::<true>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day8.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(14), solve::<false>(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(34), solve::<true>(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve::<false>("..a.\n\n.#A.\n").unwrap_err();
+        assert_eq!("line 3, column 2: Unexpected map character: '#'", err.to_string());
+    }
+}
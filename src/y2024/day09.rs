@@ -0,0 +1,386 @@
+//! Day 9: Disk Fragmenter
+//!
+//! <https://adventofcode.com/2024/day/9>
+//!
+//! Part 2 works on file and free space extents rather than individual blocks. With the `parallel`
+//! feature, parsing, gap indexing, and the checksum are split across threads, and
+//! `--bench-scaling` compares sequential and parallel part 2 on generated disk maps.
+
+use crate::days::Solver;
+use crate::{generate, parallel, time_micros};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::iter;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Empty,
+    Occupied(u64),
+}
+
+fn parse_input(input: &str) -> Vec<Space> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .flat_map(|line| line.chars())
+        .enumerate()
+        .flat_map(|(i, c)| {
+            let size = c.to_digit(10).unwrap();
+            match i % 2 {
+                0 => {
+                    let id = (i / 2) as u64;
+                    iter::repeat_n(Space::Occupied(id), size as usize)
+                }
+                1 => iter::repeat_n(Space::Empty, size as usize),
+                _ => unreachable!(),
+            }
+        })
+        .collect()
+}
+
+fn solve_part_1(input: &str) -> u64 {
+    let mut disk = parse_input(input);
+
+    let mut i = 0;
+    let mut j = disk.len() - 1;
+    loop {
+        while i < disk.len() && matches!(disk[i], Space::Occupied(..)) {
+            i += 1;
+        }
+
+        while j > 0 && disk[j] == Space::Empty {
+            j -= 1;
+        }
+
+        if i > j {
+            break;
+        }
+
+        disk[i] = disk[j];
+        disk[j] = Space::Empty;
+    }
+
+    evaluate_disk(&disk)
+}
+
+fn evaluate_disk(disk: &[Space]) -> u64 {
+    disk.iter()
+        .enumerate()
+        .map(|(i, &space)| match space {
+            Space::Occupied(id) => id * i as u64,
+            Space::Empty => 0,
+        })
+        .sum()
+}
+
+// Contiguous run of blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Extent {
+    start: usize,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct File {
+    id: u64,
+    extent: Extent,
+}
+
+// Disk as lists of file and free space extents, both in position order
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiskMap {
+    files: Vec<File>,
+    gaps: Vec<Extent>,
+}
+
+fn parse_digits(input: &str) -> Vec<u8> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .flat_map(|line| line.bytes())
+        .map(|b| {
+            assert!(b.is_ascii_digit(), "Unexpected disk map character: '{}'", char::from(b));
+            b - b'0'
+        })
+        .collect()
+}
+
+// `digits` starts at map position `first_index`, which must be even (a file), and its first block
+// is at disk position `offset`
+fn append_extents(digits: &[u8], first_index: usize, mut offset: usize, map: &mut DiskMap) {
+    for (i, &size) in digits.iter().enumerate() {
+        let extent = Extent { start: offset, len: size.into() };
+        if i % 2 == 0 {
+            map.files.push(File { id: ((first_index + i) / 2) as u64, extent });
+        } else if size != 0 {
+            map.gaps.push(extent);
+        }
+        offset += usize::from(size);
+    }
+}
+
+fn parse_disk_map(digits: &[u8]) -> DiskMap {
+    let mut map = DiskMap { files: Vec::new(), gaps: Vec::new() };
+    append_extents(digits, 0, 0, &mut map);
+    map
+}
+
+// Splits the digits into even-length chunks so that every chunk starts with a file. Each chunk's
+// starting disk position is the sum of all earlier chunks' sizes, so the chunk totals are computed
+// in parallel first and then the extents; concatenating in chunk order gives the same result as
+// parse_disk_map
+fn parse_disk_map_parallel(digits: &[u8]) -> DiskMap {
+    let chunk_size = digits.len().div_ceil(parallel::thread_count()).next_multiple_of(2).max(2);
+    let ranges: Vec<_> = (0..digits.len())
+        .step_by(chunk_size)
+        .map(|start| start..(start + chunk_size).min(digits.len()))
+        .collect();
+
+    let totals = parallel::spawn_chunks(&ranges, |ranges| {
+        let total =
+            |range: &Range<usize>| digits[range.clone()].iter().map(|&n| usize::from(n)).sum();
+        ranges.iter().map(total).collect::<Vec<usize>>()
+    });
+    let offsets: Vec<_> = totals
+        .into_iter()
+        .flatten()
+        .scan(0, |offset, total| {
+            let start = *offset;
+            *offset += total;
+            Some(start)
+        })
+        .collect();
+    let chunks: Vec<_> = ranges.into_iter().zip(offsets).collect();
+
+    let parts = parallel::spawn_chunks(&chunks, |chunks| {
+        let mut map = DiskMap { files: Vec::new(), gaps: Vec::new() };
+        for (range, offset) in chunks {
+            append_extents(&digits[range.clone()], range.start, *offset, &mut map);
+        }
+        map
+    });
+
+    let mut map = DiskMap { files: Vec::new(), gaps: Vec::new() };
+    for part in parts {
+        map.files.extend(part.files);
+        map.gaps.extend(part.gaps);
+    }
+    map
+}
+
+// Gap sizes are digits, so gaps are indexed by size with one min-heap of start positions per size.
+// The leftmost gap that fits a file is then the minimum over the heaps for sizes >= the file's size
+const MAX_GAP_LEN: usize = 9;
+
+type GapHeaps = [BinaryHeap<Reverse<usize>>; MAX_GAP_LEN + 1];
+
+fn gap_heaps(gaps: &[Extent]) -> GapHeaps {
+    let mut heaps: GapHeaps = Default::default();
+    for gap in gaps {
+        heaps[gap.len].push(Reverse(gap.start));
+    }
+    heaps
+}
+
+// Builds the heaps for different sizes on different threads. Each size scans all gaps, but heap
+// construction from a Vec is linear, unlike pushing one at a time
+fn gap_heaps_parallel(gaps: &[Extent]) -> GapHeaps {
+    let lens: Vec<_> = (0..=MAX_GAP_LEN).collect();
+    let heaps = parallel::spawn_chunks(&lens, |lens| {
+        let heap = |len| {
+            let starts: Vec<_> =
+                gaps.iter().filter(|gap| gap.len == len).map(|gap| Reverse(gap.start)).collect();
+            BinaryHeap::from(starts)
+        };
+        lens.iter().map(|&len| heap(len)).collect::<Vec<_>>()
+    });
+    let heaps: Vec<_> = heaps.into_iter().flatten().collect();
+    heaps.try_into().unwrap()
+}
+
+// Moves each file, in decreasing ID order, to the leftmost gap to its left that fits it
+fn compact_files(files: &mut [File], heaps: &mut GapHeaps) {
+    for file in files.iter_mut().rev() {
+        let best = (file.extent.len..=MAX_GAP_LEN)
+            .filter_map(|len| heaps[len].peek().map(|&Reverse(start)| (start, len)))
+            .min();
+        let Some((start, len)) = best else { continue };
+        if start >= file.extent.start {
+            continue;
+        }
+
+        heaps[len].pop();
+        file.extent.start = start;
+        let remaining = len - file.extent.len;
+        if remaining != 0 {
+            heaps[remaining].push(Reverse(start + file.extent.len));
+        }
+    }
+}
+
+// id * (start + (start + 1) + ... + (start + len - 1))
+fn file_checksum(file: &File) -> u128 {
+    let Extent { start, len } = file.extent;
+    let position_sum = (len * start + len * len.saturating_sub(1) / 2) as u128;
+    u128::from(file.id) * position_sum
+}
+
+fn checksum(files: &[File]) -> u128 {
+    files.iter().map(file_checksum).sum()
+}
+
+// Integer addition is associative, so summing per-chunk totals gives the same result as checksum
+fn checksum_parallel(files: &[File]) -> u128 {
+    parallel::spawn_chunks(files, checksum).into_iter().sum()
+}
+
+fn compacted_checksum(digits: &[u8], parallel: bool) -> u128 {
+    if parallel {
+        let mut map = parse_disk_map_parallel(digits);
+        let mut heaps = gap_heaps_parallel(&map.gaps);
+        compact_files(&mut map.files, &mut heaps);
+        checksum_parallel(&map.files)
+    } else {
+        let mut map = parse_disk_map(digits);
+        let mut heaps = gap_heaps(&map.gaps);
+        compact_files(&mut map.files, &mut heaps);
+        checksum(&map.files)
+    }
+}
+
+fn solve_part_2(input: &str) -> u64 {
+    let checksum = compacted_checksum(&parse_digits(input), parallel::ENABLED);
+    u64::try_from(checksum).expect("Checksum does not fit in u64")
+}
+
+// Times sequential and parallel part 2 on generated disk maps with up to a million files
+fn bench_scaling() {
+    let mut rng = StdRng::seed_from_u64(9);
+    for files in [1_000, 100_000, 1_000_000] {
+        let digits = parse_digits(&generate::disk_map(&mut rng, 2 * files - 1));
+        let sequential = time_micros(|| compacted_checksum(&digits, false));
+        let parallel = time_micros(|| compacted_checksum(&digits, true));
+        println!("{files} files: {sequential}μs sequential, {parallel}μs parallel");
+    }
+}
+
+// Moves one block at a time, rescanning the disk from both ends on every move
+#[cfg(feature = "reference")]
+fn solve_reference_part_1(input: &str) -> u64 {
+    let mut disk = parse_input(input);
+    loop {
+        let first_empty = disk.iter().position(|&space| space == Space::Empty);
+        let last_occupied = disk.iter().rposition(|&space| space != Space::Empty);
+        match (first_empty, last_occupied) {
+            (Some(i), Some(j)) if i < j => disk.swap(i, j),
+            _ => break,
+        }
+    }
+
+    evaluate_disk(&disk)
+}
+
+// For each file in decreasing ID order, scans the whole disk for the file's location and then for
+// the leftmost run of free space that fits it
+#[cfg(feature = "reference")]
+fn solve_reference_part_2(input: &str) -> u64 {
+    let mut disk = parse_input(input);
+    let max_id = disk
+        .iter()
+        .filter_map(|&space| match space {
+            Space::Occupied(id) => Some(id),
+            Space::Empty => None,
+        })
+        .max()
+        .unwrap_or(0);
+
+    for id in (0..=max_id).rev() {
+        let file = Space::Occupied(id);
+        let Some(start) = disk.iter().position(|&space| space == file) else { continue };
+        let len = disk[start..].iter().take_while(|&&space| space == file).count();
+
+        let free_start = (0..start).find(|&i| {
+            i + len <= start && disk[i..i + len].iter().all(|&space| space == Space::Empty)
+        });
+        if let Some(free_start) = free_start {
+            disk[free_start..free_start + len].fill(file);
+            disk[start..start + len].fill(Space::Empty);
+        }
+    }
+
+    evaluate_disk(&disk)
+}
+
+#[cfg(feature = "reference")]
+pub const DIFFERENTIAL: crate::differential::Differential = crate::differential::Differential {
+    optimized: SOLVER,
+    reference: Solver::Parts(
+        |input| solve_reference_part_1(input).into(),
+        |input| solve_reference_part_2(input).into(),
+    ),
+    generate: crate::generate::disk_map,
+    size: 99,
+};
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve_part_1(input).into(), |input| solve_part_2(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::has_flag("--bench-scaling") {
+        bench_scaling();
+        return Ok(());
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day9.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(1928, solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(2858, solve_part_2(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn extents() {
+        let map = parse_disk_map(&parse_digits("12345"));
+        assert_eq!(
+            vec![
+                File { id: 0, extent: Extent { start: 0, len: 1 } },
+                File { id: 1, extent: Extent { start: 3, len: 3 } },
+                File { id: 2, extent: Extent { start: 10, len: 5 } },
+            ],
+            map.files
+        );
+        assert_eq!(vec![Extent { start: 1, len: 2 }, Extent { start: 6, len: 4 }], map.gaps);
+        assert_eq!(2 * (10 + 11 + 12 + 13 + 14), file_checksum(&map.files[2]));
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let mut rng = StdRng::seed_from_u64(9);
+        for len in [1, 2, 3, 17, 1001] {
+            let digits = parse_digits(&generate::disk_map(&mut rng, len));
+            let map = parse_disk_map(&digits);
+            assert_eq!(map, parse_disk_map_parallel(&digits));
+
+            let heaps = gap_heaps(&map.gaps).map(BinaryHeap::into_sorted_vec);
+            assert_eq!(heaps, gap_heaps_parallel(&map.gaps).map(BinaryHeap::into_sorted_vec));
+            assert_eq!(checksum(&map.files), checksum_parallel(&map.files));
+            assert_eq!(compacted_checksum(&digits, false), compacted_checksum(&digits, true));
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Day 10: Hoof It
+//!
+//! <https://adventofcode.com/2024/day/10>
+//!
+//! Scores and ratings are computed in a single bottom-up pass over heights 9 down to 0. With the
+//! `parallel` feature, the positions at each height are split across threads, and
+//! `--bench-scaling` compares sequential and parallel evaluation on generated height maps.
+
+use crate::days::Solver;
+use crate::{Grid, Pos2, generate, parallel, time_micros};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rustc_hash::FxHashSet;
+use std::error::Error;
+use std::iter;
+
+type Position = Pos2<i32>;
+
+trait Accumulator {
+    fn new() -> Self;
+
+    fn new_for_pos(pos: Position) -> Self;
+
+    fn accumulate(&mut self, other: &Self);
+
+    fn score(&self) -> usize;
+}
+
+// Part 1: Accumulate unique number of 9s reachable
+impl Accumulator for FxHashSet<Position> {
+    fn new() -> Self {
+        FxHashSet::default()
+    }
+
+    fn new_for_pos(pos: Position) -> Self {
+        iter::once(pos).collect()
+    }
+
+    fn accumulate(&mut self, other: &Self) {
+        self.extend(other.iter().copied());
+    }
+
+    fn score(&self) -> usize {
+        self.len()
+    }
+}
+
+// Part 2: Accumulate the total number of ways to reach a 9
+impl Accumulator for usize {
+    fn new() -> Self {
+        0
+    }
+
+    fn new_for_pos(_pos: Position) -> Self {
+        1
+    }
+
+    fn accumulate(&mut self, other: &Self) {
+        *self += *other;
+    }
+
+    fn score(&self) -> usize {
+        *self
+    }
+}
+
+fn parse_input(input: &str) -> Grid<u8> {
+    Grid(
+        input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.bytes().map(|b| b - b'0').collect())
+            .collect(),
+    )
+}
+
+const SUMMIT: u8 = 9;
+
+// Positions grouped by height, each in reading order
+fn height_levels(map: &Grid<u8>) -> [Vec<Position>; SUMMIT as usize + 1] {
+    let mut levels: [Vec<Position>; SUMMIT as usize + 1] = Default::default();
+    for y in 0..map.rows() {
+        for x in 0..map.cols() {
+            let pos = Position { x: x as i32, y: y as i32 };
+            levels[map[pos] as usize].push(pos);
+        }
+    }
+    levels
+}
+
+fn uphill_neighbors(map: &Grid<u8>, pos: Position) -> impl Iterator<Item = Position> + '_ {
+    [(-1, 0), (0, -1), (1, 0), (0, 1)]
+        .into_iter()
+        .map(move |(dy, dx)| pos + Position { y: dy, x: dx })
+        .filter(move |new_pos| map.in_bounds(*new_pos) && map[*new_pos] == map[pos] + 1)
+}
+
+fn level_values<Acc: Accumulator>(
+    map: &Grid<u8>,
+    values: &Grid<Acc>,
+    level: &[Position],
+) -> Vec<Acc> {
+    level
+        .iter()
+        .map(|&pos| {
+            let mut acc = Acc::new();
+            for neighbor in uphill_neighbors(map, pos) {
+                acc.accumulate(&values[neighbor]);
+            }
+            acc
+        })
+        .collect()
+}
+
+fn level_values_parallel<Acc: Accumulator + Send + Sync>(
+    map: &Grid<u8>,
+    values: &Grid<Acc>,
+    level: &[Position],
+) -> Vec<Acc> {
+    parallel::spawn_chunks(level, |chunk| level_values(map, values, chunk))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+// Computes every position's accumulator in one bottom-up pass, from the 9s down to the trailheads.
+// A position's value only depends on its neighbors one height up, which are all final by the time
+// its level is reached, so positions within a level are independent and can be split across
+// threads
+fn trail_values<Acc: Clone + Accumulator + Send + Sync>(
+    map: &Grid<u8>,
+    levels: &[Vec<Position>],
+    parallel: bool,
+) -> Grid<Acc> {
+    let mut values: Grid<Acc> = Grid(vec![vec![Acc::new(); map.cols()]; map.rows()]);
+    for &pos in &levels[SUMMIT as usize] {
+        values[pos] = Acc::new_for_pos(pos);
+    }
+
+    for level in levels[..SUMMIT as usize].iter().rev() {
+        let level_values = if parallel {
+            level_values_parallel(map, &values, level)
+        } else {
+            level_values(map, &values, level)
+        };
+        for (&pos, value) in level.iter().zip(level_values) {
+            values[pos] = value;
+        }
+    }
+
+    values
+}
+
+fn total_score<Acc: Clone + Accumulator + Send + Sync>(map: &Grid<u8>, parallel: bool) -> usize {
+    let levels = height_levels(map);
+    let values: Grid<Acc> = trail_values(map, &levels, parallel);
+    levels[0].iter().map(|&pos| values[pos].score()).sum()
+}
+
+fn solve_part_1(input: &str) -> usize {
+    total_score::<FxHashSet<Position>>(&parse_input(input), parallel::ENABLED)
+}
+
+fn solve_part_2(input: &str) -> usize {
+    total_score::<usize>(&parse_input(input), parallel::ENABLED)
+}
+
+// Times sequential and parallel evaluation of both parts on generated height maps
+fn bench_scaling() {
+    let mut rng = StdRng::seed_from_u64(10);
+    for width in [50, 200, 1000] {
+        let map = parse_input(&generate::height_map(&mut rng, width));
+        let part1 = [false, true]
+            .map(|parallel| time_micros(|| total_score::<FxHashSet<Position>>(&map, parallel)));
+        let part2 =
+            [false, true].map(|parallel| time_micros(|| total_score::<usize>(&map, parallel)));
+        for (part, [sequential, parallel]) in [(1, part1), (2, part2)] {
+            println!(
+                "{width}x{width} part {part}: {sequential}μs sequential, {parallel}μs parallel"
+            );
+        }
+    }
+}
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve_part_1(input).into(), |input| solve_part_2(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::has_flag("--bench-scaling") {
+        bench_scaling();
+        return Ok(());
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day10.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample/2024/day10-2.txt");
+    const SAMPLE_INPUT_3: &str = include_str!("../../sample/2024/day10-3.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(1, solve_part_1(SAMPLE_INPUT));
+        assert_eq!(36, solve_part_1(SAMPLE_INPUT_2));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(227, solve_part_2(SAMPLE_INPUT_3));
+        assert_eq!(81, solve_part_2(SAMPLE_INPUT_2));
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let mut rng = StdRng::seed_from_u64(10);
+        for width in [1, 7, 40] {
+            let map = parse_input(&generate::height_map(&mut rng, width));
+            for parallel in [false, true] {
+                assert_eq!(
+                    total_score::<FxHashSet<Position>>(&map, false),
+                    total_score::<FxHashSet<Position>>(&map, parallel)
+                );
+                assert_eq!(total_score::<usize>(&map, false), total_score::<usize>(&map, parallel));
+            }
+        }
+
+        let map = parse_input(SAMPLE_INPUT_2);
+        assert_eq!(36, total_score::<FxHashSet<Position>>(&map, true));
+        assert_eq!(81, total_score::<usize>(&map, true));
+    }
+}
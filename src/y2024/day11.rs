@@ -0,0 +1,104 @@
+//! Day 11: Plutonian Pebbles
+//!
+//! <https://adventofcode.com/2024/day/11>
+
+use crate::days::Solver;
+use crate::{Memo, math};
+use std::error::Error;
+
+// Stones never affect each other, so a stone's count after some number of blinks only depends on
+// its number, and the same numbers come up over and over
+fn stone_count(stone: u64, blinks: u32, memo: &mut Memo<(u64, u32), u64>) -> u64 {
+    if blinks == 0 {
+        return 1;
+    }
+
+    memo.get_or_compute((stone, blinks), |memo| {
+        if stone == 0 {
+            // All 0s become 1
+            stone_count(1, blinks - 1, memo)
+        } else if math::num_digits(stone) % 2 == 1 {
+            // Odd number of digits; multiply by 2024
+            stone_count(stone * 2024, blinks - 1, memo)
+        } else {
+            // Even number of digits; split into left half of digits and right half of digits
+            let (l, r) = math::split_digits(stone);
+            stone_count(l, blinks - 1, memo) + stone_count(r, blinks - 1, memo)
+        }
+    })
+}
+
+fn solve(input: &str, blinks: u32) -> u64 {
+    let stones = input.lines().next().unwrap().split(' ').map(|s| s.parse::<u64>().unwrap());
+
+    let mut memo = Memo::new();
+    let count = stones.map(|stone| stone_count(stone, blinks, &mut memo)).sum();
+    memo.record_stats();
+    count
+}
+
+const P1_BLINKS: u32 = 25;
+const P2_BLINKS: u32 = 75;
+
+// Simulates every stone individually, so only practical for few blinks
+#[cfg(feature = "reference")]
+fn solve_reference(input: &str, blinks: u32) -> u64 {
+    let mut stones: Vec<String> = input.split_whitespace().map(String::from).collect();
+    for _ in 0..blinks {
+        stones = stones
+            .into_iter()
+            .flat_map(|stone| {
+                if stone == "0" {
+                    vec!["1".into()]
+                } else if stone.len() % 2 == 0 {
+                    let (l, r) = stone.split_at(stone.len() / 2);
+                    let r = r.trim_start_matches('0');
+                    vec![l.into(), if r.is_empty() { "0".into() } else { r.into() }]
+                } else {
+                    vec![(stone.parse::<u64>().unwrap() * 2024).to_string()]
+                }
+            })
+            .collect();
+    }
+
+    stones.len() as u64
+}
+
+#[cfg(feature = "reference")]
+const REFERENCE_BLINKS: [u32; 2] = [10, 20];
+
+#[cfg(feature = "reference")]
+pub const DIFFERENTIAL: crate::differential::Differential = crate::differential::Differential {
+    optimized: Solver::Parts(
+        |input| solve(input, REFERENCE_BLINKS[0]).into(),
+        |input| solve(input, REFERENCE_BLINKS[1]).into(),
+    ),
+    reference: Solver::Parts(
+        |input| solve_reference(input, REFERENCE_BLINKS[0]).into(),
+        |input| solve_reference(input, REFERENCE_BLINKS[1]).into(),
+    ),
+    generate: crate::generate::stones,
+    size: 4,
+};
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve(input, P1_BLINKS).into(), |input| solve(input, P2_BLINKS).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run(|input| solve(input, P1_BLINKS), |input| solve(input, P2_BLINKS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "0 1 10 99 999";
+    const SAMPLE_INPUT_2: &str = "125 17";
+
+    #[test]
+    fn solution() {
+        assert_eq!(7, solve(SAMPLE_INPUT, 1));
+        assert_eq!(22, solve(SAMPLE_INPUT_2, 6));
+        assert_eq!(55312, solve(SAMPLE_INPUT_2, 25));
+    }
+}
@@ -0,0 +1,384 @@
+//! Day 12: Garden Groups
+//!
+//! <https://adventofcode.com/2024/day/12>
+//!
+//! Passing `--svg path` writes an SVG of every region's fence outline, colored by plant type.
+
+use crate::days::Solver;
+use crate::{ByteGrid, Direction, ParseError, Pos2, SolverOutput};
+use rustc_hash::FxHashMap;
+use std::cmp;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+
+fn solve_part_1(input: &str) -> Result<u32, ParseError> {
+    let map = ByteGrid::parse(input)?;
+    let (regions, region_to_area) = build_region_and_area_maps(&map);
+
+    let mut total = 0;
+    for i in 0..map.rows() {
+        for j in 0..map.cols() {
+            let area = *region_to_area.get(&regions[i][j]).unwrap();
+
+            for (di, dj) in [(-1, 0), (0, -1), (1, 0), (0, 1)] {
+                let ii = i as i32 + di;
+                let jj = j as i32 + dj;
+
+                if !(0..map.rows() as i32).contains(&ii)
+                    || !(0..map.cols() as i32).contains(&jj)
+                    || map[ii as usize][jj as usize] != map[i][j]
+                {
+                    total += area;
+                }
+            }
+        }
+    }
+
+    Ok(total)
+}
+
+fn build_region_and_area_maps(map: &ByteGrid<'_>) -> (Vec<Vec<u32>>, FxHashMap<u32, u32>) {
+    let mut regions = vec![vec![0; map.cols()]; map.rows()];
+
+    let mut current_region = 1;
+    for i in 0..map.rows() {
+        for j in 0..map.cols() {
+            if regions[i][j] == 0 {
+                floodfill(map, i, j, current_region, &mut regions);
+                current_region += 1;
+            }
+        }
+    }
+
+    let mut region_to_area: FxHashMap<u32, u32> = FxHashMap::default();
+    for row in &regions {
+        for &value in row {
+            *region_to_area.entry(value).or_default() += 1;
+        }
+    }
+
+    (regions, region_to_area)
+}
+
+fn floodfill(
+    map: &ByteGrid<'_>,
+    i: usize,
+    j: usize,
+    current_region: u32,
+    regions: &mut [Vec<u32>],
+) {
+    regions[i][j] = current_region;
+
+    for (di, dj) in [(-1, 0), (0, -1), (1, 0), (0, 1)] {
+        let ii = i as i32 + di;
+        let jj = j as i32 + dj;
+        if (0..map.rows() as i32).contains(&ii)
+            && (0..map.cols() as i32).contains(&jj)
+            && regions[ii as usize][jj as usize] == 0
+            && map[ii as usize][jj as usize] == map[i][j]
+        {
+            floodfill(map, ii as usize, jj as usize, current_region, regions);
+        }
+    }
+}
+
+fn solve_part_2(input: &str) -> Result<u32, ParseError> {
+    let map = ByteGrid::parse(input)?;
+    let (regions, region_to_area) = build_region_and_area_maps(&map);
+
+    let mut side_count: FxHashMap<u32, u32> = FxHashMap::default();
+
+    // Count vertical edges
+    for j in 0..map.cols() {
+        let first_col = j == 0;
+        let last_col = j == map.cols() - 1;
+
+        // Count edges to the left of this column
+        let mut i = 0;
+        while i < map.rows() {
+            let region = regions[i][j];
+
+            let mut ii = i;
+            while ii < map.rows()
+                && regions[ii][j] == region
+                && (first_col || regions[ii][j - 1] != region)
+            {
+                ii += 1;
+            }
+            if ii != i {
+                *side_count.entry(region).or_default() += 1;
+            }
+            i = cmp::max(ii, i + 1);
+        }
+
+        // Count edges to the right of this column
+        let mut i = 0;
+        while i < map.rows() {
+            let region = regions[i][j];
+
+            let mut ii = i;
+            while ii < map.rows()
+                && regions[ii][j] == region
+                && (last_col || regions[ii][j + 1] != region)
+            {
+                ii += 1;
+            }
+            if ii != i {
+                *side_count.entry(region).or_default() += 1;
+            }
+            i = cmp::max(ii, i + 1);
+        }
+    }
+
+    // Count horizontal edges
+    for i in 0..map.rows() {
+        let first_row = i == 0;
+        let last_row = i == map.rows() - 1;
+
+        // Count edges above this row
+        let mut j = 0;
+        while j < map.cols() {
+            let region = regions[i][j];
+
+            let mut jj = j;
+            while jj < map.cols()
+                && regions[i][jj] == region
+                && (first_row || regions[i - 1][jj] != region)
+            {
+                jj += 1;
+            }
+            if jj != j {
+                *side_count.entry(region).or_default() += 1;
+            }
+            j = cmp::max(jj, j + 1);
+        }
+
+        // Count edges below this row
+        let mut j = 0;
+        while j < map.cols() {
+            let region = regions[i][j];
+
+            let mut jj = j;
+            while jj < map.cols()
+                && regions[i][jj] == region
+                && (last_row || regions[i + 1][jj] != region)
+            {
+                jj += 1;
+            }
+            if jj != j {
+                *side_count.entry(region).or_default() += 1;
+            }
+            j = cmp::max(jj, j + 1);
+        }
+    }
+
+    let mut total = 0;
+    for (&region, &area) in &region_to_area {
+        let count = *side_count.get(&region).unwrap();
+        total += count * area;
+    }
+
+    Ok(total)
+}
+
+// Maximal straight run of fence along one side of a region. Endpoints are in corner coordinates,
+// where (x, y) is the top left corner of the cell in column x and row y, and `side` is the side of
+// the region's cells that the fence is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FenceSegment {
+    start: Pos2<usize>,
+    end: Pos2<usize>,
+    side: Direction,
+}
+
+impl FenceSegment {
+    fn len(&self) -> usize {
+        self.end.x - self.start.x + self.end.y - self.start.y
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RegionFences {
+    plant: u8,
+    area: u32,
+    segments: Vec<FenceSegment>,
+}
+
+impl RegionFences {
+    fn perimeter(&self) -> usize {
+        self.segments.iter().map(FenceSegment::len).sum()
+    }
+
+    fn sides(&self) -> usize {
+        self.segments.len()
+    }
+}
+
+fn has_fence(regions: &[Vec<u32>], i: usize, j: usize, side: Direction) -> bool {
+    let delta = side.delta();
+    let ii = i as i32 + delta.y;
+    let jj = j as i32 + delta.x;
+    !(0..regions.len() as i32).contains(&ii)
+        || !(0..regions[0].len() as i32).contains(&jj)
+        || regions[ii as usize][jj as usize] != regions[i][j]
+}
+
+// Finds each region's fence segments by merging consecutive unit edges on the same side of the same
+// region. Region IDs from build_region_and_area_maps start at 1, so region r is at index r - 1
+fn fence_segments(map: &ByteGrid<'_>) -> Vec<RegionFences> {
+    let (regions, region_to_area) = build_region_and_area_maps(map);
+    let rows = map.rows();
+    let cols = map.cols();
+
+    let mut fences: Vec<_> = (1..=region_to_area.len() as u32)
+        .map(|region| RegionFences { plant: 0, area: region_to_area[&region], segments: vec![] })
+        .collect();
+    for i in 0..rows {
+        for j in 0..cols {
+            fences[regions[i][j] as usize - 1].plant = map[i][j];
+        }
+    }
+
+    // Runs along rows for fences above and below cells, along columns for fences left and right
+    for side in Direction::ALL {
+        let horizontal = matches!(side, Direction::Up | Direction::Down);
+        let (outer_len, inner_len) = if horizontal { (rows, cols) } else { (cols, rows) };
+        let cell =
+            |outer: usize, inner: usize| if horizontal { (outer, inner) } else { (inner, outer) };
+
+        for outer in 0..outer_len {
+            let mut inner = 0;
+            while inner < inner_len {
+                let (i, j) = cell(outer, inner);
+                let region = regions[i][j];
+                let run_start = inner;
+                while inner < inner_len && {
+                    let (i, j) = cell(outer, inner);
+                    regions[i][j] == region && has_fence(&regions, i, j, side)
+                } {
+                    inner += 1;
+                }
+
+                if inner == run_start {
+                    inner += 1;
+                    continue;
+                }
+
+                let line = outer + usize::from(matches!(side, Direction::Down | Direction::Right));
+                let (start, end) = if horizontal {
+                    (Pos2 { x: run_start, y: line }, Pos2 { x: inner, y: line })
+                } else {
+                    (Pos2 { x: line, y: run_start }, Pos2 { x: line, y: inner })
+                };
+                fences[region as usize - 1].segments.push(FenceSegment { start, end, side });
+            }
+        }
+    }
+
+    fences
+}
+
+const SVG_CELL_SIZE: usize = 10;
+
+fn render_svg(map: &ByteGrid<'_>) -> String {
+    let width = map.cols() * SVG_CELL_SIZE;
+    let height = map.rows() * SVG_CELL_SIZE;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n"
+    );
+    for region in fence_segments(map) {
+        let hue = (u32::from(region.plant) * 47) % 360;
+        let mut path = String::new();
+        for segment in &region.segments {
+            let [x0, y0, x1, y1] = [segment.start.x, segment.start.y, segment.end.x, segment.end.y]
+                .map(|c| c * SVG_CELL_SIZE);
+            write!(path, "M{x0} {y0}H{x1}V{y1}").unwrap();
+        }
+        writeln!(
+            svg,
+            "<path d=\"{path}\" stroke=\"hsl({hue} 70% 40%)\" stroke-width=\"2\" fill=\"none\">\
+             <title>{}: area {}, perimeter {}, sides {}</title></path>",
+            char::from(region.plant),
+            region.area,
+            region.perimeter(),
+            region.sides()
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input).unwrap_solution(),
+    |input| solve_part_2(input).unwrap_solution(),
+);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(path) = crate::flag_value("--svg") {
+        fs::write(path, render_svg(&ByteGrid::parse(&crate::read_input()?)?))?;
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day12.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample/2024/day12-2.txt");
+    const SAMPLE_INPUT_3: &str = include_str!("../../sample/2024/day12-3.txt");
+    const SAMPLE_INPUT_4: &str = include_str!("../../sample/2024/day12-4.txt");
+    const SAMPLE_INPUT_5: &str = include_str!("../../sample/2024/day12-5.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(140), solve_part_1(SAMPLE_INPUT));
+        assert_eq!(Ok(772), solve_part_1(SAMPLE_INPUT_2));
+        assert_eq!(Ok(1930), solve_part_1(SAMPLE_INPUT_3));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(80), solve_part_2(SAMPLE_INPUT));
+        assert_eq!(Ok(436), solve_part_2(SAMPLE_INPUT_2));
+        assert_eq!(Ok(236), solve_part_2(SAMPLE_INPUT_4));
+        assert_eq!(Ok(368), solve_part_2(SAMPLE_INPUT_5));
+        assert_eq!(Ok(1206), solve_part_2(SAMPLE_INPUT_3));
+    }
+
+    #[test]
+    fn segments() {
+        let map = ByteGrid::parse(SAMPLE_INPUT).unwrap();
+        let fences = fence_segments(&map);
+        let c = fences.iter().find(|region| region.plant == b'C').unwrap();
+        assert_eq!(4, c.area);
+        assert_eq!(10, c.perimeter());
+        assert_eq!(8, c.sides());
+        assert!(c.segments.contains(&FenceSegment {
+            start: Pos2 { x: 2, y: 1 },
+            end: Pos2 { x: 2, y: 3 },
+            side: Direction::Left
+        }));
+
+        // Segments give both prices independently of the part 1 and part 2 scans
+        for input in [SAMPLE_INPUT, SAMPLE_INPUT_2, SAMPLE_INPUT_3, SAMPLE_INPUT_4, SAMPLE_INPUT_5]
+        {
+            let fences = fence_segments(&ByteGrid::parse(input).unwrap());
+            let price = |f: fn(&RegionFences) -> usize| -> u32 {
+                fences.iter().map(|region| region.area * f(region) as u32).sum()
+            };
+            assert_eq!(Ok(price(RegionFences::perimeter)), solve_part_1(input));
+            assert_eq!(Ok(price(RegionFences::sides)), solve_part_2(input));
+        }
+
+        let svg = render_svg(&map);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(5, svg.matches("<path").count());
+    }
+}
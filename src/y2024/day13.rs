@@ -0,0 +1,278 @@
+//! Day 13: Claw Contraption
+//!
+//! <https://adventofcode.com/2024/day/13>
+
+use crate::days::Solver;
+use crate::parse::{self, ParseError, blank_line_separated, labeled, unsigned};
+use crate::serialize::{Csv, DataFormat, Json};
+use crate::{Pos2, SolverOutput};
+use std::error::Error;
+use std::fs;
+use winnow::ascii::newline;
+use winnow::combinator::{opt, preceded, separated_pair, terminated};
+use winnow::prelude::*;
+
+type Position = Pos2<i64>;
+
+#[derive(Debug, Clone)]
+struct Machine {
+    a: Position,
+    b: Position,
+    prize: Position,
+}
+
+fn parse_coordinates(prefix: &'static str) -> impl FnMut(&mut &str) -> PResult<Position> {
+    move |input| {
+        let (x, y) = separated_pair(
+            preceded(('X', prefix), unsigned),
+            ", ",
+            preceded(('Y', prefix), unsigned),
+        )
+        .parse_next(input)?;
+
+        Ok(Position { x, y })
+    }
+}
+
+fn parse_machine(input: &mut &str) -> PResult<Machine> {
+    let a = terminated(labeled("Button A", parse_coordinates("+")), newline).parse_next(input)?;
+    let b = terminated(labeled("Button B", parse_coordinates("+")), newline).parse_next(input)?;
+    let prize = labeled("Prize", parse_coordinates("=")).parse_next(input)?;
+
+    Ok(Machine { a, b, prize })
+}
+
+// Rejects machines that solve_equation can't handle: a button that doesn't move along both axes, or
+// buttons that move in the same direction, which leaves infinitely many combinations to check
+fn parse_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    let machines =
+        parse::complete(terminated(blank_line_separated(parse_machine), opt(newline)), input)?;
+    for (i, Machine { a, b, .. }) in machines.iter().enumerate() {
+        // Each machine is 3 lines followed by a blank line
+        let row = 4 * i;
+        if [a.x, a.y, b.x, b.y].contains(&0) {
+            return Err(ParseError::at(row, 0, "Button offsets of 0 are not supported"));
+        }
+        if a.x * b.y == b.x * a.y {
+            return Err(ParseError::at(row, 0, "Buttons A and B move in the same direction"));
+        }
+    }
+    Ok(machines)
+}
+
+// 10 trillion
+const PART_2_ADJUSTMENT: i64 = 10_000_000_000_000;
+
+#[derive(Debug, Clone)]
+struct MachineResult {
+    machine: Machine,
+    // Prize location after the part 2 adjustment, if any
+    prize: Position,
+    // Number of A and B presses, if the prize is reachable
+    presses: Option<(i64, i64)>,
+}
+
+impl MachineResult {
+    fn tokens(&self) -> Option<i64> {
+        self.presses.map(|(a, b)| 3 * a + b)
+    }
+}
+
+fn machine_results(machines: &[Machine], part2: bool) -> Vec<MachineResult> {
+    machines
+        .iter()
+        .map(|machine| {
+            let prize = if part2 {
+                machine.prize + Position { x: PART_2_ADJUSTMENT, y: PART_2_ADJUSTMENT }
+            } else {
+                machine.prize
+            };
+
+            let presses = solve_equation(machine.a, machine.b, prize);
+            MachineResult { machine: machine.clone(), prize, presses }
+        })
+        .collect()
+}
+
+fn solve<const PART2: bool>(input: &str) -> Result<i64, ParseError> {
+    let machines = parse_input(input)?;
+    Ok(machine_results(&machines, PART2).iter().filter_map(MachineResult::tokens).sum())
+}
+
+fn point_json(p: Position) -> Json {
+    Json::object([("x", Json::from(p.x)), ("y", Json::from(p.y))])
+}
+
+fn result_json(result: &MachineResult) -> Json {
+    let solution = result.presses.map(|(a, b)| {
+        Json::object([
+            ("a_presses", Json::from(a)),
+            ("b_presses", Json::from(b)),
+            ("tokens", Json::from(result.tokens())),
+        ])
+    });
+    Json::object([("prize", point_json(result.prize)), ("solution", Json::from(solution))])
+}
+
+// One record per machine with the button vectors, the prize point, and the integer solution for
+// each part (null/empty when the prize is unreachable)
+fn export_machines(input: &str, format: DataFormat) -> Result<String, ParseError> {
+    let machines = parse_input(input)?;
+    let part1 = machine_results(&machines, false);
+    let part2 = machine_results(&machines, true);
+
+    match format {
+        DataFormat::Json => {
+            let records = machines.iter().zip(part1.iter().zip(&part2)).enumerate().map(
+                |(i, (machine, (part1, part2)))| {
+                    Json::object([
+                        ("machine", Json::from(i)),
+                        ("a", point_json(machine.a)),
+                        ("b", point_json(machine.b)),
+                        ("part1", result_json(part1)),
+                        ("part2", result_json(part2)),
+                    ])
+                },
+            );
+            Ok(format!("{}\n", Json::array(records)))
+        }
+        DataFormat::Csv => {
+            let mut csv = Csv::new(&[
+                "machine",
+                "ax",
+                "ay",
+                "bx",
+                "by",
+                "px",
+                "py",
+                "p1_a",
+                "p1_b",
+                "p1_tokens",
+                "p2_px",
+                "p2_py",
+                "p2_a",
+                "p2_b",
+                "p2_tokens",
+            ]);
+            for (i, (part1, part2)) in part1.iter().zip(&part2).enumerate() {
+                let machine = &part1.machine;
+                let mut row = vec![i as i64, machine.a.x, machine.a.y, machine.b.x, machine.b.y]
+                    .into_iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>();
+                for result in [part1, part2] {
+                    row.extend([result.prize.x.to_string(), result.prize.y.to_string()]);
+                    let (a, b) = result.presses.unzip();
+                    row.extend(
+                        [a, b, result.tokens()]
+                            .map(|n| n.map(|n| n.to_string()).unwrap_or_default()),
+                    );
+                }
+                csv.row(&row);
+            }
+            Ok(csv.finish())
+        }
+    }
+}
+
+// The problem can be represented as a system of 2 linear equations:
+//   A * ax + B * bx = px
+//   A * ay + B * by = py
+// Where A and B are unknown variables, and ax/ay/bx/by/px/py are constants (the inputs).
+//
+// If we solve both sides for A and then set both sides equal to each other, we get:
+//   (px - B * bx) / ax = (py - B * by) / ay
+//
+// Solving this for B, we ultimately get:
+//   B = (ax * py - ay * px) / (ax * by - bx * ay)
+//
+// From the original equations, we can also derive a formula for A from B:
+//   A = (px - B * bx) / ax
+//     OR
+//   A = (py - B * by) / ay
+// Either of these will produce the same result.
+//
+// Given the constraints of the problem, a solution is only valid if A and B are both integers, so
+// this function checks for that and will return None if either is not an integer.
+fn solve_equation(a: Position, b: Position, p: Position) -> Option<(i64, i64)> {
+    let b_numerator = a.x * p.y - a.y * p.x;
+    let b_denominator = a.x * b.y - b.x * a.y;
+
+    assert_ne!(
+        b_denominator, 0,
+        "unexpected input; equation has infinite solutions for a={a:?} b={b:?} p={p:?}"
+    );
+    if b_numerator % b_denominator != 0 {
+        // B is not an integer
+        return None;
+    }
+
+    let b_solution = b_numerator / b_denominator;
+    let a_numerator = p - b * b_solution;
+    if a_numerator.x % a.x != 0 || a_numerator.y % a.y != 0 {
+        // A is not an integer
+        return None;
+    }
+
+    let a_solution = a_numerator.x / a.x;
+    Some((a_solution, b_solution))
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve::<false>(input).unwrap_solution(),
+    |input| solve::<true>(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(path) = crate::flag_value("--export") {
+        fs::write(&path, export_machines(&crate::read_input()?, DataFormat::from_path(&path))?)?;
+    }
+
+    crate::run(solve::<false>, solve::<true>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day13.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(480), solve::<false>(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(875318608908), solve::<true>(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err =
+            solve::<false>(&SAMPLE_INPUT.replace("Prize: X=8400", "Prize: X=-8400")).unwrap_err();
+        assert_eq!("line 3, column 10: Unexpected character: '-'", err.to_string());
+
+        let err = solve::<false>(&SAMPLE_INPUT.replace("X+26, Y+66", "X+0, Y+66")).unwrap_err();
+        assert_eq!("line 5, column 1: Button offsets of 0 are not supported", err.to_string());
+
+        let parallel = "Button A: X+1, Y+2\nButton B: X+2, Y+4\nPrize: X=3, Y=6\n";
+        let err = solve::<true>(parallel).unwrap_err();
+        assert_eq!("line 1, column 1: Buttons A and B move in the same direction", err.to_string());
+    }
+
+    #[test]
+    fn export() {
+        let csv = export_machines(SAMPLE_INPUT, DataFormat::Csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(5, lines.len());
+        assert_eq!("0,94,34,22,67,8400,5400,80,40,280,10000000008400,10000000005400,,,", lines[1]);
+
+        let json = export_machines(SAMPLE_INPUT, DataFormat::Json).unwrap();
+        assert!(json.starts_with(r#"[{"machine":0,"a":{"x":94,"y":34},"b":{"x":22,"y":67},"#));
+        assert!(json.contains(r#""solution":{"a_presses":80,"b_presses":40,"tokens":280}"#));
+    }
+}
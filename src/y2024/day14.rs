@@ -0,0 +1,568 @@
+//! Day 14: Restroom Redoubt
+//!
+//! <https://adventofcode.com/2024/day/14>
+//!
+//! Passing `--viz` (with the `viz` feature) animates the robots one second at a time, and
+//! `--png path` (with the `image` feature) saves the part 2 tree layout as an image.
+//!
+//! Passing `--template path` instead finds the first time at which the robots occupy exactly the
+//! positions marked in a template picture, e.g. a saved copy of the part 2 output. The template uses
+//! the same format that part 2 draws with `--visualize`: any character other than a space or '.'
+//! marks a robot.
+
+use crate::days::Solver;
+use crate::image::{self, Image};
+use crate::parse::{self, ParseError, lines, signed};
+use crate::visualizer::{self, Visualizer};
+use crate::viz::{self, Frame};
+use crate::{Grid, Pos2, SolverOutput, math};
+use rustc_hash::FxHashSet;
+use std::error::Error;
+use std::fs;
+use winnow::ascii::newline;
+use winnow::combinator::{opt, preceded, separated_pair, terminated};
+use winnow::prelude::*;
+
+type Position = Pos2<i64>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Robot {
+    position: Position,
+    velocity: Position,
+}
+
+impl Robot {
+    fn position_at(&self, time: i64, width: i64, height: i64) -> Position {
+        Position {
+            x: (self.position.x + self.velocity.x * time).rem_euclid(width),
+            y: (self.position.y + self.velocity.y * time).rem_euclid(height),
+        }
+    }
+
+    fn clamp_position(&mut self, width: i64, height: i64) {
+        clamp_coordinate(&mut self.position.x, width);
+        clamp_coordinate(&mut self.position.y, height);
+    }
+}
+
+fn clamp_coordinate(coordinate: &mut i64, bound: i64) {
+    while *coordinate < 0 {
+        *coordinate += bound;
+    }
+
+    while *coordinate >= bound {
+        *coordinate -= bound;
+    }
+}
+
+fn parse_position(input: &mut &str) -> PResult<Position> {
+    let (x, y) = separated_pair(signed, ',', signed).parse_next(input)?;
+    Ok(Position { x, y })
+}
+
+fn parse_robot(input: &mut &str) -> PResult<Robot> {
+    let (position, velocity) =
+        separated_pair(preceded("p=", parse_position), ' ', preceded("v=", parse_position))
+            .parse_next(input)?;
+    Ok(Robot { position, velocity })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Robot>, ParseError> {
+    parse::complete(terminated(lines(parse_robot), opt(newline)), input)
+}
+
+const REAL_WIDTH: i64 = 101;
+const REAL_HEIGHT: i64 = 103;
+
+fn solve_part_1(input: &str, width: i64, height: i64) -> Result<i32, ParseError> {
+    let mut robots = parse_input(input)?;
+
+    for _ in 0..100 {
+        for robot in &mut robots {
+            robot.position += robot.velocity;
+            robot.clamp_position(width, height);
+        }
+    }
+
+    let mut quadrant_counts = [0; 4];
+    for robot in &robots {
+        if robot.position.x == width / 2 || robot.position.y == height / 2 {
+            continue;
+        }
+
+        let quadrant = 2 * usize::from(robot.position.x < width / 2)
+            + usize::from(robot.position.y < height / 2);
+        quadrant_counts[quadrant] += 1;
+    }
+
+    Ok(quadrant_counts.into_iter().product())
+}
+
+// Part 2 runs a list of tree detectors in order, cheapest first, and uses the first one that finds a
+// tree. The winning layout is sent to the visualizer for visual verification.
+//
+// Due to rules of modular arithmetic, the positions are guaranteed to loop after 101*103 seconds.
+//
+// At a time t, each robot's position can be defined as:
+//   x = (px + t * vx) mod 101
+//   y = (py + t * vy) mod 103
+// This means that the x positions will cycle every 101 seconds and the y positions will cycle
+// every 103 seconds, since ((d * n) mod d) is equal to 0 for any integer n.
+//
+// Then, the room layout as a whole is guaranteed to cycle every lcm(101, 103) seconds, when
+// both the x positions and the y positions are at the beginning of their cycle. 101 and 103
+// are both prime numbers, so lcm(101, 103) = 101 * 103 = 10403
+fn solve_part_2(input: &str) -> Result<i64, ParseError> {
+    solve_part_2_with(input, &mut *visualizer::current())
+}
+
+fn solve_part_2_with(input: &str, visualizer: &mut dyn Visualizer) -> Result<i64, ParseError> {
+    let robots = parse_input(input)?;
+
+    let (min_time, min_layout) = find_tree(&robots, &DETECTORS);
+    visualizer.message(&format!("Tree found after {min_time} seconds:"));
+    visualizer.frame(&layout_grid(&min_layout, REAL_WIDTH, REAL_HEIGHT));
+
+    Ok(min_time)
+}
+
+// Occupied tiles are solid blocks
+fn layout_grid(layout: &[Position], width: i64, height: i64) -> Grid<char> {
+    let mut grid = vec![vec![' '; width as usize]; height as usize];
+    for &Position { x, y } in layout {
+        grid[y as usize][x as usize] = '█';
+    }
+    Grid(grid)
+}
+
+const CYCLE_LEN: i64 = math::lcm(REAL_WIDTH, REAL_HEIGHT);
+
+// The tree picture is surrounded by a border that is 31 robots wide; most random layouts don't
+// have runs longer than a handful of robots
+const MIN_TREE_RUN: u32 = 20;
+
+trait TreeDetector {
+    // Returns the time and robot positions of the tree, or None if this detector didn't find it
+    fn find_tree(&self, robots: &[Robot]) -> Option<(i64, Vec<Position>)>;
+}
+
+const DETECTORS: [&dyn TreeDetector; 2] =
+    [&HorizontalRunDetector { min_run: MIN_TREE_RUN }, &VarianceDetector];
+
+fn find_tree(robots: &[Robot], detectors: &[&dyn TreeDetector]) -> (i64, Vec<Position>) {
+    detectors
+        .iter()
+        .find_map(|detector| detector.find_tree(robots))
+        .expect("No detector found a tree")
+}
+
+// Scans the full cycle and scores each layout by summing the distance squared of every robot from
+// the center of the room (assuming the final picture will be clustered near-ish the center of the
+// room). The layout with the min score is _probably_ the solution. Always returns a result
+struct VarianceDetector;
+
+impl TreeDetector for VarianceDetector {
+    fn find_tree(&self, robots: &[Robot]) -> Option<(i64, Vec<Position>)> {
+        let mut robots = robots.to_vec();
+
+        let mut min_score = score(&robots);
+        let mut min_layout = robot_positions(&robots);
+        let mut min_time = 0;
+
+        for second in 1..=CYCLE_LEN {
+            step_robots(&mut robots);
+
+            let second_score = score(&robots);
+            if second_score < min_score {
+                min_score = second_score;
+                min_layout = robot_positions(&robots);
+                min_time = second;
+            }
+        }
+
+        Some((min_time, min_layout))
+    }
+}
+
+// Looks for the first layout that contains a solid horizontal run of at least `min_run` robots
+// (the tree's border), stopping as soon as one is found. Each row is a 101-bit mask, and a run of
+// length N exists iff ANDing the mask with itself shifted by 1..N-1 leaves any bit set
+struct HorizontalRunDetector {
+    min_run: u32,
+}
+
+impl TreeDetector for HorizontalRunDetector {
+    fn find_tree(&self, robots: &[Robot]) -> Option<(i64, Vec<Position>)> {
+        let mut robots = robots.to_vec();
+
+        for second in 0..CYCLE_LEN {
+            if second != 0 {
+                step_robots(&mut robots);
+            }
+
+            let mut rows = [0_u128; REAL_HEIGHT as usize];
+            for robot in &robots {
+                rows[robot.position.y as usize] |= 1 << robot.position.x;
+            }
+
+            if rows.into_iter().any(|row| has_run(row, self.min_run)) {
+                return Some((second, robot_positions(&robots)));
+            }
+        }
+
+        None
+    }
+}
+
+fn has_run(mut row: u128, min_run: u32) -> bool {
+    for _ in 1..min_run {
+        if row == 0 {
+            return false;
+        }
+        row &= row >> 1;
+    }
+    row != 0
+}
+
+fn step_robots(robots: &mut [Robot]) {
+    for robot in robots {
+        robot.position += robot.velocity;
+        robot.clamp_position(REAL_WIDTH, REAL_HEIGHT);
+    }
+}
+
+fn robot_positions(robots: &[Robot]) -> Vec<Position> {
+    robots.iter().map(|robot| robot.position).collect()
+}
+
+fn score(robots: &[Robot]) -> i64 {
+    robots
+        .iter()
+        .map(|robot| {
+            let x_delta = (robot.position.x - REAL_WIDTH / 2).abs().pow(2);
+            let y_delta = (robot.position.y - REAL_HEIGHT / 2).abs().pow(2);
+            x_delta + y_delta
+        })
+        .sum()
+}
+
+// Template matching solves for the time directly instead of searching for a picture. Each robot
+// moves independently in x and y, so a robot is on cell c at time t iff
+//   vx * t = cx - px (mod width)
+//   vy * t = cy - py (mod height)
+// Each linear congruence has solutions t = r (mod n) (or none), and CRT combines the two into a
+// single residue class of arrival times modulo at most lcm(width, height).
+//
+// At an exact match every robot is on some template cell, so the first robot's arrival times over
+// all template cells are the only candidates; each candidate is then checked against every robot
+fn arrival_times(robot: &Robot, cell: Position, width: i64, height: i64) -> Option<(i64, i64)> {
+    let x = math::linear_congruence(robot.velocity.x, cell.x - robot.position.x, width)?;
+    let y = math::linear_congruence(robot.velocity.y, cell.y - robot.position.y, height)?;
+    math::crt(&[x, y])
+}
+
+// Returns the first time in one full cycle at which the set of robot positions is exactly
+// `template`, or None if the robots never form it
+fn find_template_time(
+    robots: &[Robot],
+    template: &[Position],
+    width: i64,
+    height: i64,
+) -> Option<i64> {
+    let template: FxHashSet<Position> = template.iter().copied().collect();
+    let Some(first) = robots.first() else {
+        return template.is_empty().then_some(0);
+    };
+
+    let cycle_len = math::lcm(width, height);
+    let mut candidates: Vec<i64> = template
+        .iter()
+        .filter_map(|&cell| arrival_times(first, cell, width, height))
+        .flat_map(|(time, period)| (time..cycle_len).step_by(period as usize))
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    candidates.into_iter().find(|&time| {
+        let positions: FxHashSet<Position> =
+            robots.iter().map(|robot| robot.position_at(time, width, height)).collect();
+        positions == template
+    })
+}
+
+fn parse_template(input: &str) -> Vec<Position> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c != ' ' && c != '.')
+                .map(move |(x, _)| Position { x: x as i64, y: y as i64 })
+        })
+        .collect()
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input, REAL_WIDTH, REAL_HEIGHT).unwrap_solution(),
+    |input| solve_part_2(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+// Robots advanced one second at a time, for visualization. Stops after one full cycle
+struct Room {
+    robots: Vec<Robot>,
+    time: i64,
+}
+
+impl Room {
+    fn step(&mut self) -> bool {
+        if self.time == CYCLE_LEN {
+            return false;
+        }
+
+        for robot in &mut self.robots {
+            robot.position += robot.velocity;
+            robot.clamp_position(REAL_WIDTH, REAL_HEIGHT);
+        }
+        self.time += 1;
+        true
+    }
+
+    fn render(&self) -> Frame {
+        let mut grid = vec![vec![' '; REAL_WIDTH as usize]; REAL_HEIGHT as usize];
+        for robot in &self.robots {
+            grid[robot.position.y as usize][robot.position.x as usize] = '█';
+        }
+
+        Frame {
+            lines: grid.into_iter().map(String::from_iter).collect(),
+            status: format!("t = {}", self.time),
+            focus: None,
+        }
+    }
+}
+
+const IMAGE_SCALE: usize = 4;
+
+fn render_layout(layout: &[Position]) -> Image {
+    let mut occupied = vec![vec![false; REAL_WIDTH as usize]; REAL_HEIGHT as usize];
+    for &Position { x, y } in layout {
+        occupied[y as usize][x as usize] = true;
+    }
+
+    Image::from_fn(REAL_WIDTH as usize, REAL_HEIGHT as usize, IMAGE_SCALE, |x, y| {
+        if occupied[y][x] { [40, 200, 60] } else { image::BLACK }
+    })
+}
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(path) = crate::flag_value("--png") {
+        let robots = parse_input(&crate::read_input()?)?;
+        let (time, layout) = find_tree(&robots, &DETECTORS);
+        image::save_png(&render_layout(&layout), path)?;
+        println!("{time}");
+        return Ok(());
+    }
+
+    if let Some(path) = crate::flag_value("--template") {
+        let robots = parse_input(&crate::read_input()?)?;
+        let template = parse_template(&fs::read_to_string(path)?);
+        match find_template_time(&robots, &template, REAL_WIDTH, REAL_HEIGHT) {
+            Some(time) => println!("{time}"),
+            None => println!("No time matches the template"),
+        }
+        return Ok(());
+    }
+
+    if crate::terminal_viz()? {
+        let robots = parse_input(&crate::read_input()?)?;
+        return viz::run(
+            "Day 14: Restroom Redoubt",
+            Room { robots, time: 0 },
+            Room::step,
+            Room::render,
+        );
+    }
+
+    crate::run(|input| solve_part_1(input, REAL_WIDTH, REAL_HEIGHT), solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycle;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day14.txt");
+
+    #[test]
+    fn part_1() {
+        const TEST_WIDTH: i64 = 11;
+        const TEST_HEIGHT: i64 = 7;
+
+        assert_eq!(Ok(12), solve_part_1(SAMPLE_INPUT, TEST_WIDTH, TEST_HEIGHT));
+    }
+
+    #[test]
+    fn render() {
+        let robots = parse_input(SAMPLE_INPUT).unwrap();
+        let layout: Vec<_> = robots.iter().map(|robot| robot.position_at(100, 11, 7)).collect();
+        // The layout after 100 seconds from the puzzle description
+        let expected = "......#..#.\n...........\n#..........\n.##........\n.....#.....\n\
+                        ...##......\n.#....#....\n\n";
+        let render = |layout: &[Position]| visualizer::render(&layout_grid(layout, 11, 7));
+        assert_eq!(expected.replace('.', " ").replace('#', "█"), render(&layout));
+
+        let rendered: Vec<_> = [0, 1, 2, 100]
+            .into_iter()
+            .map(|t| {
+                let layout: Vec<_> =
+                    robots.iter().map(|robot| robot.position_at(t, 11, 7)).collect();
+                render(&layout)
+            })
+            .collect();
+        crate::snapshot::assert_snapshot("day14_sample", &rendered.join("---\n"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve_part_2("p=0,4 v=3,-3\np=6,3 v=-1\n").unwrap_err();
+        assert_eq!("line 2, column 1: Unexpected line: 'p=6,3 v=-1'", err.to_string());
+    }
+
+    #[test]
+    fn room_steps() {
+        let robot = Robot { position: Position { x: 2, y: 4 }, velocity: Position { x: 2, y: -3 } };
+        let mut room = Room { robots: vec![robot], time: 0 };
+        assert!(room.step());
+        assert!(room.step());
+
+        let frame = room.render();
+        assert_eq!("t = 2", frame.status);
+        assert_eq!(Some(6), frame.lines[101].chars().position(|c| c == '█'));
+
+        room.time = CYCLE_LEN;
+        assert!(!room.step());
+    }
+
+    // The layout is periodic from time 0 with period lcm(width, height), as argued above part 2
+    #[test]
+    fn layout_cycle() {
+        let robots = parse_input(SAMPLE_INPUT).unwrap();
+        let step = |robots: &Vec<Robot>| -> Vec<Robot> {
+            robots
+                .iter()
+                .map(|robot| Robot { position: robot.position_at(1, 11, 7), ..robot.clone() })
+                .collect()
+        };
+        assert_eq!((0, 77), cycle::find_cycle(robots.clone(), step));
+        assert_eq!(
+            Some((77, robots.clone())),
+            cycle::first_repeat(robots.clone(), |r| Some(step(r)))
+        );
+
+        let after_100 = cycle::nth(robots.clone(), 100, step);
+        let expected: Vec<_> = robots.iter().map(|robot| robot.position_at(100, 11, 7)).collect();
+        assert_eq!(expected, after_100.iter().map(|robot| robot.position).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn layout_image() {
+        let image = render_layout(&[Position { x: 1, y: 2 }]);
+        assert_eq!((404, 412), (image.width(), image.height()));
+        assert_eq!([40, 200, 60], image.pixel(7, 11));
+        assert_eq!(image::BLACK, image.pixel(8, 11));
+    }
+
+    // Robots that line up into a 25-wide horizontal run at `time`, plus a clump of robots near the
+    // center of the room at time 0 so that the variance scan picks a different time
+    fn tree_robots(time: i64) -> Vec<Robot> {
+        let line = (0..25).map(|i| {
+            let velocity = Position { x: (7 * i + 3) % 50 - 25, y: (11 * i + 5) % 50 - 25 };
+            let target = Position { x: 30 + i, y: 60 };
+            let mut robot = Robot { position: target - velocity * time, velocity };
+            robot.clamp_position(REAL_WIDTH, REAL_HEIGHT);
+            robot
+        });
+        let clump = (0..100).map(|i| Robot {
+            position: Position { x: 45 + i % 10, y: 46 + i / 10 },
+            velocity: Position { x: i % 13 - 6, y: i % 7 - 3 },
+        });
+        line.chain(clump).collect()
+    }
+
+    #[test]
+    fn horizontal_run() {
+        assert!(has_run(0b111_0111, 3));
+        assert!(!has_run(0b110_0111, 4));
+        assert!(has_run(u128::MAX >> 27, 101));
+
+        let robots = tree_robots(4321);
+        let detector = HorizontalRunDetector { min_run: MIN_TREE_RUN };
+        let (time, layout) = detector.find_tree(&robots).unwrap();
+        assert_eq!(4321, time);
+        assert!((30..55).all(|x| layout.contains(&Position { x, y: 60 })));
+
+        let always_fails = HorizontalRunDetector { min_run: 101 };
+        assert_eq!(None, always_fails.find_tree(&robots));
+    }
+
+    #[test]
+    fn template_matching() {
+        let robots = tree_robots(4321);
+        let mut layout: Vec<_> =
+            robots.iter().map(|robot| robot.position_at(4321, REAL_WIDTH, REAL_HEIGHT)).collect();
+        assert_eq!(Some(4321), find_template_time(&robots, &layout, REAL_WIDTH, REAL_HEIGHT));
+
+        // Round trip through the picture format that part 2 prints
+        let mut picture = vec![vec![' '; REAL_WIDTH as usize]; REAL_HEIGHT as usize];
+        for &Position { x, y } in &layout {
+            picture[y as usize][x as usize] = '█';
+        }
+        let picture: String =
+            picture.into_iter().map(|row| String::from_iter(row) + "\n").collect();
+        let template = parse_template(&picture);
+        assert_eq!(Some(4321), find_template_time(&robots, &template, REAL_WIDTH, REAL_HEIGHT));
+
+        // Every robot fits but one template cell is never covered
+        layout.push(Position { x: 0, y: 0 });
+        assert_eq!(None, find_template_time(&robots, &layout, REAL_WIDTH, REAL_HEIGHT));
+
+        let robots = parse_input(SAMPLE_INPUT).unwrap();
+        let layout: Vec<_> = robots.iter().map(|robot| robot.position_at(100, 11, 7)).collect();
+        assert_eq!(Some(100 % 77), find_template_time(&robots, &layout, 11, 7));
+        assert_eq!(Some(0), find_template_time(&[], &[], 11, 7));
+    }
+
+    #[test]
+    fn visualizes_tree() {
+        let input: String = tree_robots(4321)
+            .iter()
+            .map(|Robot { position: p, velocity: v }| {
+                format!("p={},{} v={},{}\n", p.x, p.y, v.x, v.y)
+            })
+            .collect();
+        let mut recorder = visualizer::Recorder::default();
+        assert_eq!(Ok(4321), solve_part_2_with(&input, &mut recorder));
+        assert_eq!(vec!["Tree found after 4321 seconds:"], recorder.messages);
+        assert_eq!(1, recorder.frames.len());
+        assert_eq!(REAL_HEIGHT as usize + 1, recorder.frames[0].lines().count());
+        assert!(recorder.frames[0].contains(&"█".repeat(25)), "{}", recorder.frames[0]);
+    }
+
+    #[test]
+    fn detector_fallback() {
+        let robots = tree_robots(4321);
+
+        let run_first: [&dyn TreeDetector; 2] =
+            [&HorizontalRunDetector { min_run: MIN_TREE_RUN }, &VarianceDetector];
+        assert_eq!(4321, find_tree(&robots, &run_first).0);
+
+        let fallback: [&dyn TreeDetector; 2] =
+            [&HorizontalRunDetector { min_run: 101 }, &VarianceDetector];
+        assert_eq!(VarianceDetector.find_tree(&robots).unwrap(), find_tree(&robots, &fallback));
+        assert_ne!(4321, find_tree(&robots, &fallback).0);
+    }
+}
@@ -0,0 +1,797 @@
+//! Day 15: Warehouse Woes
+//!
+//! <https://adventofcode.com/2024/day/15>
+//!
+//! Passing `--gif path` (with the `image` feature) saves an animation of the robot's moves, on the
+//! widened part 2 warehouse if `--wide` is also passed. `--viz` (with the `viz` feature) replays the
+//! moves in the terminal one at a time, also on the widened warehouse with `--wide`. `--visualize`
+//! replays both parts' moves in color on stderr while solving.
+
+use crate::ansi::{self, Color};
+use crate::days::Solver;
+use crate::image::{self, Image, Rgb};
+use crate::viz::{self, Frame};
+use crate::{Direction, ParseError, Pos2, SolverOutput, parse};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::iter;
+
+type Position = Pos2<i32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Empty,
+    Wall,
+    Box,
+}
+
+#[derive(Debug)]
+struct Input {
+    map: Vec<Vec<Space>>,
+    robot_start: Position,
+    moves: Vec<Direction>,
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let mut lines = input.lines().enumerate();
+
+    let (map, robot_start) = parse_map(&mut lines)?;
+    let moves = parse::arrows(lines)?;
+
+    Ok(Input { map, robot_start, moves })
+}
+
+// Lines are paired with their 0-based row in the whole input, for error locations
+fn parse_map<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<(Vec<Vec<Space>>, Position), ParseError> {
+    let markers = [('@', "No robot location in map input")];
+    let (map, [robot_start]) = parse::maze(lines, markers, Space::Empty, |c| match c {
+        '.' => Some(Space::Empty),
+        '#' => Some(Space::Wall),
+        'O' => Some(Space::Box),
+        _ => None,
+    })?;
+    Ok((map.0, robot_start))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Change<T> {
+    pos: Position,
+    old: T,
+    new: T,
+}
+
+// Warehouse map that can optionally record every write as a reversible change, so that moves can be
+// undone and redone without snapshotting the whole map
+#[derive(Debug, Clone)]
+struct Map<T> {
+    grid: Vec<Vec<T>>,
+    changes: Option<Vec<Change<T>>>,
+}
+
+impl<T: Copy> Map<T> {
+    fn new(grid: Vec<Vec<T>>) -> Self {
+        Self { grid, changes: None }
+    }
+
+    fn recording(grid: Vec<Vec<T>>) -> Self {
+        Self { grid, changes: Some(Vec::new()) }
+    }
+
+    fn get(&self, pos: Position) -> T {
+        self.grid[pos.y as usize][pos.x as usize]
+    }
+
+    fn set(&mut self, pos: Position, value: T) {
+        let space = &mut self.grid[pos.y as usize][pos.x as usize];
+        if let Some(changes) = &mut self.changes {
+            changes.push(Change { pos, old: *space, new: value });
+        }
+        *space = value;
+    }
+
+    // Returns the changes recorded since the last call
+    fn take_changes(&mut self) -> Vec<Change<T>> {
+        self.changes.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn revert(&mut self, changes: &[Change<T>]) {
+        for change in changes.iter().rev() {
+            self.grid[change.pos.y as usize][change.pos.x as usize] = change.old;
+        }
+    }
+
+    fn reapply(&mut self, changes: &[Change<T>]) {
+        for change in changes {
+            self.grid[change.pos.y as usize][change.pos.x as usize] = change.new;
+        }
+    }
+}
+
+fn solve_part_1(input: &str) -> Result<usize, ParseError> {
+    let Input { map, robot_start, moves } = parse_input(input)?;
+    if crate::visualizing() {
+        animate_moves(map.clone(), robot_start, &moves, step_part_1, space_char, space_color);
+    }
+
+    let mut map = Map::new(map);
+    let mut robot_pos = robot_start;
+    for &direction in &moves {
+        robot_pos = step_part_1(&mut map, robot_pos, direction);
+    }
+
+    Ok(score_map(&map, Space::Box))
+}
+
+// Moves the robot one step, pushing boxes if possible, and returns its new position
+fn step_part_1(map: &mut Map<Space>, robot_pos: Position, direction: Direction) -> Position {
+    let delta = direction.delta();
+    let new_pos = robot_pos + delta;
+    match map.get(new_pos) {
+        Space::Empty => new_pos,
+        Space::Wall => robot_pos,
+        Space::Box => {
+            if try_push_boxes(map, new_pos, delta, Space::Empty, |space| space == Space::Box) {
+                new_pos
+            } else {
+                robot_pos
+            }
+        }
+    }
+}
+
+fn try_push_boxes<T: Copy + Eq>(
+    map: &mut Map<T>,
+    pos: Position,
+    delta: Position,
+    empty: T,
+    is_box: impl Fn(T) -> bool,
+) -> bool {
+    // Advance until end_pos hits an empty space or a wall
+    let mut end_pos = pos;
+    while is_box(map.get(end_pos)) {
+        end_pos += delta;
+    }
+
+    if map.get(end_pos) != empty {
+        // Hit a wall; can't move
+        return false;
+    }
+
+    // Shift all boxes over and fill the last space with empty
+    while end_pos != pos {
+        map.set(end_pos, map.get(end_pos - delta));
+        end_pos -= delta;
+    }
+    map.set(pos, empty);
+
+    true
+}
+
+fn score_map<T: Copy + Eq>(map: &Map<T>, target: T) -> usize {
+    map.grid
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, &space)| if space == target { 100 * y + x } else { 0 })
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BoxSide {
+    Left,
+    Right,
+}
+
+impl BoxSide {
+    fn other(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    fn other_x_adjust(self) -> i32 {
+        match self {
+            Self::Left => 1,
+            Self::Right => -1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space2 {
+    Empty,
+    Wall,
+    Box(BoxSide),
+}
+
+impl Space2 {
+    fn is_box(self) -> bool {
+        matches!(self, Self::Box(_))
+    }
+}
+
+fn solve_part_2(input: &str) -> Result<usize, ParseError> {
+    let Input { map, robot_start, moves } = parse_input(input)?;
+
+    let map = expand_map(&map);
+    let robot_start = Position { x: 2 * robot_start.x, y: robot_start.y };
+    if crate::visualizing() {
+        animate_moves(map.clone(), robot_start, &moves, step_part_2, space2_char, space2_color);
+    }
+
+    let mut map = Map::new(map);
+    let mut robot_pos = robot_start;
+    for &direction in &moves {
+        robot_pos = step_part_2(&mut map, robot_pos, direction);
+    }
+
+    Ok(score_map(&map, Space2::Box(BoxSide::Left)))
+}
+
+fn step_part_2(map: &mut Map<Space2>, robot_pos: Position, direction: Direction) -> Position {
+    let delta = direction.delta();
+    let new_pos = robot_pos + delta;
+
+    match map.get(new_pos) {
+        Space2::Empty => new_pos,
+        Space2::Wall => robot_pos,
+        Space2::Box(_) => {
+            let moved = match direction {
+                Direction::Left | Direction::Right => {
+                    // Horizontal push; easy case, basically the same as part 1
+                    try_push_boxes(map, new_pos, delta, Space2::Empty, Space2::is_box)
+                }
+                Direction::Up | Direction::Down => {
+                    // Vertical push; trickier case
+                    let can_move = can_move(map, new_pos, delta);
+                    if can_move {
+                        do_move(map, new_pos, delta, Space2::Empty);
+                    }
+                    can_move
+                }
+            };
+            if moved { new_pos } else { robot_pos }
+        }
+    }
+}
+
+fn expand_map(map: &[Vec<Space>]) -> Vec<Vec<Space2>> {
+    map.iter()
+        .map(|row| {
+            row.iter()
+                .flat_map(|&space| match space {
+                    Space::Empty => [Space2::Empty; 2],
+                    Space::Wall => [Space2::Wall; 2],
+                    Space::Box => [Space2::Box(BoxSide::Left), Space2::Box(BoxSide::Right)],
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn can_move(map: &Map<Space2>, pos: Position, delta: Position) -> bool {
+    let space = map.get(pos);
+    match space {
+        Space2::Empty => true,
+        Space2::Wall => false,
+        Space2::Box(side) => {
+            let x_adjustment = side.other_x_adjust();
+            can_move(map, pos + delta, delta)
+                && can_move(map, pos + Position { x: x_adjustment, y: delta.y }, delta)
+        }
+    }
+}
+
+fn do_move(map: &mut Map<Space2>, pos: Position, delta: Position, new_space: Space2) {
+    let space = map.get(pos);
+    match space {
+        Space2::Empty => {}
+        Space2::Box(side) => {
+            // Push this half of the box up/down
+            do_move(map, pos + delta, delta, space);
+
+            // Push the other half of the box up/down
+            let x_adjustment = side.other_x_adjust();
+            do_move(
+                map,
+                pos + Position { x: x_adjustment, y: delta.y },
+                delta,
+                Space2::Box(side.other()),
+            );
+
+            // Mark empty the space occupied by the other half of the box
+            map.set(Position { x: pos.x + x_adjustment, y: pos.y }, Space2::Empty);
+        }
+        Space2::Wall => panic!("Attempted to move a box into a wall at {pos:?}"),
+    }
+
+    map.set(pos, new_space);
+}
+
+// One robot move and every map write it caused, enough to undo or redo it
+#[derive(Debug, Clone)]
+struct MoveRecord<T> {
+    robot_from: Position,
+    robot_to: Position,
+    changes: Vec<Change<T>>,
+}
+
+// Interactive exploration state, with an undo stack of moves and a redo stack of undone moves.
+// Making a new move clears the redo stack
+struct Session<T> {
+    map: Map<T>,
+    robot_pos: Position,
+    step: fn(&mut Map<T>, Position, Direction) -> Position,
+    to_char: fn(T) -> char,
+    undo_stack: Vec<MoveRecord<T>>,
+    redo_stack: Vec<MoveRecord<T>>,
+}
+
+impl<T: Copy> Session<T> {
+    fn new(
+        grid: Vec<Vec<T>>,
+        robot_pos: Position,
+        step: fn(&mut Map<T>, Position, Direction) -> Position,
+        to_char: fn(T) -> char,
+    ) -> Self {
+        Self {
+            map: Map::recording(grid),
+            robot_pos,
+            step,
+            to_char,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    // Returns whether the robot moved; blocked moves are not recorded
+    fn apply(&mut self, direction: Direction) -> bool {
+        let robot_from = self.robot_pos;
+        self.robot_pos = (self.step)(&mut self.map, robot_from, direction);
+        let changes = self.map.take_changes();
+        if self.robot_pos == robot_from {
+            return false;
+        }
+
+        self.undo_stack.push(MoveRecord { robot_from, robot_to: self.robot_pos, changes });
+        self.redo_stack.clear();
+        true
+    }
+
+    fn undo(&mut self) -> bool {
+        let Some(record) = self.undo_stack.pop() else { return false };
+        self.map.revert(&record.changes);
+        self.robot_pos = record.robot_from;
+        self.redo_stack.push(record);
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some(record) = self.redo_stack.pop() else { return false };
+        self.map.reapply(&record.changes);
+        self.robot_pos = record.robot_to;
+        self.undo_stack.push(record);
+        true
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (y, row) in self.map.grid.iter().enumerate() {
+            for (x, &space) in row.iter().enumerate() {
+                let pos = Position { x: x as i32, y: y as i32 };
+                out.push(if pos == self.robot_pos { '@' } else { (self.to_char)(space) });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// The input's moves replayed one per step, for visualization
+struct Replay<T> {
+    map: Map<T>,
+    robot_pos: Position,
+    moves: Vec<Direction>,
+    next_move: usize,
+    step: fn(&mut Map<T>, Position, Direction) -> Position,
+    to_char: fn(T) -> char,
+    box_space: T,
+}
+
+impl<T: Copy + Eq> Replay<T> {
+    fn new(
+        grid: Vec<Vec<T>>,
+        robot_start: Position,
+        moves: Vec<Direction>,
+        step: fn(&mut Map<T>, Position, Direction) -> Position,
+        to_char: fn(T) -> char,
+        box_space: T,
+    ) -> Self {
+        Self {
+            map: Map::new(grid),
+            robot_pos: robot_start,
+            moves,
+            next_move: 0,
+            step,
+            to_char,
+            box_space,
+        }
+    }
+
+    // Returns false once every move has been made
+    fn step(&mut self) -> bool {
+        let Some(&direction) = self.moves.get(self.next_move) else { return false };
+        self.robot_pos = (self.step)(&mut self.map, self.robot_pos, direction);
+        self.next_move += 1;
+        true
+    }
+
+    fn render(&self) -> Frame {
+        let lines = self
+            .map
+            .grid
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, &space)| {
+                        let pos = Position { x: x as i32, y: y as i32 };
+                        if pos == self.robot_pos { '@' } else { (self.to_char)(space) }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Frame {
+            lines,
+            status: format!(
+                "move {} of {}, GPS sum {}",
+                self.next_move,
+                self.moves.len(),
+                score_map(&self.map, self.box_space)
+            ),
+            focus: Some((self.robot_pos.x as usize, self.robot_pos.y as usize)),
+        }
+    }
+}
+
+fn space_char(space: Space) -> char {
+    match space {
+        Space::Empty => '.',
+        Space::Wall => '#',
+        Space::Box => 'O',
+    }
+}
+
+fn space2_char(space: Space2) -> char {
+    match space {
+        Space2::Empty => '.',
+        Space2::Wall => '#',
+        Space2::Box(BoxSide::Left) => '[',
+        Space2::Box(BoxSide::Right) => ']',
+    }
+}
+
+const GIF_SCALE: usize = 4;
+const GIF_DELAY_CENTIS: u16 = 2;
+const ROBOT_COLOR: Rgb = [220, 40, 40];
+
+fn space_color(space: Space) -> Rgb {
+    match space {
+        Space::Empty => image::BLACK,
+        Space::Wall => [110, 110, 110],
+        Space::Box => [200, 150, 60],
+    }
+}
+
+fn space2_color(space: Space2) -> Rgb {
+    match space {
+        Space2::Empty => image::BLACK,
+        Space2::Wall => [110, 110, 110],
+        Space2::Box(BoxSide::Left) => [200, 150, 60],
+        Space2::Box(BoxSide::Right) => [170, 120, 40],
+    }
+}
+
+fn render_frame<T: Copy>(map: &Map<T>, robot_pos: Position, color: fn(T) -> Rgb) -> Image {
+    Image::from_fn(map.grid[0].len(), map.grid.len(), GIF_SCALE, |x, y| {
+        if (x as i32, y as i32) == (robot_pos.x, robot_pos.y) {
+            ROBOT_COLOR
+        } else {
+            color(map.grid[y][x])
+        }
+    })
+}
+
+// One frame for the starting state and one for every move where the robot actually moved. Frames
+// are rendered lazily so that long move lists don't hold every frame in memory
+fn animation_frames<T: Copy>(
+    grid: Vec<Vec<T>>,
+    robot_start: Position,
+    moves: Vec<Direction>,
+    step: fn(&mut Map<T>, Position, Direction) -> Position,
+    color: fn(T) -> Rgb,
+) -> impl Iterator<Item = Image> {
+    let mut map = Map::new(grid);
+    let mut robot_pos = robot_start;
+    let first = render_frame(&map, robot_pos, color);
+
+    iter::once(first).chain(moves.into_iter().filter_map(move |direction| {
+        let new_pos = step(&mut map, robot_pos, direction);
+        if new_pos == robot_pos {
+            return None;
+        }
+        robot_pos = new_pos;
+        Some(render_frame(&map, robot_pos, color))
+    }))
+}
+
+// Roughly how many frames `--visualize` draws, however long the move list is
+const ANIMATION_FRAMES: usize = 300;
+
+// Replays the moves on stderr, in the same colors as the GIF. Empty space is drawn in the
+// terminal's default color instead of black
+fn animate_moves<T: Copy>(
+    grid: Vec<Vec<T>>,
+    robot_start: Position,
+    moves: &[Direction],
+    step: fn(&mut Map<T>, Position, Direction) -> Position,
+    to_char: fn(T) -> char,
+    color: fn(T) -> Rgb,
+) {
+    let glyph = |space: T| match color(space) {
+        image::BLACK => (to_char(space), Color::Default),
+        rgb => (to_char(space), Color::Rgb(rgb)),
+    };
+    let render = |&(ref map, robot_pos, i): &(Map<T>, Position, usize)| {
+        let rows = ansi::render_fn(map.grid[0].len(), map.grid.len(), |x, y| {
+            if (x as i32, y as i32) == (robot_pos.x, robot_pos.y) {
+                ('@', Color::Rgb(ROBOT_COLOR))
+            } else {
+                glyph(map.grid[y][x])
+            }
+        });
+        format!("{rows}Move {i} of {}\n", moves.len())
+    };
+
+    ansi::animate(
+        (Map::new(grid), robot_start, 0),
+        |(map, robot_pos, i)| {
+            let Some(&direction) = moves.get(*i) else { return false };
+            *robot_pos = step(map, *robot_pos, direction);
+            *i += 1;
+            true
+        },
+        render,
+        (moves.len() / ANIMATION_FRAMES) as u64,
+    );
+}
+
+const INTERACTIVE_HELP: &str =
+    "Moves: ^<>v or wasd, u: undo, r: redo, q: quit. Several commands can be entered per line";
+
+// Reads commands from stdin a line at a time and prints the map after each line. Moves in the input
+// file are ignored; only the map is used
+fn run_interactive<T: Copy + Eq>(mut session: Session<T>, box_space: T) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    writeln!(stdout, "{INTERACTIVE_HELP}")?;
+    write!(stdout, "{}", session.render())?;
+
+    for line in io::stdin().lock().lines() {
+        for c in line?.chars() {
+            let direction = match c {
+                'w' => Direction::Up,
+                'a' => Direction::Left,
+                'd' => Direction::Right,
+                's' => Direction::Down,
+                'u' => {
+                    if !session.undo() {
+                        writeln!(stdout, "Nothing to undo")?;
+                    }
+                    continue;
+                }
+                'r' => {
+                    if !session.redo() {
+                        writeln!(stdout, "Nothing to redo")?;
+                    }
+                    continue;
+                }
+                'q' => return Ok(()),
+                c if c.is_whitespace() => continue,
+                _ => {
+                    let Some(direction) = Direction::from_arrow(c) else {
+                        writeln!(stdout, "Unknown command '{c}'. {INTERACTIVE_HELP}")?;
+                        continue;
+                    };
+                    direction
+                }
+            };
+            session.apply(direction);
+        }
+
+        write!(stdout, "{}", session.render())?;
+        writeln!(
+            stdout,
+            "GPS sum: {}, undoable: {}, redoable: {}",
+            score_map(&session.map, box_space),
+            session.undo_stack.len(),
+            session.redo_stack.len()
+        )?;
+    }
+
+    Ok(())
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input).unwrap_solution(),
+    |input| solve_part_2(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(path) = crate::flag_value("--gif") {
+        let Input { map, robot_start, moves } = parse_input(&crate::read_input()?)?;
+        if crate::has_flag("--wide") {
+            let robot_start = Position { x: 2 * robot_start.x, y: robot_start.y };
+            let frames =
+                animation_frames(expand_map(&map), robot_start, moves, step_part_2, space2_color);
+            image::save_gif(path, GIF_DELAY_CENTIS, frames)?;
+        } else {
+            let frames = animation_frames(map, robot_start, moves, step_part_1, space_color);
+            image::save_gif(path, GIF_DELAY_CENTIS, frames)?;
+        }
+        return Ok(());
+    }
+
+    if crate::terminal_viz()? {
+        let Input { map, robot_start, moves } = parse_input(&crate::read_input()?)?;
+        let title = "Day 15: Warehouse Woes";
+        if crate::has_flag("--wide") {
+            let robot_start = Position { x: 2 * robot_start.x, y: robot_start.y };
+            let replay = Replay::new(
+                expand_map(&map),
+                robot_start,
+                moves,
+                step_part_2,
+                space2_char,
+                Space2::Box(BoxSide::Left),
+            );
+            return viz::run(title, replay, Replay::step, Replay::render);
+        }
+
+        let replay = Replay::new(map, robot_start, moves, step_part_1, space_char, Space::Box);
+        return viz::run(title, replay, Replay::step, Replay::render);
+    }
+
+    if crate::has_flag("--interactive") {
+        let Input { map, robot_start, .. } = parse_input(&crate::read_input()?)?;
+        if crate::has_flag("--wide") {
+            let robot_start = Position { x: 2 * robot_start.x, y: robot_start.y };
+            let session = Session::new(expand_map(&map), robot_start, step_part_2, space2_char);
+            run_interactive(session, Space2::Box(BoxSide::Left))?;
+        } else {
+            let session = Session::new(map, robot_start, step_part_1, space_char);
+            run_interactive(session, Space::Box)?;
+        }
+        return Ok(());
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day15.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample/2024/day15-2.txt");
+    const SAMPLE_INPUT_3: &str = include_str!("../../sample/2024/day15-3.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(2028), solve_part_1(SAMPLE_INPUT_2));
+        assert_eq!(Ok(10092), solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(618), solve_part_2(SAMPLE_INPUT_3));
+        assert_eq!(Ok(9021), solve_part_2(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse_input("####\n#@x#\n####\n\n<>").unwrap_err();
+        assert_eq!("line 2, column 3: Unexpected map character: 'x'", err.to_string());
+
+        let err = parse_input("####\n#@.#\n####\n\n<>\n^^a").unwrap_err();
+        assert_eq!("line 6, column 3: Unexpected direction character: 'a'", err.to_string());
+
+        let err = parse_input("####\n#..#\n####\n\n<>").unwrap_err();
+        assert_eq!("No robot location in map input", err.to_string());
+    }
+
+    #[test]
+    fn undo_redo() {
+        let Input { map, robot_start, moves } = parse_input(SAMPLE_INPUT).unwrap();
+        let wide_start = Position { x: 2 * robot_start.x, y: robot_start.y };
+        let mut session = Session::new(expand_map(&map), wide_start, step_part_2, space2_char);
+        let initial = session.render();
+
+        let moved = moves.iter().filter(|&&direction| session.apply(direction)).count();
+        assert_eq!(moved, session.undo_stack.len());
+        assert_eq!(9021, score_map(&session.map, Space2::Box(BoxSide::Left)));
+        let last = session.render();
+        crate::snapshot::assert_snapshot("day15_wide_moves", &format!("{initial}---\n{last}"));
+
+        while session.undo() {}
+        assert_eq!(initial, session.render());
+        assert!(!session.undo());
+
+        while session.redo() {}
+        assert_eq!(last, session.render());
+        assert!(!session.redo());
+
+        // A new move after undoing discards the redo history
+        session.undo();
+        session.undo();
+        assert_eq!(2, session.redo_stack.len());
+        assert!(session.apply(Direction::Left));
+        assert!(session.redo_stack.is_empty());
+    }
+
+    #[test]
+    fn replay_steps() {
+        let Input { map, robot_start, moves } = parse_input(SAMPLE_INPUT_2).unwrap();
+        let mut replay = Replay::new(map, robot_start, moves, step_part_1, space_char, Space::Box);
+        assert_eq!("##@.O..#", replay.render().lines[2]);
+        assert_eq!("move 0 of 15, GPS sum 1624", replay.render().status);
+
+        // The first move is blocked by a wall
+        replay.step();
+        assert_eq!("##@.O..#", replay.render().lines[2]);
+        replay.step();
+        assert_eq!("#.@O.O.#", replay.render().lines[1]);
+        assert_eq!("##..O..#", replay.render().lines[2]);
+
+        while replay.step() {}
+        assert_eq!("move 15 of 15, GPS sum 2028", replay.render().status);
+    }
+
+    #[test]
+    fn animation() {
+        let Input { map, robot_start, moves } = parse_input(SAMPLE_INPUT).unwrap();
+        let moved = {
+            let mut map = Map::new(map.clone());
+            let mut robot_pos = robot_start;
+            moves
+                .iter()
+                .filter(|&&direction| {
+                    let new_pos = step_part_1(&mut map, robot_pos, direction);
+                    let moved = new_pos != robot_pos;
+                    robot_pos = new_pos;
+                    moved
+                })
+                .count()
+        };
+
+        let frames: Vec<_> =
+            animation_frames(map, robot_start, moves, step_part_1, space_color).collect();
+        assert_eq!(moved + 1, frames.len());
+        assert_eq!((40, 40), (frames[0].width(), frames[0].height()));
+        let robot_pixel =
+            |pos: Position| frames[0].pixel(pos.x as usize * GIF_SCALE, pos.y as usize * GIF_SCALE);
+        assert_eq!(ROBOT_COLOR, robot_pixel(robot_start));
+        assert_eq!(space_color(Space::Wall), robot_pixel(Position { x: 0, y: 0 }));
+    }
+}
@@ -0,0 +1,527 @@
+//! Day 16: Reindeer Maze
+//!
+//! <https://adventofcode.com/2024/day/16>
+//!
+//! `--algo distances` solves both parts from forward and backward scores for every (position,
+//! facing) state instead of the default `--algo paths`, which records the optimal predecessors of
+//! each state during the search.
+//!
+//! Passing `--metrics` prints statistics about the set of optimal paths instead of the answers: how
+//! many distinct optimal paths there are, how many cells lie on every one of them, and how many lie
+//! on exactly one.
+//!
+//! `--visualize` draws the maze in color on stderr with every cell on an optimal path highlighted.
+//! `--viz` (with the `viz` feature) animates the search in the terminal instead, one newly reached
+//! cell at a time, and highlights the optimal paths once it reaches the end.
+
+use crate::ansi::{self, Color};
+use crate::days::Solver;
+use crate::image::Rgb;
+use crate::search::{self, MoveCosts};
+use crate::viz::{self, Frame};
+use crate::{BucketQueue, CombinedOutput, Direction, DirectionMap, Grid, ParseError, Pos2, parse};
+use rustc_hash::FxHashSet;
+use std::error::Error;
+use std::ops::Index;
+
+type Position = Pos2<i32>;
+
+#[derive(Debug, Clone)]
+struct Walls(Vec<Vec<bool>>);
+
+impl Index<Position> for Walls {
+    type Output = bool;
+
+    fn index(&self, index: Position) -> &Self::Output {
+        &self.0[index.y as usize][index.x as usize]
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    walls: Vec<Vec<bool>>,
+    start: Position,
+    end: Position,
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let markers = [('S', "No start position in map"), ('E', "No end position in map")];
+    let (walls, [start, end]) = parse::wall_maze(input, markers)?;
+    Ok(Input { walls: walls.0, start, end })
+}
+
+fn solve(input: &str) -> Result<(u32, usize), ParseError> {
+    solve_facing(input, Direction::Right)
+}
+
+fn solve_facing(input: &str, start_direction: Direction) -> Result<(u32, usize), ParseError> {
+    let Input { walls, start, end } = parse_input(input)?;
+    let walls = Walls(walls);
+
+    let (min_score, good_seats) = optimal_seats(&walls, (start, start_direction), end);
+    crate::visualize(|| render_seats(&walls, &good_seats));
+    Ok((min_score, good_seats.len()))
+}
+
+// Min score from `start` to `end`, and every cell on a path with that score
+fn optimal_seats(
+    walls: &Walls,
+    start: (Position, Direction),
+    end: Position,
+) -> (u32, FxHashSet<Position>) {
+    let size = (walls.0[0].len(), walls.0.len());
+    let costs = MoveCosts { forward: 1, turn: TURN_SCORE };
+    let result =
+        search::facing_dijkstra(size, start, |pos| !walls[pos], costs, |pos| pos == end, true)
+            .expect("No solution found");
+
+    (result.cost, result.optimal_cells())
+}
+
+const WALL_COLOR: Rgb = [110, 110, 110];
+const SEAT_COLOR: Rgb = [40, 200, 60];
+
+fn render_seats(walls: &Walls, seats: &FxHashSet<Position>) -> String {
+    ansi::render_fn(walls.0[0].len(), walls.0.len(), |x, y| {
+        if seats.contains(&Position { x: x as i32, y: y as i32 }) {
+            ('O', Color::Rgb(SEAT_COLOR))
+        } else if walls.0[y][x] {
+            ('#', Color::Rgb(WALL_COLOR))
+        } else {
+            ('.', Color::Default)
+        }
+    })
+}
+
+const TURN_SCORE: u32 = 1000;
+
+// Score for turning in place from `from` to `to`; turning around takes two turns
+fn turn_score(from: Direction, to: Direction) -> u32 {
+    if from == to {
+        0
+    } else if from == to.reverse() {
+        2 * TURN_SCORE
+    } else {
+        TURN_SCORE
+    }
+}
+
+// Min score for every (position, facing) state, or u32::MAX for unreachable states. Each move turns
+// to face any direction and then steps forward, so that a path's moves correspond one-to-one with
+// the cells it visits; this is what lets paths be counted by cell sequence.
+//
+// Forward scores are from `sources`. Backward scores follow moves in reverse, giving the min score
+// from each state to any of `sources`
+fn state_scores(
+    walls: &Walls,
+    sources: impl IntoIterator<Item = (Position, Direction)>,
+    backward: bool,
+) -> Grid<DirectionMap<u32>> {
+    let mut sweep = ScoreSweep::new(walls, sources, backward);
+    let mut expanded = 0;
+    while sweep.expand_next(walls).is_some() {
+        expanded += 1;
+    }
+
+    search::record_search(expanded);
+    sweep.scores
+}
+
+// The search behind `state_scores`, one expanded state at a time so that it can also be animated
+struct ScoreSweep {
+    scores: Grid<DirectionMap<u32>>,
+    queue: BucketQueue<(Position, Direction)>,
+    backward: bool,
+}
+
+impl ScoreSweep {
+    fn new(
+        walls: &Walls,
+        sources: impl IntoIterator<Item = (Position, Direction)>,
+        backward: bool,
+    ) -> Self {
+        let mut scores =
+            Grid(vec![vec![DirectionMap::splat(u32::MAX); walls.0[0].len()]; walls.0.len()]);
+        // The most expensive move turns around and steps forward
+        let mut queue = BucketQueue::new(2 * TURN_SCORE as usize + 1);
+        for (pos, direction) in sources {
+            scores[pos][direction] = 0;
+            queue.push(0, (pos, direction));
+        }
+
+        Self { scores, queue, backward }
+    }
+
+    // Expands the lowest-score state not yet expanded and returns it with its score, or None once
+    // every reachable state has been expanded
+    fn expand_next(&mut self, walls: &Walls) -> Option<(Position, Direction, u32)> {
+        let (score, pos, direction) = loop {
+            let (score, (pos, direction)) = self.queue.pop()?;
+            let score = score as u32;
+            if self.scores[pos][direction] == score {
+                break (score, pos, direction);
+            }
+        };
+
+        for other in Direction::ALL {
+            // Forward: turn from `direction` to `other` and step. Backward: this state was reached
+            // by a move from the cell behind it that turned from `other` to `direction`
+            let (next_pos, next_direction, move_score) = if self.backward {
+                (pos - direction.delta(), other, turn_score(other, direction) + 1)
+            } else {
+                (pos + other.delta(), other, turn_score(direction, other) + 1)
+            };
+            if walls[next_pos] {
+                continue;
+            }
+
+            let next_score = score + move_score;
+            if next_score < self.scores[next_pos][next_direction] {
+                self.scores[next_pos][next_direction] = next_score;
+                self.queue.push(next_score as usize, (next_pos, next_direction));
+            }
+        }
+
+        Some((pos, direction, score))
+    }
+}
+
+// Forward search from the start advanced one newly reached cell at a time, for visualization. Once
+// it reaches the end, every cell on an optimal path is shown
+struct Exploration {
+    walls: Walls,
+    start: (Position, Direction),
+    end: Position,
+    sweep: ScoreSweep,
+    reached: Grid<bool>,
+    reached_count: usize,
+    last: (Position, u32),
+    seats: Option<FxHashSet<Position>>,
+}
+
+impl Exploration {
+    fn new(Input { walls, start, end }: Input, start_direction: Direction) -> Self {
+        let walls = Walls(walls);
+        let start = (start, start_direction);
+        let sweep = ScoreSweep::new(&walls, [start], false);
+        let reached = Grid(vec![vec![false; walls.0[0].len()]; walls.0.len()]);
+        Self {
+            walls,
+            start,
+            end,
+            sweep,
+            reached,
+            reached_count: 0,
+            last: (start.0, 0),
+            seats: None,
+        }
+    }
+
+    // Returns false once the search has reached the end
+    fn step(&mut self) -> bool {
+        if self.seats.is_some() {
+            return false;
+        }
+
+        while let Some((pos, _, score)) = self.sweep.expand_next(&self.walls) {
+            self.last = (pos, score);
+            if pos == self.end {
+                self.seats = Some(optimal_seats(&self.walls, self.start, self.end).1);
+                return false;
+            }
+            if !self.reached[pos] {
+                self.reached[pos] = true;
+                self.reached_count += 1;
+                return true;
+            }
+        }
+
+        panic!("No solution found")
+    }
+
+    fn render(&self) -> Frame {
+        let lines = (0..self.walls.0.len())
+            .map(|y| {
+                (0..self.walls.0[0].len())
+                    .map(|x| {
+                        let pos = Position { x: x as i32, y: y as i32 };
+                        if self.seats.as_ref().is_some_and(|seats| seats.contains(&pos)) {
+                            'O'
+                        } else if pos == self.start.0 {
+                            'S'
+                        } else if pos == self.end {
+                            'E'
+                        } else if self.walls[pos] {
+                            '#'
+                        } else if self.reached[pos] {
+                            '+'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (pos, score) = self.last;
+        let status = match &self.seats {
+            Some(seats) => format!("min score {score}, {} seats", seats.len()),
+            None => format!("{} cells reached, score {score}", self.reached_count),
+        };
+        Frame { lines, status, focus: Some((pos.x as usize, pos.y as usize)) }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PathMetrics {
+    min_score: u32,
+    // Distinct optimal paths, as sequences of cells. Saturates rather than overflowing
+    paths: u128,
+    // Cells on at least one optimal path; the same as the part 2 answer
+    seats: usize,
+    // Cells on every optimal path, including the start and end
+    critical_cells: usize,
+    // Cells on exactly one optimal path
+    unique_cells: usize,
+}
+
+// A state is on an optimal path exactly when its forward and backward scores sum to the min score,
+// and a move between two such states is part of an optimal path when its score accounts for the
+// whole difference. Counting paths into and out of each state over those moves (in order of
+// forward score, which is a topological order because every move costs at least 1) gives the
+// number of optimal paths through each cell without enumerating any paths
+fn path_metrics(input: &str, start_direction: Direction) -> Result<PathMetrics, ParseError> {
+    let Input { walls, start, end } = parse_input(input)?;
+    let walls = Walls(walls);
+
+    let forward = state_scores(&walls, [(start, start_direction)], false);
+    let backward = state_scores(&walls, Direction::ALL.map(|direction| (end, direction)), true);
+    let min_score = *forward[end].values().min().unwrap();
+    assert_ne!(min_score, u32::MAX, "No solution found");
+
+    let mut optimal_states = Vec::new();
+    for (y, row) in forward.0.iter().enumerate() {
+        for (x, scores) in row.iter().enumerate() {
+            let pos = Position { x: x as i32, y: y as i32 };
+            for (direction, &score) in scores.iter() {
+                let remaining = backward[pos][direction];
+                if score != u32::MAX && remaining != u32::MAX && score + remaining == min_score {
+                    optimal_states.push((score, pos, direction));
+                }
+            }
+        }
+    }
+    optimal_states.sort_by_key(|&(score, ..)| score);
+
+    let (forward, backward, walls) = (&forward, &backward, &walls);
+    let optimal_moves = move |pos: Position, direction: Direction| {
+        let score = forward[pos][direction];
+        Direction::ALL.into_iter().filter_map(move |next_direction| {
+            let next_pos = pos + next_direction.delta();
+            let move_score = turn_score(direction, next_direction) + 1;
+            let remaining = backward[next_pos][next_direction];
+            (!walls[next_pos]
+                && remaining != u32::MAX
+                && score + move_score + remaining == min_score)
+                .then_some((next_pos, next_direction))
+        })
+    };
+
+    let mut paths_from_start: Grid<DirectionMap<u128>> = Grid::same_size_as(forward);
+    paths_from_start[start][start_direction] = 1;
+    for &(_, pos, direction) in &optimal_states {
+        let paths = paths_from_start[pos][direction];
+        for (next_pos, next_direction) in optimal_moves(pos, direction) {
+            let next_paths = &mut paths_from_start[next_pos][next_direction];
+            *next_paths = next_paths.saturating_add(paths);
+        }
+    }
+
+    let mut paths_to_end: Grid<DirectionMap<u128>> = Grid::same_size_as(forward);
+    for &(_, pos, direction) in optimal_states.iter().rev() {
+        let paths = if pos == end {
+            1
+        } else {
+            optimal_moves(pos, direction)
+                .map(|(next_pos, next_direction)| paths_to_end[next_pos][next_direction])
+                .fold(0, u128::saturating_add)
+        };
+        paths_to_end[pos][direction] = paths;
+    }
+
+    // Optimal paths never visit a cell twice, so paths through a cell can be summed over directions
+    let total_paths = paths_to_end[start][start_direction];
+    let mut metrics =
+        PathMetrics { min_score, paths: total_paths, seats: 0, critical_cells: 0, unique_cells: 0 };
+    for (from_row, to_row) in paths_from_start.0.iter().zip(&paths_to_end.0) {
+        for (from, to) in from_row.iter().zip(to_row) {
+            let through = from
+                .values()
+                .zip(to.values())
+                .map(|(&from, &to)| from.saturating_mul(to))
+                .fold(0, u128::saturating_add);
+            metrics.seats += usize::from(through > 0);
+            metrics.critical_cells += usize::from(through > 0 && through == total_paths);
+            metrics.unique_cells += usize::from(through == 1);
+        }
+    }
+
+    Ok(metrics)
+}
+
+// Same answers as `solve`, without tracking any paths
+fn solve_distances(input: &str) -> Result<(u32, usize), ParseError> {
+    path_metrics(input, Direction::Right).map(|metrics| (metrics.min_score, metrics.seats))
+}
+
+fn print_metrics(metrics: &PathMetrics) {
+    println!("Min score: {}", metrics.min_score);
+    println!("Optimal paths: {}", metrics.paths);
+    println!("Cells on any optimal path: {}", metrics.seats);
+    println!("Cells on every optimal path: {}", metrics.critical_cells);
+    println!("Cells on exactly one optimal path: {}", metrics.unique_cells);
+}
+
+pub const SOLVER: Solver = Solver::Combined(|input| solve(input).unwrap_solutions());
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::terminal_viz()? {
+        let exploration = Exploration::new(parse_input(&crate::read_input()?)?, Direction::Right);
+        return viz::run(
+            "Day 16: Reindeer Maze",
+            exploration,
+            Exploration::step,
+            Exploration::render,
+        );
+    }
+
+    if crate::has_flag("--metrics") {
+        print_metrics(&path_metrics(&crate::read_input()?, Direction::Right)?);
+        return Ok(());
+    }
+
+    type Solve = fn(&str) -> Result<(u32, usize), ParseError>;
+    let solve = crate::select_algo::<Solve>(&[("paths", solve), ("distances", solve_distances)])?;
+    crate::run_single_fn(solve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridTransform;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day16.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample/2024/day16-2.txt");
+
+    #[test]
+    fn seats_rendering() {
+        let walls = Walls(vec![vec![true; 4], vec![true, false, false, true], vec![true; 4]]);
+        let seats = FxHashSet::from_iter([Position { x: 1, y: 1 }]);
+        let wall = "\x1b[38;2;110;110;110m";
+        assert_eq!(
+            format!(
+                "{wall}####\x1b[0m\n{wall}#\x1b[38;2;40;200;60mO\x1b[0m.{wall}#\x1b[0m\n{wall}####\x1b[0m\n"
+            ),
+            render_seats(&walls, &seats)
+        );
+    }
+
+    #[test]
+    fn exploration_steps() {
+        let mut exploration =
+            Exploration::new(parse_input(SAMPLE_INPUT).unwrap(), Direction::Right);
+        let start_row = exploration.render().lines[13].clone();
+        assert_eq!("#S..#.....#...#", start_row);
+
+        // The start itself is reached first
+        assert!(exploration.step());
+        assert_eq!("1 cells reached, score 0", exploration.render().status);
+        assert!(exploration.step());
+        assert_eq!("#S+.#.....#...#", exploration.render().lines[13]);
+        assert_eq!("2 cells reached, score 1", exploration.render().status);
+
+        while exploration.step() {}
+        assert!(!exploration.step());
+        assert_eq!("min score 7036, 45 seats", exploration.render().status);
+        assert_eq!("#O++#+++++#OOO#", exploration.render().lines[13]);
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(7036, solve(SAMPLE_INPUT).unwrap().0);
+        assert_eq!(11048, solve(SAMPLE_INPUT_2).unwrap().0);
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(45, solve(SAMPLE_INPUT).unwrap().1);
+        assert_eq!(64, solve(SAMPLE_INPUT_2).unwrap().1);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve("####\n#SE#\n#.x#\n####\n").unwrap_err();
+        assert_eq!("line 3, column 3: Unexpected map character: 'x'", err.to_string());
+        assert_eq!(Err(ParseError::new("No end position in map")), solve("####\n#S.#\n####\n"));
+    }
+
+    #[test]
+    fn metrics() {
+        for (input, min_score, seats) in [(SAMPLE_INPUT, 7036, 45), (SAMPLE_INPUT_2, 11048, 64)] {
+            let metrics = path_metrics(input, Direction::Right).unwrap();
+            assert_eq!((min_score, seats), (metrics.min_score, metrics.seats));
+            assert!(metrics.paths > 1);
+            assert!(metrics.critical_cells < seats);
+        }
+
+        // Two mirror-image paths around a wall, sharing only the start and end
+        let maze = "#####\n#...#\n#S#E#\n#...#\n#####\n";
+        assert_eq!(
+            Ok(PathMetrics {
+                min_score: 3004,
+                paths: 2,
+                seats: 8,
+                critical_cells: 2,
+                unique_cells: 6
+            }),
+            path_metrics(maze, Direction::Right)
+        );
+
+        // Turning around at the start counts once, not once per rotation direction
+        let maze = "#####\n#E.S#\n#####\n";
+        assert_eq!(
+            Ok(PathMetrics {
+                min_score: 2002,
+                paths: 1,
+                seats: 3,
+                critical_cells: 3,
+                unique_cells: 3
+            }),
+            path_metrics(maze, Direction::Right)
+        );
+    }
+
+    fn transform_maze(input: &str, transform: GridTransform) -> String {
+        let grid = Grid(input.lines().map(|line| line.chars().collect::<Vec<_>>()).collect());
+        let transformed = grid.transformed(transform);
+        transformed.0.into_iter().map(|row| row.into_iter().collect::<String>() + "\n").collect()
+    }
+
+    // S and E move with the grid, and the start direction is transformed along with it, so neither
+    // the min score nor the seat count may change
+    #[test]
+    fn transform_invariance() {
+        for (input, expected) in [(SAMPLE_INPUT, (7036, 45)), (SAMPLE_INPUT_2, (11048, 64))] {
+            for transform in GridTransform::ALL {
+                let maze = transform_maze(input, transform);
+                let start_direction = transform.map_direction(Direction::Right);
+                assert_eq!(Ok(expected), solve_facing(&maze, start_direction), "{transform:?}");
+                let metrics = path_metrics(&maze, start_direction).unwrap();
+                assert_eq!(expected, (metrics.min_score, metrics.seats), "{transform:?}");
+            }
+        }
+    }
+}
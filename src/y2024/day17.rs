@@ -0,0 +1,600 @@
+//! Day 17: Chronospatial Computer
+//!
+//! <https://adventofcode.com/2024/day/17>
+
+use crate::days::Solver;
+use crate::parse::{self, ParseError, comma_separated, labeled, unsigned};
+use crate::{SolverOutput, stats};
+use std::cmp;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write};
+use std::ops::Range;
+use winnow::ascii::newline;
+use winnow::combinator::{opt, terminated};
+use winnow::prelude::*;
+
+#[derive(Debug)]
+struct Input {
+    a: u64,
+    b: u64,
+    c: u64,
+    program: Vec<u8>,
+}
+
+fn parse_register(label: &'static str) -> impl FnMut(&mut &str) -> PResult<u64> {
+    move |input| terminated(labeled(label, unsigned), newline).parse_next(input)
+}
+
+fn parse_program(input: &mut &str) -> PResult<Vec<u8>> {
+    labeled("Program", comma_separated(unsigned)).parse_next(input)
+}
+
+fn parse_sections(input: &mut &str) -> PResult<Input> {
+    let (a, b, c) =
+        (parse_register("Register A"), parse_register("Register B"), parse_register("Register C"))
+            .parse_next(input)?;
+    newline.parse_next(input)?;
+    let program = terminated(parse_program, opt(newline)).parse_next(input)?;
+
+    Ok(Input { a, b, c, program })
+}
+
+// Also checks that the program disassembles: 3-bit values in opcode/operand pairs, with no combo
+// operand of 7
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    const PROGRAM_ROW: usize = 4;
+
+    let input = parse::complete(parse_sections, input)?;
+    if let Some(&value) = input.program.iter().find(|&&value| value >= 8) {
+        return Err(ParseError::at(PROGRAM_ROW, 0, format!("Invalid program value: {value}")));
+    }
+    if !input.program.len().is_multiple_of(2) {
+        return Err(ParseError::at(PROGRAM_ROW, 0, "Program ends with an opcode but no operand"));
+    }
+    let combo_opcodes = [0, 2, 5, 6, 7];
+    if input.program.chunks_exact(2).any(|pair| combo_opcodes.contains(&pair[0]) && pair[1] == 7) {
+        return Err(ParseError::at(PROGRAM_ROW, 0, "Invalid combo operand: 7"));
+    }
+
+    Ok(input)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComboOperand {
+    Literal(u8),
+    A,
+    B,
+    C,
+}
+
+impl ComboOperand {
+    fn from_operand(operand: u8) -> Self {
+        match operand {
+            0..=3 => Self::Literal(operand),
+            4 => Self::A,
+            5 => Self::B,
+            6 => Self::C,
+            _ => panic!("Invalid combo operand: {operand}"),
+        }
+    }
+
+    fn value(self, a: u64, b: u64, c: u64) -> u64 {
+        match self {
+            Self::Literal(literal) => literal.into(),
+            Self::A => a,
+            Self::B => b,
+            Self::C => c,
+        }
+    }
+}
+
+impl Display for ComboOperand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Literal(literal) => write!(f, "{literal}"),
+            Self::A => write!(f, "A"),
+            Self::B => write!(f, "B"),
+            Self::C => write!(f, "C"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Adv(ComboOperand),
+    Bxl(u8),
+    Bst(ComboOperand),
+    Jnz(u8),
+    Bxc,
+    Out(ComboOperand),
+    Bdv(ComboOperand),
+    Cdv(ComboOperand),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Adv(operand) => write!(f, "A >>= {operand}"),
+            Self::Bxl(operand) => write!(f, "B ^= {operand}"),
+            Self::Bst(operand) => write!(f, "B = {operand} & 7"),
+            Self::Jnz(operand) => write!(f, "JNZ {operand}"),
+            Self::Bxc => write!(f, "B ^= C"),
+            Self::Out(operand) => write!(f, "OUT ({operand} & 7)"),
+            Self::Bdv(operand) => write!(f, "B = A >> {operand}"),
+            Self::Cdv(operand) => write!(f, "C = A >> {operand}"),
+        }
+    }
+}
+
+// Set of registers, as bit flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Registers(u8);
+
+impl Registers {
+    const NONE: Self = Self(0);
+    const A: Self = Self(1 << 0);
+    const B: Self = Self(1 << 1);
+    const C: Self = Self(1 << 2);
+
+    const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl Display for Registers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if *self == Self::NONE {
+            return write!(f, "-");
+        }
+
+        for (register, name) in [(Self::A, 'A'), (Self::B, 'B'), (Self::C, 'C')] {
+            if self.0 & register.0 != 0 {
+                write!(f, "{name}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ComboOperand {
+    fn register(self) -> Registers {
+        match self {
+            Self::Literal(_) => Registers::NONE,
+            Self::A => Registers::A,
+            Self::B => Registers::B,
+            Self::C => Registers::C,
+        }
+    }
+}
+
+impl Instruction {
+    fn defs(self) -> Registers {
+        match self {
+            Self::Adv(_) => Registers::A,
+            Self::Bxl(_) | Self::Bst(_) | Self::Bxc | Self::Bdv(_) => Registers::B,
+            Self::Cdv(_) => Registers::C,
+            Self::Jnz(_) | Self::Out(_) => Registers::NONE,
+        }
+    }
+
+    fn uses(self) -> Registers {
+        match self {
+            Self::Adv(operand) | Self::Bdv(operand) | Self::Cdv(operand) => {
+                Registers::A.union(operand.register())
+            }
+            Self::Bxl(_) => Registers::B,
+            Self::Bst(operand) | Self::Out(operand) => operand.register(),
+            Self::Jnz(_) => Registers::A,
+            Self::Bxc => Registers::B.union(Registers::C),
+        }
+    }
+
+    // Instruction index that a jump goes to, if this is a jump
+    fn jump_target(self) -> Option<usize> {
+        match self {
+            Self::Jnz(operand) => Some((operand >> 1).into()),
+            _ => None,
+        }
+    }
+}
+
+// Control flow and register liveness for a disassembled program, for annotating `--print-program`
+#[derive(Debug)]
+struct Analysis {
+    // Half-open instruction index ranges, in program order
+    blocks: Vec<Range<usize>>,
+    // (jump instruction, target) pairs where the target is at or before the jump
+    back_edges: Vec<(usize, usize)>,
+    live_in: Vec<Registers>,
+    live_out: Vec<Registers>,
+}
+
+fn successors(instructions: &[Instruction], i: usize) -> impl Iterator<Item = usize> {
+    let fallthrough = (i + 1 < instructions.len()).then_some(i + 1);
+    let jump = instructions[i].jump_target().filter(|&target| target < instructions.len());
+    fallthrough.into_iter().chain(jump)
+}
+
+fn analyze(instructions: &[Instruction]) -> Analysis {
+    // Blocks start at the program start, at every jump target, and after every jump
+    let mut leaders = BTreeSet::from([0]);
+    for (i, instruction) in instructions.iter().enumerate() {
+        if let Some(target) = instruction.jump_target() {
+            leaders.insert(target);
+            leaders.insert(i + 1);
+        }
+    }
+    let leaders: Vec<_> = leaders.into_iter().filter(|&i| i < instructions.len()).collect();
+    let blocks = leaders
+        .iter()
+        .enumerate()
+        .map(|(j, &start)| start..leaders.get(j + 1).copied().unwrap_or(instructions.len()))
+        .collect();
+
+    let back_edges = instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(i, instruction)| {
+            instruction.jump_target().filter(|&target| target <= i).map(|target| (i, target))
+        })
+        .collect();
+
+    // Standard backward dataflow: live_in = uses | (live_out - defs), where live_out is the union
+    // of the successors' live_in. Iterate until nothing changes to handle loops
+    let mut live_in = vec![Registers::NONE; instructions.len()];
+    let mut live_out = vec![Registers::NONE; instructions.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in (0..instructions.len()).rev() {
+            let out = successors(instructions, i)
+                .fold(Registers::NONE, |acc, successor| acc.union(live_in[successor]));
+            let instruction = instructions[i];
+            let in_ = instruction.uses().union(out.difference(instruction.defs()));
+            if (out, in_) != (live_out[i], live_in[i]) {
+                (live_out[i], live_in[i]) = (out, in_);
+                changed = true;
+            }
+        }
+    }
+
+    Analysis { blocks, back_edges, live_in, live_out }
+}
+
+// Disassembly with basic block headers, loop back-edges, and per-instruction register defs, uses,
+// and live-out sets. Addresses are program byte offsets, matching jump operands
+fn annotate(instructions: &[Instruction]) -> String {
+    let analysis = analyze(instructions);
+
+    let mut out = String::new();
+    for (j, block) in analysis.blocks.iter().enumerate() {
+        let entered_from: Vec<_> = analysis
+            .back_edges
+            .iter()
+            .filter(|&&(_, target)| target == block.start)
+            .map(|&(jump, _)| (2 * jump).to_string())
+            .collect();
+        let loop_note = if entered_from.is_empty() {
+            String::new()
+        } else {
+            format!(", loop header (back edge from {})", entered_from.join(", "))
+        };
+        writeln!(out, "block {j}: {}..{}{loop_note}", 2 * block.start, 2 * block.end).unwrap();
+        writeln!(out, "  live in: {}", analysis.live_in[block.start]).unwrap();
+
+        for i in block.clone() {
+            let instruction = instructions[i];
+            let text = instruction.to_string();
+            let mut line = format!(
+                "  {:2}: {text:<12} def {:<3} use {:<3} live out {}",
+                2 * i,
+                instruction.defs(),
+                instruction.uses(),
+                analysis.live_out[i]
+            );
+            if let Some(&(_, target)) = analysis.back_edges.iter().find(|&&(jump, _)| jump == i) {
+                write!(line, "  <- loops back to {}", 2 * target).unwrap();
+            }
+            writeln!(out, "{}", line.trim_end()).unwrap();
+        }
+    }
+
+    out
+}
+
+fn disassemble(program: &[u8]) -> Vec<Instruction> {
+    assert!(program.len().is_multiple_of(2) && program.iter().all(|&opcode| opcode < 8));
+
+    program
+        .chunks_exact(2)
+        .map(|chunk| {
+            let &[opcode, operand] = chunk else { unreachable!() };
+
+            match opcode {
+                0 => Instruction::Adv(ComboOperand::from_operand(operand)),
+                1 => Instruction::Bxl(operand),
+                2 => Instruction::Bst(ComboOperand::from_operand(operand)),
+                3 => Instruction::Jnz(operand),
+                4 => Instruction::Bxc,
+                5 => Instruction::Out(ComboOperand::from_operand(operand)),
+                6 => Instruction::Bdv(ComboOperand::from_operand(operand)),
+                7 => Instruction::Cdv(ComboOperand::from_operand(operand)),
+                _ => unreachable!(),
+            }
+        })
+        .collect()
+}
+
+fn run_program(mut a: u64, mut b: u64, mut c: u64, program: &[Instruction]) -> Vec<u8> {
+    let mut ip = 0;
+    let mut out = Vec::new();
+    while ip < program.len() {
+        let instruction = program[ip];
+        ip += 1;
+
+        match instruction {
+            Instruction::Adv(operand) => {
+                let shift = operand.value(a, b, c);
+                a >>= shift;
+            }
+            Instruction::Bxl(operand) => {
+                b ^= u64::from(operand);
+            }
+            Instruction::Bst(operand) => {
+                b = operand.value(a, b, c) & 7;
+            }
+            Instruction::Jnz(operand) => {
+                if a != 0 {
+                    ip = (operand >> 1).into();
+                }
+            }
+            Instruction::Bxc => {
+                b ^= c;
+            }
+            Instruction::Out(operand) => {
+                out.push((operand.value(a, b, c) & 7) as u8);
+            }
+            Instruction::Bdv(operand) => {
+                let shift = operand.value(a, b, c);
+                b = a >> shift;
+            }
+            Instruction::Cdv(operand) => {
+                let shift = operand.value(a, b, c);
+                c = a >> shift;
+            }
+        }
+    }
+
+    out
+}
+
+fn solve_part_1(input: &str) -> Result<String, ParseError> {
+    let Input { a, b, c, program } = parse_input(input)?;
+
+    let instructions = disassemble(&program);
+    let out = run_program(a, b, c, &instructions);
+    let out: Vec<_> = out.iter().map(u8::to_string).collect();
+
+    Ok(out.join(","))
+}
+
+fn solve_part_2(input: &str) -> Result<u64, ParseError> {
+    let Input { program, .. } = parse_input(input)?;
+    Ok(find_quine_a(&program))
+}
+
+fn find_quine_a(program: &[u8]) -> u64 {
+    let instructions = disassemble(program);
+
+    // TODO Do all inputs look like this? What is actually variable between inputs?
+    let Instruction::Bxl(first_xor) = instructions[1] else {
+        panic!("Unexpected second instruction, expected bxl: {}", instructions[1]);
+    };
+    let Instruction::Bxl(second_xor) = instructions[3] else {
+        panic!("Unexpected fourth instruction, expected bxl: {}", instructions[3]);
+    };
+
+    let mut searcher = SolutionSearcher::new(first_xor.into(), second_xor.into());
+    searcher.search(0, 0, 10, program, 0);
+    searcher.record_stats();
+    searcher.solutions.into_iter().min().expect("No solution found")
+}
+
+#[derive(Debug, Default)]
+struct SearchStats {
+    nodes_explored: u64,
+    prunes: u64,
+    max_depth: u64,
+}
+
+struct SolutionSearcher {
+    first_xor: u64,
+    second_xor: u64,
+    solutions: Vec<u64>,
+    stats: SearchStats,
+}
+
+impl SolutionSearcher {
+    fn new(first_xor: u64, second_xor: u64) -> Self {
+        Self { first_xor, second_xor, solutions: Vec::new(), stats: SearchStats::default() }
+    }
+
+    fn record_stats(&self) {
+        stats::add("nodes_explored", self.stats.nodes_explored);
+        stats::add("prunes", self.stats.prunes);
+        stats::add("solutions_found", self.solutions.len() as u64);
+        stats::max("max_depth", self.stats.max_depth);
+        debug!(
+            nodes_explored = self.stats.nodes_explored,
+            prunes = self.stats.prunes,
+            solutions = self.solutions.len(),
+            max_depth = self.stats.max_depth,
+            "Finished register A search"
+        );
+    }
+
+    fn search(&mut self, a: u64, acc: u64, free_bits: u8, program: &[u8], program_idx: usize) {
+        self.stats.nodes_explored += 1;
+        self.stats.max_depth = cmp::max(self.stats.max_depth, program_idx as u64);
+
+        if program_idx == program.len() {
+            if a == 0 {
+                self.solutions.push(acc);
+            }
+            return;
+        }
+
+        let target: u64 = program[program_idx].into();
+        for high_bits in 0..1 << free_bits {
+            let new_a = a | (high_bits << (10 - free_bits));
+            let shift = (new_a & 7) ^ self.first_xor;
+
+            if (new_a & 7) ^ self.first_xor ^ self.second_xor ^ (new_a >> shift) == target {
+                let used_bits = shift;
+                let next_free_bits = cmp::min((10 - used_bits) as u8, free_bits + 3);
+                let next_acc = acc | ((new_a & 7) << (3 * program_idx));
+
+                self.search(new_a >> 3, next_acc, next_free_bits, program, program_idx + 1);
+            } else {
+                self.stats.prunes += 1;
+            }
+        }
+    }
+}
+
+// Prints which 3-bit chunk of A produces each output digit. The program shifts A right by 3 bits
+// per loop iteration, so output digit i is determined by bits [3i, 3i+3) of A (plus whatever higher
+// bits the C register shift reaches)
+fn explain(a: u64, program: &[u8]) {
+    println!("A = {a} (octal {a:o})");
+
+    let instructions = disassemble(program);
+    let out = run_program(a, 0, 0, &instructions);
+    for (i, &digit) in out.iter().enumerate() {
+        let chunk = (a >> (3 * i)) & 7;
+        println!(
+            "  out[{i:2}] = {digit}  <-  A bits {:2}..{:2} = {chunk:03b} ({chunk})",
+            3 * i,
+            3 * i + 3
+        );
+    }
+
+    let matches = out == program;
+    println!("Output matches program: {matches}");
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input).unwrap_solution(),
+    |input| solve_part_2(input).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::has_flag("--print-program") {
+        let Input { program, .. } = parse_input(&crate::read_input()?)?;
+        print!("{}", annotate(&disassemble(&program)));
+        return Ok(());
+    }
+
+    if crate::has_flag("--explain") {
+        let Input { program, .. } = parse_input(&crate::read_input()?)?;
+        explain(find_quine_a(&program), &program);
+        return Ok(());
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day17.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok("4,6,3,5,6,3,5,2,1,0"), solve_part_1(SAMPLE_INPUT).as_deref());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve_part_1(&SAMPLE_INPUT.replace("Register B", "Register X")).unwrap_err();
+        assert_eq!("line 2, column 1: Unexpected line: 'Register X: 0'", err.to_string());
+
+        for (program, message) in [
+            ("0,1,8,4", "Invalid program value: 8"),
+            ("0,1,5", "Program ends with an opcode but no operand"),
+            ("1,7,5,7", "Invalid combo operand: 7"),
+        ] {
+            let input =
+                format!("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n");
+            assert_eq!(
+                format!("line 5, column 1: {message}"),
+                solve_part_1(&input).unwrap_err().to_string()
+            );
+        }
+    }
+
+    // The sample doesn't have the structure that the part 2 solver expects, so use a program that
+    // does and that has a solution
+    const QUINE_INPUT: &str = "\
+Register A: 0
+Register B: 0
+Register C: 0
+
+Program: 2,4,1,5,7,5,1,6,4,2,5,5,0,3,3,0
+";
+
+    #[test]
+    fn part_2() {
+        stats::reset();
+        let a = solve_part_2(QUINE_INPUT).unwrap();
+        assert_eq!(107416870455451, a);
+
+        let Input { program, .. } = parse_input(QUINE_INPUT).unwrap();
+        assert_eq!(program, run_program(a, 0, 0, &disassemble(&program)));
+
+        assert!(stats::get("solutions_found") >= 1);
+        assert_eq!(program.len() as u64, stats::get("max_depth"));
+        assert!(stats::get("nodes_explored") > stats::get("solutions_found"));
+        assert!(stats::get("prunes") > 0);
+    }
+
+    #[test]
+    fn analysis() {
+        let Input { program, .. } = parse_input(QUINE_INPUT).unwrap();
+        let instructions = disassemble(&program);
+        let analysis = analyze(&instructions);
+
+        // One block that loops back to itself
+        assert_eq!(vec![0..8], analysis.blocks);
+        assert_eq!(vec![(7, 0)], analysis.back_edges);
+
+        // B and C are always written before being read, so only A is live across iterations
+        let ab = Registers::A.union(Registers::B);
+        let abc = ab.union(Registers::C);
+        assert_eq!(
+            vec![Registers::A, ab, ab, abc, abc, ab, Registers::A, Registers::A],
+            analysis.live_in
+        );
+        assert_eq!(Registers::A, analysis.live_out[7]);
+        assert_eq!("ABC", abc.to_string());
+
+        let annotated = annotate(&instructions);
+        assert!(annotated.starts_with("block 0: 0..16, loop header (back edge from 14)\n"));
+        assert!(annotated.contains("14: JNZ 0"));
+        assert!(annotated.contains("<- loops back to 0"));
+
+        // Straight-line code with a forward jump splits into blocks at the target and after the jump
+        let forward = disassemble(&[3, 6, 5, 4, 5, 5, 0, 3]);
+        assert_eq!(vec![0..1, 1..3, 3..4], analyze(&forward).blocks);
+        assert!(analyze(&forward).back_edges.is_empty());
+    }
+}
@@ -0,0 +1,446 @@
+//! Day 18: RAM Run
+//!
+//! <https://adventofcode.com/2024/day/18>
+//!
+//! Passing `--timed` instead finds the earliest time that the exit can be reached if movement and
+//! falling bytes happen simultaneously, with one step per nanosecond. Lines may be `x,y,t` to make
+//! a byte fall at time `t`; plain `x,y` lines fall in order, one per nanosecond starting at time 1.
+//! The memory space defaults to 71x71 and can be changed with `--size`.
+//!
+//! Passing `--viz` (with the `viz` feature) animates bytes falling one at a time along with the
+//! current shortest path, until the exit is cut off. `--visualize` plays the same animation in color
+//! on stderr while solving part 2.
+
+use crate::ansi::{self, Color, Glyph};
+use crate::days::Solver;
+use crate::image::Rgb;
+use crate::viz::{self, Frame};
+use crate::{Grid, ParseError, Pos2, Solution, SolverOutput, search};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+type Position = Pos2<i32>;
+
+// Lines paired with their 0-based row, skipping blank lines
+fn nonempty_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().filter(|(_, line)| !line.is_empty())
+}
+
+// Comma-separated numbers, e.g. `x,y` or `x,y,t`
+fn parse_fields(row: usize, line: &str) -> Result<Vec<u32>, ParseError> {
+    let mut col = 0;
+    line.split(',')
+        .map(|field| {
+            let field_col = col;
+            col += field.len() + 1;
+            field.trim().parse().map_err(|_| {
+                ParseError::at(row, field_col, format!("Invalid coordinate: '{field}'"))
+            })
+        })
+        .collect()
+}
+
+fn parse_input(input: &str) -> Result<Vec<Pos2<usize>>, ParseError> {
+    nonempty_lines(input)
+        .map(|(row, line)| match parse_fields(row, line)?[..] {
+            [x, y] => Ok(Pos2 { x: x as usize, y: y as usize }),
+            _ => Err(ParseError::at(row, 0, format!("Expected a line of the form x,y: '{line}'"))),
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct QueueEntry {
+    pos: Position,
+    len: u32,
+}
+
+fn solve_part_1(input: &str, bytes: usize, size: usize) -> Result<u32, ParseError> {
+    let bytes_list = parse_input(input)?;
+    if crate::visualizing() {
+        ansi::animate(Memory::new(bytes_list.clone(), size), Memory::step, Memory::render_ansi, 10);
+    }
+
+    let mut bytes_map = vec![vec![false; size]; size];
+
+    for &byte_pos in &bytes_list[..bytes] {
+        bytes_map[byte_pos.y][byte_pos.x] = true;
+    }
+
+    Ok(bfs_path_search(&bytes_map, size as i32).expect("No solution found"))
+}
+
+fn bfs_path_search(bytes_map: &[Vec<bool>], size: i32) -> Option<u32> {
+    let end_pos = Position { x: size - 1, y: size - 1 };
+
+    let mut visited = vec![vec![false; size as usize]; size as usize];
+    let mut queue = VecDeque::new();
+    queue.push_back(QueueEntry { pos: Position { x: 0, y: 0 }, len: 0 });
+    visited[0][0] = true;
+
+    let mut expanded = 0;
+    while let Some(QueueEntry { pos, len }) = queue.pop_front() {
+        expanded += 1;
+        for (dy, dx) in [(-1, 0), (0, -1), (1, 0), (0, 1)] {
+            let new_pos = pos + Position { x: dx, y: dy };
+            if !(0..size).contains(&new_pos.y) || !(0..size).contains(&new_pos.x) {
+                continue;
+            }
+
+            if !bytes_map[new_pos.y as usize][new_pos.x as usize]
+                && !visited[new_pos.y as usize][new_pos.x as usize]
+            {
+                if new_pos == end_pos {
+                    search::record_search(expanded);
+                    return Some(len + 1);
+                }
+
+                visited[new_pos.y as usize][new_pos.x as usize] = true;
+                queue.push_back(QueueEntry { pos: new_pos, len: len + 1 });
+            }
+        }
+    }
+
+    search::record_search(expanded);
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Part2Solution(usize, usize);
+
+impl Display for Part2Solution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.0, self.1)
+    }
+}
+
+impl From<Part2Solution> for Solution {
+    fn from(value: Part2Solution) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+fn solve_part_2(input: &str, start_bytes: usize, size: usize) -> Result<Part2Solution, ParseError> {
+    let bytes_list = parse_input(input)?;
+    let mut bytes_map = vec![vec![false; size]; size];
+
+    for &byte_pos in &bytes_list[..start_bytes] {
+        bytes_map[byte_pos.y][byte_pos.x] = true;
+    }
+
+    // The first byte after which the exit is unreachable
+    let blocking = search::partition_point_over(start_bytes..bytes_list.len(), |last| {
+        let mut bytes_map = bytes_map.clone();
+        for &byte_pos in &bytes_list[start_bytes..=last] {
+            bytes_map[byte_pos.y][byte_pos.x] = true;
+        }
+        bfs_path_search(&bytes_map, size as i32).is_some()
+    });
+
+    let byte_pos = bytes_list[blocking];
+    Ok(Part2Solution(byte_pos.x, byte_pos.y))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FallingByte {
+    pos: Pos2<usize>,
+    time: u32,
+}
+
+fn parse_timed_input(input: &str) -> Result<Vec<FallingByte>, ParseError> {
+    nonempty_lines(input)
+        .enumerate()
+        .map(|(i, (row, line))| {
+            let (x, y, time) = match parse_fields(row, line)?[..] {
+                [x, y] => (x, y, i as u32 + 1),
+                [x, y, time] => (x, y, time),
+                _ => {
+                    let message = format!("Expected a line of the form x,y or x,y,t: '{line}'");
+                    return Err(ParseError::at(row, 0, message));
+                }
+            };
+            Ok(FallingByte { pos: Pos2 { x: x as usize, y: y as usize }, time })
+        })
+        .collect()
+}
+
+// BFS from the top left corner where each step takes one unit of time, and a position can only be
+// occupied at times before a byte falls on it. Bytes never disappear, so arriving at a position
+// earlier is never worse than arriving later; that means each position only needs to be visited
+// once, at its earliest possible arrival time, and waiting in place never helps
+fn earliest_exit(bytes: &[FallingByte], size: usize) -> Option<u32> {
+    let mut fall_times = Grid(vec![vec![u32::MAX; size]; size]);
+    for byte in bytes {
+        let fall_time = &mut fall_times[byte.pos];
+        *fall_time = (*fall_time).min(byte.time);
+    }
+
+    let start = Position { x: 0, y: 0 };
+    let end = Position { x: size as i32 - 1, y: size as i32 - 1 };
+    if fall_times[start] == 0 {
+        return None;
+    }
+    if start == end {
+        return Some(0);
+    }
+
+    let mut visited: Grid<bool> = Grid::new(size, size);
+    let mut queue = VecDeque::new();
+    queue.push_back(QueueEntry { pos: start, len: 0 });
+    visited[start] = true;
+
+    let mut expanded = 0;
+    while let Some(QueueEntry { pos, len }) = queue.pop_front() {
+        expanded += 1;
+        let time = len + 1;
+        for (dy, dx) in [(-1, 0), (0, -1), (1, 0), (0, 1)] {
+            let new_pos = pos + Position { x: dx, y: dy };
+            if !visited.in_bounds(new_pos) {
+                continue;
+            }
+
+            if visited[new_pos] || fall_times[new_pos] <= time {
+                continue;
+            }
+
+            if new_pos == end {
+                search::record_search(expanded);
+                return Some(time);
+            }
+
+            visited[new_pos] = true;
+            queue.push_back(QueueEntry { pos: new_pos, len: time });
+        }
+    }
+
+    search::record_search(expanded);
+    None
+}
+
+fn solve_timed(input: &str, size: usize) -> Result<Option<u32>, ParseError> {
+    Ok(earliest_exit(&parse_timed_input(input)?, size))
+}
+
+// Bytes falling one per step, for visualization. The shortest path is only recomputed when a byte
+// lands on it
+struct Memory {
+    bytes: Vec<Pos2<usize>>,
+    fallen: usize,
+    corrupted: Vec<Vec<bool>>,
+    path: Option<Vec<Pos2<usize>>>,
+}
+
+impl Memory {
+    fn new(bytes: Vec<Pos2<usize>>, size: usize) -> Self {
+        let corrupted = vec![vec![false; size]; size];
+        let path = shortest_path(&corrupted);
+        Self { bytes, fallen: 0, corrupted, path }
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(path) = &self.path else { return false };
+        let Some(&byte) = self.bytes.get(self.fallen) else { return false };
+
+        self.corrupted[byte.y][byte.x] = true;
+        self.fallen += 1;
+        if path.contains(&byte) {
+            self.path = shortest_path(&self.corrupted);
+        }
+        true
+    }
+
+    // Cells on the current shortest path
+    fn path_cells(&self) -> Vec<Vec<bool>> {
+        let size = self.corrupted.len();
+        let mut on_path = vec![vec![false; size]; size];
+        for &pos in self.path.iter().flatten() {
+            on_path[pos.y][pos.x] = true;
+        }
+        on_path
+    }
+
+    fn glyph(&self, on_path: &[Vec<bool>], x: usize, y: usize) -> Glyph {
+        if on_path[y][x] {
+            ('O', Color::Rgb(PATH_COLOR))
+        } else if self.corrupted[y][x] {
+            ('#', Color::Rgb(CORRUPTED_COLOR))
+        } else {
+            ('.', Color::Default)
+        }
+    }
+
+    fn status(&self) -> String {
+        match &self.path {
+            Some(path) => format!("{} bytes fallen, path length {}", self.fallen, path.len() - 1),
+            None => {
+                let byte = self.bytes[self.fallen - 1];
+                format!("{} bytes fallen, exit blocked by {},{}", self.fallen, byte.x, byte.y)
+            }
+        }
+    }
+
+    fn render(&self) -> Frame {
+        let on_path = self.path_cells();
+        let size = self.corrupted.len();
+        let lines =
+            (0..size).map(|y| (0..size).map(|x| self.glyph(&on_path, x, y).0).collect()).collect();
+
+        let focus = self.fallen.checked_sub(1).map(|i| (self.bytes[i].x, self.bytes[i].y));
+        Frame { lines, status: self.status(), focus }
+    }
+
+    fn render_ansi(&self) -> String {
+        let on_path = self.path_cells();
+        let size = self.corrupted.len();
+        let map = ansi::render_fn(size, size, |x, y| self.glyph(&on_path, x, y));
+        format!("{map}{}\n", self.status())
+    }
+}
+
+const PATH_COLOR: Rgb = [40, 200, 60];
+const CORRUPTED_COLOR: Rgb = [110, 110, 110];
+
+fn shortest_path(corrupted: &[Vec<bool>]) -> Option<Vec<Pos2<usize>>> {
+    let size = corrupted.len();
+    let end = Pos2 { x: size - 1, y: size - 1 };
+    let result = search::dijkstra(
+        Pos2 { x: 0, y: 0 },
+        |&pos: &Pos2<usize>| {
+            let Pos2 { x, y } = pos;
+            [
+                (x > 0).then(|| Pos2 { x: x - 1, y }),
+                (y > 0).then(|| Pos2 { x, y: y - 1 }),
+                (x + 1 < size).then(|| Pos2 { x: x + 1, y }),
+                (y + 1 < size).then(|| Pos2 { x, y: y + 1 }),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|next| !corrupted[next.y][next.x])
+            .map(|next| (next, 1_u32))
+        },
+        |&pos| pos == end,
+    )?;
+    Some(result.path)
+}
+
+const REAL_START_BYTES: usize = 1024;
+const REAL_SIZE: usize = 71;
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input, REAL_START_BYTES, REAL_SIZE).unwrap_solution(),
+    |input| solve_part_2(input, REAL_START_BYTES, REAL_SIZE).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> =
+    |input| parse_input(input).map(drop).and(parse_timed_input(input).map(drop));
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::terminal_viz()? {
+        let memory = Memory::new(parse_input(&crate::read_input()?)?, REAL_SIZE);
+        return viz::run("Day 18: RAM Run", memory, Memory::step, Memory::render);
+    }
+
+    if crate::has_flag("--timed") {
+        let size = crate::flag_value("--size").map(|size| size.parse()).transpose()?;
+        match solve_timed(&crate::read_input()?, size.unwrap_or(REAL_SIZE))? {
+            Some(time) => println!("{time}"),
+            None => println!("Exit is unreachable"),
+        }
+        return Ok(());
+    }
+
+    crate::run(
+        |input| solve_part_1(input, REAL_START_BYTES, REAL_SIZE),
+        |input| solve_part_2(input, REAL_START_BYTES, REAL_SIZE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day18.txt");
+    const SAMPLE_START_BYTES: usize = 12;
+    const SAMPLE_SIZE: usize = 7;
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(22), solve_part_1(SAMPLE_INPUT, SAMPLE_START_BYTES, SAMPLE_SIZE));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(
+            Ok(Part2Solution(6, 1)),
+            solve_part_2(SAMPLE_INPUT, SAMPLE_START_BYTES, SAMPLE_SIZE)
+        );
+    }
+
+    #[test]
+    fn search_stats() {
+        stats::reset();
+        solve_part_2(SAMPLE_INPUT, SAMPLE_START_BYTES, SAMPLE_SIZE).unwrap();
+
+        // One BFS per binary search step
+        let searches = stats::get("searches");
+        assert!((2..=5).contains(&searches), "{searches}");
+        assert!(stats::get("nodes_expanded") > searches);
+    }
+
+    #[test]
+    fn memory_steps() {
+        let mut memory = Memory::new(parse_input(SAMPLE_INPUT).unwrap(), SAMPLE_SIZE);
+        for _ in 0..SAMPLE_START_BYTES {
+            assert!(memory.step());
+        }
+        assert_eq!("12 bytes fallen, path length 22", memory.render().status);
+        let start = memory.render_ansi();
+
+        while memory.step() {}
+        assert_eq!("21 bytes fallen, exit blocked by 6,1", memory.render().status);
+        crate::snapshot::assert_snapshot(
+            "day18_memory",
+            &format!("{start}---\n{}", memory.render_ansi()),
+        );
+    }
+
+    #[test]
+    fn timed() {
+        // With bytes falling in order, the path has to stay ahead of them
+        assert_eq!(Ok(Some(12)), solve_timed(SAMPLE_INPUT, SAMPLE_SIZE));
+
+        // Bytes that are already down at time 0 behave like part 1
+        let timed: String = SAMPLE_INPUT
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let time = if i < SAMPLE_START_BYTES { 0 } else { 1000 };
+                format!("{line},{time}\n")
+            })
+            .collect();
+        assert_eq!(
+            solve_part_1(SAMPLE_INPUT, SAMPLE_START_BYTES, SAMPLE_SIZE).map(Some),
+            solve_timed(&timed, SAMPLE_SIZE)
+        );
+
+        // A byte that falls just after the path passes through it doesn't matter, but one that falls
+        // at the moment of arrival blocks it
+        assert_eq!(Ok(Some(4)), solve_timed("1,0,0\n1,1,0\n0,2,3\n", 3));
+        assert_eq!(Ok(None), solve_timed("1,0,0\n1,1,0\n0,1,1\n", 3));
+        assert_eq!(Ok(None), solve_timed("0,0,0\n", 3));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse_input("5,4\n\n4,x2\n").unwrap_err();
+        assert_eq!("line 3, column 3: Invalid coordinate: 'x2'", err.to_string());
+
+        let err = parse_input("5,4\n4,2,1\n").unwrap_err();
+        assert_eq!("line 2, column 1: Expected a line of the form x,y: '4,2,1'", err.to_string());
+        assert!(parse_timed_input("5,4\n4,2,1\n").is_ok());
+        assert!(parse_timed_input("5\n").is_err());
+    }
+}
@@ -0,0 +1,274 @@
+//! Day 19: Linen Layout
+//!
+//! <https://adventofcode.com/2024/day/19>
+
+use crate::days::Solver;
+use crate::{Memo, Trie, parallel, time_micros};
+use std::error::Error;
+
+#[derive(Debug)]
+struct Input<'a> {
+    towels: Vec<&'a [u8]>,
+    designs: Vec<&'a [u8]>,
+}
+
+fn parse_input(input: &str) -> Input<'_> {
+    let mut lines = input.lines();
+
+    let towels: Vec<_> = lines.next().unwrap().split(", ").map(|s| s.as_bytes()).collect();
+    lines.next();
+    let designs: Vec<_> =
+        lines.filter(|line| !line.is_empty()).map(|line| line.as_bytes()).collect();
+
+    Input { towels, designs }
+}
+
+fn solve_part_1(input: &str) -> usize {
+    let Input { towels, designs } = parse_input(input);
+    let towels = possibility_towels(&towels);
+
+    parallel::count_by(&designs, |&design| is_design_possible(&towels, design))
+}
+
+fn solve_part_2(input: &str) -> u64 {
+    let Input { towels, designs } = parse_input(input);
+    let towels = counting_towels(&towels);
+
+    // Each chunk of designs gets its own memo, so suffixes shared across chunks are recomputed in
+    // each chunk; the counts are the same either way. Stats are thread-local, so each chunk returns
+    // its memo and they're recorded here
+    let chunks = parallel::map_chunks(&designs, |designs| {
+        let mut memo = Memo::new();
+        let ways: u64 =
+            designs.iter().map(|design| ways_to_make_design(&towels, design, &mut memo)).sum();
+
+        debug!(
+            entries = memo.len(),
+            hit_rate = memo.hit_rate(),
+            "Counted arrangements for designs"
+        );
+
+        (ways, memo)
+    });
+
+    chunks
+        .into_iter()
+        .map(|(ways, memo)| {
+            memo.record_stats();
+            ways
+        })
+        .sum()
+}
+
+// Source of towels that could match the start of a design
+trait TowelMatcher {
+    // Lengths of the towels that match the start of `design`
+    fn match_lens<'a>(&'a self, design: &'a [u8]) -> impl Iterator<Item = usize> + 'a;
+}
+
+// Unprocessed matcher: every towel is tested
+impl TowelMatcher for [&[u8]] {
+    fn match_lens<'a>(&'a self, design: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.iter().filter(move |&&towel| design.starts_with(towel)).map(|towel| towel.len())
+    }
+}
+
+// Preprocessed towels: walking the trie along the design finds every matching towel at once
+impl TowelMatcher for Trie {
+    fn match_lens<'a>(&'a self, design: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.prefix_lens(design)
+    }
+}
+
+// For counting arrangements, every distinct towel matters
+fn counting_towels(towels: &[&[u8]]) -> Trie {
+    dedup_towels(towels).into_iter().collect()
+}
+
+// For checking whether a design is possible at all, towels that can be composed out of other
+// towels are redundant and can be removed
+fn possibility_towels(towels: &[&[u8]]) -> Trie {
+    let mut towels = dedup_towels(towels);
+
+    // A towel can only be composed out of strictly shorter towels, so process shortest first
+    towels.sort_by_key(|towel| towel.len());
+    let mut kept = Trie::new();
+    for towel in towels {
+        if !is_design_possible(&kept, towel) {
+            kept.insert(towel);
+        }
+    }
+
+    kept
+}
+
+fn dedup_towels<'a>(towels: &[&'a [u8]]) -> Vec<&'a [u8]> {
+    let mut towels: Vec<_> = towels.iter().copied().filter(|towel| !towel.is_empty()).collect();
+    towels.sort();
+    towels.dedup();
+    towels
+}
+
+fn is_design_possible<M: TowelMatcher + ?Sized>(towels: &M, design: &[u8]) -> bool {
+    if design.is_empty() {
+        return true;
+    }
+
+    towels.match_lens(design).any(|len| is_design_possible(towels, &design[len..]))
+}
+
+fn ways_to_make_design<'a, M: TowelMatcher + ?Sized>(
+    towels: &M,
+    design: &'a [u8],
+    memo: &mut Memo<&'a [u8], u64>,
+) -> u64 {
+    if design.is_empty() {
+        return 1;
+    }
+
+    memo.get_or_compute(design, |memo| {
+        towels.match_lens(design).map(|len| ways_to_make_design(towels, &design[len..], memo)).sum()
+    })
+}
+
+// Times both parts with and without towel preprocessing
+fn bench_preprocessing(input: &str) {
+    let Input { towels, designs } = parse_input(input);
+
+    let possibility_set = possibility_towels(&towels);
+    let counting_set = counting_towels(&towels);
+    println!(
+        "Towels: {} raw, {} for part 1, {} for part 2",
+        towels.len(),
+        possibility_set.len(),
+        counting_set.len()
+    );
+
+    let part_1 = |matcher: &dyn Fn(&[u8]) -> bool| designs.iter().filter(|&&d| matcher(d)).count();
+    let raw_1 = time_micros(|| part_1(&|design| is_design_possible(towels.as_slice(), design)));
+    let processed_1 = time_micros(|| {
+        let towels = possibility_towels(&towels);
+        part_1(&|design| is_design_possible(&towels, design))
+    });
+    println!("Part 1: {raw_1}μs unprocessed, {processed_1}μs preprocessed");
+
+    let raw_2 = time_micros(|| {
+        let mut memo = Memo::new();
+        designs
+            .iter()
+            .map(|design| ways_to_make_design(towels.as_slice(), design, &mut memo))
+            .sum::<u64>()
+    });
+    let processed_2 = time_micros(|| {
+        let towels = counting_towels(&towels);
+        let mut memo = Memo::new();
+        designs.iter().map(|design| ways_to_make_design(&towels, design, &mut memo)).sum::<u64>()
+    });
+    println!("Part 2: {raw_2}μs unprocessed, {processed_2}μs preprocessed");
+}
+
+// Counts arrangements of every suffix of each design with a bottom-up table, trying every towel at
+// every position with no preprocessing
+#[cfg(feature = "reference")]
+fn reference_ways(towels: &[&[u8]], design: &[u8]) -> u64 {
+    let mut ways = vec![0; design.len() + 1];
+    ways[design.len()] = 1;
+    for i in (0..design.len()).rev() {
+        ways[i] = towels
+            .iter()
+            .filter(|towel| !towel.is_empty() && design[i..].starts_with(towel))
+            .map(|towel| ways[i + towel.len()])
+            .sum();
+    }
+    ways[0]
+}
+
+// Duplicate towels are the same pattern, so they're counted once
+#[cfg(feature = "reference")]
+fn solve_reference(input: &str, count_ways: bool) -> u64 {
+    let Input { mut towels, designs } = parse_input(input);
+    towels.sort();
+    towels.dedup();
+
+    designs
+        .iter()
+        .map(|design| {
+            let ways = reference_ways(&towels, design);
+            if count_ways { ways } else { u64::from(ways != 0) }
+        })
+        .sum()
+}
+
+#[cfg(feature = "reference")]
+pub const DIFFERENTIAL: crate::differential::Differential = crate::differential::Differential {
+    optimized: SOLVER,
+    reference: Solver::Parts(
+        |input| solve_reference(input, false).into(),
+        |input| solve_reference(input, true).into(),
+    ),
+    generate: crate::generate::towel_designs,
+    size: 50,
+};
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve_part_1(input).into(), |input| solve_part_2(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::has_flag("--bench-preprocessing") {
+        bench_preprocessing(&crate::read_input()?);
+        return Ok(());
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day19.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(6, solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(16, solve_part_2(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn preprocessing() {
+        let towels: Vec<&[u8]> = vec![b"r", b"wr", b"b", b"g", b"bwu", b"rb", b"gb", b"br", b"b"];
+
+        let counting = counting_towels(&towels);
+        assert_eq!(8, counting.len());
+        assert_eq!(vec![1, 2], counting.match_lens(b"brwu").collect::<Vec<_>>());
+        assert_eq!(0, counting.match_lens(b"x").count());
+
+        // rb, gb, and br are all compositions of single-letter towels
+        let possibility = possibility_towels(&towels);
+        assert_eq!(5, possibility.len());
+        assert!(possibility.contains(b"bwu") && !possibility.contains(b"br"));
+        assert_eq!(vec![1], possibility.match_lens(b"brwu").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn preprocessing_matches_unprocessed() {
+        let Input { towels, designs } = parse_input(SAMPLE_INPUT);
+        let possibility = possibility_towels(&towels);
+        let counting = counting_towels(&towels);
+
+        for design in designs {
+            assert_eq!(
+                is_design_possible(towels.as_slice(), design),
+                is_design_possible(&possibility, design)
+            );
+            assert_eq!(
+                ways_to_make_design(towels.as_slice(), design, &mut Memo::new()),
+                ways_to_make_design(&counting, design, &mut Memo::new())
+            );
+        }
+    }
+}
@@ -0,0 +1,483 @@
+//! Day 20: Race Condition
+//!
+//! <https://adventofcode.com/2024/day/20>
+
+use crate::days::Solver;
+use crate::serialize::Json;
+use crate::{Grid, ParseError, Pos2, SolverOutput, parallel, parse, stats};
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::fs;
+
+type Position = Pos2<i32>;
+
+#[derive(Debug)]
+struct Input {
+    walls: Grid<bool>,
+    start: Position,
+    end: Position,
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let markers = [('S', "No start position in input"), ('E', "No end position in input")];
+    let (walls, [start, end]) = parse::wall_maze(input, markers)?;
+    Ok(Input { walls, start, end })
+}
+
+fn solve(input: &str, min_save: u32, max_cheat_time: u32) -> Result<u32, ParseError> {
+    let Input { walls, start, end } = parse_input(input)?;
+
+    let distances_from_end = build_distances_from_end(&walls, end);
+    let max_path_len = distances_from_end[start] - min_save;
+
+    Ok(count_possible_cheats(&walls, start, &distances_from_end, max_cheat_time, max_path_len))
+}
+
+#[rustfmt::skip]
+const DELTAS: [Position; 4] =
+    [
+        Position { x: -1, y: 0 },
+        Position { x: 0, y: -1 },
+        Position { x: 1, y: 0 },
+        Position { x: 0, y: 1, },
+    ];
+
+#[derive(Debug, Clone, Copy)]
+struct QueueEntry {
+    pos: Position,
+    distance: u32,
+}
+
+// BFS from the end position to build a grid of the min distance from each position to the end
+fn build_distances_from_end(walls: &Grid<bool>, end: Position) -> Grid<u32> {
+    let mut distances = Grid::same_size_as(walls);
+    let mut visited = Grid::same_size_as(walls);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(QueueEntry { pos: end, distance: 0 });
+    visited[end] = true;
+
+    let mut nodes_expanded = 0;
+    while let Some(QueueEntry { pos, distance }) = queue.pop_front() {
+        distances[pos] = distance;
+        nodes_expanded += 1;
+
+        for delta in DELTAS {
+            let new_pos = pos + delta;
+            if walls[new_pos] || visited[new_pos] {
+                continue;
+            }
+
+            queue.push_back(QueueEntry { pos: new_pos, distance: distance + 1 });
+            visited[new_pos] = true;
+        }
+    }
+
+    stats::add("distance_bfs_nodes", nodes_expanded);
+    debug!(nodes_expanded, "Computed distances from end");
+
+    distances
+}
+
+// BFS from the start position, and at each position, check if it's possible to use a cheat starting
+// at that position to reach the end in less than `min_path_len`. The positions are independent, so
+// with the `parallel` feature they are split across threads after the BFS
+fn count_possible_cheats(
+    walls: &Grid<bool>,
+    start: Position,
+    distances_from_end: &Grid<u32>,
+    max_cheat_time: u32,
+    max_path_len: u32,
+) -> u32 {
+    let cheat_starts = cheat_starts(walls, start, max_path_len);
+
+    let chunks = parallel::map_chunks(&cheat_starts, |entries| {
+        let mut counters = CheatCounters::default();
+        let count: u32 = entries
+            .iter()
+            .map(|&entry| {
+                cheats_from(
+                    walls,
+                    distances_from_end,
+                    entry,
+                    max_cheat_time,
+                    max_path_len,
+                    &mut counters,
+                )
+            })
+            .sum();
+        (count, counters)
+    });
+
+    let mut count = 0;
+    let mut counters =
+        CheatCounters { nodes_expanded: cheat_starts.len() as u64, ..CheatCounters::default() };
+    for (chunk_count, chunk_counters) in chunks {
+        count += chunk_count;
+        counters.cheat_pairs_examined += chunk_counters.cheat_pairs_examined;
+        counters.pruned_candidates += chunk_counters.pruned_candidates;
+    }
+    counters.record();
+
+    count
+}
+
+// Positions in BFS order from the start, up to the last distance at which a cheat could still save
+// enough time
+fn cheat_starts(walls: &Grid<bool>, start: Position, max_path_len: u32) -> Vec<QueueEntry> {
+    let mut visited = Grid::same_size_as(walls);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(QueueEntry { pos: start, distance: 0 });
+    visited[start] = true;
+
+    let mut entries = Vec::new();
+    while let Some(entry) = queue.pop_front() {
+        if entry.distance > max_path_len - 2 {
+            // Every useful cheat must take at least 2 steps: one to step on a wall and one to step
+            // onto an open space
+            break;
+        }
+        entries.push(entry);
+
+        for delta in DELTAS {
+            let new_pos = entry.pos + delta;
+            if walls[new_pos] || visited[new_pos] {
+                continue;
+            }
+
+            queue.push_back(QueueEntry { pos: new_pos, distance: entry.distance + 1 });
+            visited[new_pos] = true;
+        }
+    }
+
+    entries
+}
+
+// Number of cheats starting at `pos` that reach the end within `max_path_len`
+fn cheats_from(
+    walls: &Grid<bool>,
+    distances_from_end: &Grid<u32>,
+    QueueEntry { pos, distance }: QueueEntry,
+    max_cheat_time: u32,
+    max_path_len: u32,
+    counters: &mut CheatCounters,
+) -> u32 {
+    let mut count = 0;
+    for cheat_distance in 2..=max_cheat_time {
+        if distance + cheat_distance > max_path_len {
+            // Every remaining diamond is too far away to save enough time
+            counters.pruned_candidates +=
+                (cheat_distance..=max_cheat_time).map(|d| 4 * u64::from(d)).sum::<u64>();
+            break;
+        }
+
+        // Traverse the diamond formed by all spaces `cheat_distance` away from `pos`
+        let mut cdx = -(cheat_distance as i32);
+        let mut cdy = 0;
+        let mut cdx_delta = 1;
+        let mut cdy_delta = -1;
+        loop {
+            let cheat_pos = pos + Position { x: cdx, y: cdy };
+            counters.cheat_pairs_examined += 1;
+            if walls.in_bounds(cheat_pos)
+                && !walls[cheat_pos]
+                && distance + cheat_distance + distances_from_end[cheat_pos] <= max_path_len
+            {
+                count += 1;
+            }
+
+            cdx += cdx_delta;
+            cdy += cdy_delta;
+            if cdx == 0 || cdy == 0 {
+                // Rotate right
+                let t = -cdy_delta;
+                cdy_delta = cdx_delta;
+                cdx_delta = t;
+            }
+
+            if cdx == -(cheat_distance as i32) {
+                break;
+            }
+        }
+    }
+
+    count
+}
+
+#[derive(Debug, Default)]
+struct CheatCounters {
+    nodes_expanded: u64,
+    cheat_pairs_examined: u64,
+    pruned_candidates: u64,
+}
+
+impl CheatCounters {
+    fn record(&self) {
+        stats::add("cheat_bfs_nodes", self.nodes_expanded);
+        stats::add("cheat_pairs_examined", self.cheat_pairs_examined);
+        stats::add("pruned_candidates", self.pruned_candidates);
+        debug!(
+            nodes_expanded = self.nodes_expanded,
+            cheat_pairs_examined = self.cheat_pairs_examined,
+            pruned_candidates = self.pruned_candidates,
+            "Finished cheat search"
+        );
+    }
+}
+
+const P1_CHEAT_DISTANCE: u32 = 2;
+const P2_CHEAT_DISTANCE: u32 = 20;
+
+fn solve_part_1(input: &str, min_save: u32) -> Result<u32, ParseError> {
+    solve(input, min_save, P1_CHEAT_DISTANCE)
+}
+
+fn solve_part_2(input: &str, min_save: u32) -> Result<u32, ParseError> {
+    solve(input, min_save, P2_CHEAT_DISTANCE)
+}
+
+const REAL_MIN_SAVE: u32 = 100;
+
+// Maps time saved to the number of distinct cheats (start and end position pairs) that save
+// exactly that much, over every cheat that saves any time at all
+fn savings_histogram(
+    walls: &Grid<bool>,
+    distances_from_end: &Grid<u32>,
+    end: Position,
+    max_cheat_time: u32,
+) -> BTreeMap<u32, u32> {
+    let max_cheat_time = max_cheat_time as i32;
+    let reachable = |pos: Position| {
+        walls.in_bounds(pos) && !walls[pos] && (pos == end || distances_from_end[pos] != 0)
+    };
+
+    let mut histogram = BTreeMap::new();
+    for y in 0..walls.rows() as i32 {
+        for x in 0..walls.cols() as i32 {
+            let pos = Position { x, y };
+            if !reachable(pos) {
+                continue;
+            }
+
+            for dy in -max_cheat_time..=max_cheat_time {
+                let max_dx = max_cheat_time - dy.abs();
+                for dx in -max_dx..=max_dx {
+                    let cheat_time = (dx.abs() + dy.abs()) as u32;
+                    let cheat_pos = pos + Position { x: dx, y: dy };
+                    if cheat_time < 2 || !reachable(cheat_pos) {
+                        continue;
+                    }
+
+                    let remaining = cheat_time + distances_from_end[cheat_pos];
+                    if remaining < distances_from_end[pos] {
+                        *histogram.entry(distances_from_end[pos] - remaining).or_default() += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    histogram
+}
+
+const REPORT_THRESHOLDS: [u32; 2] = [50, 100];
+
+// Savings distributions for both parts, for plotting outside of this crate. Threshold counts are
+// the number of cheats saving at least that much, i.e. the puzzle answer for that minimum savings
+fn build_report(input: &str) -> Result<Json, ParseError> {
+    let Input { walls, start, end } = parse_input(input)?;
+    let distances_from_end = build_distances_from_end(&walls, end);
+
+    let parts =
+        [(1_u32, P1_CHEAT_DISTANCE), (2, P2_CHEAT_DISTANCE)].map(|(part, max_cheat_time)| {
+            let histogram = savings_histogram(&walls, &distances_from_end, end, max_cheat_time);
+            let thresholds = REPORT_THRESHOLDS.map(|threshold| {
+                let count: u32 = histogram.range(threshold..).map(|(_, &count)| count).sum();
+                (threshold.to_string(), Json::from(count))
+            });
+            let histogram = histogram.into_iter().map(|(saved, count)| {
+                Json::object([("saved", Json::from(saved)), ("count", Json::from(count))])
+            });
+
+            Json::object([
+                ("part", Json::from(part)),
+                ("max_cheat_time", Json::from(max_cheat_time)),
+                ("thresholds", Json::object(thresholds)),
+                ("histogram", Json::array(histogram)),
+            ])
+        });
+
+    Ok(Json::object([
+        ("rows", Json::from(walls.rows())),
+        ("cols", Json::from(walls.cols())),
+        ("path_length", Json::from(distances_from_end[start])),
+        ("parts", Json::array(parts)),
+    ]))
+}
+
+// Checks every pair of track positions, using BFS distances from the start. Only valid for inputs
+// with a single track, which all puzzle inputs have
+#[cfg(feature = "reference")]
+fn solve_reference(input: &str, min_save: u32, max_cheat_time: u32) -> Result<u32, ParseError> {
+    let Input { walls, start, .. } = parse_input(input)?;
+
+    // Distances from the start are distances along the track
+    let distances = build_distances_from_end(&walls, start);
+    let track: Vec<Position> = (0..walls.rows() as i32)
+        .flat_map(|y| (0..walls.cols() as i32).map(move |x| Position { x, y }))
+        .filter(|&pos| !walls[pos])
+        .collect();
+
+    let mut count = 0;
+    for &from in &track {
+        for &to in &track {
+            let cheat_time = from.x.abs_diff(to.x) + from.y.abs_diff(to.y);
+            if (2..=max_cheat_time).contains(&cheat_time)
+                && distances[to] >= distances[from] + cheat_time + min_save
+            {
+                count += 1;
+            }
+        }
+    }
+
+    Ok(count)
+}
+
+// Generated tracks are much shorter than real ones, so use a smaller minimum savings
+#[cfg(feature = "reference")]
+const REFERENCE_MIN_SAVE: u32 = 10;
+
+#[cfg(feature = "reference")]
+pub const DIFFERENTIAL: crate::differential::Differential = crate::differential::Differential {
+    optimized: Solver::Parts(
+        |input| solve_part_1(input, REFERENCE_MIN_SAVE).unwrap_solution(),
+        |input| solve_part_2(input, REFERENCE_MIN_SAVE).unwrap_solution(),
+    ),
+    reference: Solver::Parts(
+        |input| solve_reference(input, REFERENCE_MIN_SAVE, P1_CHEAT_DISTANCE).unwrap_solution(),
+        |input| solve_reference(input, REFERENCE_MIN_SAVE, P2_CHEAT_DISTANCE).unwrap_solution(),
+    ),
+    generate: crate::generate::racetrack,
+    size: 21,
+};
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input, REAL_MIN_SAVE).unwrap_solution(),
+    |input| solve_part_2(input, REAL_MIN_SAVE).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(path) = crate::flag_value("--report") {
+        fs::write(path, format!("{}\n", build_report(&crate::read_input()?)?))?;
+        return Ok(());
+    }
+
+    crate::run(
+        |input| solve_part_1(input, REAL_MIN_SAVE),
+        |input| solve_part_2(input, REAL_MIN_SAVE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day20.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(1), solve_part_1(SAMPLE_INPUT, 64));
+        assert_eq!(Ok(2), solve_part_1(SAMPLE_INPUT, 40));
+        assert_eq!(Ok(3), solve_part_1(SAMPLE_INPUT, 38));
+        assert_eq!(Ok(4), solve_part_1(SAMPLE_INPUT, 36));
+        assert_eq!(Ok(5), solve_part_1(SAMPLE_INPUT, 20));
+        assert_eq!(Ok(8), solve_part_1(SAMPLE_INPUT, 12));
+        assert_eq!(Ok(10), solve_part_1(SAMPLE_INPUT, 10));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok(3), solve_part_2(SAMPLE_INPUT, 76));
+        assert_eq!(Ok(7), solve_part_2(SAMPLE_INPUT, 74));
+        assert_eq!(Ok(29), solve_part_2(SAMPLE_INPUT, 72));
+        assert_eq!(Ok(41), solve_part_2(SAMPLE_INPUT, 70));
+        assert_eq!(Ok(55), solve_part_2(SAMPLE_INPUT, 68));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse_input("#####\n#S.E#\n#.~.#\n#####\n").unwrap_err();
+        assert_eq!("line 3, column 3: Unexpected map character: '~'", err.to_string());
+        assert_eq!(Err(ParseError::new("No end position in input")), solve_part_1("#S.#\n", 1));
+    }
+
+    #[test]
+    fn histogram() {
+        let Input { walls, end, .. } = parse_input(SAMPLE_INPUT).unwrap();
+        let distances_from_end = build_distances_from_end(&walls, end);
+
+        // From the puzzle description
+        let histogram = savings_histogram(&walls, &distances_from_end, end, P1_CHEAT_DISTANCE);
+        assert_eq!(
+            BTreeMap::from([
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1)
+            ]),
+            histogram
+        );
+
+        let histogram = savings_histogram(&walls, &distances_from_end, end, P2_CHEAT_DISTANCE);
+        assert_eq!(Some(&3), histogram.get(&76));
+        let at_least_50: u32 = histogram.range(50..).map(|(_, &count)| count).sum();
+        assert_eq!(Ok(at_least_50), solve_part_2(SAMPLE_INPUT, 50));
+    }
+
+    #[test]
+    fn report() {
+        let report = build_report(SAMPLE_INPUT).unwrap().to_string();
+        assert!(report.starts_with(r#"{"rows":15,"cols":15,"path_length":84,"parts":[{"part":1,"#));
+        assert!(report.contains(r#""max_cheat_time":2,"thresholds":{"50":1,"100":0},"#));
+        assert!(report.contains(r#""histogram":[{"saved":2,"count":14},{"saved":4,"count":14},"#));
+    }
+
+    // Pins the amount of work done on the sample so that changes to the search or pruning show up
+    // as explicit count changes
+    #[test]
+    fn operation_counts() {
+        stats::reset();
+        solve_part_1(SAMPLE_INPUT, 10).unwrap();
+        assert_eq!(
+            vec![
+                ("cheat_bfs_nodes", 73),
+                ("cheat_pairs_examined", 584),
+                ("distance_bfs_nodes", 85),
+                ("pruned_candidates", 0),
+            ],
+            stats::take()
+        );
+
+        solve_part_2(SAMPLE_INPUT, 50).unwrap();
+        assert_eq!(
+            vec![
+                ("cheat_bfs_nodes", 33),
+                ("cheat_pairs_examined", 17784),
+                ("distance_bfs_nodes", 85),
+                ("pruned_candidates", 9804),
+            ],
+            stats::take()
+        );
+    }
+}
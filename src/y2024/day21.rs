@@ -0,0 +1,567 @@
+//! Day 21: Keypad Conundrum
+//!
+//! <https://adventofcode.com/2024/day/21>
+
+use crate::days::Solver;
+use crate::{Memo, Pos2, generate, parallel, stats, time_micros};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::cmp;
+use std::cmp::Ordering;
+use std::error::Error;
+
+type Position = Pos2<i32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NumericKey {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Activate,
+}
+
+// Numeric keypad layout:
+//
+//   | 0 1 2
+//  --------
+// 0 | 7 8 9
+// 1 | 4 5 6
+// 2 | 1 2 3
+// 3 |   0 A
+//
+// The bottom left corner (0, 3) is a gap that must not be touched
+impl NumericKey {
+    const GAP: Position = Position::xy(0, 3);
+
+    const fn position(self) -> Position {
+        match self {
+            Self::Zero => Position::xy(1, 3),
+            Self::One => Position::xy(0, 2),
+            Self::Two => Position::xy(1, 2),
+            Self::Three => Position::xy(2, 2),
+            Self::Four => Position::xy(0, 1),
+            Self::Five => Position::xy(1, 1),
+            Self::Six => Position::xy(2, 1),
+            Self::Seven => Position::xy(0, 0),
+            Self::Eight => Position::xy(1, 0),
+            Self::Nine => Position::xy(2, 0),
+            Self::Activate => Position::xy(2, 3),
+        }
+    }
+
+    fn from_char(c: char) -> Self {
+        match c {
+            '0' => Self::Zero,
+            '1' => Self::One,
+            '2' => Self::Two,
+            '3' => Self::Three,
+            '4' => Self::Four,
+            '5' => Self::Five,
+            '6' => Self::Six,
+            '7' => Self::Seven,
+            '8' => Self::Eight,
+            '9' => Self::Nine,
+            'A' => Self::Activate,
+            _ => panic!("Invalid input character: {c}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DirectionalKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    Activate,
+}
+
+// Directional keypad layout:
+//
+//   | 0 1 2
+//  --------
+// 0 |   ^ A
+// 1 | < v >
+//
+// The top left corner (0, 0) is a gap that must not be touched
+impl DirectionalKey {
+    const GAP: Position = Position::xy(0, 0);
+
+    const fn position(self) -> Position {
+        match self {
+            Self::Up => Position::xy(1, 0),
+            Self::Down => Position::xy(1, 1),
+            Self::Left => Position::xy(0, 1),
+            Self::Right => Position::xy(2, 1),
+            Self::Activate => Position::xy(2, 0),
+        }
+    }
+
+    fn from_position(position: Position) -> Self {
+        match (position.x, position.y) {
+            (1, 0) => Self::Up,
+            (1, 1) => Self::Down,
+            (0, 1) => Self::Left,
+            (2, 1) => Self::Right,
+            (2, 0) => Self::Activate,
+            _ => panic!("Invalid directional keypad position: {position:?}"),
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::Up),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            '>' => Some(Self::Right),
+            'A' => Some(Self::Activate),
+            _ => None,
+        }
+    }
+
+    fn x_direction(delta: Position) -> Option<Self> {
+        match delta.x.cmp(&0) {
+            Ordering::Less => Some(Self::Left),
+            Ordering::Greater => Some(Self::Right),
+            Ordering::Equal => None,
+        }
+    }
+
+    fn y_direction(delta: Position) -> Option<Self> {
+        match delta.y.cmp(&0) {
+            Ordering::Less => Some(Self::Up),
+            Ordering::Greater => Some(Self::Down),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+// Cost of the human pressing each directional key. The puzzle itself uses a cost of 1 for every key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyCosts {
+    up: u64,
+    down: u64,
+    left: u64,
+    right: u64,
+    activate: u64,
+}
+
+impl Default for KeyCosts {
+    fn default() -> Self {
+        Self { up: 1, down: 1, left: 1, right: 1, activate: 1 }
+    }
+}
+
+impl KeyCosts {
+    fn cost(&self, key: DirectionalKey) -> u64 {
+        match key {
+            DirectionalKey::Up => self.up,
+            DirectionalKey::Down => self.down,
+            DirectionalKey::Left => self.left,
+            DirectionalKey::Right => self.right,
+            DirectionalKey::Activate => self.activate,
+        }
+    }
+
+    fn cost_mut(&mut self, key: DirectionalKey) -> &mut u64 {
+        match key {
+            DirectionalKey::Up => &mut self.up,
+            DirectionalKey::Down => &mut self.down,
+            DirectionalKey::Left => &mut self.left,
+            DirectionalKey::Right => &mut self.right,
+            DirectionalKey::Activate => &mut self.activate,
+        }
+    }
+
+    // Parses a list like "<=3,>=2"; keys that are not listed keep a cost of 1
+    fn parse(s: &str) -> Result<Self, String> {
+        let mut costs = Self::default();
+        for entry in s.split(',').filter(|entry| !entry.is_empty()) {
+            let (key, cost) =
+                entry.split_once('=').ok_or_else(|| format!("Invalid key cost: '{entry}'"))?;
+
+            let mut key_chars = key.chars();
+            let (Some(c), None) = (key_chars.next(), key_chars.next()) else {
+                return Err(format!("Invalid directional key: '{key}'"));
+            };
+            let key = DirectionalKey::from_char(c)
+                .ok_or_else(|| format!("Invalid directional key: '{c}'"))?;
+            let cost: u64 = cost.parse().map_err(|_| format!("Invalid key cost: '{cost}'"))?;
+
+            *costs.cost_mut(key) = cost;
+        }
+
+        Ok(costs)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Code {
+    keys: Vec<NumericKey>,
+    value: u64,
+}
+
+fn parse_input(input: &str) -> Vec<Code> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let keys = line.chars().map(NumericKey::from_char).collect();
+            let value = line[..3].parse::<u64>().unwrap();
+
+            Code { keys, value }
+        })
+        .collect()
+}
+
+fn solve_with_costs(input: &str, middle_robots: u32, costs: &KeyCosts) -> u64 {
+    let codes = parse_input(input);
+
+    if parallel::ENABLED {
+        total_complexity_parallel(&codes, middle_robots, costs)
+    } else {
+        let mut cache = Memo::new();
+        let complexity = total_complexity(&codes, middle_robots, costs, &mut cache);
+        cache.record_stats();
+        complexity
+    }
+}
+
+fn total_complexity(
+    codes: &[Code],
+    middle_robots: u32,
+    costs: &KeyCosts,
+    cache: &mut Memo<CacheKey, u64>,
+) -> u64 {
+    let complexity = codes
+        .iter()
+        .map(|code| {
+            let min_distance =
+                find_min_distance(&code.keys, NumericKey::Activate, middle_robots, costs, cache);
+            min_distance * code.value
+        })
+        .sum();
+
+    debug!(
+        middle_robots,
+        codes = codes.len(),
+        entries = cache.len(),
+        hit_rate = cache.hit_rate(),
+        "Computed minimum distances for codes"
+    );
+
+    complexity
+}
+
+const DIRECTIONAL_KEYS: [DirectionalKey; 5] = [
+    DirectionalKey::Up,
+    DirectionalKey::Down,
+    DirectionalKey::Left,
+    DirectionalKey::Right,
+    DirectionalKey::Activate,
+];
+
+// Fills a cache with every move between directional keys at every depth. Codes only differ in their
+// numeric keypad moves, so a warmed cache makes each code's computation almost all lookups
+fn warm_cache(middle_robots: u32, costs: &KeyCosts) -> Memo<CacheKey, u64> {
+    let mut cache = Memo::new();
+    for depth in 1..=middle_robots {
+        for start in DIRECTIONAL_KEYS {
+            for target in DIRECTIONAL_KEYS {
+                find_min_distance_key(
+                    start.position(),
+                    target.position(),
+                    depth,
+                    DirectionalKey::GAP,
+                    costs,
+                    &mut cache,
+                );
+            }
+        }
+    }
+    cache
+}
+
+// Codes are independent, so split them across threads, each with its own copy of a pre-warmed
+// cache. Avoids any synchronization on the cache at the cost of a few hundred copied entries
+fn total_complexity_parallel(codes: &[Code], middle_robots: u32, costs: &KeyCosts) -> u64 {
+    let warmed = warm_cache(middle_robots, costs);
+    let chunks = parallel::spawn_chunks(codes, |chunk| {
+        let mut cache = warmed.clone();
+        (total_complexity(chunk, middle_robots, costs, &mut cache), cache)
+    });
+
+    // Each chunk's cache starts with the warmed cache's counts
+    warmed.record_stats();
+    chunks
+        .into_iter()
+        .map(|(complexity, cache)| {
+            stats::add("memo_hits", cache.hits() - warmed.hits());
+            stats::add("memo_misses", cache.misses() - warmed.misses());
+            complexity
+        })
+        .sum()
+}
+
+// Times sequential and parallel evaluation on increasingly large sets of generated codes
+fn bench_scaling() {
+    let costs = KeyCosts::default();
+    let mut rng = StdRng::seed_from_u64(21);
+    for count in [5, 50, 500, 5000, 50000] {
+        let codes = parse_input(&generate::door_codes(&mut rng, count));
+        let sequential =
+            time_micros(|| total_complexity(&codes, P2_ROBOTS, &costs, &mut Memo::new()));
+        let parallel = time_micros(|| total_complexity_parallel(&codes, P2_ROBOTS, &costs));
+        println!("{count} codes: {sequential}μs sequential, {parallel}μs parallel");
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CacheKey {
+    start: Position,
+    target: Position,
+    depth_remaining: u32,
+    gap: Position,
+}
+
+fn find_min_distance(
+    code: &[NumericKey],
+    start: NumericKey,
+    middle_robots: u32,
+    costs: &KeyCosts,
+    cache: &mut Memo<CacheKey, u64>,
+) -> u64 {
+    if code.is_empty() {
+        return 0;
+    }
+
+    find_min_distance_key(
+        start.position(),
+        code[0].position(),
+        middle_robots + 1,
+        NumericKey::GAP,
+        costs,
+        cache,
+    ) + find_min_distance(&code[1..], code[0], middle_robots, costs, cache)
+}
+
+// Find the min distance of the path from `start` to `target` at the specified depth
+fn find_min_distance_key(
+    start: Position,
+    target: Position,
+    depth_remaining: u32,
+    gap: Position,
+    costs: &KeyCosts,
+    cache: &mut Memo<CacheKey, u64>,
+) -> u64 {
+    if depth_remaining == 0 {
+        // At the bottom level, the human presses the key directly
+        return costs.cost(DirectionalKey::from_position(target));
+    }
+
+    let target_delta = target - start;
+    if target_delta == Position::xy(0, 0) {
+        // The next level only needs to press A, which it is already on, and so on down to the human
+        return costs.cost(DirectionalKey::Activate);
+    }
+
+    let cache_key = CacheKey { start, target, depth_remaining, gap };
+    cache.get_or_compute(cache_key, |cache| {
+        let mut min_distance = u64::MAX;
+
+        // Check if the path will cross the gap if moving horizontally then vertically
+        if start.y != gap.y || target.x != gap.x {
+            // At depth (depth-1), move horizontally then vertically to reach the arrow, then move back to A
+            let distance = move_to_key_and_back(
+                target_delta,
+                depth_remaining - 1,
+                MoveDirections::HThenV,
+                costs,
+                cache,
+            );
+            min_distance = cmp::min(min_distance, distance);
+        }
+
+        // Check if the path will cross the gap if moving vertically then horizontally
+        if start.x != gap.x || target.y != gap.y {
+            // At depth (depth-1), move vertically then horizontally to reach the arrow, then move back to A
+            let distance = move_to_key_and_back(
+                target_delta,
+                depth_remaining - 1,
+                MoveDirections::VThenH,
+                costs,
+                cache,
+            );
+            min_distance = cmp::min(min_distance, distance);
+        }
+
+        min_distance
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoveDirections {
+    HThenV,
+    VThenH,
+}
+
+// Find the min distance of the path to move the distance specified by `target_delta` at the specified
+// depth, and then back to the A
+fn move_to_key_and_back(
+    target_delta: Position,
+    depth: u32,
+    directions: MoveDirections,
+    costs: &KeyCosts,
+    cache: &mut Memo<CacheKey, u64>,
+) -> u64 {
+    let mut distance = 0;
+    let mut pos = DirectionalKey::Activate.position();
+
+    // Move the specified distance (to the arrow key)
+    let move_directions = match directions {
+        MoveDirections::HThenV => [MoveDirection::Horizontal, MoveDirection::Vertical],
+        MoveDirections::VThenH => [MoveDirection::Vertical, MoveDirection::Horizontal],
+    };
+    for direction in move_directions {
+        move_direction(target_delta, direction, depth, &mut distance, &mut pos, costs, cache);
+    }
+
+    // Move back to A
+    distance += find_min_distance_key(
+        pos,
+        DirectionalKey::Activate.position(),
+        depth,
+        DirectionalKey::GAP,
+        costs,
+        cache,
+    );
+    distance
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoveDirection {
+    Horizontal,
+    Vertical,
+}
+
+// Find the min distance of the path required to move by the specified distance in a single direction
+// (either horizontal or vertical)
+fn move_direction(
+    target_delta: Position,
+    direction: MoveDirection,
+    depth: u32,
+    distance: &mut u64,
+    pos: &mut Position,
+    costs: &KeyCosts,
+    cache: &mut Memo<CacheKey, u64>,
+) {
+    let (delta_component, direction_key) = match direction {
+        MoveDirection::Horizontal => (target_delta.x, DirectionalKey::x_direction(target_delta)),
+        MoveDirection::Vertical => (target_delta.y, DirectionalKey::y_direction(target_delta)),
+    };
+    let Some(direction_key) = direction_key else { return };
+    let new_pos = direction_key.position();
+
+    *distance += find_min_distance_key(*pos, new_pos, depth, DirectionalKey::GAP, costs, cache);
+
+    // Account for next level pressing the same key multiple times if abs(distance) > 1
+    let repeat_cost =
+        find_min_distance_key(new_pos, new_pos, depth, DirectionalKey::GAP, costs, cache);
+    *distance += (delta_component.abs() - 1) as u64 * repeat_cost;
+
+    *pos = new_pos;
+}
+
+const P1_ROBOTS: u32 = 2;
+const P2_ROBOTS: u32 = 25;
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_with_costs(input, P1_ROBOTS, &KeyCosts::default()).into(),
+    |input| solve_with_costs(input, P2_ROBOTS, &KeyCosts::default()).into(),
+);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::has_flag("--bench-scaling") {
+        bench_scaling();
+        return Ok(());
+    }
+
+    let costs = match crate::flag_value("--key-costs") {
+        Some(costs) => KeyCosts::parse(&costs)?,
+        None => KeyCosts::default(),
+    };
+
+    crate::run(
+        |input| solve_with_costs(input, P1_ROBOTS, &costs),
+        |input| solve_with_costs(input, P2_ROBOTS, &costs),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day21.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(126384, solve_with_costs(SAMPLE_INPUT, P1_ROBOTS, &KeyCosts::default()));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(
+            154115708116294,
+            solve_with_costs(SAMPLE_INPUT, P2_ROBOTS, &KeyCosts::default())
+        );
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let mut rng = StdRng::seed_from_u64(21);
+        let codes = parse_input(&generate::door_codes(&mut rng, 100));
+        let costs = KeyCosts { left: 2, ..KeyCosts::default() };
+        for robots in [P1_ROBOTS, P2_ROBOTS] {
+            assert_eq!(
+                total_complexity(&codes, robots, &costs, &mut Memo::new()),
+                total_complexity_parallel(&codes, robots, &costs)
+            );
+        }
+
+        // The warmed cache covers every directional move, so a code only adds numeric moves
+        let mut cache = warm_cache(P2_ROBOTS, &costs);
+        let warmed_entries = cache.len();
+        total_complexity(&codes[..1], P2_ROBOTS, &costs, &mut cache);
+        assert!(cache.len() - warmed_entries <= codes[0].keys.len());
+    }
+
+    #[test]
+    fn key_costs() {
+        assert_eq!(Ok(KeyCosts::default()), KeyCosts::parse(""));
+        assert_eq!(
+            Ok(KeyCosts { left: 3, activate: 2, ..KeyCosts::default() }),
+            KeyCosts::parse("<=3,A=2")
+        );
+        assert!(KeyCosts::parse("x=3").is_err());
+        assert!(KeyCosts::parse("<3").is_err());
+
+        // Uniform costs scale every press equally
+        let uniform = KeyCosts { up: 3, down: 3, left: 3, right: 3, activate: 3 };
+        assert_eq!(3 * 126384, solve_with_costs(SAMPLE_INPUT, P1_ROBOTS, &uniform));
+
+        // Direct typing: "029A" needs <A ^A >^^A vvvA, i.e. 3 ups, 3 downs, 1 left, 1 right, 4 As
+        let costs = KeyCosts { up: 10, down: 100, left: 1000, right: 10000, activate: 1 };
+        assert_eq!(29 * 11334, solve_with_costs("029A", 0, &costs));
+
+        // Making `<` expensive can never make the answer cheaper
+        let expensive_left = KeyCosts { left: 5, ..KeyCosts::default() };
+        assert!(solve_with_costs(SAMPLE_INPUT, P1_ROBOTS, &expensive_left) > 126384);
+    }
+}
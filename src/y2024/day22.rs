@@ -0,0 +1,532 @@
+//! Day 22: Monkey Market
+//!
+//! <https://adventofcode.com/2024/day/22>
+//!
+//! Passing `--algo branch-and-bound` solves part 2 with a search over sequence prefixes that skips
+//! any prefix whose banana upper bound can't beat the best sequence found so far, instead of the
+//! default `--algo enumerate`, and
+//! `--bench-branch-and-bound` times it against plain enumeration on generated buyers.
+
+use crate::days::Solver;
+use crate::{BitSet, Counter, Solution, generate, parallel, time_micros};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::cmp;
+use std::error::Error;
+
+fn solve_part_1(input: &str) -> i64 {
+    parallel::map_chunks(&parse_numbers(input), sum_of_2000th).into_iter().sum()
+}
+
+#[cfg(feature = "simd")]
+use simd::sum_of_2000th;
+#[cfg(not(feature = "simd"))]
+use sum_of_2000th_scalar as sum_of_2000th;
+
+// Sum of each buyer's 2000th secret number
+#[cfg_attr(feature = "simd", allow(dead_code))]
+fn sum_of_2000th_scalar(numbers: &[i64]) -> i64 {
+    numbers
+        .iter()
+        .map(|&number| (0..2000).fold(number, |number, _| next_secret_number(number)))
+        .sum()
+}
+
+// Evolves one buyer per lane. Every step only depends on the low 24 bits, and no intermediate value
+// needs more than 32 bits once it is masked, so u32 lanes compute exactly what the scalar i64 path
+// does
+#[cfg(feature = "simd")]
+mod simd {
+    use std::simd::prelude::*;
+
+    const LANES: usize = 16;
+
+    fn next_secret_numbers(mut numbers: Simd<u32, LANES>) -> Simd<u32, LANES> {
+        let mask = Simd::splat(0xFF_FFFF);
+        numbers = (numbers ^ (numbers << 6)) & mask;
+        numbers = (numbers ^ (numbers >> 5)) & mask;
+        (numbers ^ (numbers << 11)) & mask
+    }
+
+    pub(super) fn sum_of_2000th(numbers: &[i64]) -> i64 {
+        let (chunks, rest) = numbers.as_chunks::<LANES>();
+        let mut sum = 0;
+        for chunk in chunks {
+            let mut lanes = Simd::from_array(chunk.map(|number| number as u32 & 0xFF_FFFF));
+            for _ in 0..2000 {
+                lanes = next_secret_numbers(lanes);
+            }
+            sum += lanes.cast::<u64>().reduce_sum() as i64;
+        }
+        sum + super::sum_of_2000th_scalar(rest)
+    }
+}
+
+fn next_secret_number(mut number: i64) -> i64 {
+    const MODULO: i64 = 16_777_216;
+
+    number ^= number * 64;
+    number %= MODULO;
+
+    number ^= number / 32;
+    number %= MODULO;
+
+    number ^= number * 2048;
+    number %= MODULO;
+
+    number
+}
+
+fn parse_numbers(input: &str) -> Vec<i64> {
+    input.lines().filter(|line| !line.is_empty()).map(|line| line.parse::<i64>().unwrap()).collect()
+}
+
+fn solve_part_2(input: &str) -> i64 {
+    let numbers = parse_numbers(input);
+
+    let mut counters = parallel::map_chunks(&numbers, sequence_bananas).into_iter();
+    let mut changes_to_bananas = counters.next().unwrap_or_default();
+    for counter in counters {
+        changes_to_bananas.merge(counter);
+    }
+
+    changes_to_bananas.most_common().unwrap().1 as i64
+}
+
+// Total bananas that each sequence of changes would buy from these buyers
+fn sequence_bananas(numbers: &[i64]) -> Counter<u32> {
+    let mut changes_to_bananas: Counter<u32> = Counter::new();
+    let mut changes_for_number = BitSet::new(SEQUENCE_COUNT as usize);
+    for &start_number in numbers {
+        changes_for_number.clear();
+
+        for_each_sequence(start_number, |sequence, bananas| {
+            if changes_for_number.insert(sequence as usize) {
+                changes_to_bananas.add(sequence, bananas as u64);
+            }
+        });
+    }
+
+    changes_to_bananas
+}
+
+// Price changes are in -9..=9, so a sequence of 4 changes is encoded as a 4-digit base-19 number
+const CHANGE_VALUES: u32 = 19;
+const SEQUENCE_COUNT: u32 = CHANGE_VALUES.pow(4);
+
+// Shifts a change into an encoded sequence, dropping the oldest change
+fn push_change(sequence: u32, change: i64) -> u32 {
+    (sequence * CHANGE_VALUES + (change + 9) as u32) % SEQUENCE_COUNT
+}
+
+fn decode_sequence(mut sequence: u32) -> [i64; 4] {
+    let mut changes = [0; 4];
+    for change in changes.iter_mut().rev() {
+        *change = i64::from(sequence % CHANGE_VALUES) - 9;
+        sequence /= CHANGE_VALUES;
+    }
+    changes
+}
+
+// Calls `f` with every encoded sequence of 4 changes in the buyer's prices, along with the price
+// immediately after the sequence
+fn for_each_sequence(start_number: i64, mut f: impl FnMut(u32, i64)) {
+    let mut number = start_number;
+    let mut sequence = 0;
+    for i in 0..2000 {
+        let next_number = next_secret_number(number);
+        let difference = (next_number % 10) - (number % 10);
+        sequence = push_change(sequence, difference);
+
+        if i >= 3 {
+            f(sequence, next_number % 10);
+        }
+
+        number = next_number;
+    }
+}
+
+// Same as part 2, but with totals in a flat array indexed by encoded sequence instead of a Counter
+fn flat_array_max(numbers: &[i64]) -> u64 {
+    let mut totals = vec![0_u64; SEQUENCE_COUNT as usize];
+    let mut seen = BitSet::new(SEQUENCE_COUNT as usize);
+    for &number in numbers {
+        seen.clear();
+        for_each_sequence(number, |sequence, bananas| {
+            if seen.insert(sequence as usize) {
+                totals[sequence as usize] += bananas as u64;
+            }
+        });
+    }
+
+    totals.into_iter().max().unwrap_or(0)
+}
+
+// Upper bounds on the bananas that any sequence starting with a given prefix can earn. For prefixes
+// of 1 to 3 changes, the bound is the sum over buyers of the best price that buyer would pay for any
+// sequence with that prefix; 4-change "prefixes" are full sequences and hold exact totals
+struct PrefixBounds {
+    // levels[k - 1] is indexed by the base-19 encoding of a k-change prefix
+    levels: [Vec<u64>; 4],
+}
+
+impl PrefixBounds {
+    fn new(numbers: &[i64]) -> Self {
+        let mut levels = [1, 2, 3, 4].map(|k| vec![0_u64; CHANGE_VALUES.pow(k) as usize]);
+
+        // Per-buyer best price + 1 for each shorter prefix (0 meaning not seen), and which prefixes
+        // were touched so that they can be flushed into the bounds and reset after each buyer
+        let mut buyer_max = [1, 2, 3].map(|k| vec![0_u8; CHANGE_VALUES.pow(k) as usize]);
+        let mut touched: [Vec<u32>; 3] = Default::default();
+        let mut seen = BitSet::new(SEQUENCE_COUNT as usize);
+
+        for &number in numbers {
+            seen.clear();
+            for_each_sequence(number, |sequence, bananas| {
+                if !seen.insert(sequence as usize) {
+                    return;
+                }
+
+                levels[3][sequence as usize] += bananas as u64;
+                let mut prefix = sequence;
+                for level in (0..3).rev() {
+                    prefix /= CHANGE_VALUES;
+                    let best = &mut buyer_max[level][prefix as usize];
+                    if *best == 0 {
+                        touched[level].push(prefix);
+                    }
+                    *best = cmp::max(*best, bananas as u8 + 1);
+                }
+            });
+
+            for level in 0..3 {
+                for prefix in touched[level].drain(..) {
+                    let best = std::mem::take(&mut buyer_max[level][prefix as usize]);
+                    levels[level][prefix as usize] += u64::from(best - 1);
+                }
+            }
+        }
+
+        Self { levels }
+    }
+
+    fn bound(&self, len: usize, prefix: u32) -> u64 {
+        self.levels[len - 1][prefix as usize]
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct SearchStats {
+    nodes_visited: u64,
+    nodes_pruned: u64,
+}
+
+// Depth-first over prefixes, visiting children in descending bound order so that a good sequence is
+// found early. Once a child's bound can't beat the best total so far, neither can any of its
+// remaining siblings
+fn best_sequence(bounds: &PrefixBounds) -> (u32, u64, SearchStats) {
+    fn search(
+        bounds: &PrefixBounds,
+        len: usize,
+        prefix: u32,
+        best: &mut (u32, u64),
+        stats: &mut SearchStats,
+    ) {
+        stats.nodes_visited += 1;
+        if len == 4 {
+            if bounds.bound(4, prefix) > best.1 {
+                *best = (prefix, bounds.bound(4, prefix));
+            }
+            return;
+        }
+
+        let mut children: Vec<_> = (0..CHANGE_VALUES)
+            .map(|change| {
+                let child = prefix * CHANGE_VALUES + change;
+                (bounds.bound(len + 1, child), child)
+            })
+            .collect();
+        children.sort_by_key(|&(bound, _)| cmp::Reverse(bound));
+
+        for (i, &(bound, child)) in children.iter().enumerate() {
+            if bound <= best.1 {
+                stats.nodes_pruned += (children.len() - i) as u64;
+                break;
+            }
+            search(bounds, len + 1, child, best, stats);
+        }
+    }
+
+    let mut best = (0, 0);
+    let mut stats = SearchStats::default();
+    search(bounds, 0, 0, &mut best, &mut stats);
+    (best.0, best.1, stats)
+}
+
+fn solve_part_2_branch_and_bound(input: &str) -> u64 {
+    let numbers = parse_numbers(input);
+    let (_, bananas, stats) = best_sequence(&PrefixBounds::new(&numbers));
+
+    debug!(
+        buyers = numbers.len(),
+        nodes_visited = stats.nodes_visited,
+        nodes_pruned = stats.nodes_pruned,
+        "Searched sequence prefixes"
+    );
+
+    bananas
+}
+
+// Times branch and bound against flat-array enumeration on increasingly large sets of generated
+// buyers
+fn bench_branch_and_bound() {
+    let mut rng = StdRng::seed_from_u64(22);
+    for buyers in [100, 1000, 10000] {
+        let numbers = parse_numbers(&generate::secret_numbers(&mut rng, buyers));
+        let flat = time_micros(|| flat_array_max(&numbers));
+        let bnb = time_micros(|| best_sequence(&PrefixBounds::new(&numbers)));
+        let (_, _, stats) = best_sequence(&PrefixBounds::new(&numbers));
+        println!(
+            "{buyers} buyers: {flat}μs flat array, {bnb}μs branch and bound ({} of {} prefixes \
+             visited)",
+            stats.nodes_visited,
+            1 + (1..=4).map(|k| CHANGE_VALUES.pow(k)).sum::<u32>()
+        );
+    }
+}
+
+#[derive(Debug)]
+struct Cluster {
+    // Indices into the buyer list, in input order; the first buyer is the cluster's leader
+    buyers: Vec<usize>,
+    // The sequence that occurs for the most buyers in the cluster, and how many buyers it occurs for
+    representative: [i64; 4],
+    representative_buyers: u64,
+}
+
+fn jaccard_similarity(a: &BitSet, b: &BitSet) -> f64 {
+    let union = a.union_len(b);
+    if union == 0 { 1.0 } else { a.intersection_len(b) as f64 / union as f64 }
+}
+
+// Groups buyers by the similarity of the sets of change sequences that occur in their prices.
+// Leader clustering: each buyer joins the first cluster whose leader has Jaccard similarity at
+// least `threshold`, or else starts a new cluster. Clusters are returned largest first
+fn cluster_buyers(numbers: &[i64], threshold: f64) -> Vec<Cluster> {
+    let sequence_sets: Vec<_> = numbers
+        .iter()
+        .map(|&number| {
+            let mut sequences = BitSet::new(SEQUENCE_COUNT as usize);
+            for_each_sequence(number, |sequence, _| {
+                sequences.insert(sequence as usize);
+            });
+            sequences
+        })
+        .collect();
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for (i, sequences) in sequence_sets.iter().enumerate() {
+        match clusters
+            .iter_mut()
+            .find(|cluster| jaccard_similarity(&sequence_sets[cluster[0]], sequences) >= threshold)
+        {
+            Some(cluster) => cluster.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+
+    let mut clusters: Vec<_> = clusters
+        .into_iter()
+        .map(|buyers| {
+            let counts: Counter<usize> =
+                buyers.iter().flat_map(|&buyer| sequence_sets[buyer].iter()).collect();
+            let (&representative, representative_buyers) = counts.most_common().unwrap();
+            Cluster {
+                buyers,
+                representative: decode_sequence(representative as u32),
+                representative_buyers,
+            }
+        })
+        .collect();
+    clusters.sort_by_key(|cluster| cmp::Reverse(cluster.buyers.len()));
+
+    clusters
+}
+
+const DEFAULT_CLUSTER_SIMILARITY: f64 = 0.1;
+
+fn print_clusters(clusters: &[Cluster], numbers: &[i64]) {
+    let singletons = clusters.iter().filter(|cluster| cluster.buyers.len() == 1).count();
+    println!("{} clusters ({singletons} with a single buyer)", clusters.len());
+
+    for cluster in clusters.iter().filter(|cluster| cluster.buyers.len() > 1) {
+        let [a, b, c, d] = cluster.representative;
+        println!(
+            "{} buyers (leader {}): representative sequence {a},{b},{c},{d} occurs for {}",
+            cluster.buyers.len(),
+            numbers[cluster.buyers[0]],
+            cluster.representative_buyers
+        );
+    }
+}
+
+// Follows the puzzle text literally, with mix and prune steps and a hash map keyed by the change
+// sequence itself
+#[cfg(feature = "reference")]
+mod reference {
+    use std::collections::{HashMap, HashSet};
+
+    fn next_secret(number: u64) -> u64 {
+        let mix_prune = |number: u64, value: u64| (number ^ value) % 16777216;
+        let number = mix_prune(number, number * 64);
+        let number = mix_prune(number, number / 32);
+        mix_prune(number, number * 2048)
+    }
+
+    fn secrets(start: u64) -> Vec<u64> {
+        let mut secrets = vec![start];
+        for _ in 0..2000 {
+            secrets.push(next_secret(*secrets.last().unwrap()));
+        }
+        secrets
+    }
+
+    pub(super) fn solve_part_1(input: &str) -> u64 {
+        input.lines().map(|line| secrets(line.parse().unwrap())[2000]).sum()
+    }
+
+    pub(super) fn solve_part_2(input: &str) -> u64 {
+        let mut bananas: HashMap<[i64; 4], u64> = HashMap::new();
+        for line in input.lines() {
+            let prices: Vec<i64> =
+                secrets(line.parse().unwrap()).into_iter().map(|n| (n % 10) as i64).collect();
+            let changes: Vec<i64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+
+            let mut seen = HashSet::new();
+            for (i, window) in changes.windows(4).enumerate() {
+                let sequence = [window[0], window[1], window[2], window[3]];
+                if seen.insert(sequence) {
+                    *bananas.entry(sequence).or_default() += prices[i + 4] as u64;
+                }
+            }
+        }
+
+        bananas.into_values().max().unwrap_or(0)
+    }
+}
+
+#[cfg(feature = "reference")]
+pub const DIFFERENTIAL: crate::differential::Differential = crate::differential::Differential {
+    optimized: SOLVER,
+    reference: Solver::Parts(
+        |input| reference::solve_part_1(input).into(),
+        |input| reference::solve_part_2(input).into(),
+    ),
+    generate: crate::generate::secret_numbers,
+    size: 20,
+};
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve_part_1(input).into(), |input| solve_part_2(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if crate::has_flag("--bench-branch-and-bound") {
+        bench_branch_and_bound();
+        return Ok(());
+    }
+
+    if crate::has_flag("--clusters") {
+        let threshold = match crate::flag_value("--similarity") {
+            Some(threshold) => threshold.parse()?,
+            None => DEFAULT_CLUSTER_SIMILARITY,
+        };
+        let numbers = parse_numbers(&crate::read_input()?);
+        print_clusters(&cluster_buyers(&numbers, threshold), &numbers);
+        return Ok(());
+    }
+
+    let solve_part_2 = crate::select_algo::<fn(&str) -> Solution>(&[
+        ("enumerate", |input| solve_part_2(input).into()),
+        ("branch-and-bound", |input| solve_part_2_branch_and_bound(input).into()),
+    ])?;
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day22.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample/2024/day22-2.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(37327623, solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_matches_scalar() {
+        let mut rng = StdRng::seed_from_u64(2000);
+        let numbers = parse_numbers(&generate::secret_numbers(&mut rng, 101));
+        for len in [0, 3, 16, 101] {
+            let numbers = &numbers[..len];
+            assert_eq!(sum_of_2000th_scalar(numbers), simd::sum_of_2000th(numbers));
+        }
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(23, solve_part_2(SAMPLE_INPUT_2));
+    }
+
+    #[test]
+    fn branch_and_bound() {
+        assert_eq!(23, solve_part_2_branch_and_bound(SAMPLE_INPUT_2));
+
+        let (sequence, bananas, stats) = best_sequence(&PrefixBounds::new(&[1, 2, 3, 2024]));
+        assert_eq!([-2, 1, -1, 3], decode_sequence(sequence));
+        assert_eq!(23, bananas);
+        assert!(stats.nodes_pruned > 0);
+
+        let mut rng = StdRng::seed_from_u64(22);
+        let numbers = parse_numbers(&generate::secret_numbers(&mut rng, 50));
+        let bounds = PrefixBounds::new(&numbers);
+        assert_eq!(flat_array_max(&numbers), best_sequence(&bounds).1);
+
+        // Each bound is at least as large as the bound or total of every extension of the prefix
+        for len in 1..4 {
+            for prefix in 0..CHANGE_VALUES.pow(len as u32) {
+                let child_max = (0..CHANGE_VALUES)
+                    .map(|change| bounds.bound(len + 1, prefix * CHANGE_VALUES + change))
+                    .max();
+                assert!(bounds.bound(len, prefix) >= child_max.unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn sequence_encoding() {
+        let sequence = [-2, 1, -1, 3].into_iter().fold(0, push_change);
+        assert_eq!([-2, 1, -1, 3], decode_sequence(sequence));
+
+        // Older changes are shifted out
+        assert_eq!([1, -1, 3, 9], decode_sequence(push_change(sequence, 9)));
+        assert_eq!([-9; 4], decode_sequence(0));
+    }
+
+    #[test]
+    fn clusters() {
+        let numbers = [1, 2, 1, 3, 2024];
+
+        // Identical buyers are always clustered together
+        let clusters = cluster_buyers(&numbers, 1.0);
+        assert_eq!(4, clusters.len());
+        assert_eq!(vec![0, 2], clusters[0].buyers);
+        assert_eq!(2, clusters[0].representative_buyers);
+
+        let clusters = cluster_buyers(&numbers, 0.0);
+        assert_eq!(1, clusters.len());
+        assert_eq!(5, clusters[0].buyers.len());
+    }
+}
@@ -0,0 +1,261 @@
+//! Day 23: LAN Party
+//!
+//! <https://adventofcode.com/2024/day/23>
+
+use crate::days::Solver;
+use crate::graph_export::{ExportEdge, ExportFormat, ExportGraph, ExportNode};
+use crate::{Graph, Interner};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::error::Error;
+use std::{fs, iter};
+
+fn parse_input(input: &str) -> Vec<(&str, &str)> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split_once('-').unwrap())
+        .collect()
+}
+
+fn solve_part_1(input: &str) -> usize {
+    count_cliques_matching(input, 3, |computer| computer.starts_with('t'))
+}
+
+// Counts groups of `k` computers that are all connected to each other, where at least one
+// computer in the group matches `predicate`
+fn count_cliques_matching(input: &str, k: usize, predicate: impl Fn(&str) -> bool) -> usize {
+    let connections = parse_input(input);
+    let (computers, graph) = build_graph(&connections);
+    let matches: Vec<_> = computers.iter().map(|(_, name)| predicate(name)).collect();
+
+    let mut count = 0;
+    graph.for_each_clique(k, |clique| {
+        if clique.iter().any(|&computer| matches[computer]) {
+            count += 1;
+        }
+    });
+    count
+}
+
+fn build_graph<'a>(connections: &[(&'a str, &'a str)]) -> (Interner<'a>, Graph) {
+    let mut computers = Interner::new();
+    let edges: Vec<_> = connections
+        .iter()
+        .map(|&(a, b)| (computers.intern(a) as usize, computers.intern(b) as usize))
+        .collect();
+    let graph = Graph::from_edges(computers.len(), edges);
+    (computers, graph)
+}
+
+// Parses a comma-separated list of name prefixes, e.g. `t,c`
+fn parse_prefixes(s: &str) -> Vec<&str> {
+    s.split(',').map(str::trim).filter(|prefix| !prefix.is_empty()).collect()
+}
+
+fn solve_part_2(input: &str) -> String {
+    let connections = parse_input(input);
+    find_max_group_names(&connections).join(",")
+}
+
+// Returns the computers in the largest fully connected group, sorted by name
+fn find_max_group_names<'a>(connections: &[(&'a str, &'a str)]) -> Vec<&'a str> {
+    // Convert everything to u32s because that is significantly faster
+    let mut computers = Interner::new();
+
+    let mut connections_map: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+    let mut connections_set: FxHashSet<(u32, u32)> = FxHashSet::default();
+    for &(a, b) in connections {
+        let a_idx = computers.intern(a);
+        let b_idx = computers.intern(b);
+
+        for (aa, bb) in [(a_idx, b_idx), (b_idx, a_idx)] {
+            connections_map.entry(aa).or_default().push(bb);
+            connections_set.insert((aa, bb));
+        }
+    }
+
+    // Sort the connections map values in reverse order to make it possible to avoid needing to
+    // scan the entire Vec later
+    for value in connections_map.values_mut() {
+        value.sort_by(|a, b| a.cmp(b).reverse());
+    }
+
+    let max_group = find_max_group(computers.len() as u32, &connections_map, &connections_set);
+    let mut max_group_str: Vec<_> = max_group.into_iter().map(|idx| computers.name(idx)).collect();
+    max_group_str.sort();
+
+    max_group_str
+}
+
+fn find_max_group(
+    num_computers: u32,
+    connections_map: &FxHashMap<u32, Vec<u32>>,
+    connections_set: &FxHashSet<(u32, u32)>,
+) -> Vec<u32> {
+    // Initialize with a single group for each computer
+    let mut groups: Vec<_> = (0..num_computers).map(|computer| vec![computer]).collect();
+
+    // Loop until there is only 1 group left
+    // In each iteration, replace `groups` with all groups that are 1 larger
+    let mut solution = Vec::new();
+    while !groups.is_empty() {
+        let mut next_groups = Vec::new();
+
+        // This silliness is necessary because consuming `groups` in the following loop slightly
+        // improves performance compared to not consuming it (i.e. `for group in &groups`)
+        if groups.len() == 1 {
+            solution = groups[0].clone();
+        }
+
+        for group in groups {
+            let last = *group.last().unwrap();
+            for &connection in connections_map.get(&group[0]).unwrap() {
+                if connection <= last {
+                    break;
+                }
+
+                if group[1..].iter().all(|&other| connections_set.contains(&(other, connection))) {
+                    next_groups.push(group.iter().copied().chain(iter::once(connection)).collect());
+                }
+            }
+        }
+
+        groups = next_groups;
+    }
+
+    assert!(!solution.is_empty(), "More than 1 group of max length");
+    solution
+}
+
+// Builds the LAN graph for export, with computers starting with 't' filled in and the max group's
+// computers and connections highlighted
+fn build_export_graph(input: &str) -> ExportGraph {
+    let connections = parse_input(input);
+    let max_group: FxHashSet<_> = find_max_group_names(&connections).into_iter().collect();
+
+    let mut computers: Vec<_> = connections.iter().flat_map(|&(a, b)| [a, b]).collect();
+    computers.sort();
+    computers.dedup();
+
+    let mut graph = ExportGraph::new(false);
+    graph.node_attrs.push(("style", "filled".into()));
+    graph.nodes = computers
+        .into_iter()
+        .map(|computer| {
+            let fill = if computer.starts_with('t') { "lightblue" } else { "white" };
+            let mut node = ExportNode::new(computer).attr("fillcolor", fill);
+            if max_group.contains(computer) {
+                node = node.attr("color", "red").attr("penwidth", "3");
+            }
+            node
+        })
+        .collect();
+    graph.edges = connections
+        .into_iter()
+        .map(|(a, b)| {
+            let edge = ExportEdge::new(a, b);
+            if max_group.contains(a) && max_group.contains(b) {
+                edge.attr("color", "red").attr("penwidth", "2")
+            } else {
+                edge
+            }
+        })
+        .collect();
+
+    graph
+}
+
+pub const SOLVER: Solver =
+    Solver::Parts(|input| solve_part_1(input).into(), |input| solve_part_2(input).into());
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(path) = crate::flag_value("--export") {
+        let graph = build_export_graph(&crate::read_input()?);
+        fs::write(&path, graph.render(ExportFormat::from_path(&path)))?;
+        return Ok(());
+    }
+
+    let prefixes = crate::flag_value("--prefixes");
+    let clique_size = crate::flag_value("--clique-size");
+    if prefixes.is_some() || clique_size.is_some() {
+        let prefixes = prefixes.unwrap_or_else(|| "t".into());
+        let prefixes = parse_prefixes(&prefixes);
+        let k = clique_size.map(|k| k.parse()).transpose()?.unwrap_or(3);
+        let count = count_cliques_matching(&crate::read_input()?, k, |computer| {
+            prefixes.iter().any(|prefix| computer.starts_with(prefix))
+        });
+        println!("{count}");
+        return Ok(());
+    }
+
+    crate::run(solve_part_1, solve_part_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinatorics;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day23.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(7, solve_part_1(SAMPLE_INPUT));
+
+        // Brute force over every triple of computers
+        let (computers, graph) = build_graph(&parse_input(SAMPLE_INPUT));
+        let all: Vec<_> = (0..computers.len()).collect();
+        let triangles = combinatorics::combinations(&all, 3)
+            .filter(|triple| combinatorics::pairs(triple).all(|(&&a, &&b)| graph.has_edge(a, b)))
+            .filter(|triple| triple.iter().any(|&&c| computers.name(c as u32).starts_with('t')))
+            .count();
+        assert_eq!(7, triangles);
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!("co,de,ka,ta", solve_part_2(SAMPLE_INPUT).as_str());
+    }
+
+    #[test]
+    fn cliques_matching() {
+        let starts_with_t = |computer: &str| computer.starts_with('t');
+        assert_eq!(12, count_cliques_matching(SAMPLE_INPUT, 3, |_| true));
+        assert_eq!(7, count_cliques_matching(SAMPLE_INPUT, 3, starts_with_t));
+        assert_eq!(1, count_cliques_matching(SAMPLE_INPUT, 4, starts_with_t));
+        assert_eq!(0, count_cliques_matching(SAMPLE_INPUT, 5, |_| true));
+
+        // ka appears in 3 triangles, kh in 1, and yn in 2
+        let prefixes = parse_prefixes("k, y,");
+        assert_eq!(vec!["k", "y"], prefixes);
+        let matches_prefix =
+            |computer: &str| prefixes.iter().any(|prefix| computer.starts_with(prefix));
+        assert_eq!(6, count_cliques_matching(SAMPLE_INPUT, 3, matches_prefix));
+    }
+
+    #[test]
+    fn export() {
+        let graph = build_export_graph(SAMPLE_INPUT);
+        assert_eq!(16, graph.nodes.len());
+        assert_eq!(32, graph.edges.len());
+
+        let highlighted_nodes: Vec<_> = graph
+            .nodes
+            .iter()
+            .filter(|node| {
+                node.attrs.iter().any(|(name, value)| *name == "color" && value == "red")
+            })
+            .map(|node| node.id.as_str())
+            .collect();
+        assert_eq!(vec!["co", "de", "ka", "ta"], highlighted_nodes);
+
+        let highlighted_edges = graph
+            .edges
+            .iter()
+            .filter(|edge| {
+                edge.attrs.iter().any(|(name, value)| *name == "color" && value == "red")
+            })
+            .count();
+        assert_eq!(6, highlighted_edges);
+    }
+}
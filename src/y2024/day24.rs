@@ -0,0 +1,1007 @@
+//! Day 24: Crossed Wires
+//!
+//! <https://adventofcode.com/2024/day/24>
+//!
+//! Part 2 and the equivalence checks test circuits on random inputs; `--deterministic` uses a fixed
+//! seed so that repeated runs test the same inputs.
+
+use crate::days::Solver;
+use crate::graph_export::{ExportEdge, ExportFormat, ExportGraph, ExportNode};
+use crate::parse::{self, ParseError, lines, sections};
+use crate::{Interner, SolverOutput, combinatorics};
+use rand::Rng;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::hash::Hash;
+use std::rc::Rc;
+use winnow::ascii::{alphanumeric1, newline};
+use winnow::combinator::{alt, opt, separated_pair, terminated};
+use winnow::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Logic {
+    And,
+    Or,
+    Xor,
+}
+
+impl Logic {
+    fn name(self) -> &'static str {
+        match self {
+            Self::And => "AND",
+            Self::Or => "OR",
+            Self::Xor => "XOR",
+        }
+    }
+
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Self::And => a & b,
+            Self::Or => a | b,
+            Self::Xor => a ^ b,
+        }
+    }
+}
+
+// Interned wire name
+type Wire = u32;
+
+#[derive(Debug, Clone)]
+struct Gate {
+    input: (Wire, Wire),
+    logic: Logic,
+    output: Wire,
+}
+
+#[derive(Debug)]
+struct Input<'a> {
+    names: Interner<'a>,
+    start_wires: Vec<(Wire, bool)>,
+    gates: Vec<Gate>,
+}
+
+fn parse_bit(input: &mut &str) -> PResult<bool> {
+    let digit = alt(('0', '1')).parse_next(input)?;
+    Ok(digit == '1')
+}
+
+fn parse_start_wire<'a>(input: &mut &'a str) -> PResult<(&'a str, bool)> {
+    separated_pair(alphanumeric1, ": ", parse_bit).parse_next(input)
+}
+
+fn parse_and(input: &mut &str) -> PResult<Logic> {
+    " AND ".parse_next(input)?;
+    Ok(Logic::And)
+}
+
+fn parse_or(input: &mut &str) -> PResult<Logic> {
+    " OR ".parse_next(input)?;
+    Ok(Logic::Or)
+}
+
+fn parse_xor(input: &mut &str) -> PResult<Logic> {
+    " XOR ".parse_next(input)?;
+    Ok(Logic::Xor)
+}
+
+fn parse_logic(input: &mut &str) -> PResult<Logic> {
+    alt((parse_and, parse_or, parse_xor)).parse_next(input)
+}
+
+// Returns ((input0, logic, input1), output)
+fn parse_gate<'a>(input: &mut &'a str) -> PResult<((&'a str, Logic, &'a str), &'a str)> {
+    separated_pair((alphanumeric1, parse_logic, alphanumeric1), " -> ", alphanumeric1)
+        .parse_next(input)
+}
+
+// Also checks that every wire read by a gate is set by something, and that the z wires are numbered
+// so that they fit in the part 1 result
+fn parse_input(input: &str) -> Result<Input<'_>, ParseError> {
+    let (start_wires, gates) = parse::complete(
+        terminated(sections(lines(parse_start_wire), lines(parse_gate)), opt(newline)),
+        input,
+    )?;
+
+    let mut names = Interner::new();
+    let start_wires: Vec<_> =
+        start_wires.into_iter().map(|(wire, bit)| (names.intern(wire), bit)).collect();
+    let gates: Vec<_> = gates
+        .into_iter()
+        .map(|((input0, logic, input1), output)| Gate {
+            input: (names.intern(input0), names.intern(input1)),
+            logic,
+            output: names.intern(output),
+        })
+        .collect();
+
+    let set_wires: FxHashSet<_> = start_wires
+        .iter()
+        .map(|&(wire, _)| wire)
+        .chain(gates.iter().map(|gate| gate.output))
+        .collect();
+    for gate in &gates {
+        if let Some(&wire) =
+            [gate.input.0, gate.input.1].iter().find(|wire| !set_wires.contains(wire))
+        {
+            return Err(ParseError::new(format!("Wire {} is never set", names.name(wire))));
+        }
+    }
+    for &wire in &set_wires {
+        let name = names.name(wire);
+        if name.starts_with('z') && z_bit(name).is_none() {
+            return Err(ParseError::new(format!("Output wire {name} is not z00 through z63")));
+        }
+    }
+
+    Ok(Input { names, start_wires, gates })
+}
+
+// Bit index of a z wire, e.g. 5 for z05
+fn z_bit(name: &str) -> Option<u32> {
+    name.strip_prefix('z')?.parse().ok().filter(|&bit| bit < u64::BITS)
+}
+
+fn solve_part_1(input: &str) -> Result<u64, ParseError> {
+    let Input { names, start_wires, gates } = parse_input(input)?;
+
+    let mut wires_map: FxHashMap<Wire, bool> = start_wires.into_iter().collect();
+    let gate_map = build_gate_map(&gates);
+
+    let mut result: u64 = 0;
+    for &z_wire in gate_map.keys().filter(|&&wire| names.name(wire).starts_with('z')) {
+        let bit = evaluate_wire(z_wire, &gate_map, &mut wires_map, &mut FxHashSet::default())
+            .ok_or_else(|| ParseError::new("Gates form a cycle"))?;
+        let bit_idx = z_bit(names.name(z_wire)).unwrap();
+        result |= u64::from(bit) << bit_idx;
+    }
+
+    Ok(result)
+}
+
+fn build_gate_map(gates: &[Gate]) -> FxHashMap<Wire, Gate> {
+    gates.iter().map(|gate| (gate.output, gate.clone())).collect()
+}
+
+// Returns None if there is a cycle that prevents evaluation
+fn evaluate_wire(
+    wire: Wire,
+    gates: &FxHashMap<Wire, Gate>,
+    wires: &mut FxHashMap<Wire, bool>,
+    evaluating: &mut FxHashSet<Wire>,
+) -> Option<bool> {
+    if let Some(&output) = wires.get(&wire) {
+        return Some(output);
+    }
+
+    if !evaluating.insert(wire) {
+        // There is a cycle; can happen after swapping outputs
+        return None;
+    }
+
+    let gate = gates.get(&wire).unwrap();
+
+    let input0 = evaluate_wire(gate.input.0, gates, wires, evaluating)?;
+    let input1 = evaluate_wire(gate.input.1, gates, wires, evaluating)?;
+    let output = gate.logic.apply(input0, input1);
+
+    wires.insert(wire, output);
+    Some(output)
+}
+
+fn solve_part_2(input: &str, op: impl Copy + Fn(u64, u64) -> u64) -> Result<String, ParseError> {
+    let Input { names, start_wires, gates } = parse_input(input)?;
+
+    let output_wires: Vec<_> = gates.iter().map(|gate| gate.output).collect();
+    let mut gate_map = build_gate_map(&gates);
+
+    let start_wire_keys = start_wires.iter().map(|&(key, _)| key);
+    let x_wires = all_wires_with_prefix('x', start_wire_keys.clone(), &names);
+    let y_wires = all_wires_with_prefix('y', start_wire_keys, &names);
+    let z_wires = all_wires_with_prefix('z', gate_map.keys().copied(), &names);
+
+    let mut rng = crate::rng();
+    let mut swapped = Vec::new();
+    for bit in 0..z_wires.len() {
+        if !is_valid_for_bit(bit, op, &gate_map, &x_wires, &y_wires, &z_wires, &mut rng) {
+            swap_to_fix_bit(
+                bit,
+                op,
+                &output_wires,
+                &mut gate_map,
+                &x_wires,
+                &y_wires,
+                &z_wires,
+                &mut swapped,
+                &mut rng,
+            );
+        }
+    }
+
+    let mut swapped: Vec<_> = swapped.into_iter().map(|wire| names.name(wire)).collect();
+    swapped.sort();
+    Ok(swapped.join(","))
+}
+
+// Returns matching wires sorted by name
+fn all_wires_with_prefix(
+    prefix: char,
+    wires: impl Iterator<Item = Wire>,
+    names: &Interner<'_>,
+) -> Vec<Wire> {
+    let mut wires: Vec<_> = wires.filter(|&wire| names.name(wire).starts_with(prefix)).collect();
+    wires.sort_by_key(|&wire| names.name(wire));
+    wires
+}
+
+fn is_valid_for_bit(
+    bit: usize,
+    op: impl Fn(u64, u64) -> u64,
+    gate_map: &FxHashMap<Wire, Gate>,
+    x_wires: &[Wire],
+    y_wires: &[Wire],
+    z_wires: &[Wire],
+    rng: &mut impl Rng,
+) -> bool {
+    let mut input_wires: FxHashMap<Wire, bool> = FxHashMap::default();
+
+    // There is almost definitely a better way to do this than testing 100 random sums, but this seems to work
+    for _ in 0..100 {
+        let x = rng.gen::<u64>() & ((1 << x_wires.len()) - 1);
+        for (i, &x_wire) in x_wires.iter().enumerate() {
+            input_wires.insert(x_wire, x & (1 << i) != 0);
+        }
+
+        let y = rng.gen::<u64>() & ((1 << y_wires.len()) - 1);
+        for (i, &y_wire) in y_wires.iter().enumerate() {
+            input_wires.insert(y_wire, y & (1 << i) != 0);
+        }
+
+        let Some(z_bit) = evaluate_wire(
+            z_wires[bit],
+            gate_map,
+            &mut input_wires.clone(),
+            &mut FxHashSet::default(),
+        ) else {
+            // Cycle exists; definitely not valid
+            return false;
+        };
+
+        let expected_z_bit = op(x, y) & (1 << bit) != 0;
+        if expected_z_bit != z_bit {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[allow(clippy::too_many_arguments)]
+fn swap_to_fix_bit(
+    bit: usize,
+    op: impl Copy + Fn(u64, u64) -> u64,
+    output_wires: &[Wire],
+    gate_map: &mut FxHashMap<Wire, Gate>,
+    x_wires: &[Wire],
+    y_wires: &[Wire],
+    z_wires: &[Wire],
+    swapped: &mut Vec<Wire>,
+    rng: &mut impl Rng,
+) {
+    for (&a, &b) in combinatorics::pairs(output_wires) {
+        let mut swapped_gate_map = gate_map.clone();
+        hashmap_swap(&mut swapped_gate_map, a, b);
+
+        if is_valid_for_bit(bit, op, &swapped_gate_map, x_wires, y_wires, z_wires, rng) {
+            *gate_map = swapped_gate_map;
+            swapped.extend([a, b]);
+            return;
+        }
+    }
+
+    panic!("No valid swap found for bit {bit}");
+}
+
+fn hashmap_swap<K: Eq + Hash, V>(map: &mut FxHashMap<K, V>, k1: K, k2: K) {
+    let mut t = map.remove(&k1).unwrap();
+    t = map.insert(k2, t).unwrap();
+    map.insert(k1, t);
+}
+
+// Parsed circuit that can be evaluated under arbitrary input assignments
+#[derive(Debug, Clone)]
+struct Circuit<'a> {
+    names: Interner<'a>,
+    gate_map: FxHashMap<Wire, Gate>,
+    // All start wires, sorted by name
+    inputs: Vec<Wire>,
+    // All z wires, sorted by name
+    outputs: Vec<Wire>,
+}
+
+impl<'a> Circuit<'a> {
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let Input { names, start_wires, gates } = parse_input(input)?;
+
+        let gate_map = build_gate_map(&gates);
+        let mut inputs: Vec<_> = start_wires.into_iter().map(|(wire, _)| wire).collect();
+        inputs.sort_by_key(|&wire| names.name(wire));
+        let outputs = all_wires_with_prefix('z', gate_map.keys().copied(), &names);
+
+        Ok(Self { names, gate_map, inputs, outputs })
+    }
+
+    fn names_of(&self, wires: &[Wire]) -> Vec<&'a str> {
+        wires.iter().map(|&wire| self.names.name(wire)).collect()
+    }
+
+    // `assignment` is indexed the same as `inputs`. Outputs that are part of a cycle are None
+    fn evaluate(&self, assignment: &[bool]) -> Vec<Option<bool>> {
+        let mut wires: FxHashMap<Wire, bool> =
+            self.inputs.iter().copied().zip(assignment.iter().copied()).collect();
+        self.outputs
+            .iter()
+            .map(|&wire| evaluate_wire(wire, &self.gate_map, &mut wires, &mut FxHashSet::default()))
+            .collect()
+    }
+
+    // Returns None if the circuit contains a cycle
+    fn topology(&self) -> Option<Topology> {
+        let wire_count = self.names.len();
+
+        // Kahn's algorithm over gates, where a gate is ready once every gate driving one of its
+        // inputs has been placed
+        let mut fanout: Vec<Vec<Wire>> = vec![Vec::new(); wire_count];
+        let mut pending_inputs: FxHashMap<Wire, u8> = FxHashMap::default();
+        // Swapped outputs only swap gate map entries, so the map key is the wire a gate drives
+        for (&output, gate) in &self.gate_map {
+            let driven = [gate.input.0, gate.input.1]
+                .into_iter()
+                .filter(|wire| self.gate_map.contains_key(wire))
+                .count();
+            pending_inputs.insert(output, driven as u8);
+            for wire in [gate.input.0, gate.input.1] {
+                fanout[wire as usize].push(output);
+            }
+        }
+
+        let mut ready: Vec<_> =
+            pending_inputs.iter().filter(|&(_, &n)| n == 0).map(|(&wire, _)| wire).collect();
+        let mut order = Vec::with_capacity(self.gate_map.len());
+        while let Some(wire) = ready.pop() {
+            order.push(Gate { output: wire, ..self.gate_map[&wire].clone() });
+            for &next in &fanout[wire as usize] {
+                let pending = pending_inputs.get_mut(&next).unwrap();
+                *pending -= 1;
+                if *pending == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        if order.len() != self.gate_map.len() {
+            return None;
+        }
+
+        let mut rank = vec![usize::MAX; wire_count];
+        for (i, gate) in order.iter().enumerate() {
+            rank[gate.output as usize] = i;
+        }
+        let fanout = fanout
+            .into_iter()
+            .map(|wires| wires.into_iter().map(|wire| rank[wire as usize]).collect())
+            .collect();
+
+        let mut output_index = vec![None; wire_count];
+        for (i, &wire) in self.outputs.iter().enumerate() {
+            output_index[wire as usize] = Some(i);
+        }
+
+        Some(Topology { order, fanout, output_index, inputs: self.inputs.clone() })
+    }
+}
+
+// Gates in topological order, indexed by interned wire id, for evaluating a circuit without
+// recursion and re-evaluating only the gates affected by an input change
+#[derive(Debug, Clone)]
+struct Topology {
+    order: Vec<Gate>,
+    // Indices into `order` of the gates that read each wire
+    fanout: Vec<Vec<usize>>,
+    // Index into the circuit's outputs for each wire that is an output
+    output_index: Vec<Option<usize>>,
+    inputs: Vec<Wire>,
+}
+
+// Value of every wire, indexed by wire id
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Evaluation(Vec<bool>);
+
+impl Evaluation {
+    fn outputs(&self, outputs: &[Wire]) -> Vec<bool> {
+        outputs.iter().map(|&wire| self.0[wire as usize]).collect()
+    }
+}
+
+impl Topology {
+    // `assignment` is indexed the same as the circuit's inputs
+    fn evaluate(&self, assignment: &[bool]) -> Evaluation {
+        let mut values = vec![false; self.fanout.len()];
+        for (&wire, &bit) in self.inputs.iter().zip(assignment) {
+            values[wire as usize] = bit;
+        }
+        for gate in &self.order {
+            values[gate.output as usize] =
+                gate.logic.apply(values[gate.input.0 as usize], values[gate.input.1 as usize]);
+        }
+        Evaluation(values)
+    }
+
+    // Flips the input at `input` (indexed the same as the circuit's inputs) and updates
+    // `evaluation` to match, visiting only gates downstream of a wire whose value actually changed.
+    // Returns the indices of outputs whose values changed, in ascending order
+    fn flip_input(&self, evaluation: &mut Evaluation, input: usize) -> Vec<usize> {
+        let wire = self.inputs[input] as usize;
+        evaluation.0[wire] = !evaluation.0[wire];
+
+        let mut changed_outputs = Vec::new();
+        if let Some(i) = self.output_index[wire] {
+            changed_outputs.push(i);
+        }
+
+        // Processing pending gates in topological order guarantees each gate is recomputed at most
+        // once, after all of its changed inputs
+        let mut pending: BTreeSet<usize> = self.fanout[wire].iter().copied().collect();
+        while let Some(i) = pending.pop_first() {
+            let gate = &self.order[i];
+            let output = gate
+                .logic
+                .apply(evaluation.0[gate.input.0 as usize], evaluation.0[gate.input.1 as usize]);
+            if output == evaluation.0[gate.output as usize] {
+                continue;
+            }
+
+            evaluation.0[gate.output as usize] = output;
+            pending.extend(&self.fanout[gate.output as usize]);
+            if let Some(i) = self.output_index[gate.output as usize] {
+                changed_outputs.push(i);
+            }
+        }
+
+        changed_outputs.sort_unstable();
+        changed_outputs
+    }
+}
+
+// Generates a ripple-carry adder for `bits`-bit x and y inputs, in puzzle input format
+fn reference_adder(bits: usize) -> String {
+    let mut circuit = String::new();
+    for prefix in ['x', 'y'] {
+        for i in 0..bits {
+            circuit.push_str(&format!("{prefix}{i:02}: 0\n"));
+        }
+    }
+    circuit.push('\n');
+
+    // Sum bit i is x ^ y ^ carry_in; carry_out is (x & y) | ((x ^ y) & carry_in)
+    let carry = |i: usize| if i == bits - 1 { format!("z{bits:02}") } else { format!("c{i:02}") };
+    circuit.push_str("x00 XOR y00 -> z00\n");
+    circuit.push_str(&format!("x00 AND y00 -> {}\n", carry(0)));
+    for i in 1..bits {
+        let carry_in = carry(i - 1);
+        circuit.push_str(&format!("x{i:02} XOR y{i:02} -> s{i:02}\n"));
+        circuit.push_str(&format!("x{i:02} AND y{i:02} -> a{i:02}\n"));
+        circuit.push_str(&format!("s{i:02} XOR {carry_in} -> z{i:02}\n"));
+        circuit.push_str(&format!("s{i:02} AND {carry_in} -> b{i:02}\n"));
+        circuit.push_str(&format!("a{i:02} OR b{i:02} -> {}\n", carry(i)));
+    }
+
+    circuit
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Equivalence {
+    // Every output was shown equal, either symbolically or by testing every possible input
+    Proven,
+    // No difference found over this many random input assignments
+    Probable { trials: usize },
+    Differs(Counterexample),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Counterexample {
+    // Input wires set to 1; all other inputs are 0
+    set_inputs: Vec<String>,
+    output: String,
+    left: Option<bool>,
+    right: Option<bool>,
+}
+
+// Inputs beyond this are tested randomly rather than exhaustively
+const MAX_EXHAUSTIVE_INPUTS: usize = 16;
+
+// Checks whether two circuits with the same input and output wires compute the same function.
+//
+// Each output is first converted to algebraic normal form over GF(2), which is cheap for
+// XOR-dominated logic and gives either a proof or a guaranteed counterexample. If any output's
+// normal form grows too large (e.g. long carry chains), falls back to exhaustive testing for small
+// circuits and randomized testing otherwise
+fn check_equivalence(
+    left: &Circuit,
+    right: &Circuit,
+    random_trials: usize,
+    rng: &mut impl Rng,
+) -> Result<Equivalence, String> {
+    if left.names_of(&left.inputs) != right.names_of(&right.inputs) {
+        return Err("Circuits have different input wires".into());
+    }
+    if left.names_of(&left.outputs) != right.names_of(&right.outputs) {
+        return Err("Circuits have different output wires".into());
+    }
+
+    let mut all_proven = true;
+    if let Some(mut left_anf) = AnfBuilder::new(left) {
+        let mut right_anf = AnfBuilder::new(right).unwrap();
+        // Each circuit interns its own names, so outputs are matched by position; the names were
+        // already checked to line up
+        for (&left_output, &right_output) in left.outputs.iter().zip(&right.outputs) {
+            match (left_anf.wire(left_output), right_anf.wire(right_output)) {
+                (Some(l), Some(r)) if l == r => {}
+                (Some(l), Some(r)) => {
+                    // Setting exactly the inputs of a minimal-degree monomial in the difference
+                    // makes that monomial 1 and every other monomial in the difference 0
+                    let monomial =
+                        l.symmetric_difference(&r).copied().min_by_key(|m| m.count_ones());
+                    let assignment: Vec<_> =
+                        (0..left.inputs.len()).map(|i| monomial.unwrap() & (1 << i) != 0).collect();
+                    let counterexample = find_difference(left, right, &assignment)
+                        .expect("circuits with different normal forms evaluated the same");
+                    return Ok(Equivalence::Differs(counterexample));
+                }
+                _ => all_proven = false,
+            }
+        }
+    } else {
+        all_proven = false;
+    }
+
+    if all_proven {
+        return Ok(Equivalence::Proven);
+    }
+
+    let topologies = left.topology().zip(right.topology());
+    let input_count = left.inputs.len();
+
+    if let Some(topologies) = &topologies {
+        // Without cycles, both circuits can be re-evaluated incrementally. Exhaustive testing visits
+        // assignments in Gray code order and random testing takes a random walk, so that each step
+        // flips a single input
+        let counterexample = if input_count <= MAX_EXHAUSTIVE_INPUTS {
+            let flips = (1..1_u32 << input_count).map(|i| i.trailing_zeros() as usize);
+            search_flips(left, right, topologies, vec![false; input_count], flips)
+        } else {
+            let start = (0..input_count).map(|_| rng.gen()).collect();
+            let flips = (0..random_trials).map(|_| rng.gen_range(0..input_count));
+            search_flips(left, right, topologies, start, flips)
+        };
+
+        return Ok(match counterexample {
+            Some(counterexample) => Equivalence::Differs(counterexample),
+            None if input_count <= MAX_EXHAUSTIVE_INPUTS => Equivalence::Proven,
+            None => Equivalence::Probable { trials: random_trials },
+        });
+    }
+
+    if left.inputs.len() <= MAX_EXHAUSTIVE_INPUTS {
+        for bits in 0..1_u32 << left.inputs.len() {
+            let assignment: Vec<_> = (0..left.inputs.len()).map(|i| bits & (1 << i) != 0).collect();
+            if let Some(counterexample) = find_difference(left, right, &assignment) {
+                return Ok(Equivalence::Differs(counterexample));
+            }
+        }
+        return Ok(Equivalence::Proven);
+    }
+
+    for _ in 0..random_trials {
+        let assignment: Vec<bool> = (0..left.inputs.len()).map(|_| rng.gen()).collect();
+        if let Some(counterexample) = find_difference(left, right, &assignment) {
+            return Ok(Equivalence::Differs(counterexample));
+        }
+    }
+
+    Ok(Equivalence::Probable { trials: random_trials })
+}
+
+// Compares both circuits at `start`, then after each single-input flip. Only outputs that changed
+// in either circuit need to be compared after a flip, since every other output still matches
+fn search_flips(
+    left: &Circuit,
+    right: &Circuit,
+    (left_topology, right_topology): &(Topology, Topology),
+    mut assignment: Vec<bool>,
+    flips: impl Iterator<Item = usize>,
+) -> Option<Counterexample> {
+    let mut left_evaluation = left_topology.evaluate(&assignment);
+    let mut right_evaluation = right_topology.evaluate(&assignment);
+    if left_evaluation.outputs(&left.outputs) != right_evaluation.outputs(&right.outputs) {
+        return find_difference(left, right, &assignment);
+    }
+
+    for input in flips {
+        assignment[input] = !assignment[input];
+        let left_changed = left_topology.flip_input(&mut left_evaluation, input);
+        let right_changed = right_topology.flip_input(&mut right_evaluation, input);
+        if left_changed != right_changed {
+            return find_difference(left, right, &assignment);
+        }
+    }
+
+    None
+}
+
+fn find_difference(left: &Circuit, right: &Circuit, assignment: &[bool]) -> Option<Counterexample> {
+    let left_outputs = left.evaluate(assignment);
+    let right_outputs = right.evaluate(assignment);
+
+    let i = (0..left_outputs.len()).find(|&i| left_outputs[i] != right_outputs[i])?;
+    Some(Counterexample {
+        set_inputs: left
+            .inputs
+            .iter()
+            .zip(assignment)
+            .filter(|&(_, &bit)| bit)
+            .map(|(&wire, _)| left.names.name(wire).into())
+            .collect(),
+        output: left.names.name(left.outputs[i]).into(),
+        left: left_outputs[i],
+        right: right_outputs[i],
+    })
+}
+
+// Algebraic normal form: XOR of monomials, where each monomial is the AND of the inputs in a
+// bitmask over input indices
+type Anf = FxHashSet<u128>;
+
+// Normal forms larger than this are abandoned
+const MAX_ANF_TERMS: usize = 1 << 12;
+
+struct AnfBuilder<'a> {
+    circuit: &'a Circuit<'a>,
+    // None if the wire's normal form is too large or the wire is part of a cycle
+    cache: FxHashMap<Wire, Option<Rc<Anf>>>,
+    evaluating: FxHashSet<Wire>,
+}
+
+impl<'a> AnfBuilder<'a> {
+    // Returns None if the circuit has too many inputs to represent monomials as bitmasks
+    fn new(circuit: &'a Circuit<'a>) -> Option<Self> {
+        if circuit.inputs.len() > 128 {
+            return None;
+        }
+
+        let cache = circuit
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, &wire)| (wire, Some(Rc::new(Anf::from_iter([1 << i])))))
+            .collect();
+        Some(Self { circuit, cache, evaluating: FxHashSet::default() })
+    }
+
+    fn wire(&mut self, wire: Wire) -> Option<Rc<Anf>> {
+        if let Some(anf) = self.cache.get(&wire) {
+            return anf.clone();
+        }
+
+        if !self.evaluating.insert(wire) {
+            return None;
+        }
+
+        let gate = self.circuit.gate_map.get(&wire).unwrap();
+        let anf = self.wire(gate.input.0).zip(self.wire(gate.input.1)).and_then(|(a, b)| {
+            match gate.logic {
+                Logic::Xor => Some(anf_xor(&a, &b)),
+                Logic::And => anf_and(&a, &b),
+                // a | b = a ^ b ^ ab
+                Logic::Or => anf_and(&a, &b).map(|ab| anf_xor(&anf_xor(&a, &b), &ab)),
+            }
+        });
+        let anf = anf.filter(|anf| anf.len() <= MAX_ANF_TERMS).map(Rc::new);
+
+        self.evaluating.remove(&wire);
+        self.cache.insert(wire, anf.clone());
+        anf
+    }
+}
+
+fn anf_xor(a: &Anf, b: &Anf) -> Anf {
+    a.symmetric_difference(b).copied().collect()
+}
+
+fn anf_and(a: &Anf, b: &Anf) -> Option<Anf> {
+    if a.len() * b.len() > MAX_ANF_TERMS * 4 {
+        return None;
+    }
+
+    let mut product = Anf::default();
+    for &ma in a {
+        for &mb in b {
+            // x & x = x, and identical monomials cancel under XOR
+            if !product.insert(ma | mb) {
+                product.remove(&(ma | mb));
+            }
+        }
+    }
+    Some(product)
+}
+
+fn print_equivalence(equivalence: &Equivalence) {
+    match equivalence {
+        Equivalence::Proven => println!("Circuits are equivalent"),
+        Equivalence::Probable { trials } => {
+            println!("No differences found in {trials} random trials");
+        }
+        Equivalence::Differs(Counterexample { set_inputs, output, left, right }) => {
+            println!("Circuits differ at {output}: {left:?} vs {right:?}");
+            println!("Inputs set to 1: [{}]", set_inputs.join(", "));
+        }
+    }
+}
+
+const EQUIVALENCE_TRIALS: usize = 1000;
+
+// Builds the circuit graph for export. Each gate is a node named after its output wire, with edges
+// from the gate's input wires; x/y start wires and z output wires are colored
+fn build_export_graph(input: &str) -> Result<ExportGraph, ParseError> {
+    let Input { names, start_wires, gates } = parse_input(input)?;
+
+    let mut graph = ExportGraph::new(true);
+    graph.attrs.push(("rankdir", "LR".into()));
+    graph.node_attrs.push(("style", "filled".into()));
+
+    let mut start_wires: Vec<_> =
+        start_wires.into_iter().map(|(wire, _)| names.name(wire)).collect();
+    start_wires.sort();
+    for wire in start_wires {
+        let fill = if wire.starts_with('x') { "lightblue" } else { "lightgreen" };
+        graph.nodes.push(ExportNode::new(wire).attr("shape", "box").attr("fillcolor", fill));
+    }
+
+    let mut gates = gates;
+    gates.sort_by_key(|gate| names.name(gate.output));
+    for gate in gates {
+        let output = names.name(gate.output);
+        let fill = if output.starts_with('z') { "orange" } else { "white" };
+        graph.nodes.push(
+            ExportNode::new(output)
+                .attr("label", format!("{output}\n{}", gate.logic.name()))
+                .attr("fillcolor", fill),
+        );
+
+        for input_wire in [gate.input.0, gate.input.1] {
+            graph.edges.push(ExportEdge::new(names.name(input_wire), output));
+        }
+    }
+
+    Ok(graph)
+}
+
+pub const SOLVER: Solver = Solver::Parts(
+    |input| solve_part_1(input).unwrap_solution(),
+    |input| solve_part_2(input, |a, b| a + b).unwrap_solution(),
+);
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(path) = crate::flag_value("--export") {
+        let graph = build_export_graph(&crate::read_input()?)?;
+        fs::write(&path, graph.render(ExportFormat::from_path(&path)))?;
+        return Ok(());
+    }
+
+    let reference = if let Some(path) = crate::flag_value("--check-against") {
+        Some(fs::read_to_string(path)?)
+    } else if crate::has_flag("--check-adder") {
+        None
+    } else {
+        return crate::run(solve_part_1, |input| solve_part_2(input, |a, b| a + b));
+    };
+
+    let input = crate::read_input()?;
+    let circuit = Circuit::parse(&input)?;
+    let reference =
+        reference.unwrap_or_else(|| reference_adder(circuit.outputs.len().saturating_sub(1)));
+    let reference = Circuit::parse(&reference)?;
+    crate::set_deterministic(crate::RunOptions::from_env()?.deterministic);
+    let equivalence =
+        check_equivalence(&circuit, &reference, EQUIVALENCE_TRIALS, &mut crate::rng())?;
+    print_equivalence(&equivalence);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day24.txt");
+    const SAMPLE_INPUT_2: &str = include_str!("../../sample/2024/day24-2.txt");
+    const SAMPLE_INPUT_3: &str = include_str!("../../sample/2024/day24-3.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(4), solve_part_1(SAMPLE_INPUT));
+        assert_eq!(Ok(2024), solve_part_1(SAMPLE_INPUT_2));
+    }
+
+    #[test]
+    fn part_2() {
+        assert_eq!(Ok("z00,z01,z02,z05"), solve_part_2(SAMPLE_INPUT_3, |a, b| a & b).as_deref());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve_part_1(&SAMPLE_INPUT.replace("x00 AND", "x00 NAND")).unwrap_err();
+        assert_eq!("line 8, column 4: Unexpected character: ' '", err.to_string());
+
+        let err = solve_part_1(&SAMPLE_INPUT.replace("x01 XOR y01", "x01 XOR w01")).unwrap_err();
+        assert_eq!("Wire w01 is never set", err.to_string());
+
+        let err = solve_part_1(&SAMPLE_INPUT.replace("-> z02", "-> z64")).unwrap_err();
+        assert_eq!("Output wire z64 is not z00 through z63", err.to_string());
+
+        let cycle = "x00: 1\n\nx00 AND a -> b\nx00 OR b -> a\nb XOR a -> z00\n";
+        assert_eq!(Err(ParseError::new("Gates form a cycle")), solve_part_1(cycle));
+    }
+
+    #[test]
+    fn part_2_generated() {
+        let mut rng = StdRng::seed_from_u64(24);
+        for bits in [8, 12, 16] {
+            let (input, swapped) = generate::adder_with_swaps(&mut rng, bits, 4);
+            assert_eq!(Ok(swapped.join(",")), solve_part_2(&input, |a, b| a + b));
+        }
+    }
+
+    // Replaces each carry OR with XOR, which is equivalent because both carry terms can't be set
+    fn xor_carry_adder(bits: usize) -> String {
+        reference_adder(bits).replace(" OR ", " XOR ")
+    }
+
+    // Lists the gates in reverse and gives every internal wire a different prefix
+    fn renamed_adder(bits: usize) -> String {
+        let reference = reference_adder(bits);
+        let (inputs, gates) = reference.split_once("\n\n").unwrap();
+        let rename = |token: &str| match token.split_at(1) {
+            ("s", n) => format!("p{n}"),
+            ("a", n) => format!("g{n}"),
+            ("b", n) => format!("h{n}"),
+            ("c", n) => format!("k{n}"),
+            _ => token.into(),
+        };
+        let gates: Vec<_> = gates
+            .lines()
+            .rev()
+            .map(|line| line.split(' ').map(rename).collect::<Vec<_>>().join(" "))
+            .collect();
+        format!("{inputs}\n\n{}\n", gates.join("\n"))
+    }
+
+    #[test]
+    fn equivalence() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let (reference, xor_carry) = (reference_adder(4), xor_carry_adder(4));
+        let reference = Circuit::parse(&reference).unwrap();
+        assert_eq!(
+            Ok(Equivalence::Proven),
+            check_equivalence(&reference, &reference, 10, &mut rng)
+        );
+
+        let xor_carry = Circuit::parse(&xor_carry).unwrap();
+        assert_eq!(
+            Ok(Equivalence::Proven),
+            check_equivalence(&reference, &xor_carry, 10, &mut rng)
+        );
+
+        // Wire ids come from each circuit's own names, so reordering gates and renaming internal
+        // wires changes every id without changing the logic
+        let renamed = renamed_adder(4);
+        let renamed = Circuit::parse(&renamed).unwrap();
+        assert_eq!(Ok(Equivalence::Proven), check_equivalence(&reference, &renamed, 10, &mut rng));
+        assert_eq!(Ok(Equivalence::Proven), check_equivalence(&renamed, &xor_carry, 10, &mut rng));
+
+        // Too large for normal form analysis or exhaustive testing
+        let (reference, xor_carry) = (reference_adder(40), xor_carry_adder(40));
+        let reference = Circuit::parse(&reference).unwrap();
+        let xor_carry = Circuit::parse(&xor_carry).unwrap();
+        assert_eq!(
+            Ok(Equivalence::Probable { trials: 10 }),
+            check_equivalence(&reference, &xor_carry, 10, &mut rng)
+        );
+    }
+
+    #[test]
+    fn equivalence_counterexample() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let reference = reference_adder(4);
+        let reference = Circuit::parse(&reference).unwrap();
+        let mut swapped = reference.clone();
+        let (z01, z02) = (swapped.names.get("z01").unwrap(), swapped.names.get("z02").unwrap());
+        hashmap_swap(&mut swapped.gate_map, z01, z02);
+
+        let Ok(Equivalence::Differs(counterexample)) =
+            check_equivalence(&reference, &swapped, 10, &mut rng)
+        else {
+            panic!("Expected circuits to differ");
+        };
+        assert_eq!("z01", counterexample.output);
+        assert_ne!(counterexample.left, counterexample.right);
+
+        // An AND in place of an XOR in a long adder is still found by the normal form analysis
+        let reference = reference_adder(40);
+        let broken = reference.replace("x00 XOR y00 -> z00", "x00 AND y00 -> z00");
+        let (reference, broken) =
+            (Circuit::parse(&reference).unwrap(), Circuit::parse(&broken).unwrap());
+        let Ok(Equivalence::Differs(counterexample)) =
+            check_equivalence(&reference, &broken, 10, &mut rng)
+        else {
+            panic!("Expected circuits to differ");
+        };
+        assert_eq!("z00", counterexample.output);
+
+        let smaller = reference_adder(3);
+        let smaller = Circuit::parse(&smaller).unwrap();
+        assert!(check_equivalence(&reference, &smaller, 10, &mut rng).is_err());
+    }
+
+    #[test]
+    fn incremental_flips() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let (input, _) = generate::adder_with_swaps(&mut rng, 12, 4);
+        let circuit = Circuit::parse(&input).unwrap();
+        let topology = circuit.topology().unwrap();
+
+        let mut assignment: Vec<bool> = (0..circuit.inputs.len()).map(|_| rng.gen()).collect();
+        let mut evaluation = topology.evaluate(&assignment);
+        let full = |assignment: &[bool]| -> Vec<bool> {
+            circuit.evaluate(assignment).into_iter().map(Option::unwrap).collect()
+        };
+        assert_eq!(full(&assignment), evaluation.outputs(&circuit.outputs));
+
+        for _ in 0..200 {
+            let input = rng.gen_range(0..assignment.len());
+            let before = evaluation.outputs(&circuit.outputs);
+            let changed = topology.flip_input(&mut evaluation, input);
+            assignment[input] = !assignment[input];
+
+            let after = full(&assignment);
+            assert_eq!(after, evaluation.outputs(&circuit.outputs));
+            let expected: Vec<_> = (0..after.len()).filter(|&i| before[i] != after[i]).collect();
+            assert_eq!(expected, changed);
+        }
+
+        // Swapping z02 with its carry input makes the XOR gate read its own output
+        let reference = reference_adder(4);
+        let mut cyclic = Circuit::parse(&reference).unwrap();
+        let (z02, c01) = (cyclic.names.get("z02").unwrap(), cyclic.names.get("c01").unwrap());
+        hashmap_swap(&mut cyclic.gate_map, z02, c01);
+        assert!(cyclic.topology().is_none());
+    }
+
+    #[test]
+    fn export() {
+        let graph = build_export_graph(SAMPLE_INPUT).unwrap();
+        assert_eq!(9, graph.nodes.len());
+        assert_eq!(6, graph.edges.len());
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"x00\" -> \"z00\";"));
+        assert!(dot.contains("\"z00\" [label=\"z00\\nAND\", fillcolor=\"orange\"];"));
+    }
+}
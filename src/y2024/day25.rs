@@ -0,0 +1,175 @@
+//! Day 25: Code Chronicle
+//!
+//! <https://adventofcode.com/2024/day/25>
+
+use crate::days::Solver;
+use crate::{ParseError, SolverOutput};
+use std::error::Error;
+
+// Each schematic is stored as a bitmask of its filled cells, in row-major order from the top left.
+// Locks are filled from the top and keys from the bottom, so a lock and key fit together exactly
+// when no cell is filled in both
+#[derive(Debug)]
+struct Input {
+    locks: Vec<u64>,
+    keys: Vec<u64>,
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let mut lines = input.lines().enumerate().peekable();
+
+    let mut dimensions: Option<(usize, usize)> = None;
+    let mut locks = Vec::new();
+    let mut keys = Vec::new();
+    while lines.peek().is_some() {
+        let mut mask = 0_u64;
+        let mut bit = 0;
+        let mut width = 0;
+        let mut height = 0;
+        let mut is_lock = false;
+        let mut first_row = 0;
+        for (row, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+            if height == 0 {
+                width = line.len();
+                is_lock = line.bytes().all(|b| b == b'#');
+                first_row = row;
+            }
+            if line.len() != width {
+                return Err(ParseError::at(row, 0, "Mismatched schematic widths in input"));
+            }
+            if bit + width > 64 {
+                let message = "Schematics with more than 64 cells are not supported";
+                return Err(ParseError::at(row, 0, message));
+            }
+
+            for (col, b) in line.bytes().enumerate() {
+                if !matches!(b, b'#' | b'.') {
+                    let message = format!("Unexpected schematic character: '{}'", b as char);
+                    return Err(ParseError::at(row, col, message));
+                }
+                mask |= u64::from(b == b'#') << bit;
+                bit += 1;
+            }
+            height += 1;
+        }
+
+        if height == 0 {
+            // Extra blank line
+            continue;
+        }
+
+        if dimensions.is_some_and(|dimensions| dimensions != (width, height)) {
+            return Err(ParseError::at(first_row, 0, "Mismatched schematic dimensions in input"));
+        }
+        dimensions = Some((width, height));
+
+        if is_lock {
+            locks.push(mask);
+        } else {
+            keys.push(mask);
+        }
+    }
+
+    if dimensions.is_none() {
+        return Err(ParseError::new("Input is empty"));
+    }
+
+    Ok(Input { locks, keys })
+}
+
+fn solve_part_1(input: &str) -> Result<usize, ParseError> {
+    let Input { locks, keys } = parse_input(input)?;
+
+    Ok(locks.iter().map(|&lock| keys.iter().filter(|&&key| lock & key == 0).count()).sum())
+}
+
+pub const SOLVER: Solver = Solver::Part1(|input| solve_part_1(input).unwrap_solution());
+
+#[cfg(feature = "fuzzing")]
+pub const FUZZ_TARGET: fn(&str) -> Result<(), ParseError> = |input| parse_input(input).map(drop);
+
+pub fn main() -> Result<(), Box<dyn Error>> {
+    crate::run_part1_only(solve_part_1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const SAMPLE_INPUT: &str = include_str!("../../sample/2024/day25.txt");
+
+    #[test]
+    fn part_1() {
+        assert_eq!(Ok(3), solve_part_1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = solve_part_1("###\n.#.\n...\n\n...\n.#\n###\n").unwrap_err();
+        assert_eq!("line 6, column 1: Mismatched schematic widths in input", err.to_string());
+
+        let err = solve_part_1("###\n.#.\n...\n\n...\n...\n.#.\n###\n").unwrap_err();
+        assert_eq!("line 5, column 1: Mismatched schematic dimensions in input", err.to_string());
+
+        let err = solve_part_1("###\n.o.\n...\n").unwrap_err();
+        assert_eq!("line 2, column 2: Unexpected schematic character: 'o'", err.to_string());
+        assert_eq!(Err(ParseError::new("Input is empty")), solve_part_1("\n"));
+    }
+
+    // Original parser that materializes each schematic as a boolean grid and converts it to column
+    // heights; kept to check the bitmask parser against
+    fn parse_heights(input: &str) -> (u32, Vec<Vec<u32>>, Vec<Vec<u32>>) {
+        let mut lines = input.lines();
+
+        let mut total_height = 0;
+        let mut lock_heights = Vec::new();
+        let mut key_heights = Vec::new();
+        loop {
+            let schematic: Vec<Vec<_>> = lines
+                .by_ref()
+                .take_while(|line| !line.is_empty())
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect();
+
+            if schematic.is_empty() {
+                break;
+            }
+
+            total_height = schematic.len() as u32;
+            let heights: Vec<u32> = (0..schematic[0].len())
+                .map(|col| schematic.iter().filter(|row| row[col]).count() as u32)
+                .collect();
+            if schematic[0].iter().all(|&b| b) {
+                lock_heights.push(heights);
+            } else {
+                key_heights.push(heights);
+            }
+        }
+
+        (total_height, lock_heights, key_heights)
+    }
+
+    #[test]
+    fn bitmasks_match_heights() {
+        let mut rng = StdRng::seed_from_u64(25);
+        let schematics: Vec<_> =
+            (0..200).map(|i| generate::schematic(&mut rng, i % 3 == 0)).collect();
+        let input = schematics.join("\n");
+
+        let Input { locks, keys } = parse_input(&input).unwrap();
+        let (total_height, lock_heights, key_heights) = parse_heights(&input);
+        assert_eq!(lock_heights.len(), locks.len());
+        assert_eq!(key_heights.len(), keys.len());
+
+        for (&lock, lock_heights) in locks.iter().zip(&lock_heights) {
+            for (&key, key_heights) in keys.iter().zip(&key_heights) {
+                let fits_by_heights =
+                    lock_heights.iter().zip(key_heights).all(|(l, k)| l + k <= total_height);
+                assert_eq!(fits_by_heights, lock & key == 0);
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Advent of Code 2024, one module per day
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+use crate::days::Day;
+
+pub const DAYS: [Day; 25] = [
+    Day { year: 2024, day: 1, title: "Historian Hysteria", parts: 2, solver: day01::SOLVER },
+    Day { year: 2024, day: 2, title: "Red-Nosed Reports", parts: 2, solver: day02::SOLVER },
+    Day { year: 2024, day: 3, title: "Mull It Over", parts: 2, solver: day03::SOLVER },
+    Day { year: 2024, day: 4, title: "Ceres Search", parts: 2, solver: day04::SOLVER },
+    Day { year: 2024, day: 5, title: "Print Queue", parts: 2, solver: day05::SOLVER },
+    Day { year: 2024, day: 6, title: "Guard Gallivant", parts: 2, solver: day06::SOLVER },
+    Day { year: 2024, day: 7, title: "Bridge Repair", parts: 2, solver: day07::SOLVER },
+    Day { year: 2024, day: 8, title: "Resonant Collinearity", parts: 2, solver: day08::SOLVER },
+    Day { year: 2024, day: 9, title: "Disk Fragmenter", parts: 2, solver: day09::SOLVER },
+    Day { year: 2024, day: 10, title: "Hoof It", parts: 2, solver: day10::SOLVER },
+    Day { year: 2024, day: 11, title: "Plutonian Pebbles", parts: 2, solver: day11::SOLVER },
+    Day { year: 2024, day: 12, title: "Garden Groups", parts: 2, solver: day12::SOLVER },
+    Day { year: 2024, day: 13, title: "Claw Contraption", parts: 2, solver: day13::SOLVER },
+    Day { year: 2024, day: 14, title: "Restroom Redoubt", parts: 2, solver: day14::SOLVER },
+    Day { year: 2024, day: 15, title: "Warehouse Woes", parts: 2, solver: day15::SOLVER },
+    Day { year: 2024, day: 16, title: "Reindeer Maze", parts: 2, solver: day16::SOLVER },
+    Day { year: 2024, day: 17, title: "Chronospatial Computer", parts: 2, solver: day17::SOLVER },
+    Day { year: 2024, day: 18, title: "RAM Run", parts: 2, solver: day18::SOLVER },
+    Day { year: 2024, day: 19, title: "Linen Layout", parts: 2, solver: day19::SOLVER },
+    Day { year: 2024, day: 20, title: "Race Condition", parts: 2, solver: day20::SOLVER },
+    Day { year: 2024, day: 21, title: "Keypad Conundrum", parts: 2, solver: day21::SOLVER },
+    Day { year: 2024, day: 22, title: "Monkey Market", parts: 2, solver: day22::SOLVER },
+    Day { year: 2024, day: 23, title: "LAN Party", parts: 2, solver: day23::SOLVER },
+    Day { year: 2024, day: 24, title: "Crossed Wires", parts: 2, solver: day24::SOLVER },
+    Day { year: 2024, day: 25, title: "Code Chronicle", parts: 1, solver: day25::SOLVER },
+];
+
+// Days that call crate::visualize
+pub const VISUALIZED_DAYS: [u32; 5] = [6, 14, 15, 16, 18];
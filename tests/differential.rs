@@ -0,0 +1,37 @@
+//! Differential tests comparing optimized solvers against naive reference implementations on
+//! generated inputs. Requires the `reference` feature:
+//!
+//! ```shell
+//! cargo test --release --features reference --test differential
+//! ```
+//!
+//! Set `AOC_DIFF_SEEDS` to run more seeds than the default. Failures report the smallest generated
+//! size that still disagrees
+
+#![cfg(feature = "reference")]
+
+use advent_of_code_2024::differential;
+use std::env;
+
+const DEFAULT_SEEDS: u64 = 10;
+
+#[test]
+fn optimized_matches_reference() {
+    let seeds = env::var("AOC_DIFF_SEEDS").map_or(DEFAULT_SEEDS, |seeds| seeds.parse().unwrap());
+
+    let mut failures = Vec::new();
+    for (day, case) in differential::CASES {
+        if let Err(mismatch) = differential::check(day, &case, 0..seeds) {
+            failures.push(format!(
+                "Day {day} seed {} size {}: optimized={:?} reference={:?}\ninput:\n{}",
+                mismatch.seed,
+                mismatch.size,
+                mismatch.optimized,
+                mismatch.reference,
+                mismatch.input
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,89 @@
+//! Quick deterministic stand-in for the cargo-fuzz targets in fuzz/: runs every parser target on
+//! truncated and mutated copies of the sample inputs and checks that none of them panic. Requires
+//! the `fuzzing` feature:
+//!
+//! ```shell
+//! cargo test --release --features fuzzing --test parsers
+//! ```
+//!
+//! Set `AOC_FUZZ_MUTATIONS` to try more mutations per sample than the default
+
+#![cfg(feature = "fuzzing")]
+
+use advent_of_code_2024::fuzzing;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::env;
+use std::fs;
+use std::panic;
+
+const DEFAULT_MUTATIONS: usize = 200;
+
+// Bytes that are likely to get a parser past its first check
+const INTERESTING: &[u8] = b"0123456789-+,:|= \n.#@OSE^<>v[]xyzAXY\xff";
+
+// Sample inputs for a target: every sample for its day, or the grid days' samples for byte_grid
+fn samples(target: &str) -> Vec<Vec<u8>> {
+    let days = match target {
+        "byte_grid" => vec![4, 12],
+        _ => vec![target.strip_prefix("day").unwrap().parse::<u32>().unwrap()],
+    };
+
+    let mut samples: Vec<_> = fs::read_dir("sample/2024")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            days.iter().any(|day| {
+                name.strip_prefix(&format!("day{day}"))
+                    .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('-'))
+            })
+        })
+        .map(|path| fs::read(path).unwrap())
+        .collect();
+    samples.sort();
+    samples
+}
+
+fn mutate(rng: &mut StdRng, sample: &[u8]) -> Vec<u8> {
+    let mut input = sample.to_vec();
+    for _ in 0..rng.gen_range(1..=3) {
+        let i = rng.gen_range(0..=input.len());
+        match rng.gen_range(0..3) {
+            0 if i < input.len() => input[i] = INTERESTING[rng.gen_range(0..INTERESTING.len())],
+            1 if i < input.len() => {
+                input.remove(i);
+            }
+            _ => input.insert(i, INTERESTING[rng.gen_range(0..INTERESTING.len())]),
+        }
+    }
+    input
+}
+
+#[test]
+fn parsers_never_panic() {
+    let mutations =
+        env::var("AOC_FUZZ_MUTATIONS").map_or(DEFAULT_MUTATIONS, |n| n.parse().unwrap());
+
+    let mut failures = Vec::new();
+    for (name, _) in fuzzing::TARGETS {
+        let samples = samples(name);
+        assert!(!samples.is_empty(), "No samples for fuzz target {name}");
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut inputs = vec![Vec::new()];
+        for sample in &samples {
+            inputs.extend((0..sample.len()).map(|len| sample[..len].to_vec()));
+            inputs.extend((0..mutations).map(|_| mutate(&mut rng, sample)));
+        }
+
+        for input in inputs {
+            if panic::catch_unwind(|| fuzzing::run(name, &input)).is_err() {
+                failures.push(format!("{name}: {:?}", String::from_utf8_lossy(&input)));
+                break;
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{} targets panicked:\n{}", failures.len(), failures.join("\n"));
+}
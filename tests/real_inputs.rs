@@ -0,0 +1,207 @@
+// Runs every day's binary against real inputs and checks the results against an answers file.
+//
+// Real inputs and answers are not committed, so this test does nothing unless `AOC_INPUT_DIR` is
+// set. The directory should contain `input<N>.txt` for each day to check, plus an `answers.txt`
+// (or the file at `AOC_ANSWERS`) with one line per day:
+//
+//   <day> <part 1 answer> [<part 2 answer>] [max=<time>] [max1=<time>] [max2=<time>]
+//
+// Days without an input file or an answers line are skipped. Run with `--release` to avoid slow
+// debug builds of the solvers:
+//
+//   AOC_INPUT_DIR=/path/to/inputs cargo test --release --test real_inputs
+//
+// `max=` is a time budget for each part of that day (e.g. `max=100ms`), and `max1=`/`max2=` set a
+// budget for one part. `AOC_MAX_PART_TIME` sets a budget for every part that does not have one in
+// the answers file. Parts with a budget are timed with `AOCTIME`, and a part whose median run time
+// is over budget fails the test, or is only reported if `AOC_BUDGET_WARN` is set
+
+use advent_of_code_2024::report;
+use advent_of_code_2024::timeout::parse_duration;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+const BINARIES: [(u32, &str); 25] = [
+    (1, env!("CARGO_BIN_EXE_day1")),
+    (2, env!("CARGO_BIN_EXE_day2")),
+    (3, env!("CARGO_BIN_EXE_day3")),
+    (4, env!("CARGO_BIN_EXE_day4")),
+    (5, env!("CARGO_BIN_EXE_day5")),
+    (6, env!("CARGO_BIN_EXE_day6")),
+    (7, env!("CARGO_BIN_EXE_day7")),
+    (8, env!("CARGO_BIN_EXE_day8")),
+    (9, env!("CARGO_BIN_EXE_day9")),
+    (10, env!("CARGO_BIN_EXE_day10")),
+    (11, env!("CARGO_BIN_EXE_day11")),
+    (12, env!("CARGO_BIN_EXE_day12")),
+    (13, env!("CARGO_BIN_EXE_day13")),
+    (14, env!("CARGO_BIN_EXE_day14")),
+    (15, env!("CARGO_BIN_EXE_day15")),
+    (16, env!("CARGO_BIN_EXE_day16")),
+    (17, env!("CARGO_BIN_EXE_day17")),
+    (18, env!("CARGO_BIN_EXE_day18")),
+    (19, env!("CARGO_BIN_EXE_day19")),
+    (20, env!("CARGO_BIN_EXE_day20")),
+    (21, env!("CARGO_BIN_EXE_day21")),
+    (22, env!("CARGO_BIN_EXE_day22")),
+    (23, env!("CARGO_BIN_EXE_day23")),
+    (24, env!("CARGO_BIN_EXE_day24")),
+    (25, env!("CARGO_BIN_EXE_day25")),
+];
+
+#[derive(Debug)]
+struct Answers {
+    part1: String,
+    part2: Option<String>,
+    // Per-part time budgets, indexed by part - 1
+    budgets: [Option<Duration>; 2],
+}
+
+fn parse_answers(contents: &str) -> BTreeMap<u32, Answers> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (budgets, answers): (Vec<_>, Vec<_>) =
+                line.split_ascii_whitespace().partition(|field| field.starts_with("max"));
+            let mut answers = answers.into_iter();
+            let day = answers.next().unwrap().parse().expect("Invalid day in answers file");
+            let part1 = answers.next().expect("Missing part 1 answer").into();
+            let part2 = answers.next().map(String::from);
+
+            let mut parts = [None; 2];
+            for budget in budgets {
+                let (key, time) = budget.split_once('=').expect("Budgets are e.g. max=100ms");
+                let time = Some(parse_duration(time).unwrap());
+                match key {
+                    "max" => parts = [time; 2],
+                    "max1" => parts[0] = time,
+                    "max2" => parts[1] = time,
+                    _ => panic!("Invalid budget '{budget}' in answers file"),
+                }
+            }
+
+            (day, Answers { part1, part2, budgets: parts })
+        })
+        .collect()
+}
+
+// Median run time of each part, from AOCTIME CSV rows `day,part,mean_us,median_us,p95_us,mad_us,cold_us`
+fn part_medians(binary: &str, input_path: &Path) -> Result<Vec<(u32, Duration)>, String> {
+    let output = Command::new(binary)
+        .arg(input_path)
+        .env("AOCTIME", "1")
+        .env("AOCTIME_FORMAT", "csv")
+        .output()
+        .map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().into());
+    }
+
+    let stdout = String::from_utf8(output.stdout).map_err(|err| err.to_string())?;
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<_> = line.split(',').collect();
+            let [_, part, _, median, ..] = fields[..] else {
+                return None;
+            };
+            let median: f64 = median.parse().ok()?;
+            Some((part.parse().ok()?, Duration::from_secs_f64(median / 1_000_000.0)))
+        })
+        .collect())
+}
+
+#[test]
+fn real_inputs() {
+    let Some(input_dir) = env::var_os("AOC_INPUT_DIR").map(PathBuf::from) else {
+        eprintln!("AOC_INPUT_DIR not set; skipping real input checks");
+        return;
+    };
+
+    let answers_path =
+        env::var_os("AOC_ANSWERS").map_or_else(|| input_dir.join("answers.txt"), PathBuf::from);
+    let answers = parse_answers(&fs::read_to_string(&answers_path).unwrap_or_else(|err| {
+        panic!("Unable to read answers file {}: {err}", answers_path.display())
+    }));
+
+    let default_budget = env::var("AOC_MAX_PART_TIME")
+        .ok()
+        .map(|time| parse_duration(&time).expect("Invalid AOC_MAX_PART_TIME"));
+    let warn_only = env::var_os("AOC_BUDGET_WARN").is_some();
+
+    let mut failures = Vec::new();
+    let mut over_budget = Vec::new();
+    let mut checked = 0;
+    for (day, binary) in BINARIES {
+        let input_path = input_dir.join(format!("input{day}.txt"));
+        let Some(expected) = answers.get(&day).filter(|_| input_path.exists()) else {
+            continue;
+        };
+
+        let output = Command::new(binary).arg(&input_path).env_remove("AOCTIME").output().unwrap();
+        if !output.status.success() {
+            failures.push(format!(
+                "Day {day} exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+            continue;
+        }
+
+        // Part 2 is the last line because some days print other output (e.g. day 14's tree)
+        // between the two solutions
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<_> = stdout.lines().collect();
+        let actual1 = lines.first().copied().unwrap_or_default();
+        let actual2 = lines.last().copied().unwrap_or_default();
+
+        if actual1 != expected.part1 {
+            failures.push(format!("Day {day} part 1: expected {}, got {actual1}", expected.part1));
+        }
+        if let Some(part2) = &expected.part2 {
+            if actual2 != part2 {
+                failures.push(format!("Day {day} part 2: expected {part2}, got {actual2}"));
+            }
+        }
+        checked += 1;
+
+        let budgets = expected.budgets.map(|budget| budget.or(default_budget));
+        if budgets.iter().all(Option::is_none) {
+            continue;
+        }
+        match part_medians(binary, &input_path) {
+            Ok(medians) => {
+                for (part, median) in medians {
+                    let Some(budget) = budgets.get(part as usize - 1).copied().flatten() else {
+                        continue;
+                    };
+                    if median > budget {
+                        over_budget.push(format!(
+                            "Day {day} part {part}: {} is over the {} budget",
+                            report::format_duration(median),
+                            report::format_duration(budget)
+                        ));
+                    }
+                }
+            }
+            Err(err) => failures.push(format!("Day {day} timing failed: {err}")),
+        }
+    }
+
+    if warn_only {
+        for message in &over_budget {
+            eprintln!("Warning: {message}");
+        }
+    } else {
+        failures.extend(over_budget);
+    }
+
+    assert!(failures.is_empty(), "{} failures:\n{}", failures.len(), failures.join("\n"));
+    eprintln!("Checked {checked} days against real inputs");
+}
@@ -0,0 +1,97 @@
+//! Browser front-end that runs the solvers client-side: paste an input, pick a day, and get both
+//! answers along with how long the solver took
+//!
+//! Build and serve with [Trunk](https://trunkrs.dev): `trunk serve --release`
+
+use advent_of_code_2024::days;
+use web_sys::{HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Solution {
+    day: u32,
+    part1: String,
+    part2: String,
+    millis: f64,
+}
+
+fn now_millis() -> f64 {
+    web_sys::window().and_then(|window| window.performance()).map_or(0.0, |perf| perf.now())
+}
+
+#[function_component]
+fn App() -> Html {
+    let day = use_state(|| 1_u32);
+    let input = use_state(String::new);
+    let solution = use_state(|| None::<Solution>);
+
+    let on_day_change = {
+        let day = day.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            day.set(select.value().parse().unwrap_or(1));
+        })
+    };
+
+    let on_input = {
+        let input = input.clone();
+        Callback::from(move |e: InputEvent| {
+            let textarea: HtmlTextAreaElement = e.target_unchecked_into();
+            input.set(textarea.value());
+        })
+    };
+
+    let on_solve = {
+        let (day, input, solution) = (day.clone(), input.clone(), solution.clone());
+        Callback::from(move |_: MouseEvent| {
+            let Some(entry) = days::get(days::DEFAULT_YEAR, *day) else { return };
+
+            // Puzzle inputs always end with a newline, but pasted text often doesn't
+            let mut text = (*input).clone();
+            if !text.ends_with('\n') {
+                text.push('\n');
+            }
+
+            let start = now_millis();
+            let (part1, part2) = entry.solver.solve(&text);
+            let millis = now_millis() - start;
+            let (part1, part2) = (part1.to_string(), part2.to_string());
+            solution.set(Some(Solution { day: entry.day, part1, part2, millis }));
+        })
+    };
+
+    let options = days::year(days::DEFAULT_YEAR).unwrap_or_default().iter().map(|entry| {
+        html! {
+            <option value={entry.day.to_string()} selected={entry.day == *day}>
+                { format!("Day {}: {}", entry.day, entry.title) }
+            </option>
+        }
+    });
+
+    let result = match &*solution {
+        Some(Solution { day, part1, part2, millis }) => html! {
+            <table>
+                <tr><th>{ "Day" }</th><td>{ day }</td></tr>
+                <tr><th>{ "Part 1" }</th><td><code>{ part1 }</code></td></tr>
+                <tr><th>{ "Part 2" }</th><td><code>{ part2 }</code></td></tr>
+                <tr><th>{ "Time" }</th><td>{ format!("{millis:.1} ms") }</td></tr>
+            </table>
+        },
+        None => html! {},
+    };
+
+    html! {
+        <main>
+            <h1>{ "Advent of Code 2024" }</h1>
+            <p>{ "Solutions run entirely in your browser; input never leaves this page." }</p>
+            <select onchange={on_day_change}>{ for options }</select>
+            <button onclick={on_solve}>{ "Solve" }</button>
+            <textarea rows="20" cols="80" placeholder="Paste puzzle input here" oninput={on_input} />
+            { result }
+        </main>
+    }
+}
+
+fn main() {
+    yew::Renderer::<App>::new().render();
+}